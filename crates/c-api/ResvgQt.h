@@ -81,6 +81,12 @@ static QString errorToString(const int err)
             return QLatin1String("SVG doesn't have a valid size.");
         case RESVG_ERROR_PARSING_FAILED :
             return QLatin1String("Failed to parse an SVG data.");
+        case RESVG_ERROR_NESTED_IMAGES_LIMIT_REACHED :
+            return QLatin1String("SVG images are nested too deep.");
+        case RESVG_ERROR_UNSUPPORTED_ENCODING :
+            return QLatin1String("SVG data uses an unsupported encoding.");
+        case RESVG_ERROR_INPUT_SIZE_LIMIT_REACHED :
+            return QLatin1String("SVG data is too big.");
     }
 
     Q_UNREACHABLE();
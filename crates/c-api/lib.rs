@@ -38,6 +38,12 @@ pub enum resvg_error {
     INVALID_SIZE,
     /// Failed to parse an SVG data.
     PARSING_FAILED,
+    /// SVG images are nested too deep.
+    NESTED_IMAGES_LIMIT_REACHED,
+    /// SVG data uses an unsupported encoding.
+    UNSUPPORTED_ENCODING,
+    /// SVG data is too big.
+    INPUT_SIZE_LIMIT_REACHED,
 }
 
 /// @brief A rectangle representation.
@@ -860,6 +866,10 @@ fn convert_error(e: usvg::Error) -> resvg_error {
         usvg::Error::ElementsLimitReached => resvg_error::ELEMENTS_LIMIT_REACHED,
         usvg::Error::InvalidSize => resvg_error::INVALID_SIZE,
         usvg::Error::ParsingFailed(_) => resvg_error::PARSING_FAILED,
+        usvg::Error::NestedImagesLimitReached => resvg_error::NESTED_IMAGES_LIMIT_REACHED,
+        usvg::Error::UnsupportedEncoding(_) => resvg_error::UNSUPPORTED_ENCODING,
+        usvg::Error::InputSizeLimitReached => resvg_error::INPUT_SIZE_LIMIT_REACHED,
+        usvg::Error::ReadingFailed(_) => resvg_error::FILE_OPEN_FAILED,
     }
 }
 
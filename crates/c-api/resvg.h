@@ -54,6 +54,18 @@ typedef enum {
      * Failed to parse an SVG data.
      */
     RESVG_ERROR_PARSING_FAILED,
+    /**
+     * SVG images are nested too deep.
+     */
+    RESVG_ERROR_NESTED_IMAGES_LIMIT_REACHED,
+    /**
+     * SVG data uses an unsupported encoding.
+     */
+    RESVG_ERROR_UNSUPPORTED_ENCODING,
+    /**
+     * SVG data is too big.
+     */
+    RESVG_ERROR_INPUT_SIZE_LIMIT_REACHED,
 } resvg_error;
 
 /**
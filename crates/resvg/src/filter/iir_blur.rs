@@ -26,6 +26,7 @@
 // TODO: Blurs right and bottom sides twice for some reason.
 
 use super::ImageRefMut;
+use usvg::filter::EdgeMode;
 
 struct BlurData {
     width: usize,
@@ -41,10 +42,18 @@ struct BlurData {
 ///
 /// A negative or zero `sigma_x`/`sigma_y` will disable the blur along that axis.
 ///
+/// `edge_mode` defines how pixels beyond the image edges are treated.
+///
 /// # Allocations
 ///
 /// This method will allocate a 2x `src` buffer.
-pub fn apply(sigma_x: f64, sigma_y: f64, src: ImageRefMut) {
+/// When `edge_mode` is not `None`, a padded copy of `src` will be allocated as well.
+pub fn apply(sigma_x: f64, sigma_y: f64, edge_mode: EdgeMode, src: ImageRefMut) {
+    if edge_mode != EdgeMode::None {
+        apply_padded(sigma_x, sigma_y, edge_mode, src);
+        return;
+    }
+
     let buf_size = (src.width * src.height) as usize;
     let mut buf = vec![0.0; buf_size];
     let buf = &mut buf;
@@ -64,6 +73,59 @@ pub fn apply(sigma_x: f64, sigma_y: f64, src: ImageRefMut) {
     gaussian_channel(data, &d, 3, buf);
 }
 
+/// Blurs an image extended by the edge mode.
+///
+/// The IIR filter itself has no notion of edge modes, so we simply blur a larger image
+/// with the required pixels beyond the edges and copy the original area back.
+fn apply_padded(sigma_x: f64, sigma_y: f64, edge_mode: EdgeMode, src: ImageRefMut) {
+    // 3 sigma is enough to cover the visible part of the kernel.
+    let pad = |sigma: f64| {
+        if sigma > 0.0 {
+            (sigma * 3.0).ceil() as usize
+        } else {
+            0
+        }
+    };
+
+    let width = src.width as usize;
+    let height = src.height as usize;
+    let pad_x = pad(sigma_x);
+    let pad_y = pad(sigma_y);
+    let padded_width = width + pad_x * 2;
+    let padded_height = height + pad_y * 2;
+
+    let resolve = |i: usize, pad: usize, len: usize| {
+        let i = i as isize - pad as isize;
+        let len = len as isize;
+        let i = match edge_mode {
+            EdgeMode::Wrap => i.rem_euclid(len),
+            _ => i.clamp(0, len - 1),
+        };
+        i as usize
+    };
+
+    let mut padded = Vec::with_capacity(padded_width * padded_height);
+    for y in 0..padded_height {
+        let sy = resolve(y, pad_y, height);
+        for x in 0..padded_width {
+            let sx = resolve(x, pad_x, width);
+            padded.push(src.data[sy * width + sx]);
+        }
+    }
+
+    apply(
+        sigma_x,
+        sigma_y,
+        EdgeMode::None,
+        ImageRefMut::new(padded_width as u32, padded_height as u32, &mut padded),
+    );
+
+    for y in 0..height {
+        let start = (y + pad_y) * padded_width + pad_x;
+        src.data[y * width..(y + 1) * width].copy_from_slice(&padded[start..start + width]);
+    }
+}
+
 fn gaussian_channel(data: &mut [u8], d: &BlurData, channel: usize, buf: &mut [f64]) {
     for i in 0..data.len() / 4 {
         buf[i] = data[i * 4 + channel] as f64 / 255.0;
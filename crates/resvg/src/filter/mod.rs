@@ -3,8 +3,10 @@
 
 use std::rc::Rc;
 
+use crate::render::Context;
 use rgb::{FromSlice, RGBA8};
 use tiny_skia::IntRect;
+use usvg::filter::EdgeMode;
 use usvg::{ApproxEqUlps, ApproxZeroUlps};
 
 mod box_blur;
@@ -89,7 +91,7 @@ pub(crate) enum Error {
     NoResults,
 }
 
-trait PixmapExt: Sized {
+pub(crate) trait PixmapExt: Sized {
     fn try_create(width: u32, height: u32) -> Result<tiny_skia::Pixmap, Error>;
     fn copy_region(&self, region: IntRect) -> Result<tiny_skia::Pixmap, Error>;
     fn clear(&mut self);
@@ -334,10 +336,11 @@ struct FilterResult {
 
 pub fn apply(
     filter: &usvg::filter::Filter,
+    ctx: &Context,
     ts: tiny_skia::Transform,
     source: &mut tiny_skia::Pixmap,
 ) {
-    let result = apply_inner(filter, ts, source);
+    let result = apply_inner(filter, ctx, ts, source);
     let result = result.and_then(|image| apply_to_canvas(image, source));
 
     // Clear on error.
@@ -348,7 +351,10 @@ pub fn apply(
     match result {
         Ok(_) => {}
         Err(Error::InvalidRegion) => {
-            log::warn!("Filter has an invalid region.");
+            ctx.warn(
+                usvg::DiagnosticCode::RenderingFailed,
+                "Filter has an invalid region.",
+            );
         }
         Err(Error::NoResults) => {}
     }
@@ -356,6 +362,7 @@ pub fn apply(
 
 fn apply_inner(
     filter: &usvg::filter::Filter,
+    ctx: &Context,
     ts: usvg::Transform,
     source: &mut tiny_skia::Pixmap,
 ) -> Result<Image, Error> {
@@ -394,62 +401,62 @@ fn apply_inner(
 
         let mut result = match primitive.kind() {
             usvg::filter::Kind::Blend(fe) => {
-                let input1 = get_input(fe.input1(), region, source, &results)?;
-                let input2 = get_input(fe.input2(), region, source, &results)?;
+                let input1 = get_input(fe.input1(), region, source, &results, ctx)?;
+                let input2 = get_input(fe.input2(), region, source, &results, ctx)?;
                 apply_blend(fe, cs, region, input1, input2)
             }
             usvg::filter::Kind::DropShadow(fe) => {
-                let input = get_input(fe.input(), region, source, &results)?;
+                let input = get_input(fe.input(), region, source, &results, ctx)?;
                 apply_drop_shadow(fe, cs, ts, input)
             }
             usvg::filter::Kind::Flood(fe) => apply_flood(fe, region),
             usvg::filter::Kind::GaussianBlur(fe) => {
-                let input = get_input(fe.input(), region, source, &results)?;
+                let input = get_input(fe.input(), region, source, &results, ctx)?;
                 apply_blur(fe, cs, ts, input)
             }
             usvg::filter::Kind::Offset(fe) => {
-                let input = get_input(fe.input(), region, source, &results)?;
+                let input = get_input(fe.input(), region, source, &results, ctx)?;
                 apply_offset(fe, ts, input)
             }
             usvg::filter::Kind::Composite(fe) => {
-                let input1 = get_input(fe.input1(), region, source, &results)?;
-                let input2 = get_input(fe.input2(), region, source, &results)?;
+                let input1 = get_input(fe.input1(), region, source, &results, ctx)?;
+                let input2 = get_input(fe.input2(), region, source, &results, ctx)?;
                 apply_composite(fe, cs, region, input1, input2)
             }
-            usvg::filter::Kind::Merge(fe) => apply_merge(fe, cs, region, source, &results),
+            usvg::filter::Kind::Merge(fe) => apply_merge(fe, cs, region, source, &results, ctx),
             usvg::filter::Kind::Tile(fe) => {
-                let input = get_input(fe.input(), region, source, &results)?;
+                let input = get_input(fe.input(), region, source, &results, ctx)?;
                 apply_tile(input, region)
             }
-            usvg::filter::Kind::Image(fe) => apply_image(fe, region, subregion, ts),
+            usvg::filter::Kind::Image(fe) => apply_image(fe, ctx, region, subregion, ts),
             usvg::filter::Kind::ComponentTransfer(fe) => {
-                let input = get_input(fe.input(), region, source, &results)?;
+                let input = get_input(fe.input(), region, source, &results, ctx)?;
                 apply_component_transfer(fe, cs, input)
             }
             usvg::filter::Kind::ColorMatrix(fe) => {
-                let input = get_input(fe.input(), region, source, &results)?;
+                let input = get_input(fe.input(), region, source, &results, ctx)?;
                 apply_color_matrix(fe, cs, input)
             }
             usvg::filter::Kind::ConvolveMatrix(fe) => {
-                let input = get_input(fe.input(), region, source, &results)?;
+                let input = get_input(fe.input(), region, source, &results, ctx)?;
                 apply_convolve_matrix(fe, cs, input)
             }
             usvg::filter::Kind::Morphology(fe) => {
-                let input = get_input(fe.input(), region, source, &results)?;
+                let input = get_input(fe.input(), region, source, &results, ctx)?;
                 apply_morphology(fe, cs, ts, input)
             }
             usvg::filter::Kind::DisplacementMap(fe) => {
-                let input1 = get_input(fe.input1(), region, source, &results)?;
-                let input2 = get_input(fe.input2(), region, source, &results)?;
+                let input1 = get_input(fe.input1(), region, source, &results, ctx)?;
+                let input2 = get_input(fe.input2(), region, source, &results, ctx)?;
                 apply_displacement_map(fe, region, cs, ts, input1, input2)
             }
             usvg::filter::Kind::Turbulence(fe) => apply_turbulence(fe, region, cs, ts),
             usvg::filter::Kind::DiffuseLighting(fe) => {
-                let input = get_input(fe.input(), region, source, &results)?;
+                let input = get_input(fe.input(), region, source, &results, ctx)?;
                 apply_diffuse_lighting(fe, region, cs, ts, input)
             }
             usvg::filter::Kind::SpecularLighting(fe) => {
-                let input = get_input(fe.input(), region, source, &results)?;
+                let input = get_input(fe.input(), region, source, &results, ctx)?;
                 apply_specular_lighting(fe, region, cs, ts, input)
             }
         }?;
@@ -525,6 +532,7 @@ fn get_input(
     region: IntRect,
     source: &tiny_skia::Pixmap,
     results: &[FilterResult],
+    ctx: &Context,
 ) -> Result<Image, Error> {
     match input {
         usvg::filter::Input::SourceGraphic => {
@@ -556,8 +564,17 @@ fn get_input(
                 Ok(v.image.clone())
             } else {
                 // Technically unreachable.
-                log::warn!("Unknown filter primitive reference '{}'.", name);
-                get_input(&usvg::filter::Input::SourceGraphic, region, source, results)
+                ctx.warn(
+                    usvg::DiagnosticCode::InvalidReference,
+                    format!("Unknown filter primitive reference '{}'.", name),
+                );
+                get_input(
+                    &usvg::filter::Input::SourceGraphic,
+                    region,
+                    source,
+                    results,
+                    ctx,
+                )
             }
         }
     }
@@ -597,9 +614,19 @@ fn apply_drop_shadow(
         resolve_std_dev(fe.std_dev_x().get(), fe.std_dev_y().get(), ts)
     {
         if use_box_blur {
-            box_blur::apply(std_dx, std_dy, shadow_pixmap.as_image_ref_mut());
+            box_blur::apply(
+                std_dx,
+                std_dy,
+                EdgeMode::None,
+                shadow_pixmap.as_image_ref_mut(),
+            );
         } else {
-            iir_blur::apply(std_dx, std_dy, shadow_pixmap.as_image_ref_mut());
+            iir_blur::apply(
+                std_dx,
+                std_dy,
+                EdgeMode::None,
+                shadow_pixmap.as_image_ref_mut(),
+            );
         }
     }
 
@@ -657,9 +684,9 @@ fn apply_blur(
     let mut pixmap = input.into_color_space(cs)?.take()?;
 
     if use_box_blur {
-        box_blur::apply(std_dx, std_dy, pixmap.as_image_ref_mut());
+        box_blur::apply(std_dx, std_dy, fe.edge_mode(), pixmap.as_image_ref_mut());
     } else {
-        iir_blur::apply(std_dx, std_dy, pixmap.as_image_ref_mut());
+        iir_blur::apply(std_dx, std_dy, fe.edge_mode(), pixmap.as_image_ref_mut());
     }
 
     Ok(Image::from_image(pixmap, cs))
@@ -725,6 +752,29 @@ fn apply_blend(
         None,
     );
 
+    if fe.no_composite() {
+        // Remove the `input2` part that is not covered by `input1`,
+        // which is added by the source-over compositing.
+        let source = input1.as_ref().data();
+        let backdrop = input2.as_ref().data();
+        for ((p, s), b) in pixmap
+            .data_mut()
+            .chunks_exact_mut(4)
+            .zip(source.chunks_exact(4))
+            .zip(backdrop.chunks_exact(4))
+        {
+            let sa = s[3] as u32;
+            for i in 0..4 {
+                let v = ((255 - sa) * b[i] as u32 + 127) / 255;
+                p[i] = p[i].saturating_sub(v as u8);
+            }
+
+            // Keep the premultiplied color valid.
+            let a = p[3];
+            p[..3].iter_mut().for_each(|c| *c = (*c).min(a));
+        }
+    }
+
     Ok(Image::from_image(pixmap, cs))
 }
 
@@ -798,11 +848,12 @@ fn apply_merge(
     region: IntRect,
     source: &tiny_skia::Pixmap,
     results: &[FilterResult],
+    ctx: &Context,
 ) -> Result<Image, Error> {
     let mut pixmap = tiny_skia::Pixmap::try_create(region.width(), region.height())?;
 
     for input in fe.inputs() {
-        let input = get_input(input, region, source, results)?;
+        let input = get_input(input, region, source, results, ctx)?;
         let input = input.into_color_space(cs)?;
         pixmap.draw_pixmap(
             0,
@@ -860,6 +911,7 @@ fn apply_tile(input: Image, region: IntRect) -> Result<Image, Error> {
 
 fn apply_image(
     fe: &usvg::filter::Image,
+    ctx: &Context,
     region: IntRect,
     subregion: IntRect,
     ts: usvg::Transform,
@@ -876,8 +928,9 @@ fn apply_image(
         subregion.y() as f32,
     );
 
-    let ctx = crate::render::Context {
+    let ctx = Context {
         max_bbox: tiny_skia::IntRect::from_xywh(0, 0, region.width(), region.height()).unwrap(),
+        diagnostics: ctx.diagnostics,
     };
 
     crate::render::render_nodes(fe.root(), &ctx, transform, &mut pixmap.as_mut());
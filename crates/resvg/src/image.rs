@@ -1,8 +1,11 @@
 // Copyright 2018 the Resvg Authors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use crate::render::Context;
+
 pub fn render(
     image: &usvg::Image,
+    ctx: &Context,
     transform: tiny_skia::Transform,
     pixmap: &mut tiny_skia::PixmapMut,
 ) {
@@ -10,37 +13,42 @@ pub fn render(
         return;
     }
 
-    render_inner(image.kind(), transform, image.rendering_mode(), pixmap);
+    render_inner(image.kind(), ctx, transform, image.rendering_mode(), pixmap);
 }
 
 pub fn render_inner(
     image_kind: &usvg::ImageKind,
+    ctx: &Context,
     transform: tiny_skia::Transform,
     #[allow(unused_variables)] rendering_mode: usvg::ImageRendering,
     pixmap: &mut tiny_skia::PixmapMut,
 ) {
     match image_kind {
         usvg::ImageKind::SVG(tree) => {
-            render_vector(tree, transform, pixmap);
+            render_vector(tree, ctx, transform, pixmap);
         }
         #[cfg(feature = "raster-images")]
         _ => {
-            raster_images::render_raster(image_kind, transform, rendering_mode, pixmap);
+            raster_images::render_raster(image_kind, ctx, transform, rendering_mode, pixmap);
         }
         #[cfg(not(feature = "raster-images"))]
         _ => {
-            log::warn!("Images decoding was disabled by a build feature.");
+            ctx.warn(
+                usvg::DiagnosticCode::Unsupported,
+                "Images decoding was disabled by a build feature.",
+            );
         }
     }
 }
 
 fn render_vector(
     tree: &usvg::Tree,
+    ctx: &Context,
     transform: tiny_skia::Transform,
     pixmap: &mut tiny_skia::PixmapMut,
 ) -> Option<()> {
     let mut sub_pixmap = tiny_skia::Pixmap::new(pixmap.width(), pixmap.height()).unwrap();
-    crate::render(tree, transform, &mut sub_pixmap.as_mut());
+    crate::render_with_diagnostics(tree, transform, &mut sub_pixmap.as_mut(), ctx.diagnostics);
     pixmap.draw_pixmap(
         0,
         0,
@@ -56,25 +64,25 @@ fn render_vector(
 #[cfg(feature = "raster-images")]
 mod raster_images {
     use crate::OptionLog;
+    use crate::render::Context;
     use std::io::Cursor;
-    use usvg::ImageRendering;
+    use usvg::{DiagnosticCode, ImageRendering};
+
+    fn decode_raster(image: &usvg::ImageKind, ctx: &Context) -> Option<tiny_skia::Pixmap> {
+        let (pixmap, format) = match image {
+            usvg::ImageKind::SVG(_) => return None,
+            usvg::ImageKind::JPEG(data) => (decode_jpeg(data), "JPEG"),
+            usvg::ImageKind::PNG(data) => (decode_png(data), "PNG"),
+            usvg::ImageKind::GIF(data) => (decode_gif(data), "GIF"),
+            usvg::ImageKind::WEBP(data) => (decode_webp(data), "WebP"),
+        };
 
-    fn decode_raster(image: &usvg::ImageKind) -> Option<tiny_skia::Pixmap> {
-        match image {
-            usvg::ImageKind::SVG(_) => None,
-            usvg::ImageKind::JPEG(data) => {
-                decode_jpeg(data).log_none(|| log::warn!("Failed to decode a JPEG image."))
-            }
-            usvg::ImageKind::PNG(data) => {
-                decode_png(data).log_none(|| log::warn!("Failed to decode a PNG image."))
-            }
-            usvg::ImageKind::GIF(data) => {
-                decode_gif(data).log_none(|| log::warn!("Failed to decode a GIF image."))
-            }
-            usvg::ImageKind::WEBP(data) => {
-                decode_webp(data).log_none(|| log::warn!("Failed to decode a WebP image."))
-            }
-        }
+        pixmap.log_none(|| {
+            ctx.error(
+                DiagnosticCode::ImageDecodingFailed,
+                format!("Failed to decode a {} image.", format),
+            )
+        })
     }
 
     fn decode_png(data: &[u8]) -> Option<tiny_skia::Pixmap> {
@@ -172,11 +180,12 @@ mod raster_images {
 
     pub(crate) fn render_raster(
         image: &usvg::ImageKind,
+        ctx: &Context,
         transform: tiny_skia::Transform,
         rendering_mode: usvg::ImageRendering,
         pixmap: &mut tiny_skia::PixmapMut,
     ) -> Option<()> {
-        let raster = decode_raster(image)?;
+        let raster = decode_raster(image, ctx)?;
 
         let rect = tiny_skia::Size::from_wh(raster.width() as f32, raster.height() as f32)?
             .to_rect(0.0, 0.0)?;
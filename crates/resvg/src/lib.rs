@@ -31,14 +31,32 @@ mod render;
 /// Can be used to position SVG inside the `pixmap`.
 ///
 /// The produced content is in the sRGB color space.
+///
+/// Rendering diagnostics are forwarded to the `log` crate.
+/// Use [`render_with_diagnostics`] to collect them.
 pub fn render(
     tree: &usvg::Tree,
     transform: tiny_skia::Transform,
     pixmap: &mut tiny_skia::PixmapMut,
+) {
+    render_with_diagnostics(tree, transform, pixmap, &usvg::DiagnosticSink::default());
+}
+
+/// Renders a tree onto the pixmap and reports problems to the provided sink.
+///
+/// Same as [`render`], otherwise.
+pub fn render_with_diagnostics(
+    tree: &usvg::Tree,
+    transform: tiny_skia::Transform,
+    pixmap: &mut tiny_skia::PixmapMut,
+    diagnostics: &usvg::DiagnosticSink,
 ) {
     let max_bbox = max_filter_bbox(pixmap.width(), pixmap.height());
 
-    let ctx = render::Context { max_bbox };
+    let ctx = render::Context {
+        max_bbox,
+        diagnostics,
+    };
     render::render_nodes(tree.root(), &ctx, transform, pixmap);
 }
 
@@ -52,10 +70,25 @@ pub fn render(
 /// Returns `None` when `node` has a zero size.
 ///
 /// The produced content is in the sRGB color space.
+///
+/// Rendering diagnostics are forwarded to the `log` crate.
+/// Use [`render_node_with_diagnostics`] to collect them.
 pub fn render_node(
+    node: &usvg::Node,
+    transform: tiny_skia::Transform,
+    pixmap: &mut tiny_skia::PixmapMut,
+) -> Option<()> {
+    render_node_with_diagnostics(node, transform, pixmap, &usvg::DiagnosticSink::default())
+}
+
+/// Renders a node onto the pixmap and reports problems to the provided sink.
+///
+/// Same as [`render_node`], otherwise.
+pub fn render_node_with_diagnostics(
     node: &usvg::Node,
     mut transform: tiny_skia::Transform,
     pixmap: &mut tiny_skia::PixmapMut,
+    diagnostics: &usvg::DiagnosticSink,
 ) -> Option<()> {
     let bbox = node.abs_layer_bounding_box()?;
 
@@ -63,7 +96,10 @@ pub fn render_node(
 
     transform = transform.pre_translate(-bbox.x(), -bbox.y());
 
-    let ctx = render::Context { max_bbox };
+    let ctx = render::Context {
+        max_bbox,
+        diagnostics,
+    };
     render::render_node(node, &ctx, transform, pixmap);
 
     Some(())
@@ -106,4 +142,44 @@ mod tests {
         assert_eq!(bbox.right(), i32::MAX / 2);
         assert_eq!(bbox.bottom(), i32::MAX / 2);
     }
+
+    #[cfg(feature = "raster-images")]
+    #[test]
+    fn render_with_diagnostics_reports_decoding_errors() {
+        use std::sync::{Arc, Mutex};
+
+        // A PNG with a valid header, but without any image data.
+        let svg = "
+        <svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 10 10'>
+            <image id='image1' width='10' height='10'
+                   href='data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAAAAAA'/>
+        </svg>
+        ";
+
+        let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+
+        let diagnostics = Arc::new(Mutex::new(Vec::new()));
+        let sink = {
+            let diagnostics = diagnostics.clone();
+            usvg::DiagnosticSink {
+                report: Box::new(move |diagnostic| diagnostics.lock().unwrap().push(diagnostic)),
+            }
+        };
+
+        let mut pixmap = tiny_skia::Pixmap::new(10, 10).unwrap();
+        super::render_with_diagnostics(
+            &tree,
+            tiny_skia::Transform::default(),
+            &mut pixmap.as_mut(),
+            &sink,
+        );
+
+        let diagnostics = diagnostics.lock().unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].code,
+            usvg::DiagnosticCode::ImageDecodingFailed
+        );
+        assert_eq!(diagnostics[0].severity, usvg::Severity::Error);
+    }
 }
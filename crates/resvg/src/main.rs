@@ -47,36 +47,25 @@ fn process() -> Result<(), String> {
         }
     }
 
-    let mut svg_data = timed(args.perf, "Reading", || -> Result<Vec<u8>, &str> {
+    // SVGZ data is decompressed while reading.
+    let svg_data = timed(args.perf, "Reading", || -> Result<Vec<u8>, String> {
+        let max_input_size = args.usvg.limits.max_input_size;
         if let InputFrom::File(ref file) = args.in_svg {
-            std::fs::read(file).map_err(|_| "failed to open the provided file")
+            let file = std::fs::File::open(file).map_err(|_| "failed to open the provided file")?;
+            usvg::read_svg_data(file, max_input_size).map_err(|e| e.to_string())
         } else {
-            use std::io::Read;
-            let mut buf = Vec::new();
-            let stdin = std::io::stdin();
-            let mut handle = stdin.lock();
-            handle
-                .read_to_end(&mut buf)
-                .map_err(|_| "failed to read stdin")?;
-            Ok(buf)
+            usvg::read_svg_data(std::io::stdin().lock(), max_input_size).map_err(|e| e.to_string())
         }
     })?;
 
-    if svg_data.starts_with(&[0x1f, 0x8b]) {
-        svg_data = timed(args.perf, "SVGZ Decoding", || {
-            usvg::decompress_svgz(&svg_data).map_err(|e| e.to_string())
-        })?;
-    };
-
-    let svg_string = std::str::from_utf8(&svg_data)
-        .map_err(|_| "provided data has not an UTF-8 encoding".to_string())?;
+    let svg_string = usvg::decode_text(&svg_data).map_err(|e| e.to_string())?;
 
     let xml_tree = timed(args.perf, "XML Parsing", || {
         let xml_opt = usvg::roxmltree::ParsingOptions {
             allow_dtd: true,
             ..Default::default()
         };
-        usvg::roxmltree::Document::parse_with_options(svg_string, xml_opt)
+        usvg::roxmltree::Document::parse_with_options(&svg_string, xml_opt)
             .map_err(|e| e.to_string())
     })?;
 
@@ -91,6 +80,17 @@ fn process() -> Result<(), String> {
         });
     }
 
+    // Views are not rendered by themselves, but select a part of the image.
+    if let Some(ref id) = args.export_id {
+        let is_view = xml_tree.descendants().any(|n| {
+            n.has_tag_name(("http://www.w3.org/2000/svg", "view")) && n.attribute("id") == Some(id)
+        });
+
+        if is_view {
+            args.usvg.fragment = args.export_id.take();
+        }
+    }
+
     let tree = timed(args.perf, "SVG Parsing", || {
         usvg::Tree::from_xmltree(&xml_tree, &args.usvg).map_err(|e| e.to_string())
     })?;
@@ -145,6 +145,11 @@ OPTIONS:
                                 Examples: red, #fff, #fff000
   --stylesheet PATH             Inject a stylesheet that should be used when resolving
                                 CSS attributes.
+  --color-scheme SCHEME         Sets the preferred color scheme used by
+                                '@media (prefers-color-scheme)' rules
+                                [default: light] [possible values: light, dark]
+  --animation-time SECONDS      Renders SMIL animations as they appear
+                                at the specified time
 
   --languages LANG              Sets a comma-separated list of languages that
                                 will be used during the 'systemLanguage'
@@ -167,6 +172,14 @@ OPTIONS:
                                 Expected to be the same as the directory that
                                 contains the SVG file, but can be set to any.
                                 [default: input file directory]
+  --resource-policy POLICY      Sets which local files referenced by the SVG
+                                can be loaded
+                                [default: allow-all]
+                                [possible values: allow-all, deny-all, resources-dir]
+  --allow-resource PATH         Allows loading only the specified file or files
+                                inside the specified directory.
+                                Cannot be used with --resource-policy.
+                                This option can be set multiple times
 
   --font-family FAMILY          Sets the default font family that will be
                                 used when no 'font-family' is present
@@ -200,7 +213,9 @@ OPTIONS:
 
 
   --query-all                   Queries all valid SVG ids with bounding boxes
-  --export-id ID                Renders an object only with a specified ID
+  --export-id ID                Renders an object only with a specified ID.
+                                When ID references a view element,
+                                renders the whole image using that view
   --export-area-page            Use an image size instead of an object size during ID exporting
 
   --export-area-drawing         Use drawing's tight bounding box instead of image size.
@@ -227,6 +242,8 @@ struct CliArgs {
     text_rendering: usvg::TextRendering,
     image_rendering: usvg::ImageRendering,
     resources_dir: Option<path::PathBuf>,
+    resource_policy: Option<usvg::ResourcePolicy>,
+    allowed_resources: Vec<path::PathBuf>,
 
     font_family: Option<String>,
     font_size: u32,
@@ -240,6 +257,8 @@ struct CliArgs {
     skip_system_fonts: bool,
     list_fonts: bool,
     style_sheet: Option<path::PathBuf>,
+    color_scheme: usvg::ColorScheme,
+    animation_time: Option<f32>,
 
     query_all: bool,
     export_id: Option<String>,
@@ -289,6 +308,8 @@ fn collect_args() -> Result<CliArgs, pico_args::Error> {
         resources_dir: input
             .opt_value_from_str("--resources-dir")
             .unwrap_or_default(),
+        resource_policy: input.opt_value_from_str("--resource-policy")?,
+        allowed_resources: input.values_from_str("--allow-resource")?,
 
         font_family: input.opt_value_from_str("--font-family")?,
         font_size: input
@@ -310,6 +331,10 @@ fn collect_args() -> Result<CliArgs, pico_args::Error> {
 
         export_area_drawing: input.contains("--export-area-drawing"),
         style_sheet: input.opt_value_from_str("--stylesheet").unwrap_or_default(),
+        color_scheme: input
+            .opt_value_from_str("--color-scheme")?
+            .unwrap_or_default(),
+        animation_time: input.opt_value_from_fn("--animation-time", parse_animation_time)?,
 
         perf: input.contains("--perf"),
         quiet: input.contains("--quiet"),
@@ -359,6 +384,16 @@ fn parse_font_size(s: &str) -> Result<u32, String> {
     }
 }
 
+fn parse_animation_time(s: &str) -> Result<f32, String> {
+    let n: f32 = s.parse().map_err(|_| "invalid number")?;
+
+    if n.is_finite() {
+        Ok(n)
+    } else {
+        Err("animation time should be finite".to_string())
+    }
+}
+
 fn parse_languages(s: &str) -> Result<Vec<String>, String> {
     let mut langs = Vec::new();
     for lang in s.split(',') {
@@ -551,6 +586,15 @@ fn parse_args() -> Result<Args, String> {
         }
     };
 
+    let resource_policy = match (&args.resource_policy, args.allowed_resources.is_empty()) {
+        (Some(policy), true) => policy.clone(),
+        (None, true) => usvg::ResourcePolicy::AllowAll,
+        (None, false) => usvg::ResourcePolicy::Allowlist(args.allowed_resources.clone()),
+        (Some(_), false) => {
+            return Err("--allow-resource cannot be used with --resource-policy".to_string());
+        }
+    };
+
     let style_sheet = match args.style_sheet.as_ref() {
         Some(p) => Some(
             std::fs::read(p)
@@ -563,6 +607,7 @@ fn parse_args() -> Result<Args, String> {
 
     let usvg = usvg::Options {
         resources_dir,
+        resource_policy,
         dpi: args.dpi as f32,
         font_family: args
             .font_family
@@ -578,6 +623,21 @@ fn parse_args() -> Result<Args, String> {
         font_resolver: usvg::FontResolver::default(),
         fontdb: Arc::new(fontdb::Database::new()),
         style_sheet,
+        condition_resolver: usvg::ConditionResolver::default(),
+        foreign_object_resolver: usvg::ForeignObjectResolver::default(),
+        style_sheet_resolver: usvg::StyleSheetResolver::default(),
+        media: usvg::MediaContext {
+            color_scheme: args.color_scheme,
+            ..usvg::MediaContext::default()
+        },
+        custom_properties: Default::default(),
+        diagnostics: usvg::DiagnosticSink::default(),
+        source_spans: false,
+        metadata: usvg::MetadataOptions::default(),
+        limits: usvg::Limits::default(),
+        recover_malformed_xml: false,
+        fragment: None,
+        animation_time: args.animation_time,
     };
 
     Ok(Args {
@@ -4,7 +4,37 @@
 use crate::render::Context;
 
 pub fn apply(
+    layers: &[usvg::MaskLayer],
+    ctx: &Context,
+    transform: tiny_skia::Transform,
+    pixmap: &mut tiny_skia::Pixmap,
+) {
+    // A single layer can be applied directly, without an intermediate mask.
+    if let [layer] = layers {
+        apply_mask(layer.mask(), layer.mode(), ctx, transform, pixmap);
+        return;
+    }
+
+    // Layers are composited starting from the bottom one.
+    // The compositing operator of the bottom layer is ignored.
+    let mut result: Option<tiny_skia::Mask> = None;
+    for layer in layers.iter().rev() {
+        let mut mask = render_mask(layer.mask(), layer.mode(), ctx, transform, pixmap);
+        if let Some(below) = result {
+            composite(&mut mask, &below, layer.composite());
+        }
+
+        result = Some(mask);
+    }
+
+    if let Some(mask) = result {
+        pixmap.apply_mask(&mask);
+    }
+}
+
+fn apply_mask(
     mask: &usvg::Mask,
+    mode: usvg::MaskType,
     ctx: &Context,
     transform: tiny_skia::Transform,
     pixmap: &mut tiny_skia::Pixmap,
@@ -14,6 +44,44 @@ pub fn apply(
         return;
     }
 
+    let mask_pixmap = render_mask_pixmap(mask, ctx, transform, pixmap);
+
+    if let Some(mask) = mask.mask() {
+        self::apply_mask(mask, mask.kind(), ctx, transform, pixmap);
+    }
+
+    let mask = tiny_skia::Mask::from_pixmap(mask_pixmap.as_ref(), to_mask_type(mode));
+    pixmap.apply_mask(&mask);
+}
+
+fn render_mask(
+    mask: &usvg::Mask,
+    mode: usvg::MaskType,
+    ctx: &Context,
+    transform: tiny_skia::Transform,
+    pixmap: &tiny_skia::Pixmap,
+) -> tiny_skia::Mask {
+    if mask.root().children().is_empty() {
+        return tiny_skia::Mask::new(pixmap.width(), pixmap.height()).unwrap();
+    }
+
+    let mask_pixmap = render_mask_pixmap(mask, ctx, transform, pixmap);
+    let mut result = tiny_skia::Mask::from_pixmap(mask_pixmap.as_ref(), to_mask_type(mode));
+
+    if let Some(sub_mask) = mask.mask() {
+        let sub_mask = render_mask(sub_mask, sub_mask.kind(), ctx, transform, pixmap);
+        composite(&mut result, &sub_mask, usvg::MaskComposite::Intersect);
+    }
+
+    result
+}
+
+fn render_mask_pixmap(
+    mask: &usvg::Mask,
+    ctx: &Context,
+    transform: tiny_skia::Transform,
+    pixmap: &tiny_skia::Pixmap,
+) -> tiny_skia::Pixmap {
     let mut mask_pixmap = tiny_skia::Pixmap::new(pixmap.width(), pixmap.height()).unwrap();
 
     {
@@ -32,15 +100,32 @@ pub fn apply(
         mask_pixmap.apply_mask(&alpha_mask);
     }
 
-    if let Some(mask) = mask.mask() {
-        self::apply(mask, ctx, transform, pixmap);
-    }
+    mask_pixmap
+}
 
-    let mask_type = match mask.kind() {
+fn to_mask_type(mode: usvg::MaskType) -> tiny_skia::MaskType {
+    match mode {
         usvg::MaskType::Luminance => tiny_skia::MaskType::Luminance,
         usvg::MaskType::Alpha => tiny_skia::MaskType::Alpha,
-    };
+    }
+}
 
-    let mask = tiny_skia::Mask::from_pixmap(mask_pixmap.as_ref(), mask_type);
-    pixmap.apply_mask(&mask);
+/// Composites the `source` mask with the `destination` one.
+fn composite(
+    source: &mut tiny_skia::Mask,
+    destination: &tiny_skia::Mask,
+    operator: usvg::MaskComposite,
+) {
+    for (s, d) in source.data_mut().iter_mut().zip(destination.data()) {
+        let sa = *s as u32;
+        let da = *d as u32;
+        let value = match operator {
+            usvg::MaskComposite::Add => sa * 255 + da * (255 - sa),
+            usvg::MaskComposite::Subtract => sa * (255 - da),
+            usvg::MaskComposite::Intersect => sa * da,
+            usvg::MaskComposite::Exclude => sa * (255 - da) + da * (255 - sa),
+        };
+
+        *s = ((value + 127) / 255) as u8;
+    }
 }
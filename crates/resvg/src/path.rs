@@ -110,7 +110,20 @@ fn stroke_path(
     paint.anti_alias = path.rendering_mode().use_shape_antialiasing();
     paint.blend_mode = blend_mode;
 
-    pixmap.stroke_path(path.data(), &paint, &stroke.to_tiny_skia(), transform, None);
+    if stroke.linejoin() == usvg::LineJoin::Arcs {
+        // tiny-skia cannot stroke `arcs` joins, so we have to fill an outline instead.
+        let res_scale = tiny_skia::PathStroker::compute_resolution_scale(&transform);
+        let outline = stroke.outline(path.data(), res_scale)?;
+        pixmap.fill_path(
+            &outline,
+            &paint,
+            tiny_skia::FillRule::Winding,
+            transform,
+            None,
+        );
+    } else {
+        pixmap.stroke_path(path.data(), &paint, &stroke.to_tiny_skia(), transform, None);
+    }
 
     Some(())
 }
@@ -161,6 +174,10 @@ fn convert_base_gradient(
         usvg::SpreadMethod::Repeat => tiny_skia::SpreadMode::Repeat,
     };
 
+    if gradient.color_interpolation() == usvg::filter::ColorInterpolation::LinearRGB {
+        return Some((mode, to_linear_rgb_stops(gradient.stops(), opacity)));
+    }
+
     let mut points = Vec::with_capacity(gradient.stops().len());
     for stop in gradient.stops() {
         let alpha = stop.opacity() * opacity;
@@ -176,6 +193,83 @@ fn convert_base_gradient(
     Some((mode, points))
 }
 
+/// Approximates interpolation in linearRGB by inserting intermediate stops,
+/// since tiny-skia can interpolate only in sRGB.
+fn to_linear_rgb_stops(
+    stops: &[usvg::Stop],
+    opacity: usvg::Opacity,
+) -> Vec<tiny_skia::GradientStop> {
+    const STEPS: usize = 16;
+
+    let to_linear = |c: u8| {
+        let c = c as f32 / 255.0;
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    let from_linear = |c: f32| {
+        let c = if c <= 0.0031308 {
+            c * 12.92
+        } else {
+            1.055 * c.powf(1.0 / 2.4) - 0.055
+        };
+        (c * 255.0).round().clamp(0.0, 255.0) as u8
+    };
+    let to_color = |c: [f32; 4]| {
+        tiny_skia::Color::from_rgba8(
+            from_linear(c[0]),
+            from_linear(c[1]),
+            from_linear(c[2]),
+            (c[3] * 255.0).round().clamp(0.0, 255.0) as u8,
+        )
+    };
+    let convert = |stop: &usvg::Stop| {
+        let c = stop.color();
+        [
+            to_linear(c.red),
+            to_linear(c.green),
+            to_linear(c.blue),
+            (stop.opacity() * opacity).get(),
+        ]
+    };
+
+    let mut points = Vec::with_capacity(stops.len() * STEPS);
+    for (i, stop) in stops.iter().enumerate() {
+        let c1 = convert(stop);
+        let offset1 = stop.offset().get();
+        points.push(tiny_skia::GradientStop::new(offset1, to_color(c1)));
+
+        let Some(next) = stops.get(i + 1) else {
+            break;
+        };
+
+        let offset2 = next.offset().get();
+        if offset1 == offset2 || stop.color() == next.color() {
+            continue;
+        }
+
+        let c2 = convert(next);
+        for step in 1..STEPS {
+            let t = step as f32 / STEPS as f32;
+            let lerp = |a: f32, b: f32| a + (b - a) * t;
+            let color = [
+                lerp(c1[0], c2[0]),
+                lerp(c1[1], c2[1]),
+                lerp(c1[2], c2[2]),
+                lerp(c1[3], c2[3]),
+            ];
+            points.push(tiny_skia::GradientStop::new(
+                lerp(offset1, offset2),
+                to_color(color),
+            ));
+        }
+    }
+
+    points
+}
+
 fn render_pattern_pixmap(
     pattern: &usvg::Pattern,
     ctx: &Context,
@@ -3,8 +3,29 @@
 
 use crate::OptionLog;
 
-pub struct Context {
+pub struct Context<'a> {
     pub max_bbox: tiny_skia::IntRect,
+    pub diagnostics: &'a usvg::DiagnosticSink<'a>,
+}
+
+impl Context<'_> {
+    pub fn warn(&self, code: usvg::DiagnosticCode, message: impl Into<String>) {
+        self.report(code, usvg::Severity::Warning, message);
+    }
+
+    pub fn error(&self, code: usvg::DiagnosticCode, message: impl Into<String>) {
+        self.report(code, usvg::Severity::Error, message);
+    }
+
+    fn report(
+        &self,
+        code: usvg::DiagnosticCode,
+        severity: usvg::Severity,
+        message: impl Into<String>,
+    ) {
+        self.diagnostics
+            .report(usvg::Diagnostic::new(code, severity, message));
+    }
 }
 
 pub fn render_nodes(
@@ -38,7 +59,7 @@ pub fn render_node(
             );
         }
         usvg::Node::Image(image) => {
-            crate::image::render(image, transform, pixmap);
+            crate::image::render(image, ctx, transform, pixmap);
         }
         usvg::Node::Text(text) => {
             render_group(text.flattened(), ctx, transform, pixmap);
@@ -105,23 +126,33 @@ fn render_group(
 
     let transform = shift_ts.pre_concat(transform);
 
-    let mut sub_pixmap = tiny_skia::Pixmap::new(ibbox.width(), ibbox.height())
-        .log_none(|| log::warn!("Failed to allocate a group layer for: {:?}.", ibbox))?;
+    let mut sub_pixmap = tiny_skia::Pixmap::new(ibbox.width(), ibbox.height()).log_none(|| {
+        ctx.error(
+            usvg::DiagnosticCode::RenderingFailed,
+            format!("Failed to allocate a group layer for: {:?}.", ibbox),
+        )
+    })?;
 
-    render_nodes(group, ctx, transform, &mut sub_pixmap.as_mut());
+    if group.color_interpolation() == usvg::filter::ColorInterpolation::LinearRGB
+        && group.children().len() > 1
+    {
+        render_nodes_in_linear_rgb(group, ctx, transform, &mut sub_pixmap)?;
+    } else {
+        render_nodes(group, ctx, transform, &mut sub_pixmap.as_mut());
+    }
 
     if !group.filters().is_empty() {
         for filter in group.filters() {
-            crate::filter::apply(filter, transform, &mut sub_pixmap);
+            crate::filter::apply(filter, ctx, transform, &mut sub_pixmap);
         }
     }
 
     if let Some(clip_path) = group.clip_path() {
-        crate::clip::apply(clip_path, transform, &mut sub_pixmap);
+        crate::clip::apply(clip_path, ctx, transform, &mut sub_pixmap);
     }
 
-    if let Some(mask) = group.mask() {
-        crate::mask::apply(mask, ctx, transform, &mut sub_pixmap);
+    if !group.masks().is_empty() {
+        crate::mask::apply(group.masks(), ctx, transform, &mut sub_pixmap);
     }
 
     let paint = tiny_skia::PixmapPaint {
@@ -142,6 +173,49 @@ fn render_group(
     Some(())
 }
 
+/// Renders group's children while compositing them in linearRGB.
+///
+/// Each child is rendered into its own layer, which is then converted into linearRGB
+/// and blended onto the group's layer. The result is converted back into sRGB.
+fn render_nodes_in_linear_rgb(
+    group: &usvg::Group,
+    ctx: &Context,
+    transform: tiny_skia::Transform,
+    pixmap: &mut tiny_skia::Pixmap,
+) -> Option<()> {
+    use crate::filter::PixmapExt;
+
+    let mut layer = tiny_skia::Pixmap::new(pixmap.width(), pixmap.height())?;
+    for node in group.children() {
+        layer.fill(tiny_skia::Color::TRANSPARENT);
+        render_node(node, ctx, transform, &mut layer.as_mut());
+        layer.into_linear_rgb();
+
+        // Blending with a transparent backdrop doesn't change the source,
+        // so a child group's blend mode can be applied here instead.
+        let blend_mode = match node {
+            usvg::Node::Group(g) => convert_blend_mode(g.blend_mode()),
+            _ => tiny_skia::BlendMode::SourceOver,
+        };
+
+        let paint = tiny_skia::PixmapPaint {
+            blend_mode,
+            ..tiny_skia::PixmapPaint::default()
+        };
+        pixmap.draw_pixmap(
+            0,
+            0,
+            layer.as_ref(),
+            &paint,
+            tiny_skia::Transform::identity(),
+            None,
+        );
+    }
+
+    pixmap.into_srgb();
+    Some(())
+}
+
 pub fn convert_blend_mode(mode: usvg::BlendMode) -> tiny_skia::BlendMode {
     match mode {
         usvg::BlendMode::Normal => tiny_skia::BlendMode::SourceOver,
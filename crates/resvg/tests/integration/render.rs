@@ -36,6 +36,8 @@ use crate::render;
 #[test] fn filters_feBlend_mode_eq_multiply() { assert_eq!(render("tests/filters/feBlend/mode=multiply"), 0); }
 #[test] fn filters_feBlend_mode_eq_normal() { assert_eq!(render("tests/filters/feBlend/mode=normal"), 0); }
 #[test] fn filters_feBlend_mode_eq_screen() { assert_eq!(render("tests/filters/feBlend/mode=screen"), 0); }
+#[test] fn filters_feBlend_no_composite_with_semi_transparent_input() { assert_eq!(render("tests/filters/feBlend/no-composite-with-semi-transparent-input"), 0); }
+#[test] fn filters_feBlend_no_composite() { assert_eq!(render("tests/filters/feBlend/no-composite"), 0); }
 #[test] fn filters_feBlend_with_subregion_on_input_1() { assert_eq!(render("tests/filters/feBlend/with-subregion-on-input-1"), 0); }
 #[test] fn filters_feBlend_with_subregion_on_input_2() { assert_eq!(render("tests/filters/feBlend/with-subregion-on-input-2"), 0); }
 #[test] fn filters_feColorMatrix_invalid_type() { assert_eq!(render("tests/filters/feColorMatrix/invalid-type"), 0); }
@@ -128,6 +130,7 @@ use crate::render;
 #[test] fn filters_feDiffuseLighting_lighting_color_eq_currentColor() { assert_eq!(render("tests/filters/feDiffuseLighting/lighting-color=currentColor"), 0); }
 #[test] fn filters_feDiffuseLighting_lighting_color_eq_hsla() { assert_eq!(render("tests/filters/feDiffuseLighting/lighting-color=hsla"), 0); }
 #[test] fn filters_feDiffuseLighting_lighting_color_eq_inherit() { assert_eq!(render("tests/filters/feDiffuseLighting/lighting-color=inherit"), 0); }
+#[test] fn filters_feDiffuseLighting_lighting_color_eq_oklch() { assert_eq!(render("tests/filters/feDiffuseLighting/lighting-color=oklch"), 0); }
 #[test] fn filters_feDiffuseLighting_lighting_color_eq_seagreen() { assert_eq!(render("tests/filters/feDiffuseLighting/lighting-color=seagreen"), 0); }
 #[test] fn filters_feDiffuseLighting_linearRGB_color_interpolation() { assert_eq!(render("tests/filters/feDiffuseLighting/linearRGB-color-interpolation"), 0); }
 #[test] fn filters_feDiffuseLighting_multiple_light_sources() { assert_eq!(render("tests/filters/feDiffuseLighting/multiple-light-sources"), 0); }
@@ -164,6 +167,12 @@ use crate::render;
 #[test] fn filters_feFlood_with_opacity_on_target_element() { assert_eq!(render("tests/filters/feFlood/with-opacity-on-target-element"), 0); }
 #[test] fn filters_feFlood_with_opacity() { assert_eq!(render("tests/filters/feFlood/with-opacity"), 0); }
 #[test] fn filters_feGaussianBlur_complex_transform() { assert_eq!(render("tests/filters/feGaussianBlur/complex-transform"), 0); }
+#[test] fn filters_feGaussianBlur_edgeMode_eq_duplicate_with_IIR_blur() { assert_eq!(render("tests/filters/feGaussianBlur/edgeMode=duplicate-with-IIR-blur"), 0); }
+#[test] fn filters_feGaussianBlur_edgeMode_eq_duplicate() { assert_eq!(render("tests/filters/feGaussianBlur/edgeMode=duplicate"), 0); }
+#[test] fn filters_feGaussianBlur_edgeMode_eq_invalid() { assert_eq!(render("tests/filters/feGaussianBlur/edgeMode=invalid"), 0); }
+#[test] fn filters_feGaussianBlur_edgeMode_eq_none() { assert_eq!(render("tests/filters/feGaussianBlur/edgeMode=none"), 0); }
+#[test] fn filters_feGaussianBlur_edgeMode_eq_wrap_with_IIR_blur() { assert_eq!(render("tests/filters/feGaussianBlur/edgeMode=wrap-with-IIR-blur"), 0); }
+#[test] fn filters_feGaussianBlur_edgeMode_eq_wrap() { assert_eq!(render("tests/filters/feGaussianBlur/edgeMode=wrap"), 0); }
 #[test] fn filters_feGaussianBlur_empty_stdDeviation() { assert_eq!(render("tests/filters/feGaussianBlur/empty-stdDeviation"), 0); }
 #[test] fn filters_feGaussianBlur_huge_stdDeviation() { assert_eq!(render("tests/filters/feGaussianBlur/huge-stdDeviation"), 0); }
 #[test] fn filters_feGaussianBlur_negative_stdDeviation() { assert_eq!(render("tests/filters/feGaussianBlur/negative-stdDeviation"), 0); }
@@ -401,11 +410,13 @@ use crate::render;
 #[test] fn filters_flood_color_inheritance_3() { assert_eq!(render("tests/filters/flood-color/inheritance-3"), 0); }
 #[test] fn filters_flood_color_inheritance_4() { assert_eq!(render("tests/filters/flood-color/inheritance-4"), 0); }
 #[test] fn filters_flood_color_inheritance_5() { assert_eq!(render("tests/filters/flood-color/inheritance-5"), 0); }
+#[test] fn filters_flood_color_oklch_color() { assert_eq!(render("tests/filters/flood-color/oklch-color"), 0); }
 #[test] fn filters_flood_color_simple_case() { assert_eq!(render("tests/filters/flood-color/simple-case"), 0); }
 #[test] fn filters_flood_opacity_50percent() { assert_eq!(render("tests/filters/flood-opacity/50percent"), 0); }
 #[test] fn filters_flood_opacity_simple_case() { assert_eq!(render("tests/filters/flood-opacity/simple-case"), 0); }
 #[test] fn masking_clip_simple_case() { assert_eq!(render("tests/masking/clip/simple-case"), 0); }
 #[test] fn masking_clip_rule_clip_rule_eq_evenodd() { assert_eq!(render("tests/masking/clip-rule/clip-rule=evenodd"), 0); }
+#[test] fn masking_clipPath_circle_shorthand_with_position() { assert_eq!(render("tests/masking/clipPath/circle-shorthand-with-position"), 0); }
 #[test] fn masking_clipPath_circle_shorthand_with_stroke_box() { assert_eq!(render("tests/masking/clipPath/circle-shorthand-with-stroke-box"), 0); }
 #[test] fn masking_clipPath_circle_shorthand_with_view_box() { assert_eq!(render("tests/masking/clipPath/circle-shorthand-with-view-box"), 0); }
 #[test] fn masking_clipPath_circle_shorthand() { assert_eq!(render("tests/masking/clipPath/circle-shorthand"), 0); }
@@ -423,10 +434,13 @@ use crate::render;
 #[test] fn masking_clipPath_clipping_with_complex_text_2() { assert_eq!(render("tests/masking/clipPath/clipping-with-complex-text-2"), 0); }
 #[test] fn masking_clipPath_clipping_with_complex_text_and_clip_rule() { assert_eq!(render("tests/masking/clipPath/clipping-with-complex-text-and-clip-rule"), 0); }
 #[test] fn masking_clipPath_clipping_with_text() { assert_eq!(render("tests/masking/clipPath/clipping-with-text"), 0); }
+#[test] fn masking_clipPath_ellipse_shorthand() { assert_eq!(render("tests/masking/clipPath/ellipse-shorthand"), 0); }
+#[test] fn masking_clipPath_fill_box() { assert_eq!(render("tests/masking/clipPath/fill-box"), 0); }
 #[test] fn masking_clipPath_fill_has_no_effect() { assert_eq!(render("tests/masking/clipPath/fill-has-no-effect"), 0); }
 #[test] fn masking_clipPath_filter_has_no_effect() { assert_eq!(render("tests/masking/clipPath/filter-has-no-effect"), 0); }
 #[test] fn masking_clipPath_g_is_not_a_valid_child() { assert_eq!(render("tests/masking/clipPath/g-is-not-a-valid-child"), 0); }
 #[test] fn masking_clipPath_image_is_not_a_valid_child() { assert_eq!(render("tests/masking/clipPath/image-is-not-a-valid-child"), 0); }
+#[test] fn masking_clipPath_inset_shorthand() { assert_eq!(render("tests/masking/clipPath/inset-shorthand"), 0); }
 #[test] fn masking_clipPath_invalid_FuncIRI() { assert_eq!(render("tests/masking/clipPath/invalid-FuncIRI"), 0); }
 #[test] fn masking_clipPath_invalid_clip_path_on_child() { assert_eq!(render("tests/masking/clipPath/invalid-clip-path-on-child"), 0); }
 #[test] fn masking_clipPath_invalid_clip_path_on_self() { assert_eq!(render("tests/masking/clipPath/invalid-clip-path-on-self"), 0); }
@@ -446,6 +460,8 @@ use crate::render;
 #[test] fn masking_clipPath_on_the_root_svg_without_size() { assert_eq!(render("tests/masking/clipPath/on-the-root-svg-without-size"), 0); }
 #[test] fn masking_clipPath_opacity_has_no_effect() { assert_eq!(render("tests/masking/clipPath/opacity-has-no-effect"), 0); }
 #[test] fn masking_clipPath_overlapped_shapes_with_evenodd() { assert_eq!(render("tests/masking/clipPath/overlapped-shapes-with-evenodd"), 0); }
+#[test] fn masking_clipPath_path_shorthand() { assert_eq!(render("tests/masking/clipPath/path-shorthand"), 0); }
+#[test] fn masking_clipPath_polygon_shorthand() { assert_eq!(render("tests/masking/clipPath/polygon-shorthand"), 0); }
 #[test] fn masking_clipPath_recursive_on_child() { assert_eq!(render("tests/masking/clipPath/recursive-on-child"), 0); }
 #[test] fn masking_clipPath_recursive_on_self() { assert_eq!(render("tests/masking/clipPath/recursive-on-self"), 0); }
 #[test] fn masking_clipPath_recursive() { assert_eq!(render("tests/masking/clipPath/recursive"), 0); }
@@ -464,6 +480,12 @@ use crate::render;
 #[test] fn masking_mask_invalid_child() { assert_eq!(render("tests/masking/mask/invalid-child"), 0); }
 #[test] fn masking_mask_invisible_child_1() { assert_eq!(render("tests/masking/mask/invisible-child-1"), 0); }
 #[test] fn masking_mask_invisible_child_2() { assert_eq!(render("tests/masking/mask/invisible-child-2"), 0); }
+#[test] fn masking_mask_mask_composite_longhand() { assert_eq!(render("tests/masking/mask/mask-composite-longhand"), 0); }
+#[test] fn masking_mask_mask_composite_eq_exclude() { assert_eq!(render("tests/masking/mask/mask-composite=exclude"), 0); }
+#[test] fn masking_mask_mask_composite_eq_intersect() { assert_eq!(render("tests/masking/mask/mask-composite=intersect"), 0); }
+#[test] fn masking_mask_mask_composite_eq_subtract() { assert_eq!(render("tests/masking/mask/mask-composite=subtract"), 0); }
+#[test] fn masking_mask_mask_mode_on_attribute() { assert_eq!(render("tests/masking/mask/mask-mode-on-attribute"), 0); }
+#[test] fn masking_mask_mask_mode_eq_alpha() { assert_eq!(render("tests/masking/mask/mask-mode=alpha"), 0); }
 #[test] fn masking_mask_mask_on_child() { assert_eq!(render("tests/masking/mask/mask-on-child"), 0); }
 #[test] fn masking_mask_mask_on_self_with_mask_type_eq_alpha() { assert_eq!(render("tests/masking/mask/mask-on-self-with-mask-type=alpha"), 0); }
 #[test] fn masking_mask_mask_on_self_with_mixed_mask_type() { assert_eq!(render("tests/masking/mask/mask-on-self-with-mixed-mask-type"), 0); }
@@ -478,12 +500,14 @@ use crate::render;
 #[test] fn masking_mask_maskUnits_eq_userSpaceOnUse_with_rect() { assert_eq!(render("tests/masking/mask/maskUnits=userSpaceOnUse-with-rect"), 0); }
 #[test] fn masking_mask_maskUnits_eq_userSpaceOnUse_with_width_only() { assert_eq!(render("tests/masking/mask/maskUnits=userSpaceOnUse-with-width-only"), 0); }
 #[test] fn masking_mask_maskUnits_eq_userSpaceOnUse_without_rect() { assert_eq!(render("tests/masking/mask/maskUnits=userSpaceOnUse-without-rect"), 0); }
+#[test] fn masking_mask_multiple_masks() { assert_eq!(render("tests/masking/mask/multiple-masks"), 0); }
 #[test] fn masking_mask_nested_objectBoundingBox() { assert_eq!(render("tests/masking/mask/nested-objectBoundingBox"), 0); }
 #[test] fn masking_mask_no_children() { assert_eq!(render("tests/masking/mask/no-children"), 0); }
 #[test] fn masking_mask_none() { assert_eq!(render("tests/masking/mask/none"), 0); }
 #[test] fn masking_mask_on_a_horizontal_line() { assert_eq!(render("tests/masking/mask/on-a-horizontal-line"), 0); }
 #[test] fn masking_mask_on_a_small_object() { assert_eq!(render("tests/masking/mask/on-a-small-object"), 0); }
 #[test] fn masking_mask_on_group_with_transform() { assert_eq!(render("tests/masking/mask/on-group-with-transform"), 0); }
+#[test] fn masking_mask_recursive_in_list() { assert_eq!(render("tests/masking/mask/recursive-in-list"), 0); }
 #[test] fn masking_mask_recursive_on_child() { assert_eq!(render("tests/masking/mask/recursive-on-child"), 0); }
 #[test] fn masking_mask_recursive_on_self() { assert_eq!(render("tests/masking/mask/recursive-on-self"), 0); }
 #[test] fn masking_mask_recursive() { assert_eq!(render("tests/masking/mask/recursive"), 0); }
@@ -497,11 +521,25 @@ use crate::render;
 #[test] fn masking_mask_with_opacity_1() { assert_eq!(render("tests/masking/mask/with-opacity-1"), 0); }
 #[test] fn masking_mask_with_opacity_2() { assert_eq!(render("tests/masking/mask/with-opacity-2"), 0); }
 #[test] fn masking_mask_with_opacity_3() { assert_eq!(render("tests/masking/mask/with-opacity-3"), 0); }
+#[test] fn masking_mask_image_image() { assert_eq!(render("tests/masking/mask-image/image"), 0); }
+#[test] fn masking_mask_image_invalid_value() { assert_eq!(render("tests/masking/mask-image/invalid-value"), 0); }
+#[test] fn masking_mask_image_linear_gradient_with_angle() { assert_eq!(render("tests/masking/mask-image/linear-gradient-with-angle"), 0); }
+#[test] fn masking_mask_image_linear_gradient() { assert_eq!(render("tests/masking/mask-image/linear-gradient"), 0); }
+#[test] fn masking_mask_image_mask_origin_and_clip() { assert_eq!(render("tests/masking/mask-image/mask-origin-and-clip"), 0); }
+#[test] fn masking_mask_image_mask_position() { assert_eq!(render("tests/masking/mask-image/mask-position"), 0); }
+#[test] fn masking_mask_image_mask_repeat_eq_space() { assert_eq!(render("tests/masking/mask-image/mask-repeat=space"), 0); }
+#[test] fn masking_mask_image_mask_size_and_repeat() { assert_eq!(render("tests/masking/mask-image/mask-size-and-repeat"), 0); }
+#[test] fn masking_mask_image_no_clip() { assert_eq!(render("tests/masking/mask-image/no-clip"), 0); }
+#[test] fn masking_mask_image_paint_server() { assert_eq!(render("tests/masking/mask-image/paint-server"), 0); }
+#[test] fn masking_mask_image_radial_gradient() { assert_eq!(render("tests/masking/mask-image/radial-gradient"), 0); }
+#[test] fn masking_mask_image_with_element_mask() { assert_eq!(render("tests/masking/mask-image/with-element-mask"), 0); }
 #[test] fn paint_servers_linearGradient_attributes_via_xlink_href_complex_order() { assert_eq!(render("tests/paint-servers/linearGradient/attributes-via-xlink-href-complex-order"), 0); }
 #[test] fn paint_servers_linearGradient_attributes_via_xlink_href_from_radialGradient() { assert_eq!(render("tests/paint-servers/linearGradient/attributes-via-xlink-href-from-radialGradient"), 0); }
 #[test] fn paint_servers_linearGradient_attributes_via_xlink_href_from_rect() { assert_eq!(render("tests/paint-servers/linearGradient/attributes-via-xlink-href-from-rect"), 0); }
 #[test] fn paint_servers_linearGradient_attributes_via_xlink_href_only_required() { assert_eq!(render("tests/paint-servers/linearGradient/attributes-via-xlink-href-only-required"), 0); }
 #[test] fn paint_servers_linearGradient_attributes_via_xlink_href() { assert_eq!(render("tests/paint-servers/linearGradient/attributes-via-xlink-href"), 0); }
+#[test] fn paint_servers_linearGradient_color_interpolation_eq_linearRGB_via_xlink_href() { assert_eq!(render("tests/paint-servers/linearGradient/color-interpolation=linearRGB-via-xlink-href"), 0); }
+#[test] fn paint_servers_linearGradient_color_interpolation_eq_linearRGB() { assert_eq!(render("tests/paint-servers/linearGradient/color-interpolation=linearRGB"), 0); }
 #[test] fn paint_servers_linearGradient_default_attributes() { assert_eq!(render("tests/paint-servers/linearGradient/default-attributes"), 0); }
 #[test] fn paint_servers_linearGradient_gradientTransform_and_transform() { assert_eq!(render("tests/paint-servers/linearGradient/gradientTransform-and-transform"), 0); }
 #[test] fn paint_servers_linearGradient_gradientTransform() { assert_eq!(render("tests/paint-servers/linearGradient/gradientTransform"), 0); }
@@ -573,6 +611,7 @@ use crate::render;
 #[test] fn paint_servers_radialGradient_attributes_via_xlink_href_from_rect() { assert_eq!(render("tests/paint-servers/radialGradient/attributes-via-xlink-href-from-rect"), 0); }
 #[test] fn paint_servers_radialGradient_attributes_via_xlink_href_only_required() { assert_eq!(render("tests/paint-servers/radialGradient/attributes-via-xlink-href-only-required"), 0); }
 #[test] fn paint_servers_radialGradient_attributes_via_xlink_href() { assert_eq!(render("tests/paint-servers/radialGradient/attributes-via-xlink-href"), 0); }
+#[test] fn paint_servers_radialGradient_color_interpolation_eq_linearRGB() { assert_eq!(render("tests/paint-servers/radialGradient/color-interpolation=linearRGB"), 0); }
 #[test] fn paint_servers_radialGradient_default_attributes() { assert_eq!(render("tests/paint-servers/radialGradient/default-attributes"), 0); }
 #[test] fn paint_servers_radialGradient_fr_eq__1() { assert_eq!(render("tests/paint-servers/radialGradient/fr=-1"), 0); }
 #[test] fn paint_servers_radialGradient_fr_eq_0_2() { assert_eq!(render("tests/paint-servers/radialGradient/fr=0.2"), 0); }
@@ -644,6 +683,7 @@ use crate::render;
 #[test] fn paint_servers_stop_stops_with_equal_offset_5() { assert_eq!(render("tests/paint-servers/stop/stops-with-equal-offset-5"), 0); }
 #[test] fn paint_servers_stop_stops_with_equal_offset_6() { assert_eq!(render("tests/paint-servers/stop/stops-with-equal-offset-6"), 0); }
 #[test] fn paint_servers_stop_zero_offset_in_the_middle() { assert_eq!(render("tests/paint-servers/stop/zero-offset-in-the-middle"), 0); }
+#[test] fn paint_servers_stop_color_oklch_color() { assert_eq!(render("tests/paint-servers/stop-color/oklch-color"), 0); }
 #[test] fn paint_servers_stop_color_simple_case() { assert_eq!(render("tests/paint-servers/stop-color/simple-case"), 0); }
 #[test] fn paint_servers_stop_opacity_50percent() { assert_eq!(render("tests/paint-servers/stop-opacity/50percent"), 0); }
 #[test] fn paint_servers_stop_opacity_simple_case() { assert_eq!(render("tests/paint-servers/stop-opacity/simple-case"), 0); }
@@ -651,6 +691,10 @@ use crate::render;
 #[test] fn painting_color_recursive_nested_context_without_color() { assert_eq!(render("tests/painting/color/recursive-nested-context-without-color"), 0); }
 #[test] fn painting_color_recursive_nested_context() { assert_eq!(render("tests/painting/color/recursive-nested-context"), 0); }
 #[test] fn painting_color_simple_case() { assert_eq!(render("tests/painting/color/simple-case"), 0); }
+#[test] fn painting_color_interpolation_inherit() { assert_eq!(render("tests/painting/color-interpolation/inherit"), 0); }
+#[test] fn painting_color_interpolation_linearRGB_on_group() { assert_eq!(render("tests/painting/color-interpolation/linearRGB-on-group"), 0); }
+#[test] fn painting_color_interpolation_linearRGB_on_root() { assert_eq!(render("tests/painting/color-interpolation/linearRGB-on-root"), 0); }
+#[test] fn painting_color_interpolation_sRGB_on_group() { assert_eq!(render("tests/painting/color-interpolation/sRGB-on-group"), 0); }
 #[test] fn painting_context_in_marker() { assert_eq!(render("tests/painting/context/in-marker"), 0); }
 #[test] fn painting_context_in_nested_marker() { assert_eq!(render("tests/painting/context/in-nested-marker"), 0); }
 #[test] fn painting_context_in_nested_use_and_marker() { assert_eq!(render("tests/painting/context/in-nested-use-and-marker"), 0); }
@@ -681,20 +725,24 @@ use crate::render;
 #[test] fn painting_fill_RRGGBB_color() { assert_eq!(render("tests/painting/fill/#RRGGBB-color"), 0); }
 #[test] fn painting_fill_RRGGBB_uppercase_color() { assert_eq!(render("tests/painting/fill/#RRGGBB-uppercase-color"), 0); }
 #[test] fn painting_fill_RRGGBBAA() { assert_eq!(render("tests/painting/fill/#RRGGBBAA"), 0); }
+#[test] fn painting_fill_color_mix() { assert_eq!(render("tests/painting/fill/color-mix"), 0); }
 #[test] fn painting_fill_currentColor_without_parent() { assert_eq!(render("tests/painting/fill/currentColor-without-parent"), 0); }
 #[test] fn painting_fill_currentColor() { assert_eq!(render("tests/painting/fill/currentColor"), 0); }
+#[test] fn painting_fill_display_p3_color() { assert_eq!(render("tests/painting/fill/display-p3-color"), 0); }
 #[test] fn painting_fill_double_inherit() { assert_eq!(render("tests/painting/fill/double-inherit"), 0); }
 #[test] fn painting_fill_funcIRI_to_a_missing_element_with_a_fallback_color() { assert_eq!(render("tests/painting/fill/funcIRI-to-a-missing-element-with-a-fallback-color"), 0); }
 #[test] fn painting_fill_funcIRI_to_a_missing_element_with_a_none_fallback() { assert_eq!(render("tests/painting/fill/funcIRI-to-a-missing-element-with-a-none-fallback"), 0); }
 #[test] fn painting_fill_funcIRI_to_an_invalid_element_with_a_none_fallback() { assert_eq!(render("tests/painting/fill/funcIRI-to-an-invalid-element-with-a-none-fallback"), 0); }
 #[test] fn painting_fill_funcIRI_to_an_unsupported_element() { assert_eq!(render("tests/painting/fill/funcIRI-to-an-unsupported-element"), 0); }
 #[test] fn painting_fill_funcIRI_with_a_fallback_color() { assert_eq!(render("tests/painting/fill/funcIRI-with-a-fallback-color"), 0); }
+#[test] fn painting_fill_funcIRI_with_an_oklch_fallback_color() { assert_eq!(render("tests/painting/fill/funcIRI-with-an-oklch-fallback-color"), 0); }
 #[test] fn painting_fill_hsl_120_100percent_25percent() { assert_eq!(render("tests/painting/fill/hsl-120-100percent-25percent"), 0); }
 #[test] fn painting_fill_hsl_120_200percent_25percent() { assert_eq!(render("tests/painting/fill/hsl-120-200percent-25percent"), 0); }
 #[test] fn painting_fill_hsl_360_100percent_25percent() { assert_eq!(render("tests/painting/fill/hsl-360-100percent-25percent"), 0); }
 #[test] fn painting_fill_hsl_999_100percent_25percent() { assert_eq!(render("tests/painting/fill/hsl-999-100percent-25percent"), 0); }
 #[test] fn painting_fill_hsl_with_alpha() { assert_eq!(render("tests/painting/fill/hsl-with-alpha"), 0); }
 #[test] fn painting_fill_hsla_with_percentage_s_and_l_values() { assert_eq!(render("tests/painting/fill/hsla-with-percentage-s-and-l-values"), 0); }
+#[test] fn painting_fill_hwb_color() { assert_eq!(render("tests/painting/fill/hwb-color"), 0); }
 #[test] fn painting_fill_icc_color() { assert_eq!(render("tests/painting/fill/icc-color"), 0); }
 #[test] fn painting_fill_inherit_without_parent() { assert_eq!(render("tests/painting/fill/inherit-without-parent"), 0); }
 #[test] fn painting_fill_inherit() { assert_eq!(render("tests/painting/fill/inherit"), 0); }
@@ -703,6 +751,8 @@ use crate::render;
 #[test] fn painting_fill_invalid_RRGGBB_3() { assert_eq!(render("tests/painting/fill/invalid-#RRGGBB-3"), 0); }
 #[test] fn painting_fill_invalid_FuncIRI_with_a_currentColor_fallback() { assert_eq!(render("tests/painting/fill/invalid-FuncIRI-with-a-currentColor-fallback"), 0); }
 #[test] fn painting_fill_invalid_FuncIRI_with_a_fallback_color() { assert_eq!(render("tests/painting/fill/invalid-FuncIRI-with-a-fallback-color"), 0); }
+#[test] fn painting_fill_lab_color() { assert_eq!(render("tests/painting/fill/lab-color"), 0); }
+#[test] fn painting_fill_lch_color_with_alpha() { assert_eq!(render("tests/painting/fill/lch-color-with-alpha"), 0); }
 #[test] fn painting_fill_linear_gradient_on_shape() { assert_eq!(render("tests/painting/fill/linear-gradient-on-shape"), 0); }
 #[test] fn painting_fill_linear_gradient_on_text() { assert_eq!(render("tests/painting/fill/linear-gradient-on-text"), 0); }
 #[test] fn painting_fill_missing_FuncIRI_with_a_currentColor_fallback() { assert_eq!(render("tests/painting/fill/missing-FuncIRI-with-a-currentColor-fallback"), 0); }
@@ -711,6 +761,9 @@ use crate::render;
 #[test] fn painting_fill_named_color() { assert_eq!(render("tests/painting/fill/named-color"), 0); }
 #[test] fn painting_fill_none() { assert_eq!(render("tests/painting/fill/none"), 0); }
 #[test] fn painting_fill_not_trimmed_attribute_value() { assert_eq!(render("tests/painting/fill/not-trimmed-attribute-value"), 0); }
+#[test] fn painting_fill_oklab_color() { assert_eq!(render("tests/painting/fill/oklab-color"), 0); }
+#[test] fn painting_fill_oklch_color() { assert_eq!(render("tests/painting/fill/oklch-color"), 0); }
+#[test] fn painting_fill_out_of_gamut_display_p3_color() { assert_eq!(render("tests/painting/fill/out-of-gamut-display-p3-color"), 0); }
 #[test] fn painting_fill_pattern_on_shape() { assert_eq!(render("tests/painting/fill/pattern-on-shape"), 0); }
 #[test] fn painting_fill_pattern_on_text() { assert_eq!(render("tests/painting/fill/pattern-on-text"), 0); }
 #[test] fn painting_fill_radial_gradient_on_shape() { assert_eq!(render("tests/painting/fill/radial-gradient-on-shape"), 0); }
@@ -723,6 +776,7 @@ use crate::render;
 #[test] fn painting_fill_rgb_color_with_floats() { assert_eq!(render("tests/painting/fill/rgb-color-with-floats"), 0); }
 #[test] fn painting_fill_rgb_color_with_percentage_overflow() { assert_eq!(render("tests/painting/fill/rgb-color-with-percentage-overflow"), 0); }
 #[test] fn painting_fill_rgb_color_with_percentage_values() { assert_eq!(render("tests/painting/fill/rgb-color-with-percentage-values"), 0); }
+#[test] fn painting_fill_rgb_color_with_space_separated_values() { assert_eq!(render("tests/painting/fill/rgb-color-with-space-separated-values"), 0); }
 #[test] fn painting_fill_rgb_color() { assert_eq!(render("tests/painting/fill/rgb-color"), 0); }
 #[test] fn painting_fill_rgb_int_int_int() { assert_eq!(render("tests/painting/fill/rgb-int-int-int"), 0); }
 #[test] fn painting_fill_rgba_0_127_0__1() { assert_eq!(render("tests/painting/fill/rgba-0-127-0--1"), 0); }
@@ -1155,24 +1209,54 @@ use crate::render;
 #[test] fn structure_image_with_zero_width_and_height() { assert_eq!(render("tests/structure/image/with-zero-width-and-height"), 0); }
 #[test] fn structure_image_zero_height() { assert_eq!(render("tests/structure/image/zero-height"), 0); }
 #[test] fn structure_image_zero_width() { assert_eq!(render("tests/structure/image/zero-width"), 0); }
+#[test] fn structure_style_attribute_selector_operators() { assert_eq!(render("tests/structure/style/attribute-selector-operators"), 0); }
 #[test] fn structure_style_attribute_selector() { assert_eq!(render("tests/structure/style/attribute-selector"), 0); }
+#[test] fn structure_style_child_pseudo_classes() { assert_eq!(render("tests/structure/style/child-pseudo-classes"), 0); }
 #[test] fn structure_style_class_selector() { assert_eq!(render("tests/structure/style/class-selector"), 0); }
 #[test] fn structure_style_combined_selectors() { assert_eq!(render("tests/structure/style/combined-selectors"), 0); }
 #[test] fn structure_style_current_color_fill_before_color() { assert_eq!(render("tests/structure/style/current-color-fill-before-color"), 0); }
 #[test] fn structure_style_current_color_stroke_before_color() { assert_eq!(render("tests/structure/style/current-color-stroke-before-color"), 0); }
+#[test] fn structure_style_custom_property_fallback() { assert_eq!(render("tests/structure/style/custom-property-fallback"), 0); }
+#[test] fn structure_style_custom_property_in_shorthand() { assert_eq!(render("tests/structure/style/custom-property-in-shorthand"), 0); }
+#[test] fn structure_style_custom_property_in_use() { assert_eq!(render("tests/structure/style/custom-property-in-use"), 0); }
+#[test] fn structure_style_custom_property_inheritance() { assert_eq!(render("tests/structure/style/custom-property-inheritance"), 0); }
+#[test] fn structure_style_custom_property_invalid_at_computed_value_time() { assert_eq!(render("tests/structure/style/custom-property-invalid-at-computed-value-time"), 0); }
+#[test] fn structure_style_custom_property_with_cycle() { assert_eq!(render("tests/structure/style/custom-property-with-cycle"), 0); }
+#[test] fn structure_style_custom_property() { assert_eq!(render("tests/structure/style/custom-property"), 0); }
 #[test] fn structure_style_external_CSS() { assert_eq!(render("tests/structure/style/external-CSS"), 0); }
 #[test] fn structure_style_iD_selector() { assert_eq!(render("tests/structure/style/iD-selector"), 0); }
+#[test] fn structure_style_import_after_rule() { assert_eq!(render("tests/structure/style/import-after-rule"), 0); }
+#[test] fn structure_style_import_cycle() { assert_eq!(render("tests/structure/style/import-cycle"), 0); }
+#[test] fn structure_style_import_missing() { assert_eq!(render("tests/structure/style/import-missing"), 0); }
+#[test] fn structure_style_import_nested() { assert_eq!(render("tests/structure/style/import-nested"), 0); }
+#[test] fn structure_style_import_precedence() { assert_eq!(render("tests/structure/style/import-precedence"), 0); }
+#[test] fn structure_style_import_with_media() { assert_eq!(render("tests/structure/style/import-with-media"), 0); }
+#[test] fn structure_style_import_with_url_function() { assert_eq!(render("tests/structure/style/import-with-url-function"), 0); }
+#[test] fn structure_style_important_with_specificity() { assert_eq!(render("tests/structure/style/important-with-specificity"), 0); }
 #[test] fn structure_style_important() { assert_eq!(render("tests/structure/style/important"), 0); }
 #[test] fn structure_style_invalid_type() { assert_eq!(render("tests/structure/style/invalid-type"), 0); }
+#[test] fn structure_style_is_pseudo_class() { assert_eq!(render("tests/structure/style/is-pseudo-class"), 0); }
+#[test] fn structure_style_media_attribute() { assert_eq!(render("tests/structure/style/media-attribute"), 0); }
+#[test] fn structure_style_media_prefers_color_scheme() { assert_eq!(render("tests/structure/style/media-prefers-color-scheme"), 0); }
+#[test] fn structure_style_media_with_invalid_query() { assert_eq!(render("tests/structure/style/media-with-invalid-query"), 0); }
+#[test] fn structure_style_media_with_print_type() { assert_eq!(render("tests/structure/style/media-with-print-type"), 0); }
 #[test] fn structure_style_non_presentational_attribute() { assert_eq!(render("tests/structure/style/non-presentational-attribute"), 0); }
+#[test] fn structure_style_not_pseudo_class() { assert_eq!(render("tests/structure/style/not-pseudo-class"), 0); }
+#[test] fn structure_style_nth_child_of_selector_pseudo_class() { assert_eq!(render("tests/structure/style/nth-child-of-selector-pseudo-class"), 0); }
+#[test] fn structure_style_nth_child_pseudo_class() { assert_eq!(render("tests/structure/style/nth-child-pseudo-class"), 0); }
+#[test] fn structure_style_nth_last_child_pseudo_class() { assert_eq!(render("tests/structure/style/nth-last-child-pseudo-class"), 0); }
+#[test] fn structure_style_nth_of_type_pseudo_class() { assert_eq!(render("tests/structure/style/nth-of-type-pseudo-class"), 0); }
 #[test] fn structure_style_resolve_order() { assert_eq!(render("tests/structure/style/resolve-order"), 0); }
+#[test] fn structure_style_root_pseudo_class() { assert_eq!(render("tests/structure/style/root-pseudo-class"), 0); }
 #[test] fn structure_style_rule_specificity() { assert_eq!(render("tests/structure/style/rule-specificity"), 0); }
+#[test] fn structure_style_sibling_combinators() { assert_eq!(render("tests/structure/style/sibling-combinators"), 0); }
 #[test] fn structure_style_style_after_usage() { assert_eq!(render("tests/structure/style/style-after-usage"), 0); }
 #[test] fn structure_style_style_inside_CDATA() { assert_eq!(render("tests/structure/style/style-inside-CDATA"), 0); }
 #[test] fn structure_style_transform() { assert_eq!(render("tests/structure/style/transform"), 0); }
 #[test] fn structure_style_type_selector() { assert_eq!(render("tests/structure/style/type-selector"), 0); }
 #[test] fn structure_style_universal_selector() { assert_eq!(render("tests/structure/style/universal-selector"), 0); }
 #[test] fn structure_style_unresolved_class_selector() { assert_eq!(render("tests/structure/style/unresolved-class-selector"), 0); }
+#[test] fn structure_style_unsupported_pseudo_element() { assert_eq!(render("tests/structure/style/unsupported-pseudo-element"), 0); }
 #[test] fn structure_style_attribute_comments() { assert_eq!(render("tests/structure/style-attribute/comments"), 0); }
 #[test] fn structure_style_attribute_non_presentational_attribute() { assert_eq!(render("tests/structure/style-attribute/non-presentational-attribute"), 0); }
 #[test] fn structure_style_attribute_simple_case() { assert_eq!(render("tests/structure/style-attribute/simple-case"), 0); }
@@ -0,0 +1,183 @@
+// Copyright 2026 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+/// A diagnostic severity.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Severity {
+    /// Not a problem, but something worth knowing, like a font fallback.
+    Info,
+    /// A recoverable problem. The affected part was skipped or replaced with a fallback.
+    Warning,
+    /// An operation has failed, like loading an image or allocating a layer.
+    Error,
+}
+
+/// A diagnostic kind.
+///
+/// Unlike messages, codes are stable and can be used to filter diagnostics.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[non_exhaustive]
+pub enum DiagnosticCode {
+    /// An attribute or a property has an invalid value.
+    InvalidAttribute,
+    /// An element is invalid, like a shape with a negative size, and was skipped.
+    InvalidElement,
+    /// A link to an element is invalid, like `url(#missing)` or a recursive one.
+    InvalidReference,
+    /// An element, an attribute value or a CSS feature is not supported.
+    Unsupported,
+    /// A style sheet, a selector or a declaration cannot be parsed.
+    InvalidStyleSheet,
+    /// An external resource, like an image or a style sheet, cannot be loaded.
+    ResourceLoadingFailed,
+    /// No font matches the requested font families.
+    FontNotFound,
+    /// A fallback font was used for characters missing in the requested font.
+    FontFallback,
+    /// A raster image cannot be decoded.
+    ImageDecodingFailed,
+    /// An element cannot be rendered.
+    RenderingFailed,
+    /// An element exceeds one of the [`Limits`](crate::Limits) and was skipped.
+    LimitExceeded,
+    /// A file access was denied by the [`ResourcePolicy`](crate::ResourcePolicy).
+    AccessDenied,
+    /// An XML is malformed and was repaired.
+    ///
+    /// Reported only when [`Options::recover_malformed_xml`](crate::Options::recover_malformed_xml)
+    /// is enabled.
+    MalformedXml,
+}
+
+impl DiagnosticCode {
+    /// Returns a code as a string, like `invalid-attribute`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DiagnosticCode::InvalidAttribute => "invalid-attribute",
+            DiagnosticCode::InvalidElement => "invalid-element",
+            DiagnosticCode::InvalidReference => "invalid-reference",
+            DiagnosticCode::Unsupported => "unsupported",
+            DiagnosticCode::InvalidStyleSheet => "invalid-style-sheet",
+            DiagnosticCode::ResourceLoadingFailed => "resource-loading-failed",
+            DiagnosticCode::FontNotFound => "font-not-found",
+            DiagnosticCode::FontFallback => "font-fallback",
+            DiagnosticCode::ImageDecodingFailed => "image-decoding-failed",
+            DiagnosticCode::RenderingFailed => "rendering-failed",
+            DiagnosticCode::LimitExceeded => "limit-exceeded",
+            DiagnosticCode::AccessDenied => "access-denied",
+            DiagnosticCode::MalformedXml => "malformed-xml",
+        }
+    }
+}
+
+impl std::fmt::Display for DiagnosticCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A problem found during parsing or rendering.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Diagnostic {
+    /// A diagnostic kind.
+    pub code: DiagnosticCode,
+    /// A diagnostic severity.
+    pub severity: Severity,
+    /// A human-readable message.
+    pub message: String,
+    /// An `id` attribute of the element that caused the problem.
+    ///
+    /// `None` when the problem isn't related to an element or when the element has no ID.
+    pub element_id: Option<String>,
+    /// A position of the element that caused the problem in the source XML.
+    ///
+    /// Available only during parsing.
+    pub position: Option<roxmltree::TextPos>,
+}
+
+impl Diagnostic {
+    /// Creates a new diagnostic that isn't related to an element.
+    pub fn new(code: DiagnosticCode, severity: Severity, message: impl Into<String>) -> Self {
+        Diagnostic {
+            code,
+            severity,
+            message: message.into(),
+            element_id: None,
+            position: None,
+        }
+    }
+
+    /// Sets an element ID. Empty IDs are ignored.
+    pub fn with_element_id(mut self, id: &str) -> Self {
+        if !id.is_empty() {
+            self.element_id = Some(id.to_string());
+        }
+
+        self
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match (self.position, &self.element_id) {
+            (Some(pos), Some(id)) => write!(f, "{} (element '{}' at {})", self.message, id, pos),
+            (Some(pos), None) => write!(f, "{} (at {})", self.message, pos),
+            (None, Some(id)) => write!(f, "{} (element '{}')", self.message, id),
+            (None, None) => f.write_str(&self.message),
+        }
+    }
+}
+
+/// A shorthand for [DiagnosticSink]'s report function.
+pub type DiagnosticReportFn<'a> = Box<dyn Fn(Diagnostic) + Send + Sync + 'a>;
+
+/// A receiver of parsing and rendering diagnostics.
+///
+/// This type can be useful if you want to collect diagnostics per document,
+/// instead of the global logger, which is used by default.
+pub struct DiagnosticSink<'a> {
+    /// Function that will be called for each diagnostic.
+    pub report: DiagnosticReportFn<'a>,
+}
+
+impl Default for DiagnosticSink<'_> {
+    fn default() -> Self {
+        DiagnosticSink {
+            report: DiagnosticSink::default_report(),
+        }
+    }
+}
+
+impl DiagnosticSink<'_> {
+    /// Creates a default report function.
+    ///
+    /// The default implementation forwards diagnostics to the `log` crate.
+    pub fn default_report() -> DiagnosticReportFn<'static> {
+        Box::new(|diagnostic: Diagnostic| match diagnostic.severity {
+            Severity::Info => log::info!("{}", diagnostic),
+            Severity::Warning => log::warn!("{}", diagnostic),
+            Severity::Error => log::error!("{}", diagnostic),
+        })
+    }
+
+    /// Reports a diagnostic.
+    pub fn report(&self, diagnostic: Diagnostic) {
+        (self.report)(diagnostic)
+    }
+
+    /// Reports a warning that isn't related to an element.
+    pub(crate) fn warn(&self, code: DiagnosticCode, message: impl Into<String>) {
+        self.report(Diagnostic::new(code, Severity::Warning, message));
+    }
+
+    /// Reports an error that isn't related to an element.
+    pub(crate) fn error(&self, code: DiagnosticCode, message: impl Into<String>) {
+        self.report(Diagnostic::new(code, Severity::Error, message));
+    }
+}
+
+impl std::fmt::Debug for DiagnosticSink<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("DiagnosticSink { .. }")
+    }
+}
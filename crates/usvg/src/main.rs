@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use std::fs::File;
-use std::io::{self, Read, Write};
+use std::io::{self, Write};
 use std::path::PathBuf;
 use std::process;
 use std::sync::Arc;
@@ -27,6 +27,11 @@ OPTIONS:
                                     [default: 96] [possible values: 10..4000 (inclusive)]
   --stylesheet PATH                 Inject a stylesheet that should be used when resolving
                                     CSS attributes.
+  --color-scheme SCHEME             Sets the preferred color scheme used by
+                                    '@media (prefers-color-scheme)' rules
+                                    [default: light] [possible values: light, dark]
+  --animation-time SECONDS          Converts SMIL animations as they appear
+                                    at the specified time
   --languages LANG                  Sets a comma-separated list of languages that
                                     will be used during the 'systemLanguage'
                                     attribute resolving
@@ -49,6 +54,14 @@ OPTIONS:
                                     contains the SVG file, but can be set to any.
                                     [default: input file directory
                                     or none when reading from stdin]
+  --resource-policy POLICY          Sets which local files referenced by the SVG
+                                    can be loaded
+                                    [default: allow-all]
+                                    [possible values: allow-all, deny-all, resources-dir]
+  --allow-resource PATH             Allows loading only the specified file or files
+                                    inside the specified directory.
+                                    Cannot be used with --resource-policy.
+                                    This option can be set multiple times
 
   --font-family FAMILY              Sets the default font family that will be
                                     used when no 'font-family' is present
@@ -91,6 +104,10 @@ OPTIONS:
                                     option. [values: 1..4294967295 (inclusive)] [default: 100]
 
   --preserve-text                   Do not convert text into paths.
+  --preserve-metadata               Keeps title, desc and metadata elements,
+                                    as well as class and data-* attributes
+  --preserve-namespace URI          Keeps attributes from the specified namespace.
+                                    Can be set multiple times
   --id-prefix                       Adds a prefix to each ID attribute
   --indent INDENT                   Sets the XML nodes indent
                                     [values: none, 0, 1, 2, 3, 4, tabs] [default: 4]
@@ -102,6 +119,9 @@ OPTIONS:
   --transforms-precision NUM        Set the transform values numeric precision.
                                     Smaller precision can lead to a malformed output in some cases
                                     [values: 2..8 (inclusive)] [default: 8]
+  --list-deps                       Prints external files referenced by the input SVG
+                                    in the Makefile dependency format, instead of
+                                    converting it. The rule target is the output file
   --quiet                           Disables warnings
 
 ARGS:
@@ -117,6 +137,8 @@ struct Args {
     text_rendering: usvg::TextRendering,
     image_rendering: usvg::ImageRendering,
     resources_dir: Option<PathBuf>,
+    resource_policy: Option<usvg::ResourcePolicy>,
+    allowed_resources: Vec<PathBuf>,
 
     font_family: Option<String>,
     font_size: u32,
@@ -129,7 +151,10 @@ struct Args {
     font_dirs: Vec<PathBuf>,
     skip_system_fonts: bool,
     preserve_text: bool,
+    preserve_metadata: bool,
+    preserve_namespaces: Vec<String>,
     list_fonts: bool,
+    list_deps: bool,
     default_width: u32,
     default_height: u32,
 
@@ -139,6 +164,8 @@ struct Args {
     coordinates_precision: Option<u8>,
     transforms_precision: Option<u8>,
     style_sheet: Option<PathBuf>,
+    color_scheme: usvg::ColorScheme,
+    animation_time: Option<f32>,
 
     quiet: bool,
 
@@ -176,6 +203,8 @@ fn collect_args() -> Result<Args, pico_args::Error> {
         resources_dir: input
             .opt_value_from_str("--resources-dir")
             .unwrap_or_default(),
+        resource_policy: input.opt_value_from_str("--resource-policy")?,
+        allowed_resources: input.values_from_str("--allow-resource")?,
 
         font_family: input.opt_value_from_str("--font-family")?,
         font_size: input
@@ -190,7 +219,10 @@ fn collect_args() -> Result<Args, pico_args::Error> {
         font_dirs: input.values_from_str("--use-fonts-dir")?,
         skip_system_fonts: input.contains("--skip-system-fonts"),
         preserve_text: input.contains("--preserve-text"),
+        preserve_metadata: input.contains("--preserve-metadata"),
+        preserve_namespaces: input.values_from_str("--preserve-namespace")?,
         list_fonts: input.contains("--list-fonts"),
+        list_deps: input.contains("--list-deps"),
         default_width: input
             .opt_value_from_fn("--default-width", parse_length)?
             .unwrap_or(100),
@@ -209,6 +241,10 @@ fn collect_args() -> Result<Args, pico_args::Error> {
             .opt_value_from_fn("--coordinates-precision", parse_precision)?,
         transforms_precision: input.opt_value_from_fn("--transforms-precision", parse_precision)?,
         style_sheet: input.opt_value_from_str("--stylesheet").unwrap_or_default(),
+        color_scheme: input
+            .opt_value_from_str("--color-scheme")?
+            .unwrap_or_default(),
+        animation_time: input.opt_value_from_fn("--animation-time", parse_animation_time)?,
 
         quiet: input.contains("--quiet"),
 
@@ -237,6 +273,16 @@ fn parse_font_size(s: &str) -> Result<u32, String> {
     }
 }
 
+fn parse_animation_time(s: &str) -> Result<f32, String> {
+    let n: f32 = s.parse().map_err(|_| "invalid number")?;
+
+    if n.is_finite() {
+        Ok(n)
+    } else {
+        Err("animation time should be finite".to_string())
+    }
+}
+
 fn parse_languages(s: &str) -> Result<Vec<String>, String> {
     let mut langs = Vec::new();
     for lang in s.split(',') {
@@ -403,6 +449,15 @@ fn process(args: Args) -> Result<(), String> {
         }
     };
 
+    let resource_policy = match (args.resource_policy, args.allowed_resources.is_empty()) {
+        (Some(policy), true) => policy,
+        (None, true) => usvg::ResourcePolicy::AllowAll,
+        (None, false) => usvg::ResourcePolicy::Allowlist(args.allowed_resources),
+        (Some(_), false) => {
+            return Err("--allow-resource cannot be used with --resource-policy".to_string());
+        }
+    };
+
     let style_sheet = match args.style_sheet.as_ref() {
         Some(p) => Some(
             std::fs::read(&p)
@@ -413,8 +468,16 @@ fn process(args: Args) -> Result<(), String> {
         None => None,
     };
 
+    let mut metadata = if args.preserve_metadata {
+        usvg::MetadataOptions::all()
+    } else {
+        usvg::MetadataOptions::default()
+    };
+    metadata.attribute_namespaces = args.preserve_namespaces;
+
     let re_opt = usvg::Options {
         resources_dir,
+        resource_policy,
         dpi: args.dpi as f32,
         font_family: args
             .font_family
@@ -432,12 +495,40 @@ fn process(args: Args) -> Result<(), String> {
         font_resolver: usvg::FontResolver::default(),
         fontdb: Arc::new(fontdb),
         style_sheet,
+        condition_resolver: usvg::ConditionResolver::default(),
+        foreign_object_resolver: usvg::ForeignObjectResolver::default(),
+        style_sheet_resolver: usvg::StyleSheetResolver::default(),
+        media: usvg::MediaContext {
+            color_scheme: args.color_scheme,
+            ..usvg::MediaContext::default()
+        },
+        custom_properties: Default::default(),
+        diagnostics: usvg::DiagnosticSink::default(),
+        source_spans: false,
+        metadata,
+        limits: usvg::Limits::default(),
+        recover_malformed_xml: false,
+        fragment: None,
+        animation_time: args.animation_time,
     };
 
+    let max_input_size = re_opt.limits.max_input_size;
     let input_svg = match in_svg {
-        InputFrom::Stdin => load_stdin(),
-        InputFrom::File(ref path) => std::fs::read(path).map_err(|e| e.to_string()),
-    }?;
+        InputFrom::Stdin => usvg::read_svg_data(io::stdin().lock(), max_input_size),
+        InputFrom::File(ref path) => File::open(path)
+            .map_err(usvg::Error::ReadingFailed)
+            .and_then(|file| usvg::read_svg_data(file, max_input_size)),
+    }
+    .map_err(|e| e.to_string())?;
+
+    if args.list_deps {
+        let target = match out_svg {
+            OutputTo::Stdout => in_svg_name(&in_svg),
+            OutputTo::File(path) => path,
+        };
+
+        return list_deps(target, &in_svg, &input_svg, &re_opt);
+    }
 
     let tree = usvg::Tree::from_data(&input_svg, &re_opt).map_err(|e| format!("{}", e))?;
 
@@ -469,16 +560,63 @@ fn process(args: Args) -> Result<(), String> {
     Ok(())
 }
 
-fn load_stdin() -> Result<Vec<u8>, String> {
-    let mut buf = Vec::new();
-    let stdin = io::stdin();
-    let mut handle = stdin.lock();
+fn in_svg_name<'a>(in_svg: &InputFrom<'a>) -> &'a str {
+    match in_svg {
+        InputFrom::Stdin => "-",
+        InputFrom::File(path) => path,
+    }
+}
+
+/// Prints a Makefile rule with the input file and all of its dependencies.
+fn list_deps(
+    target: &str,
+    in_svg: &InputFrom,
+    data: &[u8],
+    opt: &usvg::Options,
+) -> Result<(), String> {
+    let text = usvg::decode_text(data).map_err(|e| e.to_string())?;
+
+    let deps = usvg::collect_dependencies(&text, opt).map_err(|e| e.to_string())?;
+
+    let mut rule = escape_make_path(target);
+    rule.push(':');
+    // Skip the input when it is the target itself.
+    if let InputFrom::File(path) = in_svg {
+        if *path != target {
+            rule.push(' ');
+            rule.push_str(&escape_make_path(path));
+        }
+    }
+
+    // The same file can be referenced in different ways, like an image and an feImage.
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for dep in deps {
+        if !paths.contains(&dep.path) {
+            rule.push(' ');
+            rule.push_str(&escape_make_path(&dep.path.to_string_lossy()));
+            paths.push(dep.path);
+        }
+    }
+
+    println!("{}", rule);
+    Ok(())
+}
 
-    handle
-        .read_to_end(&mut buf)
-        .map_err(|_| "failed to read from stdin".to_string())?;
+/// Escapes characters that have a special meaning in Makefile rules.
+fn escape_make_path(path: &str) -> String {
+    let mut s = String::with_capacity(path.len());
+    for c in path.chars() {
+        match c {
+            ' ' | '#' | ':' => {
+                s.push('\\');
+                s.push(c);
+            }
+            '$' => s.push_str("$$"),
+            _ => s.push(c),
+        }
+    }
 
-    Ok(buf)
+    s
 }
 
 /// A simple stderr logger.
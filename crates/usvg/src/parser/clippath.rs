@@ -4,9 +4,19 @@
 use std::str::FromStr;
 use std::sync::Arc;
 
-use super::converter;
-use super::svgtree::{AId, EId, SvgNode};
-use crate::{ClipPath, Group, NonEmptyString, NonZeroRect, Transform, Units};
+use svgtypes::Length;
+use tiny_skia_path::PathBuilder;
+
+use super::css::{
+    self, GeometryBox, PositionOffset, Token, expand_sides, parse_lengths, parse_position, tokenize,
+};
+use super::shapes::PathBuilderExt;
+use super::svgtree::{AId, EId, FromValue, SvgNode};
+use super::{converter, shapes};
+use crate::{
+    ClipPath, DiagnosticCode, Fill, FillRule, Group, IsValidLength, Node, NonEmptyString,
+    NonZeroRect, Path, Rect, Transform, Units,
+};
 
 pub(crate) fn convert(
     node: SvgNode,
@@ -42,7 +52,10 @@ pub(crate) fn convert(
         let object_bbox = match object_bbox {
             Some(v) => v,
             None => {
-                log::warn!("Clipping of zero-sized shapes is not allowed.");
+                node.warn(
+                    DiagnosticCode::InvalidElement,
+                    "Clipping of zero-sized shapes is not allowed.",
+                );
                 return None;
             }
         };
@@ -103,7 +116,10 @@ fn resolve_clip_path_transform(node: SvgNode, state: &converter::State) -> Optio
     let ts = match svgtypes::Transform::from_str(value) {
         Ok(v) => v,
         Err(_) => {
-            log::warn!("Failed to parse {} value: '{}'.", AId::Transform, value);
+            node.warn(
+                DiagnosticCode::InvalidAttribute,
+                format!("Failed to parse {} value: '{}'.", AId::Transform, value),
+            );
             return None;
         }
     };
@@ -123,3 +139,388 @@ fn resolve_clip_path_transform(node: SvgNode, state: &converter::State) -> Optio
         None
     }
 }
+
+/// A `clip-path` property value.
+pub(crate) enum ClipPathValue<'a, 'input: 'a> {
+    /// A link to a `clipPath` element.
+    Link(SvgNode<'a, 'input>),
+    /// A `<basic-shape>` and/or a `<geometry-box>`.
+    ///
+    /// When the shape is not set, the reference box itself is used.
+    Shape(Option<BasicShape>, GeometryBox),
+}
+
+impl<'a, 'input: 'a> FromValue<'a, 'input> for ClipPathValue<'a, 'input> {
+    fn parse(node: SvgNode<'a, 'input>, aid: AId, value: &'a str) -> Option<Self> {
+        if value.trim_start().starts_with("url(") {
+            return SvgNode::parse(node, aid, value).map(ClipPathValue::Link);
+        }
+
+        let (shape, geometry_box) = parse_shape_value(node, value)?;
+        Some(ClipPathValue::Shape(shape, geometry_box))
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum ShapeRadius {
+    Length(Length),
+    ClosestSide,
+    FarthestSide,
+}
+
+/// A [`<basic-shape>`](https://www.w3.org/TR/css-shapes-1/#basic-shape-functions).
+#[derive(Clone, Debug)]
+pub(crate) enum BasicShape {
+    Circle {
+        r: ShapeRadius,
+        cx: PositionOffset,
+        cy: PositionOffset,
+    },
+    Ellipse {
+        rx: ShapeRadius,
+        ry: ShapeRadius,
+        cx: PositionOffset,
+        cy: PositionOffset,
+    },
+    Inset {
+        /// Top, right, bottom and left offsets.
+        offsets: [Length; 4],
+        /// Top-left, top-right, bottom-right and bottom-left corner radii.
+        radii: [(Length, Length); 4],
+    },
+    Polygon {
+        rule: FillRule,
+        points: Vec<(Length, Length)>,
+    },
+    Path {
+        rule: FillRule,
+        path: Arc<tiny_skia_path::Path>,
+    },
+}
+
+/// Converts a `clip-path` `<basic-shape>` or `<geometry-box>` into a `ClipPath`.
+///
+/// Unlike `clipPath` elements, such clip paths are always element-specific,
+/// so they are never cached.
+pub(crate) fn convert_shape(
+    node: SvgNode,
+    shape: Option<&BasicShape>,
+    geometry_box: GeometryBox,
+    object_bbox: Option<NonZeroRect>,
+    group: &mut Group,
+    state: &converter::State,
+    cache: &mut converter::Cache,
+) -> Option<Arc<ClipPath>> {
+    let reference_box = match geometry_box {
+        GeometryBox::Fill => object_bbox,
+        GeometryBox::Stroke => {
+            group.calculate_bounding_boxes();
+            group.stroke_bounding_box.to_non_zero_rect()
+        }
+        // The reference box is positioned at the origin of the viewBox coordinate system.
+        GeometryBox::View => {
+            NonZeroRect::from_xywh(0.0, 0.0, state.view_box.width(), state.view_box.height())
+        }
+    };
+
+    let reference_box = match reference_box {
+        Some(v) => v,
+        None => {
+            node.warn(
+                DiagnosticCode::InvalidElement,
+                "Clipping of zero-sized shapes is not allowed.",
+            );
+            return None;
+        }
+    };
+
+    let (path, rule) = match shape {
+        Some(shape) => shape.to_path(reference_box, node, state)?,
+        None => (
+            Arc::new(PathBuilder::from_rect(reference_box.to_rect())),
+            FillRule::NonZero,
+        ),
+    };
+
+    let mut path = Path::new_simple(path)?;
+    path.fill = Some(Fill {
+        rule,
+        ..Fill::default()
+    });
+
+    let mut clip = ClipPath::empty(cache.gen_clip_path_id());
+    clip.root.children.push(Node::Path(Box::new(path)));
+    clip.root.calculate_bounding_boxes();
+    Some(Arc::new(clip))
+}
+
+impl BasicShape {
+    fn to_path(
+        &self,
+        rect: NonZeroRect,
+        node: SvgNode,
+        state: &converter::State,
+    ) -> Option<(Arc<tiny_skia_path::Path>, FillRule)> {
+        let len = |length: Length, base: f32| css::convert_length(length, base, node, state);
+        let pos = |p: PositionOffset, start: f32, size: f32| p.resolve(start, size, node, state);
+
+        // `closest-side` and `farthest-side` distances along a single axis.
+        let sides = |c: f32, start: f32, end: f32| {
+            let d1 = (c - start).abs();
+            let d2 = (c - end).abs();
+            (d1.min(d2), d1.max(d2))
+        };
+
+        match self {
+            BasicShape::Circle { r, cx, cy } => {
+                let cx = pos(*cx, rect.x(), rect.width());
+                let cy = pos(*cy, rect.y(), rect.height());
+                let (closest_x, farthest_x) = sides(cx, rect.left(), rect.right());
+                let (closest_y, farthest_y) = sides(cy, rect.top(), rect.bottom());
+                let r = match *r {
+                    ShapeRadius::Length(r) => {
+                        let diagonal = (rect.width().powi(2) + rect.height().powi(2)).sqrt();
+                        len(r, diagonal / std::f32::consts::SQRT_2)
+                    }
+                    ShapeRadius::ClosestSide => closest_x.min(closest_y),
+                    ShapeRadius::FarthestSide => farthest_x.max(farthest_y),
+                };
+
+                if !r.is_valid_length() {
+                    return None;
+                }
+
+                Some((shapes::ellipse_to_path(cx, cy, r, r)?, FillRule::NonZero))
+            }
+            BasicShape::Ellipse { rx, ry, cx, cy } => {
+                let cx = pos(*cx, rect.x(), rect.width());
+                let cy = pos(*cy, rect.y(), rect.height());
+                let radius = |r: ShapeRadius, (closest, farthest): (f32, f32), base: f32| match r {
+                    ShapeRadius::Length(r) => len(r, base),
+                    ShapeRadius::ClosestSide => closest,
+                    ShapeRadius::FarthestSide => farthest,
+                };
+                let rx = radius(*rx, sides(cx, rect.left(), rect.right()), rect.width());
+                let ry = radius(*ry, sides(cy, rect.top(), rect.bottom()), rect.height());
+
+                if !rx.is_valid_length() || !ry.is_valid_length() {
+                    return None;
+                }
+
+                Some((shapes::ellipse_to_path(cx, cy, rx, ry)?, FillRule::NonZero))
+            }
+            BasicShape::Inset { offsets, radii } => {
+                let [top, right, bottom, left] = *offsets;
+                let inner = Rect::from_ltrb(
+                    rect.left() + len(left, rect.width()),
+                    rect.top() + len(top, rect.height()),
+                    rect.right() - len(right, rect.width()),
+                    rect.bottom() - len(bottom, rect.height()),
+                )?;
+
+                let mut radii =
+                    radii.map(|(rx, ry)| (len(rx, rect.width()), len(ry, rect.height())));
+
+                // Scale down overlapping corners, just like `border-radius` does.
+                let [tl, tr, br, bl] = radii;
+                let mut scale: f32 = 1.0;
+                for (side, sum) in [
+                    (inner.width(), tl.0 + tr.0),
+                    (inner.width(), bl.0 + br.0),
+                    (inner.height(), tl.1 + bl.1),
+                    (inner.height(), tr.1 + br.1),
+                ] {
+                    if sum > side {
+                        scale = scale.min(side / sum);
+                    }
+                }
+
+                for r in &mut radii {
+                    r.0 *= scale;
+                    r.1 *= scale;
+                }
+
+                if radii.iter().all(|r| r.0 <= 0.0 || r.1 <= 0.0) {
+                    return Some((Arc::new(PathBuilder::from_rect(inner)), FillRule::NonZero));
+                }
+
+                let [tl, tr, br, bl] = radii;
+                let (l, t, r, b) = (inner.left(), inner.top(), inner.right(), inner.bottom());
+                let mut builder = PathBuilder::new();
+                builder.move_to(l + tl.0, t);
+                builder.line_to(r - tr.0, t);
+                builder.arc_to(tr.0, tr.1, 0.0, false, true, r, t + tr.1);
+                builder.line_to(r, b - br.1);
+                builder.arc_to(br.0, br.1, 0.0, false, true, r - br.0, b);
+                builder.line_to(l + bl.0, b);
+                builder.arc_to(bl.0, bl.1, 0.0, false, true, l, b - bl.1);
+                builder.line_to(l, t + tl.1);
+                builder.arc_to(tl.0, tl.1, 0.0, false, true, l + tl.0, t);
+                builder.close();
+                Some((Arc::new(builder.finish()?), FillRule::NonZero))
+            }
+            BasicShape::Polygon { rule, points } => {
+                let mut builder = PathBuilder::new();
+                for &(x, y) in points {
+                    let x = rect.x() + len(x, rect.width());
+                    let y = rect.y() + len(y, rect.height());
+                    if builder.is_empty() {
+                        builder.move_to(x, y);
+                    } else {
+                        builder.line_to(x, y);
+                    }
+                }
+                builder.close();
+                Some((Arc::new(builder.finish()?), *rule))
+            }
+            BasicShape::Path { rule, path } => {
+                // Path coordinates are relative to the reference box origin.
+                let ts = Transform::from_translate(rect.x(), rect.y());
+                let path = path.as_ref().clone().transform(ts)?;
+                Some((Arc::new(path), *rule))
+            }
+        }
+    }
+}
+
+fn parse_shape_value(node: SvgNode, value: &str) -> Option<(Option<BasicShape>, GeometryBox)> {
+    let mut shape = None;
+    let mut geometry_box = None;
+    for token in tokenize(value)? {
+        if let Some((name, args)) = token.function() {
+            if shape.is_some() {
+                return None;
+            }
+
+            shape = Some(parse_basic_shape(node, name, args)?);
+        } else {
+            if geometry_box.is_some() {
+                return None;
+            }
+
+            geometry_box = match token {
+                Token::Word(s) => Some(GeometryBox::parse(s)?),
+                _ => return None,
+            };
+        }
+    }
+
+    if shape.is_none() && geometry_box.is_none() {
+        return None;
+    }
+
+    // The default reference box is `border-box`, which is `stroke-box` for SVG elements.
+    Some((shape, geometry_box.unwrap_or(GeometryBox::Stroke)))
+}
+
+fn parse_basic_shape(node: SvgNode, name: &str, args: &str) -> Option<BasicShape> {
+    let tokens = tokenize(args)?;
+    match name {
+        "circle" => {
+            let (radii, (cx, cy)) = parse_radii_and_position(&tokens)?;
+            let r = match radii.as_slice() {
+                [] => ShapeRadius::ClosestSide,
+                [r] => *r,
+                _ => return None,
+            };
+
+            Some(BasicShape::Circle { r, cx, cy })
+        }
+        "ellipse" => {
+            let (radii, (cx, cy)) = parse_radii_and_position(&tokens)?;
+            let (rx, ry) = match radii.as_slice() {
+                [] => (ShapeRadius::ClosestSide, ShapeRadius::ClosestSide),
+                [rx, ry] => (*rx, *ry),
+                _ => return None,
+            };
+
+            Some(BasicShape::Ellipse { rx, ry, cx, cy })
+        }
+        "inset" => {
+            let (offsets, radii) = match tokens.iter().position(|t| *t == Token::Word("round")) {
+                Some(idx) => (&tokens[..idx], Some(&tokens[idx + 1..])),
+                None => (tokens.as_slice(), None),
+            };
+
+            let offsets = expand_sides(&parse_lengths(offsets)?)?;
+            let radii = match radii {
+                Some(radii) => parse_border_radius(radii)?,
+                None => [(Length::zero(), Length::zero()); 4],
+            };
+
+            Some(BasicShape::Inset { offsets, radii })
+        }
+        "polygon" => {
+            let (rule, tokens) = split_fill_rule(&tokens);
+            let mut points = Vec::new();
+            for point in tokens.split(|t| *t == Token::Comma) {
+                match parse_lengths(point)?.as_slice() {
+                    [x, y] => points.push((*x, *y)),
+                    _ => return None,
+                }
+            }
+
+            Some(BasicShape::Polygon { rule, points })
+        }
+        "path" => {
+            let (rule, tokens) = split_fill_rule(&tokens);
+            let path = match tokens {
+                [Token::String(data)] => shapes::parse_path_data(node, data)?,
+                _ => return None,
+            };
+
+            Some(BasicShape::Path {
+                rule,
+                path: Arc::new(path),
+            })
+        }
+        _ => None,
+    }
+}
+
+fn split_fill_rule<'a, 'b>(tokens: &'b [Token<'a>]) -> (FillRule, &'b [Token<'a>]) {
+    match tokens {
+        [Token::Word("nonzero"), Token::Comma, rest @ ..] => (FillRule::NonZero, rest),
+        [Token::Word("evenodd"), Token::Comma, rest @ ..] => (FillRule::EvenOdd, rest),
+        _ => (FillRule::NonZero, tokens),
+    }
+}
+
+fn parse_border_radius(tokens: &[Token]) -> Option<[(Length, Length); 4]> {
+    let (horizontal, vertical) = match tokens.iter().position(|t| *t == Token::Slash) {
+        Some(idx) => (&tokens[..idx], &tokens[idx + 1..]),
+        None => (tokens, tokens),
+    };
+
+    let horizontal = expand_sides(&parse_lengths(horizontal)?)?;
+    let vertical = expand_sides(&parse_lengths(vertical)?)?;
+    if horizontal.iter().chain(&vertical).any(|r| r.number < 0.0) {
+        return None;
+    }
+
+    Some([0, 1, 2, 3].map(|i| (horizontal[i], vertical[i])))
+}
+
+fn parse_radii_and_position(
+    tokens: &[Token],
+) -> Option<(Vec<ShapeRadius>, (PositionOffset, PositionOffset))> {
+    let (radii, position) = match tokens.iter().position(|t| *t == Token::Word("at")) {
+        Some(idx) => (&tokens[..idx], parse_position(&tokens[idx + 1..])?),
+        None => (tokens, (PositionOffset::CENTER, PositionOffset::CENTER)),
+    };
+
+    let radii = radii
+        .iter()
+        .map(|t| match *t {
+            Token::Word("closest-side") => Some(ShapeRadius::ClosestSide),
+            Token::Word("farthest-side") => Some(ShapeRadius::FarthestSide),
+            Token::Word(s) => {
+                let r = Length::from_str(s).ok()?;
+                (r.number >= 0.0).then_some(ShapeRadius::Length(r))
+            }
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some((radii, position))
+}
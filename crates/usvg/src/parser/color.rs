@@ -0,0 +1,1104 @@
+// Copyright 2026 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! CSS Color Module Level 4 and 5 parsing.
+//!
+//! `svgtypes` understands only the legacy sRGB syntaxes, so everything else,
+//! like `oklch()`, `lab()`, `color(display-p3 ...)` and `color-mix()`,
+//! is handled here and gamut mapped to 8-bit sRGB.
+
+use std::str::FromStr;
+
+type Matrix = [[f64; 3]; 3];
+
+const SRGB_TO_XYZ: Matrix = [
+    [0.41239080, 0.35758434, 0.18048079],
+    [0.21263901, 0.71516868, 0.07219232],
+    [0.01933082, 0.11919478, 0.95053215],
+];
+
+const XYZ_TO_SRGB: Matrix = [
+    [3.24096994, -1.53738318, -0.49861076],
+    [-0.96924364, 1.87596750, 0.04155506],
+    [0.05563008, -0.20397696, 1.05697151],
+];
+
+const DISPLAY_P3_TO_XYZ: Matrix = [
+    [0.48657095, 0.26566769, 0.19821729],
+    [0.22897456, 0.69173852, 0.07928691],
+    [0.00000000, 0.04511338, 1.04394437],
+];
+
+const XYZ_TO_DISPLAY_P3: Matrix = [
+    [2.49349691, -0.93138362, -0.40271078],
+    [-0.82948897, 1.76266406, 0.02362469],
+    [0.03584583, -0.07617239, 0.95688452],
+];
+
+const A98_RGB_TO_XYZ: Matrix = [
+    [0.57666904, 0.18555824, 0.18822865],
+    [0.29734498, 0.62736357, 0.07529146],
+    [0.02703136, 0.07068885, 0.99133754],
+];
+
+const XYZ_TO_A98_RGB: Matrix = [
+    [2.04158790, -0.56500697, -0.34473135],
+    [-0.96924364, 1.87596750, 0.04155506],
+    [0.01344428, -0.11836239, 1.01517499],
+];
+
+const PROPHOTO_RGB_TO_XYZ_D50: Matrix = [
+    [0.79776664, 0.13518130, 0.03134773],
+    [0.28807483, 0.71183523, 0.00008994],
+    [0.00000000, 0.00000000, 0.82510460],
+];
+
+const XYZ_D50_TO_PROPHOTO_RGB: Matrix = [
+    [1.34578688, -0.25557209, -0.05110186],
+    [-0.54463071, 1.50824774, 0.02052745],
+    [0.00000000, 0.00000000, 1.21196755],
+];
+
+const REC2020_TO_XYZ: Matrix = [
+    [0.63695805, 0.14461690, 0.16888098],
+    [0.26270021, 0.67799807, 0.05930172],
+    [0.00000000, 0.02807269, 1.06098506],
+];
+
+const XYZ_TO_REC2020: Matrix = [
+    [1.71665119, -0.35567078, -0.25336628],
+    [-0.66668435, 1.61648124, 0.01576855],
+    [0.01763986, -0.04277061, 0.94210312],
+];
+
+// Bradford chromatic adaptation.
+const D50_TO_D65: Matrix = [
+    [0.95547342, -0.02309845, 0.06325924],
+    [-0.02836971, 1.00999540, 0.02104144],
+    [0.01231401, -0.02050765, 1.33036593],
+];
+
+const D65_TO_D50: Matrix = [
+    [1.04792979, 0.02294687, -0.05019227],
+    [0.02962781, 0.99043443, -0.01707380],
+    [-0.00924304, 0.01505519, 0.75187428],
+];
+
+const XYZ_TO_OKLAB_LMS: Matrix = [
+    [0.81902244, 0.36190626, -0.12887378],
+    [0.03298365, 0.92928686, 0.03614467],
+    [0.04817719, 0.26423953, 0.63354783],
+];
+
+const OKLAB_LMS_TO_XYZ: Matrix = [
+    [1.22687988, -0.55781499, 0.28139105],
+    [-0.04057575, 1.11228680, -0.07171106],
+    [-0.07637294, -0.42149333, 1.58692402],
+];
+
+const LMS_TO_OKLAB: Matrix = [
+    [0.21045427, 0.79361777, -0.00407204],
+    [1.97799853, -2.42859224, 0.45059371],
+    [0.02590404, 0.78277171, -0.80867575],
+];
+
+const OKLAB_TO_LMS: Matrix = [
+    [1.0, 0.39633778, 0.21580376],
+    [1.0, -0.10556135, -0.06385417],
+    [1.0, -0.08948418, -1.29148555],
+];
+
+const D50_WHITE: [f64; 3] = [0.3457 / 0.3585, 1.0, (1.0 - 0.3457 - 0.3585) / 0.3585];
+
+/// A color space that a color can be specified or interpolated in.
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Space {
+    Srgb,
+    SrgbLinear,
+    DisplayP3,
+    A98Rgb,
+    ProphotoRgb,
+    Rec2020,
+    XyzD50,
+    XyzD65,
+    Lab,
+    Lch,
+    Oklab,
+    Oklch,
+    Hsl,
+    Hwb,
+}
+
+impl Space {
+    fn from_name(name: &str) -> Option<Self> {
+        let space = match name.to_ascii_lowercase().as_str() {
+            "srgb" => Space::Srgb,
+            "srgb-linear" => Space::SrgbLinear,
+            "display-p3" => Space::DisplayP3,
+            "a98-rgb" => Space::A98Rgb,
+            "prophoto-rgb" => Space::ProphotoRgb,
+            "rec2020" => Space::Rec2020,
+            "xyz-d50" => Space::XyzD50,
+            "xyz" | "xyz-d65" => Space::XyzD65,
+            "lab" => Space::Lab,
+            "lch" => Space::Lch,
+            "oklab" => Space::Oklab,
+            "oklch" => Space::Oklch,
+            "hsl" => Space::Hsl,
+            "hwb" => Space::Hwb,
+            _ => return None,
+        };
+        Some(space)
+    }
+
+    /// Returns the index of the hue component, if any.
+    fn hue_index(self) -> Option<usize> {
+        match self {
+            Space::Hsl | Space::Hwb => Some(0),
+            Space::Lch | Space::Oklch => Some(2),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for spaces that are allowed in `color()`.
+    fn is_predefined(self) -> bool {
+        !matches!(
+            self,
+            Space::Lab | Space::Lch | Space::Oklab | Space::Oklch | Space::Hsl | Space::Hwb
+        )
+    }
+}
+
+/// A color in an arbitrary color space.
+///
+/// Missing components, aka `none`, are stored as `None`.
+#[derive(Clone, Copy, Debug)]
+struct Value {
+    space: Space,
+    components: [Option<f64>; 3],
+    alpha: Option<f64>,
+}
+
+impl Value {
+    fn from_svgtypes(c: svgtypes::Color) -> Self {
+        Value {
+            space: Space::Srgb,
+            components: [
+                Some(c.red as f64 / 255.0),
+                Some(c.green as f64 / 255.0),
+                Some(c.blue as f64 / 255.0),
+            ],
+            alpha: Some(c.alpha as f64 / 255.0),
+        }
+    }
+
+    fn resolved(&self) -> [f64; 3] {
+        self.components.map(|c| c.unwrap_or(0.0))
+    }
+
+    /// Converts the color into another space.
+    ///
+    /// Powerless hues are marked as missing.
+    fn convert(&self, space: Space) -> Value {
+        if self.space == space {
+            return *self;
+        }
+
+        let xyz = to_xyz(self.space, self.resolved());
+        let c = from_xyz(space, xyz);
+        let mut components = c.map(Some);
+        let achromatic = match space {
+            Space::Hsl => c[1].abs() < 1e-4,
+            Space::Hwb => c[1] + c[2] >= 1.0 - 1e-4,
+            Space::Lch => c[1] < 0.0015,
+            Space::Oklch => c[1] < 4e-6,
+            _ => false,
+        };
+        if achromatic {
+            if let Some(idx) = space.hue_index() {
+                components[idx] = None;
+            }
+        }
+
+        Value {
+            space,
+            components,
+            alpha: self.alpha,
+        }
+    }
+
+    fn to_svgtypes(self) -> svgtypes::Color {
+        let rgb = match self.space {
+            // These can only express sRGB colors to begin with.
+            Space::Hsl | Space::Hwb => {
+                to_srgb(self.space, self.resolved()).map(|c| c.clamp(0.0, 1.0))
+            }
+            _ => gamut_map(to_xyz(self.space, self.resolved())),
+        };
+
+        let to_u8 = |c: f64| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+        svgtypes::Color::new_rgba(
+            to_u8(rgb[0]),
+            to_u8(rgb[1]),
+            to_u8(rgb[2]),
+            to_u8(self.alpha.unwrap_or(0.0)),
+        )
+    }
+}
+
+/// Parses a CSS `<color>`.
+///
+/// Legacy syntaxes are delegated to `svgtypes`. `currentColor` is not resolved
+/// and must be handled by the caller.
+pub(crate) fn parse_color(text: &str) -> Option<svgtypes::Color> {
+    let text = text.trim();
+    if let Ok(c) = svgtypes::Color::from_str(text) {
+        return Some(c);
+    }
+
+    parse_value(text).map(Value::to_svgtypes)
+}
+
+/// Parses an SVG `<paint>`, including CSS Color 4 colors and fallbacks.
+pub(crate) fn parse_paint(text: &str) -> Option<svgtypes::Paint<'_>> {
+    if let Ok(paint) = svgtypes::Paint::from_str(text) {
+        return Some(paint);
+    }
+
+    let text = text.trim();
+    if let Some(rest) = text.strip_prefix("url(") {
+        let end = rest.find(')')?;
+        let link = rest[..end].trim().strip_prefix('#')?;
+        let fallback = rest[end + 1..].trim();
+        let fallback = match fallback {
+            "" => None,
+            "none" => Some(svgtypes::PaintFallback::None),
+            "currentColor" => Some(svgtypes::PaintFallback::CurrentColor),
+            _ => Some(svgtypes::PaintFallback::Color(parse_color(fallback)?)),
+        };
+        return Some(svgtypes::Paint::FuncIRI(link, fallback));
+    }
+
+    parse_color(text).map(svgtypes::Paint::Color)
+}
+
+fn parse_value(text: &str) -> Option<Value> {
+    let text = text.trim();
+    if let Ok(c) = svgtypes::Color::from_str(text) {
+        return Some(Value::from_svgtypes(c));
+    }
+
+    let open = text.find('(')?;
+    let inner = text.strip_suffix(')')?.get(open + 1..)?;
+    let name = text[..open].to_ascii_lowercase();
+    let tokens = tokenize(inner)?;
+
+    match name.as_str() {
+        "rgb" | "rgba" => parse_rgb(&tokens),
+        "hsl" | "hsla" => parse_hsl(&tokens),
+        "hwb" => {
+            let (values, alpha) = split_components(&tokens)?;
+            parse_polar_srgb(Space::Hwb, values, alpha)
+        }
+        "lab" => parse_lab(Space::Lab, 100.0, 125.0, &tokens),
+        "oklab" => parse_lab(Space::Oklab, 1.0, 0.4, &tokens),
+        "lch" => parse_lch(Space::Lch, 100.0, 150.0, &tokens),
+        "oklch" => parse_lch(Space::Oklch, 1.0, 0.4, &tokens),
+        "color" => parse_color_function(&tokens),
+        "color-mix" => parse_color_mix(&tokens),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Token<'a> {
+    Number(f64),
+    Percent(f64),
+    Dimension(f64, &'a str),
+    Ident(&'a str),
+    /// A color that will be parsed separately, like a hex color or a nested function.
+    Color(&'a str),
+    Comma,
+    Slash,
+}
+
+fn tokenize(text: &str) -> Option<Vec<Token<'_>>> {
+    let bytes = text.as_bytes();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let c = bytes[pos];
+        let is_number_start = c.is_ascii_digit()
+            || (matches!(c, b'+' | b'-' | b'.')
+                && bytes
+                    .get(pos + 1)
+                    .is_some_and(|n| n.is_ascii_digit() || *n == b'.'));
+
+        if c.is_ascii_whitespace() {
+            pos += 1;
+        } else if c == b',' {
+            tokens.push(Token::Comma);
+            pos += 1;
+        } else if c == b'/' {
+            tokens.push(Token::Slash);
+            pos += 1;
+        } else if c == b'#' {
+            let start = pos;
+            pos += 1;
+            while pos < bytes.len() && bytes[pos].is_ascii_alphanumeric() {
+                pos += 1;
+            }
+            tokens.push(Token::Color(&text[start..pos]));
+        } else if is_number_start {
+            let start = pos;
+            if matches!(c, b'+' | b'-') {
+                pos += 1;
+            }
+            while pos < bytes.len() && (bytes[pos].is_ascii_digit() || bytes[pos] == b'.') {
+                pos += 1;
+            }
+            if pos < bytes.len() && matches!(bytes[pos], b'e' | b'E') {
+                let mut exp = pos + 1;
+                if exp < bytes.len() && matches!(bytes[exp], b'+' | b'-') {
+                    exp += 1;
+                }
+                if exp < bytes.len() && bytes[exp].is_ascii_digit() {
+                    pos = exp;
+                    while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+                        pos += 1;
+                    }
+                }
+            }
+            let number = f64::from_str(&text[start..pos]).ok()?;
+
+            if bytes.get(pos) == Some(&b'%') {
+                pos += 1;
+                tokens.push(Token::Percent(number));
+            } else {
+                let unit_start = pos;
+                while pos < bytes.len() && bytes[pos].is_ascii_alphabetic() {
+                    pos += 1;
+                }
+                if unit_start == pos {
+                    tokens.push(Token::Number(number));
+                } else {
+                    tokens.push(Token::Dimension(number, &text[unit_start..pos]));
+                }
+            }
+        } else if c.is_ascii_alphabetic() || c == b'-' || c == b'_' {
+            let start = pos;
+            while pos < bytes.len()
+                && (bytes[pos].is_ascii_alphanumeric() || matches!(bytes[pos], b'-' | b'_'))
+            {
+                pos += 1;
+            }
+
+            if bytes.get(pos) == Some(&b'(') {
+                let mut depth = 0;
+                loop {
+                    match bytes.get(pos)? {
+                        b'(' => depth += 1,
+                        b')' => depth -= 1,
+                        _ => {}
+                    }
+                    pos += 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                tokens.push(Token::Color(&text[start..pos]));
+            } else {
+                tokens.push(Token::Ident(&text[start..pos]));
+            }
+        } else {
+            return None;
+        }
+    }
+
+    Some(tokens)
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Component {
+    Number(f64),
+    Percent(f64),
+    /// An angle in degrees.
+    Angle(f64),
+    None,
+}
+
+impl Component {
+    fn from_token(token: Token) -> Option<Self> {
+        match token {
+            Token::Number(n) => Some(Component::Number(n)),
+            Token::Percent(n) => Some(Component::Percent(n)),
+            Token::Dimension(n, unit) => {
+                let deg = match unit.to_ascii_lowercase().as_str() {
+                    "deg" => n,
+                    "grad" => n * 360.0 / 400.0,
+                    "rad" => n.to_degrees(),
+                    "turn" => n * 360.0,
+                    _ => return None,
+                };
+                Some(Component::Angle(deg))
+            }
+            Token::Ident(s) if s.eq_ignore_ascii_case("none") => Some(Component::None),
+            _ => None,
+        }
+    }
+
+    /// Resolves a number or a percentage, where `100%` is equal to `reference`.
+    fn number(self, reference: f64) -> Option<Option<f64>> {
+        match self {
+            Component::Number(n) => Some(Some(n)),
+            Component::Percent(n) => Some(Some(n / 100.0 * reference)),
+            Component::None => Some(None),
+            Component::Angle(_) => None,
+        }
+    }
+
+    fn hue(self) -> Option<Option<f64>> {
+        match self {
+            Component::Number(n) | Component::Angle(n) => Some(Some(n.rem_euclid(360.0))),
+            Component::None => Some(None),
+            Component::Percent(_) => None,
+        }
+    }
+
+    fn alpha(self) -> Option<Option<f64>> {
+        self.number(1.0).map(|a| a.map(|a| a.clamp(0.0, 1.0)))
+    }
+}
+
+/// Splits function arguments in the modern `a b c / alpha` syntax
+/// into three components and an optional alpha.
+fn split_components(tokens: &[Token]) -> Option<([Component; 3], Component)> {
+    let mut values = Vec::with_capacity(3);
+    let mut alpha = None;
+    let mut iter = tokens.iter();
+    for token in iter.by_ref() {
+        if *token == Token::Slash {
+            alpha = Some(Component::from_token(*iter.next()?)?);
+            break;
+        }
+
+        values.push(Component::from_token(*token)?);
+    }
+
+    if iter.next().is_some() {
+        return None;
+    }
+
+    let values: [Component; 3] = values.try_into().ok()?;
+    Some((values, alpha.unwrap_or(Component::Number(1.0))))
+}
+
+/// Splits function arguments in the legacy `a, b, c, alpha` syntax,
+/// which is allowed only in `rgb()` and `hsl()`.
+///
+/// Unlike the modern syntax, `none` is not allowed.
+fn split_legacy_components(tokens: &[Token]) -> Option<([Component; 3], Component)> {
+    let mut values = Vec::with_capacity(4);
+    for (i, token) in tokens.iter().enumerate() {
+        if i % 2 == 1 {
+            if *token != Token::Comma {
+                return None;
+            }
+        } else {
+            match Component::from_token(*token)? {
+                Component::None => return None,
+                c => values.push(c),
+            }
+        }
+    }
+
+    if tokens.last() == Some(&Token::Comma) {
+        return None;
+    }
+
+    let alpha = if values.len() == 4 {
+        values.pop()?
+    } else {
+        Component::Number(1.0)
+    };
+
+    let values: [Component; 3] = values.try_into().ok()?;
+    Some((values, alpha))
+}
+
+fn parse_rgb(tokens: &[Token]) -> Option<Value> {
+    let (values, alpha) = if tokens.contains(&Token::Comma) {
+        let (values, alpha) = split_legacy_components(tokens)?;
+        // Numbers and percentages cannot be mixed.
+        let percents = values
+            .iter()
+            .filter(|c| matches!(c, Component::Percent(_)))
+            .count();
+        if percents != 0 && percents != values.len() {
+            return None;
+        }
+
+        (values, alpha)
+    } else {
+        split_components(tokens)?
+    };
+    let mut components = [None; 3];
+    for (c, v) in components.iter_mut().zip(values) {
+        *c = v.number(255.0)?.map(|n| (n / 255.0).clamp(0.0, 1.0));
+    }
+
+    Some(Value {
+        space: Space::Srgb,
+        components,
+        alpha: alpha.alpha()?,
+    })
+}
+
+fn parse_hsl(tokens: &[Token]) -> Option<Value> {
+    let (values, alpha) = if tokens.contains(&Token::Comma) {
+        let (values, alpha) = split_legacy_components(tokens)?;
+        // Saturation and lightness must be percentages.
+        if !values[1..]
+            .iter()
+            .all(|c| matches!(c, Component::Percent(_)))
+        {
+            return None;
+        }
+
+        (values, alpha)
+    } else {
+        split_components(tokens)?
+    };
+
+    let mut value = parse_polar_srgb(Space::Hsl, values, alpha)?;
+    value.components[1] = value.components[1].map(|s| s.max(0.0));
+    value.components[2] = value.components[2].map(|l| l.clamp(0.0, 1.0));
+    Some(value)
+}
+
+/// Parses `hsl()` and `hwb()`, where the last two components are percentages.
+fn parse_polar_srgb(space: Space, values: [Component; 3], alpha: Component) -> Option<Value> {
+    let to_fraction = |c: Component| c.number(100.0).map(|n| n.map(|n| n / 100.0));
+    Some(Value {
+        space,
+        components: [
+            values[0].hue()?,
+            to_fraction(values[1])?,
+            to_fraction(values[2])?,
+        ],
+        alpha: alpha.alpha()?,
+    })
+}
+
+fn parse_lab(space: Space, lightness: f64, ab: f64, tokens: &[Token]) -> Option<Value> {
+    let (values, alpha) = split_components(tokens)?;
+    Some(Value {
+        space,
+        components: [
+            values[0]
+                .number(lightness)?
+                .map(|l| l.clamp(0.0, lightness)),
+            values[1].number(ab)?,
+            values[2].number(ab)?,
+        ],
+        alpha: alpha.alpha()?,
+    })
+}
+
+fn parse_lch(space: Space, lightness: f64, chroma: f64, tokens: &[Token]) -> Option<Value> {
+    let (values, alpha) = split_components(tokens)?;
+    Some(Value {
+        space,
+        components: [
+            values[0]
+                .number(lightness)?
+                .map(|l| l.clamp(0.0, lightness)),
+            values[1].number(chroma)?.map(|c| c.max(0.0)),
+            values[2].hue()?,
+        ],
+        alpha: alpha.alpha()?,
+    })
+}
+
+fn parse_color_function(tokens: &[Token]) -> Option<Value> {
+    let (space, tokens) = tokens.split_first()?;
+    let Token::Ident(space) = space else {
+        return None;
+    };
+
+    let space = Space::from_name(space).filter(|s| s.is_predefined())?;
+    let (values, alpha) = split_components(tokens)?;
+    let mut components = [None; 3];
+    for (c, v) in components.iter_mut().zip(values) {
+        *c = v.number(1.0)?;
+    }
+
+    Some(Value {
+        space,
+        components,
+        alpha: alpha.alpha()?,
+    })
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum HueMethod {
+    Shorter,
+    Longer,
+    Increasing,
+    Decreasing,
+}
+
+fn parse_color_mix(tokens: &[Token]) -> Option<Value> {
+    let mut args = tokens.split(|t| *t == Token::Comma);
+
+    let mut first = args.next()?;
+    let mut space = Space::Oklab;
+    let mut hue_method = HueMethod::Shorter;
+    if let [Token::Ident(keyword), rest @ ..] = first {
+        if keyword.eq_ignore_ascii_case("in") {
+            first = args.next()?;
+            match rest {
+                [Token::Ident(name)] => {
+                    space = Space::from_name(name)?;
+                }
+                [Token::Ident(name), Token::Ident(method), Token::Ident(hue)]
+                    if hue.eq_ignore_ascii_case("hue") =>
+                {
+                    space = Space::from_name(name)?;
+                    space.hue_index()?;
+                    hue_method = match method.to_ascii_lowercase().as_str() {
+                        "shorter" => HueMethod::Shorter,
+                        "longer" => HueMethod::Longer,
+                        "increasing" => HueMethod::Increasing,
+                        "decreasing" => HueMethod::Decreasing,
+                        _ => return None,
+                    };
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    let second = args.next()?;
+    if args.next().is_some() {
+        return None;
+    }
+
+    let (color1, p1) = parse_mix_color(first)?;
+    let (color2, p2) = parse_mix_color(second)?;
+
+    let (p1, p2) = match (p1, p2) {
+        (None, None) => (0.5, 0.5),
+        (Some(p1), None) => (p1, 1.0 - p1),
+        (None, Some(p2)) => (1.0 - p2, p2),
+        (Some(p1), Some(p2)) => (p1, p2),
+    };
+
+    let sum = p1 + p2;
+    if sum <= 0.0 {
+        return None;
+    }
+
+    let alpha_multiplier = sum.min(1.0);
+    let t = p2 / sum;
+    let mut value = interpolate(color1, color2, t, space, hue_method);
+    value.alpha = value.alpha.map(|a| a * alpha_multiplier);
+    Some(value)
+}
+
+fn parse_mix_color(tokens: &[Token]) -> Option<(Value, Option<f64>)> {
+    let (color, percent) = match tokens {
+        [Token::Color(c) | Token::Ident(c)] => (*c, None),
+        [Token::Color(c) | Token::Ident(c), Token::Percent(p)]
+        | [Token::Percent(p), Token::Color(c) | Token::Ident(c)] => (*c, Some(*p)),
+        _ => return None,
+    };
+
+    if let Some(p) = percent {
+        if !(0.0..=100.0).contains(&p) {
+            return None;
+        }
+    }
+
+    Some((parse_value(color)?, percent.map(|p| p / 100.0)))
+}
+
+fn interpolate(a: Value, b: Value, t: f64, space: Space, hue_method: HueMethod) -> Value {
+    let a = a.convert(space);
+    let b = b.convert(space);
+
+    let alpha1 = a.alpha.or(b.alpha).unwrap_or(1.0);
+    let alpha2 = b.alpha.or(a.alpha).unwrap_or(1.0);
+    let alpha = alpha1 + (alpha2 - alpha1) * t;
+
+    let hue_index = space.hue_index();
+    let mut components = [None; 3];
+    for (i, component) in components.iter_mut().enumerate() {
+        let (c1, c2) = match (a.components[i], b.components[i]) {
+            (None, None) => continue,
+            (Some(c1), None) => (c1, c1),
+            (None, Some(c2)) => (c2, c2),
+            (Some(c1), Some(c2)) => (c1, c2),
+        };
+
+        *component = Some(if Some(i) == hue_index {
+            let (h1, h2) = fix_hues(c1, c2, hue_method);
+            (h1 + (h2 - h1) * t).rem_euclid(360.0)
+        } else {
+            // Interpolate premultiplied values.
+            let c = c1 * alpha1 + (c2 * alpha2 - c1 * alpha1) * t;
+            if alpha != 0.0 { c / alpha } else { c }
+        });
+    }
+
+    Value {
+        space,
+        components,
+        alpha: Some(alpha),
+    }
+}
+
+fn fix_hues(h1: f64, h2: f64, method: HueMethod) -> (f64, f64) {
+    let (mut h1, mut h2) = (h1.rem_euclid(360.0), h2.rem_euclid(360.0));
+    let diff = h2 - h1;
+    match method {
+        HueMethod::Shorter => {
+            if diff > 180.0 {
+                h1 += 360.0;
+            } else if diff < -180.0 {
+                h2 += 360.0;
+            }
+        }
+        HueMethod::Longer => {
+            if 0.0 < diff && diff < 180.0 {
+                h1 += 360.0;
+            } else if -180.0 < diff && diff <= 0.0 {
+                h2 += 360.0;
+            }
+        }
+        HueMethod::Increasing => {
+            if h2 < h1 {
+                h2 += 360.0;
+            }
+        }
+        HueMethod::Decreasing => {
+            if h1 < h2 {
+                h1 += 360.0;
+            }
+        }
+    }
+
+    (h1, h2)
+}
+
+/// Maps a color into the sRGB gamut.
+///
+/// Uses the CSS Color 4 algorithm, which reduces the OKLCH chroma until
+/// the clipped color is indistinguishable from the reduced one.
+fn gamut_map(xyz: [f64; 3]) -> [f64; 3] {
+    const JND: f64 = 0.02;
+    const EPSILON: f64 = 0.0001;
+
+    let rgb = from_xyz(Space::Srgb, xyz);
+    if in_srgb_gamut(rgb) {
+        return rgb.map(|c| c.clamp(0.0, 1.0));
+    }
+
+    let mut current = from_xyz(Space::Oklch, xyz);
+    if current[0] >= 1.0 {
+        return [1.0; 3];
+    } else if current[0] <= 0.0 {
+        return [0.0; 3];
+    }
+
+    let clip = |lch: [f64; 3]| to_srgb(Space::Oklch, lch).map(|c| c.clamp(0.0, 1.0));
+    let delta_eok = |rgb: [f64; 3], lch: [f64; 3]| {
+        let a = from_xyz(Space::Oklab, to_xyz(Space::Srgb, rgb));
+        let b = polar_to_rect(lch);
+        ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+    };
+
+    let mut clipped = clip(current);
+    if delta_eok(clipped, current) < JND {
+        return clipped;
+    }
+
+    let mut min = 0.0;
+    let mut max = current[1];
+    let mut min_in_gamut = true;
+    while max - min > EPSILON {
+        let chroma = (min + max) / 2.0;
+        current[1] = chroma;
+        if min_in_gamut && in_srgb_gamut(to_srgb(Space::Oklch, current)) {
+            min = chroma;
+            continue;
+        }
+
+        clipped = clip(current);
+        let e = delta_eok(clipped, current);
+        if e < JND {
+            if JND - e < EPSILON {
+                break;
+            }
+
+            min_in_gamut = false;
+            min = chroma;
+        } else {
+            max = chroma;
+        }
+    }
+
+    clipped
+}
+
+fn in_srgb_gamut(rgb: [f64; 3]) -> bool {
+    const TOLERANCE: f64 = 0.0001;
+    rgb.iter()
+        .all(|c| (-TOLERANCE..=1.0 + TOLERANCE).contains(c))
+}
+
+fn to_srgb(space: Space, c: [f64; 3]) -> [f64; 3] {
+    match space {
+        Space::Srgb => c,
+        Space::Hsl => hsl_to_srgb(c),
+        Space::Hwb => hwb_to_srgb(c),
+        _ => from_xyz(Space::Srgb, to_xyz(space, c)),
+    }
+}
+
+/// Converts a color into CIE XYZ with a D65 white point.
+fn to_xyz(space: Space, c: [f64; 3]) -> [f64; 3] {
+    match space {
+        Space::Srgb => mul(SRGB_TO_XYZ, c.map(srgb_to_linear)),
+        Space::SrgbLinear => mul(SRGB_TO_XYZ, c),
+        Space::DisplayP3 => mul(DISPLAY_P3_TO_XYZ, c.map(srgb_to_linear)),
+        Space::A98Rgb => mul(A98_RGB_TO_XYZ, c.map(a98_to_linear)),
+        Space::ProphotoRgb => mul(
+            D50_TO_D65,
+            mul(PROPHOTO_RGB_TO_XYZ_D50, c.map(prophoto_to_linear)),
+        ),
+        Space::Rec2020 => mul(REC2020_TO_XYZ, c.map(rec2020_to_linear)),
+        Space::XyzD50 => mul(D50_TO_D65, c),
+        Space::XyzD65 => c,
+        Space::Lab => mul(D50_TO_D65, lab_to_xyz_d50(c)),
+        Space::Lch => mul(D50_TO_D65, lab_to_xyz_d50(polar_to_rect(c))),
+        Space::Oklab => oklab_to_xyz(c),
+        Space::Oklch => oklab_to_xyz(polar_to_rect(c)),
+        Space::Hsl => to_xyz(Space::Srgb, hsl_to_srgb(c)),
+        Space::Hwb => to_xyz(Space::Srgb, hwb_to_srgb(c)),
+    }
+}
+
+/// Converts a color from CIE XYZ with a D65 white point.
+fn from_xyz(space: Space, xyz: [f64; 3]) -> [f64; 3] {
+    match space {
+        Space::Srgb => mul(XYZ_TO_SRGB, xyz).map(linear_to_srgb),
+        Space::SrgbLinear => mul(XYZ_TO_SRGB, xyz),
+        Space::DisplayP3 => mul(XYZ_TO_DISPLAY_P3, xyz).map(linear_to_srgb),
+        Space::A98Rgb => mul(XYZ_TO_A98_RGB, xyz).map(linear_to_a98),
+        Space::ProphotoRgb => {
+            mul(XYZ_D50_TO_PROPHOTO_RGB, mul(D65_TO_D50, xyz)).map(linear_to_prophoto)
+        }
+        Space::Rec2020 => mul(XYZ_TO_REC2020, xyz).map(linear_to_rec2020),
+        Space::XyzD50 => mul(D65_TO_D50, xyz),
+        Space::XyzD65 => xyz,
+        Space::Lab => xyz_d50_to_lab(mul(D65_TO_D50, xyz)),
+        Space::Lch => rect_to_polar(xyz_d50_to_lab(mul(D65_TO_D50, xyz))),
+        Space::Oklab => xyz_to_oklab(xyz),
+        Space::Oklch => rect_to_polar(xyz_to_oklab(xyz)),
+        Space::Hsl => srgb_to_hsl(from_xyz(Space::Srgb, xyz)),
+        Space::Hwb => srgb_to_hwb(from_xyz(Space::Srgb, xyz)),
+    }
+}
+
+fn mul(m: Matrix, c: [f64; 3]) -> [f64; 3] {
+    [
+        m[0][0] * c[0] + m[0][1] * c[1] + m[0][2] * c[2],
+        m[1][0] * c[0] + m[1][1] * c[1] + m[1][2] * c[2],
+        m[2][0] * c[0] + m[2][1] * c[1] + m[2][2] * c[2],
+    ]
+}
+
+fn srgb_to_linear(c: f64) -> f64 {
+    let abs = c.abs();
+    if abs <= 0.04045 {
+        c / 12.92
+    } else {
+        c.signum() * ((abs + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f64) -> f64 {
+    let abs = c.abs();
+    if abs <= 0.0031308 {
+        c * 12.92
+    } else {
+        c.signum() * (1.055 * abs.powf(1.0 / 2.4) - 0.055)
+    }
+}
+
+fn a98_to_linear(c: f64) -> f64 {
+    c.signum() * c.abs().powf(563.0 / 256.0)
+}
+
+fn linear_to_a98(c: f64) -> f64 {
+    c.signum() * c.abs().powf(256.0 / 563.0)
+}
+
+fn prophoto_to_linear(c: f64) -> f64 {
+    let abs = c.abs();
+    if abs <= 16.0 / 512.0 {
+        c / 16.0
+    } else {
+        c.signum() * abs.powf(1.8)
+    }
+}
+
+fn linear_to_prophoto(c: f64) -> f64 {
+    let abs = c.abs();
+    if abs >= 1.0 / 512.0 {
+        c.signum() * abs.powf(1.0 / 1.8)
+    } else {
+        c * 16.0
+    }
+}
+
+const REC2020_ALPHA: f64 = 1.0992968;
+const REC2020_BETA: f64 = 0.01805397;
+
+fn rec2020_to_linear(c: f64) -> f64 {
+    let abs = c.abs();
+    if abs < REC2020_BETA * 4.5 {
+        c / 4.5
+    } else {
+        c.signum() * ((abs + REC2020_ALPHA - 1.0) / REC2020_ALPHA).powf(1.0 / 0.45)
+    }
+}
+
+fn linear_to_rec2020(c: f64) -> f64 {
+    let abs = c.abs();
+    if abs > REC2020_BETA {
+        c.signum() * (REC2020_ALPHA * abs.powf(0.45) - (REC2020_ALPHA - 1.0))
+    } else {
+        c * 4.5
+    }
+}
+
+const LAB_KAPPA: f64 = 24389.0 / 27.0;
+const LAB_EPSILON: f64 = 216.0 / 24389.0;
+
+fn lab_to_xyz_d50([l, a, b]: [f64; 3]) -> [f64; 3] {
+    let f1 = (l + 16.0) / 116.0;
+    let f0 = a / 500.0 + f1;
+    let f2 = f1 - b / 200.0;
+
+    let x = if f0.powi(3) > LAB_EPSILON {
+        f0.powi(3)
+    } else {
+        (116.0 * f0 - 16.0) / LAB_KAPPA
+    };
+    let y = if l > LAB_KAPPA * LAB_EPSILON {
+        f1.powi(3)
+    } else {
+        l / LAB_KAPPA
+    };
+    let z = if f2.powi(3) > LAB_EPSILON {
+        f2.powi(3)
+    } else {
+        (116.0 * f2 - 16.0) / LAB_KAPPA
+    };
+
+    [x * D50_WHITE[0], y * D50_WHITE[1], z * D50_WHITE[2]]
+}
+
+fn xyz_d50_to_lab(xyz: [f64; 3]) -> [f64; 3] {
+    let f = |i: usize| {
+        let v = xyz[i] / D50_WHITE[i];
+        if v > LAB_EPSILON {
+            v.cbrt()
+        } else {
+            (LAB_KAPPA * v + 16.0) / 116.0
+        }
+    };
+
+    let (f0, f1, f2) = (f(0), f(1), f(2));
+    [116.0 * f1 - 16.0, 500.0 * (f0 - f1), 200.0 * (f1 - f2)]
+}
+
+fn oklab_to_xyz(lab: [f64; 3]) -> [f64; 3] {
+    let lms = mul(OKLAB_TO_LMS, lab).map(|c| c.powi(3));
+    mul(OKLAB_LMS_TO_XYZ, lms)
+}
+
+fn xyz_to_oklab(xyz: [f64; 3]) -> [f64; 3] {
+    let lms = mul(XYZ_TO_OKLAB_LMS, xyz).map(f64::cbrt);
+    mul(LMS_TO_OKLAB, lms)
+}
+
+fn polar_to_rect([l, c, h]: [f64; 3]) -> [f64; 3] {
+    let h = h.to_radians();
+    [l, c * h.cos(), c * h.sin()]
+}
+
+fn rect_to_polar([l, a, b]: [f64; 3]) -> [f64; 3] {
+    let h = b.atan2(a).to_degrees().rem_euclid(360.0);
+    [l, (a * a + b * b).sqrt(), h]
+}
+
+fn hsl_to_srgb([h, s, l]: [f64; 3]) -> [f64; 3] {
+    let a = s * l.min(1.0 - l);
+    let f = |n: f64| {
+        let k = (n + h / 30.0).rem_euclid(12.0);
+        l - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+    };
+
+    [f(0.0), f(8.0), f(4.0)]
+}
+
+fn srgb_to_hsl([r, g, b]: [f64; 3]) -> [f64; 3] {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let d = max - min;
+    let l = (min + max) / 2.0;
+
+    let mut h = 0.0;
+    let mut s = 0.0;
+    if d != 0.0 {
+        s = if l == 0.0 || l == 1.0 {
+            0.0
+        } else {
+            (max - l) / l.min(1.0 - l)
+        };
+
+        h = if max == r {
+            (g - b) / d + if g < b { 6.0 } else { 0.0 }
+        } else if max == g {
+            (b - r) / d + 2.0
+        } else {
+            (r - g) / d + 4.0
+        } * 60.0;
+    }
+
+    if s < 0.0 {
+        h += 180.0;
+        s = s.abs();
+    }
+
+    [h.rem_euclid(360.0), s, l]
+}
+
+fn hwb_to_srgb([h, w, b]: [f64; 3]) -> [f64; 3] {
+    if w + b >= 1.0 {
+        let gray = w / (w + b);
+        return [gray; 3];
+    }
+
+    hsl_to_srgb([h, 1.0, 0.5]).map(|c| c * (1.0 - w - b) + w)
+}
+
+fn srgb_to_hwb(rgb: [f64; 3]) -> [f64; 3] {
+    let h = srgb_to_hsl(rgb)[0];
+    let w = rgb[0].min(rgb[1]).min(rgb[2]);
+    let b = 1.0 - rgb[0].max(rgb[1]).max(rgb[2]);
+    [h, w, b]
+}
@@ -15,6 +15,8 @@ use fontdb::ID;
 use svgtypes::{Length, LengthUnit as Unit, PaintOrderKind, TransformOrigin};
 use tiny_skia_path::PathBuilder;
 
+use super::clippath::ClipPathValue;
+use super::fragment::{Fragment, ViewSpec};
 use super::svgtree::{self, AId, EId, FromValue, SvgNode};
 use super::units::{self, convert_length};
 use super::{Error, Options, marker};
@@ -39,11 +41,40 @@ pub struct State<'a> {
     /// Used only during nested `svg` size resolving.
     /// Width and height can be set independently.
     pub(crate) use_size: (Option<f32>, Option<f32>),
+    /// A canvas rect in absolute coordinates.
+    ///
+    /// Nothing outside of it would be rendered. Used to check filter regions size.
+    pub(crate) canvas: NonZeroRect,
     pub(crate) opt: &'a Options<'a>,
 }
 
+impl State<'_> {
+    /// Returns element's location in the source SVG, when requested by the user.
+    pub(crate) fn source_span(&self, node: SvgNode) -> Option<SourceSpan> {
+        if self.opt.source_spans {
+            node.source_span()
+        } else {
+            None
+        }
+    }
+
+    /// Returns element's non-rendering data.
+    ///
+    /// Nodes generated by markers must not have metadata, just like IDs.
+    pub(crate) fn metadata(&self, node: SvgNode) -> Option<Box<Metadata>> {
+        if self.parent_markers.is_empty() {
+            node.metadata().map(|metadata| Box::new(metadata.clone()))
+        } else {
+            None
+        }
+    }
+}
+
 #[derive(Clone)]
-pub struct Cache {
+pub struct Cache<'a> {
+    /// A receiver of diagnostics found during conversion.
+    pub diagnostics: &'a DiagnosticSink<'a>,
+
     /// This fontdb is initialized from [`Options::fontdb`] and then populated
     /// over the course of conversion.
     #[cfg(feature = "text")]
@@ -93,9 +124,14 @@ macro_rules! font_lookup {
     };
 }
 
-impl Cache {
-    pub(crate) fn new(#[cfg(feature = "text")] fontdb: Arc<Database>) -> Self {
+impl<'a> Cache<'a> {
+    pub(crate) fn new(
+        diagnostics: &'a DiagnosticSink<'a>,
+        #[cfg(feature = "text")] fontdb: Arc<Database>,
+    ) -> Self {
         Self {
+            diagnostics,
+
             #[cfg(feature = "text")]
             fontdb,
 
@@ -204,7 +240,19 @@ impl Cache {
         }
     }
 
-    font_lookup!(fontdb_svg, cache_svg, svg, Node);
+    #[cfg(feature = "text")]
+    pub(crate) fn fontdb_svg(&mut self, font: ID, glyph: GlyphId) -> Option<Node> {
+        let key = (font, glyph);
+        match self.cache_svg.get(&key) {
+            Some(cache_hit) => cache_hit.clone(),
+            None => {
+                let lookup = self.fontdb.svg(font, glyph, self.diagnostics);
+                self.cache_svg.insert(key, lookup.clone());
+                lookup
+            }
+        }
+    }
+
     font_lookup!(fontdb_raster, cache_raster, raster, BitmapImage);
 
     #[cfg(feature = "text")]
@@ -236,7 +284,7 @@ impl Cache {
         match self.cache_colr.get(&key) {
             Some(cache_hit) => cache_hit.clone(),
             None => {
-                let lookup = self.fontdb.colr(font, glyph, variations);
+                let lookup = self.fontdb.colr(font, glyph, variations, self.diagnostics);
                 self.cache_colr.insert(key, lookup.clone());
                 lookup
             }
@@ -380,19 +428,63 @@ impl SvgColorExt for svgtypes::Color {
 /// - If `Document` doesn't have an SVG node - returns an empty tree.
 /// - If `Document` doesn't have a valid size - returns `Error::InvalidSize`.
 pub(crate) fn convert_doc(svg_doc: &svgtree::Document, opt: &Options) -> Result<Tree, Error> {
+    match svg_doc.fragment() {
+        Some(Fragment::View(view)) => {
+            convert_doc_with_view(svg_doc, view, opt).map(|(tree, _)| tree)
+        }
+        Some(Fragment::Element(id)) => {
+            // An element bounding box is known only after conversion,
+            // so the document is converted twice.
+            // Diagnostics are reported only by the second pass.
+            let silent_opt = Options {
+                diagnostics: DiagnosticSink {
+                    report: Box::new(|_| {}),
+                },
+                ..opt.forwarding()
+            };
+            let (tree, root_ts) =
+                convert_doc_with_view(svg_doc, &ViewSpec::default(), &silent_opt)?;
+
+            let view = super::fragment::element_view(&tree, root_ts, id).unwrap_or_else(|| {
+                opt.diagnostics.warn(
+                    DiagnosticCode::InvalidReference,
+                    format!(
+                        "Fragment '#{}' references a non-rendered element. Skipped.",
+                        id
+                    ),
+                );
+                ViewSpec::default()
+            });
+            convert_doc_with_view(svg_doc, &view, opt).map(|(tree, _)| tree)
+        }
+        None => convert_doc_with_view(svg_doc, &ViewSpec::default(), opt).map(|(tree, _)| tree),
+    }
+}
+
+/// Converts an input `Document` into a `Tree`, overriding the root viewport with a view.
+///
+/// Also returns the root transform.
+fn convert_doc_with_view(
+    svg_doc: &svgtree::Document,
+    view: &ViewSpec,
+    opt: &Options,
+) -> Result<(Tree, Transform), Error> {
     let svg = svg_doc.root_element();
-    let (size, restore_viewbox) = resolve_svg_size(&svg, opt);
+    let (size, restore_viewbox) = resolve_svg_size(&svg, view, opt);
     let size = size?;
     let view_box = ViewBox {
-        rect: svg
-            .parse_viewbox()
+        rect: view
+            .view_box
+            .or_else(|| svg.parse_viewbox())
             .unwrap_or_else(|| size.to_non_zero_rect(0.0, 0.0)),
-        aspect: svg.attribute(AId::PreserveAspectRatio).unwrap_or_default(),
+        aspect: view
+            .aspect
+            .unwrap_or_else(|| svg.attribute(AId::PreserveAspectRatio).unwrap_or_default()),
     };
 
     let background_color = svg
         .attribute::<&str>(AId::BackgroundColor)
-        .and_then(|s| svgtypes::Paint::from_str(s).ok())
+        .and_then(super::color::parse_paint)
         .and_then(|paint| match paint {
             svgtypes::Paint::Color(c) => Some(c),
             _ => None,
@@ -401,6 +493,7 @@ pub(crate) fn convert_doc(svg_doc: &svgtree::Document, opt: &Options) -> Result<
     let mut tree = Tree {
         size,
         root: Group::empty(),
+        metadata: svg.metadata().map(|metadata| Box::new(metadata.clone())),
         linear_gradients: Vec::new(),
         radial_gradients: Vec::new(),
         patterns: Vec::new(),
@@ -411,8 +504,12 @@ pub(crate) fn convert_doc(svg_doc: &svgtree::Document, opt: &Options) -> Result<
         fontdb: opt.fontdb.clone(),
     };
 
+    let root_ts = view_box
+        .to_transform(tree.size())
+        .pre_concat(view.transform);
+
     if !svg.is_visible_element(opt) {
-        return Ok(tree);
+        return Ok((tree, root_ts));
     }
 
     let state = State {
@@ -422,10 +519,12 @@ pub(crate) fn convert_doc(svg_doc: &svgtree::Document, opt: &Options) -> Result<
         fe_image_link: false,
         view_box: view_box.rect,
         use_size: (None, None),
+        canvas: size.to_non_zero_rect(0.0, 0.0),
         opt,
     };
 
     let mut cache = Cache::new(
+        &opt.diagnostics,
         #[cfg(feature = "text")]
         opt.fontdb.clone(),
     );
@@ -449,8 +548,14 @@ pub(crate) fn convert_doc(svg_doc: &svgtree::Document, opt: &Options) -> Result<
         }
     }
 
-    let root_ts = view_box.to_transform(tree.size());
-    if root_ts.is_identity() && background_color.is_none() {
+    // Root children can be composited in linearRGB only inside a group.
+    let color_interpolation = svg
+        .attribute(AId::ColorInterpolation)
+        .unwrap_or(filter::ColorInterpolation::SRGB);
+    if root_ts.is_identity()
+        && background_color.is_none()
+        && color_interpolation == filter::ColorInterpolation::SRGB
+    {
         convert_children(svg_doc.root(), &state, &mut cache, &mut tree.root);
     } else {
         let mut g = Group::empty();
@@ -463,6 +568,7 @@ pub(crate) fn convert_doc(svg_doc: &svgtree::Document, opt: &Options) -> Result<
 
         g.transform = root_ts;
         g.abs_transform = root_ts;
+        g.color_interpolation = color_interpolation;
         convert_children(svg_doc.root(), &state, &mut cache, &mut g);
         g.calculate_bounding_boxes();
         tree.root.children.push(Node::Group(Box::new(g)));
@@ -498,7 +604,7 @@ pub(crate) fn convert_doc(svg_doc: &svgtree::Document, opt: &Options) -> Result<
         calculate_svg_bbox(&mut tree);
     }
 
-    Ok(tree)
+    Ok((tree, root_ts))
 }
 
 fn background_path(background_color: svgtypes::Color, area: Rect) -> Option<Path> {
@@ -520,7 +626,11 @@ fn background_path(background_color: svgtypes::Color, area: Rect) -> Option<Path
     Some(path)
 }
 
-fn resolve_svg_size(svg: &SvgNode, opt: &Options) -> (Result<Size, Error>, bool) {
+fn resolve_svg_size(svg: &SvgNode, view: &ViewSpec, opt: &Options) -> (Result<Size, Error>, bool) {
+    if let Some(size) = view.size {
+        return (Ok(size), false);
+    }
+
     let mut state = State {
         parent_clip_path: None,
         context_element: None,
@@ -528,6 +638,7 @@ fn resolve_svg_size(svg: &SvgNode, opt: &Options) -> (Result<Size, Error>, bool)
         fe_image_link: false,
         view_box: NonZeroRect::from_xywh(0.0, 0.0, 100.0, 100.0).unwrap(),
         use_size: (None, None),
+        canvas: NonZeroRect::from_xywh(0.0, 0.0, 100.0, 100.0).unwrap(),
         opt,
     };
 
@@ -535,7 +646,7 @@ fn resolve_svg_size(svg: &SvgNode, opt: &Options) -> (Result<Size, Error>, bool)
     let mut width: Length = svg.attribute(AId::Width).unwrap_or(def);
     let mut height: Length = svg.attribute(AId::Height).unwrap_or(def);
 
-    let view_box = svg.parse_viewbox();
+    let view_box = view.view_box.or_else(|| svg.parse_viewbox());
 
     let restore_viewbox =
         if (width.unit == Unit::Percent || height.unit == Unit::Percent) && view_box.is_none() {
@@ -623,7 +734,12 @@ pub(crate) fn convert_element(node: SvgNode, state: &State, cache: &mut Cache, p
         None => return,
     };
 
-    if !tag_name.is_graphic() && !matches!(tag_name, EId::G | EId::Switch | EId::Svg) {
+    if !tag_name.is_graphic()
+        && !matches!(
+            tag_name,
+            EId::G | EId::Switch | EId::Svg | EId::ForeignObject
+        )
+    {
         return;
     }
 
@@ -683,6 +799,9 @@ fn convert_element_impl(
         EId::Image => {
             super::image::convert(node, state, cache, parent);
         }
+        EId::ForeignObject => {
+            super::foreign_object::convert(node, state, cache, parent);
+        }
         EId::Text => {
             #[cfg(feature = "text")]
             {
@@ -761,7 +880,10 @@ fn convert_clip_path_elements_impl(
             }
         }
         _ => {
-            log::warn!("'{}' is no a valid 'clip-path' child.", tag_name);
+            node.warn(
+                DiagnosticCode::InvalidElement,
+                format!("'{}' is no a valid 'clip-path' child.", tag_name),
+            );
         }
     }
 }
@@ -809,6 +931,9 @@ pub(crate) fn convert_group(
     let blend_mode: BlendMode = node.attribute(AId::MixBlendMode).unwrap_or_default();
     let isolation: Isolation = node.attribute(AId::Isolation).unwrap_or_default();
     let isolate = isolation == Isolation::Isolate;
+    let color_interpolation = node
+        .find_attribute(AId::ColorInterpolation)
+        .unwrap_or(filter::ColorInterpolation::SRGB);
 
     // Nodes generated by markers must not have an ID. Otherwise we would have duplicates.
     let is_g_or_use = matches!(node.tag_name(), Some(EId::G) | Some(EId::Use));
@@ -822,13 +947,20 @@ pub(crate) fn convert_group(
     let dummy = Rect::from_xywh(0.0, 0.0, 0.0, 0.0).unwrap();
     let mut g = Group {
         id,
+        source_span: state.source_span(node),
+        metadata: if is_g_or_use {
+            state.metadata(node)
+        } else {
+            None
+        },
         transform,
         abs_transform,
         opacity,
         blend_mode,
         isolate,
+        color_interpolation,
         clip_path: None,
-        mask: None,
+        masks: Vec::new(),
         filters: Vec::new(),
         is_context_element: false,
         bounding_box: dummy,
@@ -849,23 +981,24 @@ pub(crate) fn convert_group(
     // But `clip-path` can.
 
     let mut clip_path = None;
-    if let Some(link) = node.attribute::<SvgNode>(AId::ClipPath) {
-        clip_path = super::clippath::convert(link, state, object_bbox, cache);
+    if let Some(value) = node.attribute::<ClipPathValue>(AId::ClipPath) {
+        clip_path = match value {
+            ClipPathValue::Link(link) => super::clippath::convert(link, state, object_bbox, cache),
+            ClipPathValue::Shape(shape, geometry_box) => super::clippath::convert_shape(
+                node,
+                shape.as_ref(),
+                geometry_box,
+                object_bbox,
+                &mut g,
+                state,
+                cache,
+            ),
+        };
         if clip_path.is_none() {
             return None;
         }
     }
 
-    let mut mask = None;
-    if state.parent_clip_path.is_none() {
-        if let Some(link) = node.attribute::<SvgNode>(AId::Mask) {
-            mask = super::mask::convert(link, state, object_bbox, cache);
-            if mask.is_none() {
-                return None;
-            }
-        }
-    }
-
     let filters = {
         let mut filters = Vec::new();
         if state.parent_clip_path.is_none() {
@@ -895,10 +1028,38 @@ pub(crate) fn convert_group(
         filters
     };
 
+    for filter in &filters {
+        // Filters are rendered only inside the canvas.
+        let region = filter
+            .rect
+            .transform(abs_transform)
+            .and_then(|r| r.to_rect().intersect(&state.canvas.to_rect()));
+        let pixels = region.map_or(0.0, |r| r.width() as f64 * r.height() as f64);
+        if pixels > state.opt.limits.max_filter_region_pixels as f64 {
+            node.warn(
+                DiagnosticCode::LimitExceeded,
+                format!(
+                    "Filter '{}' region is larger than allowed. Skipped.",
+                    filter.id()
+                ),
+            );
+            return None;
+        }
+    }
+
+    // Must be set before resolving masks, since mask layers may depend on the group's
+    // bounding boxes.
+    g.filters = filters;
+
+    let mut masks = Vec::new();
+    if state.parent_clip_path.is_none() {
+        masks = super::mask::convert_layers(node, state, object_bbox, &mut g, cache)?;
+    }
+
     let required = opacity.get().approx_ne_ulps(&1.0, 4)
         || clip_path.is_some()
-        || mask.is_some()
-        || !filters.is_empty()
+        || !masks.is_empty()
+        || !g.filters.is_empty()
         || !transform.is_identity()
         || blend_mode != BlendMode::Normal
         || isolate
@@ -911,8 +1072,7 @@ pub(crate) fn convert_group(
     }
 
     g.clip_path = clip_path;
-    g.mask = mask;
-    g.filters = filters;
+    g.masks = masks;
 
     // Must be called after we set Group::filters
     g.calculate_bounding_boxes();
@@ -990,6 +1150,7 @@ fn convert_path(
     let mut marker = None;
     if marker::is_valid(node) && visibility == Visibility::Visible {
         let mut marker_group = Group {
+            source_span: state.source_span(node),
             abs_transform: parent.abs_transform,
             ..Group::empty()
         };
@@ -1041,10 +1202,12 @@ fn convert_path(
         path_transform,
     );
 
-    let path = match path {
+    let mut path = match path {
         Some(v) => v,
         None => return,
     };
+    path.source_span = state.source_span(node);
+    path.metadata = state.metadata(node);
 
     match (raw_paint_order.order, marker) {
         ([PaintOrderKind::Markers, _, _], Some(markers_node)) => {
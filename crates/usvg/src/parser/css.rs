@@ -0,0 +1,264 @@
+// Copyright 2026 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Helpers for parsing CSS values that are not covered by `svgtypes`.
+
+use std::str::FromStr;
+
+use svgtypes::{Length, LengthUnit as Unit};
+
+use super::svgtree::{AId, SvgNode};
+use super::{converter, units};
+
+/// A reference box.
+///
+/// SVG elements do not have CSS layout boxes, so `content-box` and `padding-box`
+/// are treated as `fill-box`, while `border-box` and `margin-box` are treated as `stroke-box`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) enum GeometryBox {
+    Fill,
+    Stroke,
+    View,
+}
+
+impl GeometryBox {
+    pub(crate) fn parse(text: &str) -> Option<Self> {
+        match text {
+            "content-box" | "padding-box" | "fill-box" => Some(GeometryBox::Fill),
+            "border-box" | "margin-box" | "stroke-box" => Some(GeometryBox::Stroke),
+            "view-box" => Some(GeometryBox::View),
+            _ => None,
+        }
+    }
+}
+
+/// A `<position>` component, relative to the start or to the end of the reference box side.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct PositionOffset {
+    from_end: bool,
+    offset: Length,
+}
+
+impl PositionOffset {
+    pub(crate) const CENTER: Self = PositionOffset {
+        from_end: false,
+        offset: Length {
+            number: 50.0,
+            unit: Unit::Percent,
+        },
+    };
+
+    pub(crate) fn start(offset: Length) -> Self {
+        PositionOffset {
+            from_end: false,
+            offset,
+        }
+    }
+
+    pub(crate) fn end(offset: Length) -> Self {
+        PositionOffset {
+            from_end: true,
+            offset,
+        }
+    }
+
+    /// Resolves the offset inside the `start..start + size` range.
+    ///
+    /// Percentages are resolved against `size`.
+    pub(crate) fn resolve(
+        &self,
+        start: f32,
+        size: f32,
+        node: SvgNode,
+        state: &converter::State,
+    ) -> f32 {
+        let d = convert_length(self.offset, size, node, state);
+        if self.from_end {
+            start + size - d
+        } else {
+            start + d
+        }
+    }
+}
+
+/// Converts a `<length-percentage>` into user units.
+pub(crate) fn convert_length(
+    length: Length,
+    base: f32,
+    node: SvgNode,
+    state: &converter::State,
+) -> f32 {
+    if length.unit == Unit::Percent {
+        base * length.number as f32 / 100.0
+    } else {
+        // Any non-percentage attribute will do here.
+        units::convert_user_length(length, node, AId::StrokeWidth, state)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) enum Token<'a> {
+    Comma,
+    Slash,
+    String(&'a str),
+    /// A keyword, a number or a whole function, like `url(image.png)`.
+    Word(&'a str),
+}
+
+impl<'a> Token<'a> {
+    /// Returns a function name and its arguments.
+    pub(crate) fn function(&self) -> Option<(&'a str, &'a str)> {
+        match *self {
+            Token::Word(s) => {
+                let (name, args) = s.split_once('(')?;
+                Some((name, args.strip_suffix(')')?))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Splits a CSS value into tokens.
+///
+/// Functions arguments are not tokenized.
+pub(crate) fn tokenize(mut s: &str) -> Option<Vec<Token<'_>>> {
+    let mut tokens = Vec::new();
+    loop {
+        s = s.trim_start();
+        let c = match s.chars().next() {
+            Some(c) => c,
+            None => break,
+        };
+
+        match c {
+            ',' => {
+                tokens.push(Token::Comma);
+                s = &s[1..];
+            }
+            '/' => {
+                tokens.push(Token::Slash);
+                s = &s[1..];
+            }
+            '"' | '\'' => {
+                let end = s[1..].find(c)? + 1;
+                tokens.push(Token::String(&s[1..end]));
+                s = &s[end + 1..];
+            }
+            _ => {
+                let end = word_end(s)?;
+                tokens.push(Token::Word(&s[..end]));
+                s = &s[end..];
+            }
+        }
+    }
+
+    Some(tokens)
+}
+
+fn word_end(s: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut quote = None;
+    for (i, c) in s.char_indices() {
+        match (quote, c) {
+            (Some(q), _) if q == c => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') if depth > 0 => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') if depth > 0 => depth -= 1,
+            (None, ')') => return None,
+            (None, _)
+                if depth == 0 && (c.is_whitespace() || matches!(c, ',' | '/' | '"' | '\'')) =>
+            {
+                return Some(i);
+            }
+            _ => {}
+        }
+    }
+
+    if depth != 0 || quote.is_some() {
+        return None;
+    }
+
+    Some(s.len())
+}
+
+pub(crate) fn parse_lengths(tokens: &[Token]) -> Option<Vec<Length>> {
+    tokens
+        .iter()
+        .map(|t| match t {
+            Token::Word(s) => Length::from_str(s).ok(),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Expands a 1-4 values list using the CSS `margin`/`border-radius` shorthand rules.
+pub(crate) fn expand_sides<T: Copy>(values: &[T]) -> Option<[T; 4]> {
+    match *values {
+        [a] => Some([a, a, a, a]),
+        [a, b] => Some([a, b, a, b]),
+        [a, b, c] => Some([a, b, c, b]),
+        [a, b, c, d] => Some([a, b, c, d]),
+        _ => None,
+    }
+}
+
+/// Parses a `<position>`.
+pub(crate) fn parse_position(tokens: &[Token]) -> Option<(PositionOffset, PositionOffset)> {
+    let words = tokens
+        .iter()
+        .map(|t| match *t {
+            Token::Word(s) => Some(s),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let parse_x = |s: &str| match s {
+        "left" => Some(PositionOffset::start(Length::new(0.0, Unit::Percent))),
+        "center" => Some(PositionOffset::CENTER),
+        "right" => Some(PositionOffset::end(Length::new(0.0, Unit::Percent))),
+        _ => Length::from_str(s).ok().map(PositionOffset::start),
+    };
+
+    let parse_y = |s: &str| match s {
+        "top" => Some(PositionOffset::start(Length::new(0.0, Unit::Percent))),
+        "center" => Some(PositionOffset::CENTER),
+        "bottom" => Some(PositionOffset::end(Length::new(0.0, Unit::Percent))),
+        _ => Length::from_str(s).ok().map(PositionOffset::start),
+    };
+
+    let is_length = |s: &str| Length::from_str(s).is_ok();
+
+    // An edge keyword followed by an offset. `true` indicates a horizontal edge.
+    let parse_edge = |edge: &str, offset: &str| {
+        let offset = Length::from_str(offset).ok()?;
+        match edge {
+            "left" => Some((true, PositionOffset::start(offset))),
+            "right" => Some((true, PositionOffset::end(offset))),
+            "top" => Some((false, PositionOffset::start(offset))),
+            "bottom" => Some((false, PositionOffset::end(offset))),
+            _ => None,
+        }
+    };
+
+    match words.as_slice() {
+        [a] => {
+            if matches!(*a, "top" | "bottom") {
+                Some((PositionOffset::CENTER, parse_y(a)?))
+            } else {
+                Some((parse_x(a)?, PositionOffset::CENTER))
+            }
+        }
+        [a, b] => match (parse_x(a), parse_y(b)) {
+            (Some(x), Some(y)) => Some((x, y)),
+            // Keywords can be specified in any order, like `top left`.
+            _ if !is_length(a) && !is_length(b) => Some((parse_x(b)?, parse_y(a)?)),
+            _ => None,
+        },
+        [a, b, c, d] => match (parse_edge(a, b)?, parse_edge(c, d)?) {
+            ((true, x), (false, y)) | ((false, y), (true, x)) => Some((x, y)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -0,0 +1,260 @@
+// Copyright 2026 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use super::image::ImageHrefResolver;
+use super::svgtree::{self, SVG_NS, XLINK_NS};
+use super::{Error, Options, converter, xml_options};
+use crate::{DiagnosticCode, DiagnosticSink, Font, Tree};
+
+/// An external resource required to finish parsing.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ResourceRequest {
+    /// An `image` element or an `feImage` filter primitive source,
+    /// as it was written in the document.
+    ///
+    /// `data:` URLs are never requested.
+    Image(String),
+    /// A font that is not present in [`Options::fontdb`](crate::Options::fontdb).
+    ///
+    /// A font is requested when its first family is missing.
+    /// Never requested when the `text` feature is disabled.
+    Font(Font),
+    /// A document referenced by an external `use` element, without a fragment.
+    ///
+    /// For example, `sprites.svg` for `<use href="sprites.svg#icon"/>`.
+    Sprite(String),
+}
+
+/// An SVG document parsed up to the point where external resources are needed.
+///
+/// Allows loading resources asynchronously or in parallel,
+/// instead of blocking in [`ImageHrefResolver`] during parsing.
+///
+/// Parsing is done in two phases:
+///
+/// 1. [`PendingTree::from_str`] parses the document and collects
+///    [`requests`](PendingTree::requests) without loading anything.
+/// 2. A caller loads resources by any means and provides them via [`PendingTree::fulfill`].
+///    Then [`PendingTree::finish`] builds the final [`Tree`].
+///
+/// Requests that were not fulfilled are treated as missing resources.
+#[derive(Clone, Debug)]
+pub struct PendingTree {
+    text: String,
+    requests: Vec<ResourceRequest>,
+    images: HashMap<String, Arc<Vec<u8>>>,
+    #[cfg(feature = "text")]
+    fonts: Vec<Arc<Vec<u8>>>,
+    sprites: HashMap<String, Vec<u8>>,
+}
+
+impl PendingTree {
+    /// Parses `PendingTree` from an SVG data.
+    ///
+    /// Can contain an SVG string or a gzip compressed data.
+    /// Non-UTF-8 text is converted using [`decode_text`](crate::decode_text).
+    pub fn from_data(data: &[u8], opt: &Options) -> Result<Self, Error> {
+        if data.starts_with(&[0x1f, 0x8b]) {
+            let data = super::read_svg_data(data, opt.limits.max_input_size)?;
+            Self::from_str(&super::decode_text(&data)?, opt)
+        } else if data.len() as u64 > opt.limits.max_input_size {
+            Err(Error::InputSizeLimitReached)
+        } else {
+            Self::from_str(&super::decode_text(data)?, opt)
+        }
+    }
+
+    /// Parses `PendingTree` from an SVG string.
+    ///
+    /// The document is fully parsed, but no external resources are loaded.
+    /// [`Options::image_href_resolver`] is called only for `data:` URLs.
+    pub fn from_str(text: &str, opt: &Options) -> Result<Self, Error> {
+        let doc = match roxmltree::Document::parse_with_options(text, xml_options()) {
+            Ok(doc) => doc,
+            Err(e) => return Self::from_str(&super::recovery::recover(text, e, opt)?, opt),
+        };
+
+        let requests = Mutex::new(Vec::new());
+        for node in doc.descendants() {
+            if !node.has_tag_name((SVG_NS, "use")) {
+                continue;
+            }
+
+            let href = match node
+                .attribute((XLINK_NS, "href"))
+                .or_else(|| node.attribute("href"))
+            {
+                Some(href) => href.trim(),
+                None => continue,
+            };
+
+            if let Some((path, _)) = href.split_once('#') {
+                if !path.is_empty() {
+                    push_request(&requests, ResourceRequest::Sprite(path.to_string()));
+                }
+            }
+        }
+
+        {
+            let forwarding = opt.forwarding();
+            let nested_opt = Options {
+                image_href_resolver: ImageHrefResolver {
+                    resolve_string: Box::new(|href, _| {
+                        push_request(&requests, ResourceRequest::Image(href.to_string()));
+                        None
+                    }),
+                    ..forwarding.image_href_resolver
+                },
+                #[cfg(feature = "text")]
+                font_resolver: crate::FontResolver {
+                    select_font: Box::new(|font, db| {
+                        if !is_font_available(font, db) {
+                            push_request(&requests, ResourceRequest::Font(font.clone()));
+                        }
+
+                        (opt.font_resolver.select_font)(font, db)
+                    }),
+                    ..forwarding.font_resolver
+                },
+                // Everything will be reported during the second phase.
+                diagnostics: DiagnosticSink {
+                    report: Box::new(|_| {}),
+                },
+                ..forwarding
+            };
+
+            Tree::from_xmltree(&doc, &nested_opt)?;
+        }
+
+        Ok(PendingTree {
+            text: text.to_string(),
+            requests: requests.into_inner().unwrap(),
+            images: HashMap::new(),
+            #[cfg(feature = "text")]
+            fonts: Vec::new(),
+            sprites: HashMap::new(),
+        })
+    }
+
+    /// Returns resources required by the document.
+    ///
+    /// Each resource is listed only once.
+    pub fn requests(&self) -> &[ResourceRequest] {
+        &self.requests
+    }
+
+    /// Provides a resource data.
+    ///
+    /// Images can be in any format supported by
+    /// [`ImageHrefResolver::resolve_data`], which is called with a `text/plain` mime.
+    /// Fonts are loaded into a copy of [`Options::fontdb`].
+    /// Sprites must be SVG strings.
+    pub fn fulfill(&mut self, request: ResourceRequest, data: Vec<u8>) {
+        match request {
+            ResourceRequest::Image(href) => {
+                self.images.insert(href, Arc::new(data));
+            }
+            #[cfg(feature = "text")]
+            ResourceRequest::Font(_) => self.fonts.push(Arc::new(data)),
+            #[cfg(not(feature = "text"))]
+            ResourceRequest::Font(_) => {}
+            ResourceRequest::Sprite(href) => {
+                self.sprites.insert(href, data);
+            }
+        }
+    }
+
+    /// Builds a [`Tree`] using fulfilled resources.
+    ///
+    /// `opt` should be the same as the one used during the first phase.
+    pub fn finish(self, opt: &Options) -> Result<Tree, Error> {
+        let doc = roxmltree::Document::parse_with_options(&self.text, xml_options())
+            .map_err(Error::ParsingFailed)?;
+
+        // Sprite documents borrow the decoded text, so it has to outlive them.
+        let sprite_texts: Vec<_> = self
+            .sprites
+            .iter()
+            .map(|(href, data)| (href, super::decode_text(data).ok()))
+            .collect();
+
+        let mut sprites = HashMap::new();
+        for (href, text) in &sprite_texts {
+            let sprite = text
+                .as_deref()
+                .and_then(|text| roxmltree::Document::parse_with_options(text, xml_options()).ok());
+            match sprite {
+                Some(sprite) => {
+                    sprites.insert((*href).clone(), sprite);
+                }
+                None => opt.diagnostics.error(
+                    DiagnosticCode::ResourceLoadingFailed,
+                    format!("Failed to parse '{}'. Skipped.", href),
+                ),
+            }
+        }
+
+        let forwarding = opt.forwarding();
+        #[allow(unused_mut)]
+        let mut nested_opt = Options {
+            image_href_resolver: ImageHrefResolver {
+                resolve_string: Box::new(|href, nested_opt| match self.images.get(href) {
+                    Some(data) => (opt.image_href_resolver.resolve_data)(
+                        "text/plain",
+                        data.clone(),
+                        nested_opt,
+                    ),
+                    None => {
+                        nested_opt.diagnostics.error(
+                            DiagnosticCode::ResourceLoadingFailed,
+                            format!("'{}' was not provided. Skipped.", href),
+                        );
+                        None
+                    }
+                }),
+                ..forwarding.image_href_resolver
+            },
+            ..forwarding
+        };
+
+        #[cfg(feature = "text")]
+        for data in &self.fonts {
+            nested_opt.fontdb_mut().load_font_data(data.to_vec());
+        }
+
+        let doc = svgtree::Document::parse_tree_with_sprites(&doc, &sprites, &nested_opt)?;
+        converter::convert_doc(&doc, &nested_opt)
+    }
+}
+
+fn push_request(requests: &Mutex<Vec<ResourceRequest>>, request: ResourceRequest) {
+    let mut requests = requests.lock().unwrap();
+    if !requests.contains(&request) {
+        requests.push(request);
+    }
+}
+
+/// Checks that the first family of a font is present in the database.
+#[cfg(feature = "text")]
+fn is_font_available(font: &Font, db: &fontdb::Database) -> bool {
+    use svgtypes::FontFamily;
+
+    let family = match font.families().first() {
+        Some(FontFamily::Named(name)) => name.as_str(),
+        Some(FontFamily::Serif) => db.family_name(&fontdb::Family::Serif),
+        Some(FontFamily::SansSerif) => db.family_name(&fontdb::Family::SansSerif),
+        Some(FontFamily::Cursive) => db.family_name(&fontdb::Family::Cursive),
+        Some(FontFamily::Fantasy) => db.family_name(&fontdb::Family::Fantasy),
+        Some(FontFamily::Monospace) => db.family_name(&fontdb::Family::Monospace),
+        None => return true,
+    };
+
+    db.faces().any(|face| {
+        face.families
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case(family))
+    })
+}
@@ -0,0 +1,168 @@
+// Copyright 2026 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use super::style_sheet::StyleSheetResolver;
+use super::svgtree::{SVG_NS, XLINK_NS};
+use super::{Error, Options, xml_options};
+
+/// A kind of an external resource.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum DependencyKind {
+    /// An `image` element source.
+    Image,
+    /// An `feImage` filter primitive source.
+    FilterImage,
+    /// An external `use` element target, like `shapes.svg#circle`.
+    ///
+    /// Such targets are not supported by usvg and are never loaded,
+    /// but are still reported.
+    Use,
+    /// A style sheet loaded via an `@import` rule.
+    StyleSheet,
+    /// A font file that contains a font selected for a text.
+    Font,
+}
+
+/// An external resource referenced by a document.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Dependency {
+    /// A resource kind.
+    pub kind: DependencyKind,
+    /// A reference as it was written in the document, without a fragment.
+    ///
+    /// For fonts, this is a font file path.
+    pub href: String,
+    /// An absolute path to the resource.
+    ///
+    /// Resolved relative to [`Options::resources_dir`](crate::Options::resources_dir).
+    pub path: PathBuf,
+}
+
+/// Collects external resources referenced by an SVG document.
+///
+/// The document is parsed, but not rendered.
+/// Images, `use` targets and `feImage` sources are collected from the XML as is,
+/// so they are reported even when they are not loaded.
+/// Style sheets and fonts are reported only when they are requested
+/// during parsing, via [`Options::style_sheet_resolver`] and [`Options::font_resolver`].
+///
+/// `data:` URLs and references to elements in the same document are ignored.
+pub fn collect_dependencies(text: &str, opt: &Options) -> Result<Vec<Dependency>, Error> {
+    let doc = roxmltree::Document::parse_with_options(text, xml_options())?;
+
+    let mut deps = Vec::new();
+    for node in doc.descendants().filter(|n| n.is_element()) {
+        let kind = match node.tag_name().name() {
+            "image" => DependencyKind::Image,
+            "feImage" => DependencyKind::FilterImage,
+            "use" => DependencyKind::Use,
+            _ => continue,
+        };
+
+        if node.tag_name().namespace() != Some(SVG_NS) {
+            continue;
+        }
+
+        let href = match node
+            .attribute((XLINK_NS, "href"))
+            .or_else(|| node.attribute("href"))
+        {
+            Some(href) => href.trim(),
+            None => continue,
+        };
+
+        // Remove a fragment, like in `shapes.svg#circle`.
+        let href = href.split('#').next().unwrap_or_default();
+        if href.is_empty() || href.starts_with("data:") || href.contains("://") {
+            continue;
+        }
+
+        push_dependency(
+            &mut deps,
+            kind,
+            href.to_string(),
+            opt.get_abs_path(Path::new(href)),
+        );
+    }
+
+    let requested = Mutex::new(Vec::new());
+    {
+        let nested_opt = recording_options(opt, &requested);
+        crate::Tree::from_xmltree(&doc, &nested_opt)?;
+    }
+
+    for (kind, href, path) in requested.into_inner().unwrap() {
+        push_dependency(&mut deps, kind, href, path);
+    }
+
+    Ok(deps)
+}
+
+fn push_dependency(deps: &mut Vec<Dependency>, kind: DependencyKind, href: String, path: PathBuf) {
+    let path = std::fs::canonicalize(&path).unwrap_or(path);
+    if !deps.iter().any(|dep| dep.kind == kind && dep.path == path) {
+        deps.push(Dependency { kind, href, path });
+    }
+}
+
+/// Creates options that forward to the provided ones and record requested resources.
+fn recording_options<'a>(
+    opt: &'a Options,
+    requested: &'a Mutex<Vec<(DependencyKind, String, PathBuf)>>,
+) -> Options<'a> {
+    Options {
+        #[cfg(feature = "text")]
+        font_resolver: crate::FontResolver {
+            select_font: Box::new(|font, db| {
+                let id = (opt.font_resolver.select_font)(font, db)?;
+                record_font(id, db, requested);
+                Some(id)
+            }),
+            select_fallback: Box::new(|c, used_fonts, db| {
+                let id = (opt.font_resolver.select_fallback)(c, used_fonts, db)?;
+                record_font(id, db, requested);
+                Some(id)
+            }),
+        },
+        style_sheet_resolver: StyleSheetResolver {
+            resolve_import: Box::new(|url, nested_opt| {
+                let path = opt.get_abs_path(Path::new(url));
+                requested
+                    .lock()
+                    .unwrap()
+                    .push((DependencyKind::StyleSheet, url.to_string(), path));
+                (opt.style_sheet_resolver.resolve_import)(url, nested_opt)
+            }),
+        },
+        source_spans: false,
+        metadata: Default::default(),
+        ..opt.forwarding()
+    }
+}
+
+#[cfg(feature = "text")]
+fn record_font(
+    id: fontdb::ID,
+    db: &fontdb::Database,
+    requested: &Mutex<Vec<(DependencyKind, String, PathBuf)>>,
+) {
+    let path: Option<PathBuf> = match db.face_source(id) {
+        #[cfg(feature = "system-fonts")]
+        Some((fontdb::Source::File(path), _)) => Some(path),
+        #[cfg(all(feature = "system-fonts", feature = "memmap-fonts"))]
+        Some((fontdb::Source::SharedFile(path, _), _)) => Some(path),
+        // Fonts loaded from memory are not files.
+        _ => None,
+    };
+    let Some(path) = path else {
+        return;
+    };
+
+    requested
+        .lock()
+        .unwrap()
+        .push((DependencyKind::Font, path.display().to_string(), path));
+}
@@ -0,0 +1,407 @@
+// Copyright 2026 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::borrow::Cow;
+use std::ops::Range;
+
+use super::recovery::start_tag_len;
+use super::svgtree::{SVG_NS, XLINK_NS};
+use super::{Error, Options, xml_options};
+use crate::Tree;
+
+const XHTML_NS: &str = "http://www.w3.org/1999/xhtml";
+
+/// An SVG document embedded into an HTML or XHTML document.
+#[derive(Debug)]
+pub struct EmbeddedSvg {
+    /// An index of the `svg` element among all top-level `svg` elements in the document.
+    pub index: usize,
+    /// An `id` attribute of the `svg` element.
+    pub id: Option<String>,
+    /// A byte range of the `svg` element in the document.
+    pub range: Range<usize>,
+    /// A parsed tree.
+    ///
+    /// Each SVG is parsed separately, so an error doesn't affect other ones.
+    pub tree: Result<Tree, Error>,
+}
+
+/// A `style` element of a page.
+pub(crate) struct PageStyle<'a> {
+    text: Cow<'a, str>,
+    media: Option<&'a str>,
+}
+
+/// Parses all top-level `svg` elements inside an HTML or XHTML document.
+///
+/// A well-formed XML document is processed as XML, so namespace declarations
+/// from parent elements are preserved.
+/// Otherwise, the document is scanned as HTML, where `svg` elements
+/// do not require namespace declarations.
+///
+/// Page `style` elements are applied to each SVG as if they were preceding internal style sheets.
+/// Selectors that rely on elements outside of an SVG, like `body svg`, will not match.
+///
+/// Nested `svg` elements are parsed as a part of their parent SVG.
+pub fn parse_embedded_svgs(text: &str, opt: &Options) -> Vec<EmbeddedSvg> {
+    let doc = roxmltree::Document::parse_with_options(text, xml_options()).ok();
+    let (svgs, styles) = match doc {
+        Some(ref doc) => scan_xml(doc),
+        None => scan_html(text),
+    };
+
+    svgs.into_iter()
+        .enumerate()
+        .map(|(index, svg)| {
+            let text = standalone_element(&svg.text, &svg.namespaces, &styles);
+            EmbeddedSvg {
+                index,
+                id: svg.id,
+                range: svg.range,
+                tree: Tree::from_str(&text, opt),
+            }
+        })
+        .collect()
+}
+
+/// An `svg` element found in a document.
+struct SvgElement<'a> {
+    range: Range<usize>,
+    /// An element text, with the root element converted to XML in HTML documents.
+    text: Cow<'a, str>,
+    id: Option<String>,
+    namespaces: Namespaces<'a>,
+}
+
+type Namespaces<'a> = Vec<(Option<&'a str>, &'a str)>;
+
+fn scan_xml<'a>(doc: &'a roxmltree::Document<'a>) -> (Vec<SvgElement<'a>>, Vec<PageStyle<'a>>) {
+    // `svg` elements without a namespace declaration are also allowed,
+    // since they are common in XHTML-like HTML pages.
+    let is_svg = |node: roxmltree::Node| {
+        node.tag_name().name() == "svg"
+            && matches!(
+                node.tag_name().namespace(),
+                None | Some(SVG_NS) | Some(XHTML_NS)
+            )
+    };
+    let inside_svg = |node: roxmltree::Node| node.ancestors().skip(1).any(is_svg);
+
+    let mut svgs = Vec::new();
+    let mut styles = Vec::new();
+    for node in doc.descendants().filter(|n| n.is_element()) {
+        if inside_svg(node) {
+            continue;
+        }
+
+        if is_svg(node) {
+            let namespaces = node
+                .namespaces()
+                .filter(|ns| ns.name() != Some("xml"))
+                .map(|ns| match ns.name() {
+                    // The default namespace of an SVG is always SVG.
+                    None => (None, SVG_NS),
+                    Some(name) => (Some(name), ns.uri()),
+                })
+                .collect();
+            svgs.push(SvgElement {
+                range: node.range(),
+                text: Cow::Borrowed(&doc.input_text()[node.range()]),
+                id: node.attribute("id").map(str::to_string),
+                namespaces,
+            });
+        } else if node.tag_name().name() == "style" {
+            if !matches!(node.attribute("type"), None | Some("text/css")) {
+                continue;
+            }
+
+            if let Some(text) = node.text() {
+                styles.push(PageStyle {
+                    text: text.into(),
+                    media: node.attribute("media"),
+                });
+            }
+        }
+    }
+
+    (svgs, styles)
+}
+
+fn scan_html(text: &str) -> (Vec<SvgElement<'_>>, Vec<PageStyle<'_>>) {
+    // ASCII lowercasing preserves byte offsets.
+    let lower = text.to_ascii_lowercase();
+
+    let mut svgs = Vec::new();
+    let mut styles = Vec::new();
+    let mut pos = 0;
+    while let Some(idx) = lower[pos..].find('<') {
+        let start = pos + idx;
+        let rest = &lower[start..];
+
+        if rest.starts_with("<!--") {
+            pos = start + rest.find("-->").map_or(rest.len(), |i| i + 3);
+            continue;
+        }
+
+        let name = ["script", "style", "svg"]
+            .into_iter()
+            .find(|name| is_start_tag(rest, name));
+        let Some(name) = name else {
+            pos = start + 1;
+            continue;
+        };
+
+        let Some(tag_len) = start_tag_len(&text[start..]) else {
+            break;
+        };
+        let content_start = start + tag_len;
+        let tag = &text[start..content_start];
+
+        match name {
+            "script" => {
+                pos = lower[content_start..]
+                    .find("</script")
+                    .map_or(text.len(), |i| content_start + i);
+            }
+            "style" => {
+                let content_end = lower[content_start..]
+                    .find("</style")
+                    .map_or(text.len(), |i| content_start + i);
+
+                if matches!(html_attribute(tag, "type"), None | Some("text/css")) {
+                    styles.push(PageStyle {
+                        text: decode_html_text(&text[content_start..content_end]),
+                        media: html_attribute(tag, "media"),
+                    });
+                }
+
+                pos = content_end;
+            }
+            _ => {
+                let end = if tag.ends_with("/>") {
+                    content_start
+                } else {
+                    svg_end(&lower, content_start)
+                };
+
+                svgs.push(SvgElement {
+                    range: start..end,
+                    text: Cow::Owned(html_svg_to_xml(tag, &text[content_start..end])),
+                    id: html_attribute(tag, "id").map(str::to_string),
+                    namespaces: vec![(None, SVG_NS), (Some("xlink"), XLINK_NS)],
+                });
+                pos = end;
+            }
+        }
+    }
+
+    (svgs, styles)
+}
+
+/// Checks that a text starts with a start tag with the specified name.
+fn is_start_tag(text: &str, name: &str) -> bool {
+    text.strip_prefix('<')
+        .and_then(|s| s.strip_prefix(name))
+        .and_then(|s| s.chars().next())
+        .is_some_and(|c| c.is_ascii_whitespace() || c == '>' || c == '/')
+}
+
+/// Returns an end offset of an `svg` element, including nested ones.
+///
+/// Returns the text length when the element is not closed.
+fn svg_end(lower: &str, mut pos: usize) -> usize {
+    let mut depth = 1;
+    while let Some(idx) = lower[pos..].find('<') {
+        let start = pos + idx;
+        let rest = &lower[start..];
+        if rest.starts_with("<!--") {
+            pos = start + rest.find("-->").map_or(rest.len(), |i| i + 3);
+            continue;
+        }
+
+        if is_start_tag(rest, "svg") {
+            if !start_tag_len(rest).is_some_and(|len| rest[..len].ends_with("/>")) {
+                depth += 1;
+            }
+        } else if rest.starts_with("</svg") {
+            depth -= 1;
+            if depth == 0 {
+                return rest.find('>').map_or(lower.len(), |i| start + i + 1);
+            }
+        }
+
+        pos = start + 1;
+    }
+
+    lower.len()
+}
+
+/// Returns an attribute value of an HTML start tag.
+///
+/// Names are case-insensitive.
+fn html_attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    html_attributes(tag)
+        .find(|(attr_name, _)| attr_name.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value)
+}
+
+/// Returns attributes of an HTML start tag.
+///
+/// Values can be quoted or not. Attributes without a value have an empty one.
+fn html_attributes(tag: &str) -> impl Iterator<Item = (&str, &str)> {
+    let mut rest = tag.strip_prefix('<').unwrap_or(tag);
+    // Skip the tag name.
+    rest = rest.trim_start_matches(|c: char| !c.is_ascii_whitespace() && c != '>' && c != '/');
+
+    std::iter::from_fn(move || {
+        rest = rest.trim_start_matches(|c: char| c.is_ascii_whitespace() || c == '/');
+        if rest.is_empty() || rest.starts_with('>') {
+            return None;
+        }
+
+        let name_len = rest
+            .find(|c: char| c.is_ascii_whitespace() || c == '=' || c == '>' || c == '/')
+            .unwrap_or(rest.len());
+        let name = &rest[..name_len];
+        rest = rest[name_len..].trim_start();
+
+        let mut value = "";
+        if let Some(s) = rest.strip_prefix('=') {
+            let s = s.trim_start();
+            let (v, tail) = match s.chars().next() {
+                Some(q @ ('"' | '\'')) => {
+                    let end = s[1..].find(q).map_or(s.len(), |i| i + 1);
+                    (&s[1..end], s.get(end + 1..).unwrap_or_default())
+                }
+                _ => {
+                    let end = s
+                        .find(|c: char| c.is_ascii_whitespace() || c == '>')
+                        .unwrap_or(s.len());
+                    (&s[..end], &s[end..])
+                }
+            };
+            value = v;
+            rest = tail;
+        }
+
+        Some((name, value))
+    })
+}
+
+/// Converts the root element of an HTML `svg` element to XML.
+///
+/// HTML allows uppercase tag names and unquoted attribute values,
+/// but the element content must still be a well-formed XML.
+fn html_svg_to_xml(tag: &str, rest: &str) -> String {
+    let mut text = String::from("<svg");
+    for (name, value) in html_attributes(tag) {
+        text.push_str(&format!(" {}=\"{}\"", name, escape(value)));
+    }
+
+    if tag.ends_with("/>") {
+        text.push_str("/>");
+        return text;
+    }
+
+    text.push('>');
+    match rest.len().checked_sub(6) {
+        // Lowercase the end tag as well.
+        Some(idx) if rest.as_bytes()[idx..].eq_ignore_ascii_case(b"</svg>") => {
+            text.push_str(&rest[..idx]);
+            text.push_str("</svg>");
+        }
+        _ => text.push_str(rest),
+    }
+
+    text
+}
+
+/// Decodes character references that can appear in an HTML `style` element.
+///
+/// Style elements contain raw text in HTML, but XHTML-like pages can still use them.
+fn decode_html_text(text: &str) -> Cow<'_, str> {
+    if !text.contains('&') {
+        return text.into();
+    }
+
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&amp;", "&")
+        .into()
+}
+
+/// Creates a standalone XML document from an element text.
+///
+/// Missing namespace declarations and page style sheets are added to the root element.
+pub(crate) fn standalone_element(
+    element: &str,
+    namespaces: &[(Option<&str>, &str)],
+    styles: &[PageStyle],
+) -> String {
+    let Some(tag_len) = start_tag_len(element) else {
+        return element.to_string();
+    };
+
+    let start_tag = &element[..tag_len];
+    let name_end = start_tag
+        .find(|c: char| c.is_ascii_whitespace() || c == '>' || c == '/')
+        .unwrap_or(tag_len);
+
+    let mut text = String::with_capacity(element.len());
+    text.push_str(&element[..name_end]);
+
+    // Element prefix, like in `svg:svg`.
+    let prefix = element[1..name_end]
+        .split_once(':')
+        .map(|(prefix, _)| prefix);
+    for (name, uri) in namespaces {
+        // An explicit prefix is already declared by a parent, so the default namespace
+        // should not be overwritten.
+        if name.is_none() && prefix.is_some() {
+            continue;
+        }
+
+        let attr = match name {
+            Some(name) => format!("xmlns:{}", name),
+            None => "xmlns".to_string(),
+        };
+
+        let is_declared = start_tag
+            .match_indices(&attr)
+            .any(|(idx, _)| start_tag[idx + attr.len()..].trim_start().starts_with('='));
+        if !is_declared {
+            text.push_str(&format!(" {}=\"{}\"", attr, escape(uri)));
+        }
+    }
+
+    text.push_str(&element[name_end..tag_len]);
+
+    // Self-closing elements have no content, so there is nothing to style.
+    if !start_tag.ends_with("/>") {
+        let style_name = match prefix {
+            Some(prefix) => format!("{}:style", prefix),
+            None => "style".to_string(),
+        };
+
+        for style in styles {
+            text.push('<');
+            text.push_str(&style_name);
+            if let Some(media) = style.media {
+                text.push_str(&format!(" media=\"{}\"", escape(media)));
+            }
+            text.push('>');
+            text.push_str(&escape(&style.text));
+            text.push_str(&format!("</{}>", style_name));
+        }
+    }
+
+    text.push_str(&element[tag_len..]);
+    text
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('"', "&quot;")
+}
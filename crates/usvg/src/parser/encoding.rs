@@ -0,0 +1,152 @@
+// Copyright 2026 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::borrow::Cow;
+
+use super::Error;
+
+/// Converts SVG data into a UTF-8 string.
+///
+/// The encoding is detected using a byte order mark or an encoding declared
+/// in the XML declaration, like `<?xml version="1.0" encoding="ISO-8859-1"?>`.
+/// Data without both is expected to be UTF-8.
+///
+/// Supported encodings are UTF-8, UTF-16, Windows-1252 and ISO-8859-15.
+/// Like in browsers, ISO-8859-1 and US-ASCII are decoded as Windows-1252.
+pub fn decode_text(data: &[u8]) -> Result<Cow<'_, str>, Error> {
+    if let Some(data) = data.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        return decode_utf8(data);
+    }
+
+    if data.starts_with(&[0xFF, 0xFE, 0x00, 0x00]) || data.starts_with(&[0x00, 0x00, 0xFE, 0xFF]) {
+        return Err(Error::UnsupportedEncoding("UTF-32".to_string()));
+    }
+
+    if let Some(data) = data.strip_prefix(&[0xFF, 0xFE]) {
+        return decode_utf16(data, u16::from_le_bytes);
+    }
+
+    if let Some(data) = data.strip_prefix(&[0xFE, 0xFF]) {
+        return decode_utf16(data, u16::from_be_bytes);
+    }
+
+    // UTF-16 without a byte order mark. Detected by the `<?` sequence.
+    if data.starts_with(&[0x3C, 0x00, 0x3F, 0x00]) {
+        return decode_utf16(data, u16::from_le_bytes);
+    }
+
+    if data.starts_with(&[0x00, 0x3C, 0x00, 0x3F]) {
+        return decode_utf16(data, u16::from_be_bytes);
+    }
+
+    let encoding = match declared_encoding(data) {
+        Some(encoding) => encoding.to_ascii_lowercase(),
+        None => return decode_utf8(data),
+    };
+
+    match encoding.as_str() {
+        // A UTF-16 declaration in an ASCII-compatible data is wrong and can be ignored.
+        "utf-8" | "utf8" | "unicode-1-1-utf-8" | "utf-16" | "utf-16le" | "utf-16be" => {
+            decode_utf8(data)
+        }
+        "windows-1252" | "cp1252" | "x-cp1252" | "iso-8859-1" | "iso8859-1" | "iso_8859-1"
+        | "iso-ir-100" | "latin1" | "l1" | "us-ascii" | "ascii" | "cp819" => {
+            Ok(Cow::Owned(decode_single_byte(data, windows_1252_char)))
+        }
+        "iso-8859-15" | "iso8859-15" | "iso_8859-15" | "latin9" | "latin-9" | "l9" => {
+            Ok(Cow::Owned(decode_single_byte(data, iso_8859_15_char)))
+        }
+        _ => Err(Error::UnsupportedEncoding(encoding)),
+    }
+}
+
+fn decode_utf8(data: &[u8]) -> Result<Cow<'_, str>, Error> {
+    std::str::from_utf8(data)
+        .map(Cow::Borrowed)
+        .map_err(|_| Error::NotAnUtf8Str)
+}
+
+fn decode_utf16(data: &[u8], to_u16: fn([u8; 2]) -> u16) -> Result<Cow<'_, str>, Error> {
+    if data.len() % 2 != 0 {
+        return Err(Error::NotAnUtf8Str);
+    }
+
+    let units = data.chunks_exact(2).map(|c| to_u16([c[0], c[1]]));
+    char::decode_utf16(units)
+        .collect::<Result<String, _>>()
+        .map(Cow::Owned)
+        .map_err(|_| Error::NotAnUtf8Str)
+}
+
+fn decode_single_byte(data: &[u8], to_char: fn(u8) -> char) -> String {
+    data.iter().map(|b| to_char(*b)).collect()
+}
+
+/// Returns an `encoding` value of the XML declaration.
+fn declared_encoding(data: &[u8]) -> Option<&str> {
+    let rest = data.strip_prefix(b"<?xml")?;
+    if !rest.first()?.is_ascii_whitespace() {
+        return None;
+    }
+
+    let end = rest.windows(2).position(|w| w == b"?>")?;
+    let decl = std::str::from_utf8(&rest[..end]).ok()?;
+
+    let value = decl.split_once("encoding")?.1.trim_start();
+    let value = value.strip_prefix('=')?.trim_start();
+    let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let value = &value[1..];
+    value.split_once(quote).map(|(value, _)| value.trim())
+}
+
+fn windows_1252_char(b: u8) -> char {
+    let c = match b {
+        0x80 => 0x20AC,
+        0x82 => 0x201A,
+        0x83 => 0x0192,
+        0x84 => 0x201E,
+        0x85 => 0x2026,
+        0x86 => 0x2020,
+        0x87 => 0x2021,
+        0x88 => 0x02C6,
+        0x89 => 0x2030,
+        0x8A => 0x0160,
+        0x8B => 0x2039,
+        0x8C => 0x0152,
+        0x8E => 0x017D,
+        0x91 => 0x2018,
+        0x92 => 0x2019,
+        0x93 => 0x201C,
+        0x94 => 0x201D,
+        0x95 => 0x2022,
+        0x96 => 0x2013,
+        0x97 => 0x2014,
+        0x98 => 0x02DC,
+        0x99 => 0x2122,
+        0x9A => 0x0161,
+        0x9B => 0x203A,
+        0x9C => 0x0153,
+        0x9E => 0x017E,
+        0x9F => 0x0178,
+        // Other bytes match Unicode code points, like in ISO-8859-1.
+        _ => return char::from(b),
+    };
+
+    char::from_u32(c).unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+fn iso_8859_15_char(b: u8) -> char {
+    let c = match b {
+        0xA4 => 0x20AC,
+        0xA6 => 0x0160,
+        0xA8 => 0x0161,
+        0xB4 => 0x017D,
+        0xB8 => 0x017E,
+        0xBC => 0x0152,
+        0xBD => 0x0153,
+        0xBE => 0x0178,
+        _ => return char::from(b),
+    };
+
+    char::from_u32(c).unwrap_or(char::REPLACEMENT_CHARACTER)
+}
@@ -4,15 +4,14 @@
 //! A collection of SVG filters.
 
 use std::collections::HashSet;
-use std::str::FromStr;
 use std::sync::Arc;
 
 use strict_num::PositiveF32;
 use svgtypes::{AspectRatio, Length, LengthUnit as Unit};
 
 use crate::{
-    ApproxZeroUlps, Color, Group, Node, NonEmptyString, NonZeroF32, NonZeroRect, Opacity, Size,
-    Units,
+    ApproxZeroUlps, Color, DiagnosticCode, Group, Node, NonEmptyString, NonZeroF32, NonZeroRect,
+    Opacity, Size, Units,
     filter::{self, *},
 };
 
@@ -61,9 +60,12 @@ pub(crate) fn convert(
             let object_bbox = match object_bbox {
                 Some(v) => v,
                 None => {
-                    log::warn!(
-                        "Filter '{}' has an invalid region. Skipped.",
-                        node.element_id()
+                    node.warn(
+                        DiagnosticCode::InvalidElement,
+                        format!(
+                            "Filter '{}' has an invalid region. Skipped.",
+                            node.element_id()
+                        ),
                     );
                     return;
                 }
@@ -89,7 +91,10 @@ pub(crate) fn convert(
             Ok(v) => v,
             Err(e) => {
                 // Skip the whole attribute list on error.
-                log::warn!("Failed to parse a filter value cause {}. Skipping.", e);
+                node.warn(
+                    DiagnosticCode::InvalidAttribute,
+                    format!("Failed to parse a filter value cause {}. Skipping.", e),
+                );
                 return Ok(Vec::new());
             }
         };
@@ -215,9 +220,12 @@ fn convert_url(
 
     let mut rect = rect
         .log_none(|| {
-            log::warn!(
-                "Filter '{}' has an invalid region. Skipped.",
-                node.element_id()
+            node.warn(
+                DiagnosticCode::InvalidElement,
+                format!(
+                    "Filter '{}' has an invalid region. Skipped.",
+                    node.element_id()
+                ),
             );
         })
         .ok_or(())?;
@@ -226,7 +234,10 @@ fn convert_url(
         if let Some(object_bbox) = object_bbox {
             rect = rect.bbox_transform(object_bbox);
         } else {
-            log::warn!("Filters on zero-sized shapes are not allowed.");
+            node.warn(
+                DiagnosticCode::InvalidElement,
+                "Filters on zero-sized shapes are not allowed.",
+            );
             return Err(());
         }
     }
@@ -268,10 +279,13 @@ fn convert_url(
 fn find_filter_with_primitives<'a>(node: SvgNode<'a, 'a>) -> Option<SvgNode<'a, 'a>> {
     for link in node.href_iter() {
         if link.tag_name() != Some(EId::Filter) {
-            log::warn!(
-                "Filter '{}' cannot reference '{}' via 'xlink:href'.",
-                node.element_id(),
-                link.tag_name().unwrap()
+            node.warn(
+                DiagnosticCode::InvalidReference,
+                format!(
+                    "Filter '{}' cannot reference '{}' via 'xlink:href'.",
+                    node.element_id(),
+                    link.tag_name().unwrap()
+                ),
             );
             return None;
         }
@@ -356,7 +370,10 @@ fn collect_children(
                 EId::FeSpecularLighting => convert_specular_lighting(child, &primitives)
                     .unwrap_or_else(create_dummy_primitive),
                 tag_name => {
-                    log::warn!("'{}' is not a valid filter primitive. Skipped.", tag_name);
+                    child.warn(
+                        DiagnosticCode::InvalidElement,
+                        format!("'{}' is not a valid filter primitive. Skipped.", tag_name),
+                    );
                     continue;
                 }
             };
@@ -451,7 +468,7 @@ pub(crate) fn create_dummy_primitive() -> Kind {
 fn resolve_input(node: SvgNode, aid: AId, primitives: &[Primitive]) -> Input {
     match node.attribute(aid) {
         Some(s) => {
-            let input = parse_in(s);
+            let input = parse_in(node, s);
 
             // If `in` references an unknown `result` than fallback
             // to previous result or `SourceGraphic`.
@@ -481,12 +498,15 @@ fn resolve_input(node: SvgNode, aid: AId, primitives: &[Primitive]) -> Input {
     }
 }
 
-fn parse_in(s: &str) -> Input {
+fn parse_in(node: SvgNode, s: &str) -> Input {
     match s {
         "SourceGraphic" => Input::SourceGraphic,
         "SourceAlpha" => Input::SourceAlpha,
         "BackgroundImage" | "BackgroundAlpha" | "FillPaint" | "StrokePaint" => {
-            log::warn!("{} filter input isn't supported and not planed.", s);
+            node.warn(
+                DiagnosticCode::Unsupported,
+                format!("{} filter input isn't supported and not planed.", s),
+            );
             Input::SourceGraphic
         }
         _ => Input::Reference(s.to_string()),
@@ -524,6 +544,7 @@ fn convert_blend(fe: SvgNode, primitives: &[Primitive]) -> Kind {
         mode,
         input1,
         input2,
+        no_composite: fe.has_attribute(AId::NoComposite),
     })
 }
 
@@ -774,10 +795,19 @@ fn convert_flood(fe: SvgNode) -> Kind {
 
 fn convert_gaussian_blur(fe: SvgNode, scale: Size, primitives: &[Primitive]) -> Kind {
     let (std_dev_x, std_dev_y) = convert_std_dev_attr(fe, scale, "0 0");
+
+    // Unlike `feConvolveMatrix`, the default value is `none`.
+    let edge_mode = match fe.attribute(AId::EdgeMode).unwrap_or("none") {
+        "duplicate" => EdgeMode::Duplicate,
+        "wrap" => EdgeMode::Wrap,
+        _ => EdgeMode::None,
+    };
+
     Kind::GaussianBlur(GaussianBlur {
         input: resolve_input(fe, AId::In, primitives),
         std_dev_x,
         std_dev_y,
+        edge_mode,
     })
 }
 
@@ -855,10 +885,18 @@ fn convert_image_inner(
     }
 
     let href = fe.try_attribute(AId::Href).log_none(|| {
-        log::warn!("The 'feImage' element lacks the 'xlink:href' attribute. Skipped.");
+        fe.warn(
+            DiagnosticCode::InvalidElement,
+            "The 'feImage' element lacks the 'xlink:href' attribute. Skipped.",
+        );
     })?;
     let img_data = super::image::get_href_data(href, state)?;
-    let actual_size = img_data.actual_size()?;
+    let actual_size = img_data.actual_size().log_none(|| {
+        fe.warn(
+            DiagnosticCode::InvalidElement,
+            "Image has an invalid size. Skipped.",
+        )
+    })?;
 
     let aspect: AspectRatio = fe.attribute(AId::PreserveAspectRatio).unwrap_or_default();
 
@@ -866,6 +904,8 @@ fn convert_image_inner(
     super::image::convert_inner(
         img_data,
         cache.gen_image_id().take(),
+        state.source_span(fe),
+        None,
         true,
         rendering_mode,
         aspect,
@@ -923,10 +963,13 @@ fn convert_lighting_color(node: SvgNode) -> Color {
                 .0
         }
         Some(value) => {
-            if let Ok(c) = svgtypes::Color::from_str(value) {
+            if let Some(c) = super::color::parse_color(value) {
                 c.split_alpha().0
             } else {
-                log::warn!("Failed to parse lighting-color value: '{}'.", value);
+                node.warn(
+                    DiagnosticCode::InvalidAttribute,
+                    format!("Failed to parse lighting-color value: '{}'.", value),
+                );
                 Color::white()
             }
         }
@@ -1240,6 +1283,7 @@ fn convert_blur_function(node: SvgNode, std_dev: Length, state: &converter::Stat
         input: Input::SourceGraphic,
         std_dev_x: std_dev,
         std_dev_y: std_dev,
+        edge_mode: EdgeMode::None,
     })
 }
 
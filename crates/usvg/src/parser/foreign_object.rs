@@ -0,0 +1,352 @@
+// Copyright 2026 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use svgtypes::Length;
+
+use super::svgtree::{AId, SvgNode};
+use super::{Options, converter};
+use crate::{Group, ImageKind, NonZeroRect, Size, Visibility};
+
+/// A shorthand for [ForeignObjectResolver]'s function.
+pub type ForeignObjectResolverFn<'a> =
+    Box<dyn Fn(&ForeignObject, &Options) -> Option<ImageKind> + Send + Sync + 'a>;
+
+/// A `foreignObject` element.
+#[derive(Clone, Copy, Debug)]
+pub struct ForeignObject<'a> {
+    /// An element ID. Empty when not set.
+    pub id: &'a str,
+    /// The element XML, including its content.
+    ///
+    /// Namespaces declared by ancestors are declared on the element itself,
+    /// so it can be parsed as a standalone document.
+    pub xml: &'a str,
+    /// The element viewport size in user units.
+    pub size: Size,
+}
+
+/// A `foreignObject` resolver.
+///
+/// The returned image is rendered as an `image` element
+/// with the `foreignObject` position and size.
+///
+/// Note that `switch` elements prefer their fallback content,
+/// unless [`ConditionResolver`](crate::ConditionResolver) accepts
+/// the `http://www.w3.org/TR/SVG11/feature#Extensibility` feature.
+pub struct ForeignObjectResolver<'a> {
+    /// Resolver function that will be used to render a `foreignObject` element.
+    ///
+    /// Returning `None` skips the element.
+    pub resolve: ForeignObjectResolverFn<'a>,
+}
+
+impl Default for ForeignObjectResolver<'_> {
+    fn default() -> Self {
+        ForeignObjectResolver {
+            resolve: ForeignObjectResolver::default_resolver(),
+        }
+    }
+}
+
+impl ForeignObjectResolver<'_> {
+    /// Creates a default resolver.
+    ///
+    /// Renders only text, split into paragraphs by block elements, like `p` and `div`,
+    /// and by `br`. Paragraphs are wrapped to the element width
+    /// and use [`Options::font_family`] and [`Options::font_size`].
+    /// Any styling is ignored.
+    ///
+    /// Skips all elements when the `text` feature is disabled.
+    pub fn default_resolver() -> ForeignObjectResolverFn<'static> {
+        #[cfg(feature = "text")]
+        {
+            Box::new(text::resolve)
+        }
+
+        #[cfg(not(feature = "text"))]
+        {
+            Box::new(|_, _| None)
+        }
+    }
+}
+
+impl std::fmt::Debug for ForeignObjectResolver<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ForeignObjectResolver { .. }")
+    }
+}
+
+pub(crate) fn convert(
+    node: SvgNode,
+    state: &converter::State,
+    cache: &mut converter::Cache,
+    parent: &mut Group,
+) -> Option<()> {
+    let xml = node.foreign_object_xml()?;
+
+    // `width` and `height` are zero by default, which disables rendering.
+    let x = node.convert_user_length(AId::X, state, Length::zero());
+    let y = node.convert_user_length(AId::Y, state, Length::zero());
+    let width = node.convert_user_length(AId::Width, state, Length::zero());
+    let height = node.convert_user_length(AId::Height, state, Length::zero());
+    let rect = NonZeroRect::from_xywh(x, y, width, height)?;
+
+    let object = ForeignObject {
+        id: node.element_id(),
+        xml,
+        size: rect.size(),
+    };
+    let kind = (state.opt.foreign_object_resolver.resolve)(&object, state.opt)?;
+    let actual_size = kind.actual_size()?;
+
+    let visibility: Visibility = node.find_attribute(AId::Visibility).unwrap_or_default();
+
+    // Nodes generated by markers must not have an ID. Otherwise we would have duplicates.
+    let id = if state.parent_markers.is_empty() {
+        node.element_id().to_string()
+    } else {
+        String::new()
+    };
+
+    super::image::convert_inner(
+        kind,
+        id,
+        state.source_span(node),
+        state.metadata(node),
+        visibility == Visibility::Visible,
+        state.opt.image_rendering,
+        svgtypes::AspectRatio::default(),
+        actual_size,
+        rect,
+        cache,
+        parent,
+    )
+}
+
+#[cfg(feature = "text")]
+mod text {
+    use std::collections::{HashMap, HashSet};
+
+    use super::*;
+    use crate::{DiagnosticCode, DiagnosticSink, Tree};
+
+    /// Elements that start a new paragraph.
+    const BLOCK_ELEMENTS: &[&str] = &[
+        "address",
+        "article",
+        "blockquote",
+        "dd",
+        "div",
+        "dl",
+        "dt",
+        "figcaption",
+        "footer",
+        "h1",
+        "h2",
+        "h3",
+        "h4",
+        "h5",
+        "h6",
+        "header",
+        "li",
+        "ol",
+        "p",
+        "pre",
+        "section",
+        "table",
+        "tr",
+        "ul",
+    ];
+
+    pub(super) fn resolve(object: &ForeignObject, opt: &Options) -> Option<ImageKind> {
+        let doc = roxmltree::Document::parse(object.xml).ok()?;
+
+        let mut paragraphs = vec![String::new()];
+        collect_paragraphs(doc.root_element(), &mut paragraphs);
+        let paragraphs: Vec<_> = paragraphs
+            .iter()
+            .map(|p| p.split_whitespace().collect::<Vec<_>>())
+            .filter(|words| !words.is_empty())
+            .collect();
+        if paragraphs.is_empty() {
+            return None;
+        }
+
+        let text_len: usize = paragraphs.iter().flatten().map(|w| w.chars().count()).sum();
+        if text_len > opt.limits.max_text_length {
+            opt.diagnostics.warn(
+                DiagnosticCode::LimitExceeded,
+                format!(
+                    "'{}' foreignObject has more characters than allowed. Skipped.",
+                    object.id
+                ),
+            );
+            return None;
+        }
+
+        // The generated document must not be affected by the user style sheet.
+        let text_opt = Options {
+            style_sheet: None,
+            custom_properties: Default::default(),
+            fragment: None,
+            ..opt.forwarding()
+        };
+        // Font problems will be reported by the final tree.
+        let measure_opt = Options {
+            diagnostics: DiagnosticSink {
+                report: Box::new(|_| {}),
+            },
+            ..text_opt.forwarding()
+        };
+
+        let metrics = Metrics::measure(paragraphs.iter().flatten().copied(), &measure_opt);
+
+        let mut lines = Vec::new();
+        for words in paragraphs {
+            let mut line = words[0].to_string();
+            let mut line_advance = metrics.words[words[0]].advance;
+            for word in &words[1..] {
+                let word_metrics = &metrics.words[word];
+                let start = line_advance + metrics.space_advance;
+                if start + word_metrics.width <= object.size.width() {
+                    line.push(' ');
+                    line.push_str(word);
+                    line_advance = start + word_metrics.advance;
+                } else {
+                    lines.push(std::mem::replace(&mut line, word.to_string()));
+                    line_advance = word_metrics.advance;
+                }
+            }
+            lines.push(line);
+        }
+
+        let svg = text_svg(&lines, object.size, opt);
+        Tree::from_str(&svg, &text_opt).ok().map(ImageKind::SVG)
+    }
+
+    fn collect_paragraphs(parent: roxmltree::Node, paragraphs: &mut Vec<String>) {
+        for node in parent.children() {
+            if let Some(text) = node.text().filter(|_| node.is_text()) {
+                if let Some(paragraph) = paragraphs.last_mut() {
+                    paragraph.push_str(text);
+                    paragraph.push(' ');
+                }
+                continue;
+            }
+
+            if !node.is_element() {
+                continue;
+            }
+
+            let name = node.tag_name().name();
+            if name == "br" {
+                paragraphs.push(String::new());
+            } else if name == "script" || name == "style" {
+                continue;
+            } else if BLOCK_ELEMENTS.contains(&name) {
+                paragraphs.push(String::new());
+                collect_paragraphs(node, paragraphs);
+                paragraphs.push(String::new());
+            } else {
+                collect_paragraphs(node, paragraphs);
+            }
+        }
+    }
+
+    struct WordMetrics {
+        /// The distance to the start of the next word.
+        advance: f32,
+        /// The distance to the right edge of the word outline.
+        width: f32,
+    }
+
+    struct Metrics<'a> {
+        words: HashMap<&'a str, WordMetrics>,
+        space_advance: f32,
+    }
+
+    impl<'a> Metrics<'a> {
+        /// Measures all words at once, so a paragraph is never shaped word by word.
+        ///
+        /// Only outline edges can be measured, so advances are found by appending
+        /// a reference character: `advance("word") = width("wordx") - width("x")`.
+        fn measure(words: impl Iterator<Item = &'a str>, opt: &Options) -> Self {
+            let mut seen = HashSet::new();
+            let unique: Vec<&str> = words.filter(|word| seen.insert(*word)).collect();
+
+            let mut texts = vec!["x".to_string(), "xx".to_string(), "x x".to_string()];
+            for word in &unique {
+                texts.push(word.to_string());
+                texts.push(format!("{}x", word));
+            }
+
+            let mut svg = String::from("<svg xmlns='http://www.w3.org/2000/svg'>");
+            for (i, text) in texts.iter().enumerate() {
+                svg.push_str(&format!(
+                    "<text id='t{}' y='{}' font-family='{}' font-size='{}'>{}</text>",
+                    i,
+                    opt.font_size,
+                    escape(&opt.font_family),
+                    opt.font_size,
+                    escape(text)
+                ));
+            }
+            svg.push_str("</svg>");
+
+            let tree = Tree::from_str(&svg, opt).ok();
+            let width = |i: usize| {
+                tree.as_ref()
+                    .and_then(|tree| tree.node_by_id(&format!("t{}", i)))
+                    .and_then(|node| node.abs_stroke_bounding_box().to_non_zero_rect())
+                    .map_or(0.0, |bbox| bbox.right())
+            };
+
+            let x_width = width(0);
+            let space_advance = (width(2) - width(1)).max(0.0);
+            let words = unique
+                .iter()
+                .enumerate()
+                .map(|(i, word)| {
+                    let metrics = WordMetrics {
+                        advance: (width(3 + i * 2 + 1) - x_width).max(0.0),
+                        width: width(3 + i * 2),
+                    };
+                    (*word, metrics)
+                })
+                .collect();
+
+            Metrics {
+                words,
+                space_advance,
+            }
+        }
+    }
+
+    fn text_svg(lines: &[String], size: Size, opt: &Options) -> String {
+        let line_height = opt.font_size * 1.2;
+
+        let mut svg = format!(
+            "<svg xmlns='http://www.w3.org/2000/svg' width='{w}' height='{h}' viewBox='0 0 {w} {h}'>\
+             <text font-family='{}' font-size='{}'>",
+            escape(&opt.font_family),
+            opt.font_size,
+            w = size.width(),
+            h = size.height(),
+        );
+        for (i, line) in lines.iter().enumerate() {
+            svg.push_str(&format!(
+                "<tspan x='0' y='{}'>{}</tspan>",
+                line_height * i as f32 + opt.font_size,
+                escape(line)
+            ));
+        }
+        svg.push_str("</text></svg>");
+        svg
+    }
+
+    fn escape(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('\'', "&apos;")
+    }
+}
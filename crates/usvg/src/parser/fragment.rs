@@ -0,0 +1,142 @@
+// Copyright 2026 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::str::FromStr;
+
+use svgtypes::AspectRatio;
+use tiny_skia_path::{NonZeroRect, Size, Transform};
+
+use super::svgtree::{self, SVG_NS};
+use crate::{DiagnosticCode, Options, Tree};
+
+/// A view that overrides the root element viewport.
+#[derive(Clone, Copy, Default, Debug)]
+pub(crate) struct ViewSpec {
+    pub(crate) view_box: Option<NonZeroRect>,
+    pub(crate) aspect: Option<AspectRatio>,
+    /// Applied to the root element content, after the `viewBox` transform.
+    pub(crate) transform: Transform,
+    /// A canvas size that overrides the root element `width` and `height`.
+    pub(crate) size: Option<Size>,
+}
+
+/// A fragment identifier from [`Options::fragment`].
+#[derive(Clone, Debug)]
+pub(crate) enum Fragment {
+    /// A `view` element or an SVG view specification.
+    View(ViewSpec),
+    /// An ID of an element that should fill the viewport.
+    Element(String),
+}
+
+/// Resolves [`Options::fragment`] using the source XML.
+///
+/// `view` elements are not preserved by [`svgtree`], so they have to be resolved before conversion.
+pub(crate) fn resolve(
+    xml: &roxmltree::Document,
+    doc: &svgtree::Document,
+    opt: &Options,
+) -> Option<Fragment> {
+    let fragment = opt.fragment.as_deref()?.trim();
+    let fragment = fragment.strip_prefix('#').unwrap_or(fragment);
+    if fragment.is_empty() {
+        return None;
+    }
+
+    if fragment.starts_with("svgView(") {
+        let view = parse_svg_view(fragment, opt);
+        if view.is_none() {
+            opt.diagnostics.warn(
+                DiagnosticCode::InvalidAttribute,
+                format!("Failed to parse a '{}' view. Skipped.", fragment),
+            );
+        }
+
+        return view.map(Fragment::View);
+    }
+
+    let view = xml
+        .descendants()
+        .find(|n| n.has_tag_name((SVG_NS, "view")) && n.attribute("id") == Some(fragment));
+    if let Some(view) = view {
+        return Some(Fragment::View(ViewSpec {
+            view_box: view.attribute("viewBox").and_then(parse_view_box),
+            aspect: view
+                .attribute("preserveAspectRatio")
+                .and_then(|v| AspectRatio::from_str(v).ok()),
+            ..ViewSpec::default()
+        }));
+    }
+
+    // The root element is always rendered as is.
+    if doc.root_element().element_id() == fragment {
+        return None;
+    }
+
+    match doc.element_by_id(fragment) {
+        Some(_) => Some(Fragment::Element(fragment.to_string())),
+        None => {
+            opt.diagnostics.warn(
+                DiagnosticCode::InvalidReference,
+                format!(
+                    "Fragment '#{}' doesn't reference an element. Skipped.",
+                    fragment
+                ),
+            );
+            None
+        }
+    }
+}
+
+/// Parses an SVG view specification, like `svgView(viewBox(0,0,10,10);transform(scale(2)))`.
+///
+/// `zoomAndPan` and `viewTarget` have no effect on rendering and are ignored.
+fn parse_svg_view(text: &str, opt: &Options) -> Option<ViewSpec> {
+    let specs = text.strip_prefix("svgView(")?.strip_suffix(')')?;
+
+    let mut view = ViewSpec::default();
+    for spec in specs.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+        let (name, value) = spec.strip_suffix(')')?.split_once('(')?;
+        match name.trim() {
+            "viewBox" => view.view_box = Some(parse_view_box(value)?),
+            "preserveAspectRatio" => view.aspect = Some(AspectRatio::from_str(value).ok()?),
+            "transform" => {
+                let ts = svgtypes::Transform::from_str(value).ok()?;
+                let ts = Transform::from_row(
+                    ts.a as f32,
+                    ts.b as f32,
+                    ts.c as f32,
+                    ts.d as f32,
+                    ts.e as f32,
+                    ts.f as f32,
+                );
+                view.transform = Some(ts).filter(|ts| ts.is_valid())?;
+            }
+            "zoomAndPan" | "viewTarget" => {}
+            name => opt.diagnostics.warn(
+                DiagnosticCode::Unsupported,
+                format!("'{}' view specification is not supported.", name),
+            ),
+        }
+    }
+
+    Some(view)
+}
+
+fn parse_view_box(text: &str) -> Option<NonZeroRect> {
+    let vb = svgtypes::ViewBox::from_str(text).ok()?;
+    NonZeroRect::from_xywh(vb.x as f32, vb.y as f32, vb.w as f32, vb.h as f32)
+}
+
+/// Creates a view that fits an element into the viewport.
+///
+/// `tree` is the document converted without a view and `root_ts` is its root transform.
+pub(crate) fn element_view(tree: &Tree, root_ts: Transform, id: &str) -> Option<ViewSpec> {
+    let bbox = tree.node_by_id(id)?.abs_layer_bounding_box()?;
+    let view_box = bbox.transform(root_ts.invert()?)?;
+    Some(ViewSpec {
+        view_box: Some(view_box),
+        size: Some(bbox.size()),
+        ..ViewSpec::default()
+    })
+}
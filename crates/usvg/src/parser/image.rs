@@ -8,8 +8,8 @@ use svgtypes::{AspectRatio, Length};
 use super::svgtree::{AId, SvgNode};
 use super::{OptionLog, Options, converter};
 use crate::{
-    ClipPath, Group, Image, ImageKind, ImageRendering, Node, NonZeroRect, Path, Size, Transform,
-    Tree, Visibility,
+    ClipPath, DiagnosticCode, Group, Image, ImageKind, ImageRendering, Metadata, Node, NonZeroRect,
+    Path, Size, SourceSpan, Transform, Tree, Visibility,
 };
 
 /// A shorthand for [ImageHrefResolver]'s data function.
@@ -82,15 +82,20 @@ impl ImageHrefResolver<'_> {
     ///
     /// Paths have to be absolute or relative to the input SVG file or relative to
     /// [Options::resources_dir](crate::Options::resources_dir).
+    ///
+    /// Follows [Options::resource_policy](crate::Options::resource_policy).
     pub fn default_string_resolver() -> ImageHrefStringResolverFn<'static> {
         Box::new(move |href: &str, opts: &Options| {
-            let path = opts.get_abs_path(std::path::Path::new(href));
+            let path = opts.resolve_resource_path(href)?;
 
             if path.exists() {
                 let data = match std::fs::read(&path) {
                     Ok(data) => data,
                     Err(_) => {
-                        log::warn!("Failed to load '{}'. Skipped.", href);
+                        opts.diagnostics.error(
+                            DiagnosticCode::ResourceLoadingFailed,
+                            format!("Failed to load '{}'. Skipped.", href),
+                        );
                         return None;
                     }
                 };
@@ -102,12 +107,18 @@ impl ImageHrefResolver<'_> {
                     Some(ImageFormat::WEBP) => Some(ImageKind::WEBP(Arc::new(data))),
                     Some(ImageFormat::SVG) => load_sub_svg(&data, opts),
                     _ => {
-                        log::warn!("'{}' is not a PNG, JPEG, GIF, WebP or SVG(Z) image.", href);
+                        opts.diagnostics.error(
+                            DiagnosticCode::ResourceLoadingFailed,
+                            format!("'{}' is not a PNG, JPEG, GIF, WebP or SVG(Z) image.", href),
+                        );
                         None
                     }
                 }
             } else {
-                log::warn!("'{}' is not a path to an image.", href);
+                opts.diagnostics.error(
+                    DiagnosticCode::ResourceLoadingFailed,
+                    format!("'{}' is not a path to an image.", href),
+                );
                 None
             }
         })
@@ -135,9 +146,12 @@ pub(crate) fn convert(
     cache: &mut converter::Cache,
     parent: &mut Group,
 ) -> Option<()> {
-    let href = node
-        .try_attribute(AId::Href)
-        .log_none(|| log::warn!("Image lacks the 'xlink:href' attribute. Skipped."))?;
+    let href = node.try_attribute(AId::Href).log_none(|| {
+        node.warn(
+            DiagnosticCode::InvalidElement,
+            "Image lacks the 'xlink:href' attribute. Skipped.",
+        )
+    })?;
 
     let kind = get_href_data(href, state)?;
 
@@ -155,7 +169,12 @@ pub(crate) fn convert(
         String::new()
     };
 
-    let actual_size = kind.actual_size()?;
+    let actual_size = kind.actual_size().log_none(|| {
+        node.warn(
+            DiagnosticCode::InvalidElement,
+            "Image has an invalid size. Skipped.",
+        )
+    })?;
 
     let x = node.convert_user_length(AId::X, state, Length::zero());
     let y = node.convert_user_length(AId::Y, state, Length::zero());
@@ -188,11 +207,18 @@ pub(crate) fn convert(
     let aspect: AspectRatio = node.attribute(AId::PreserveAspectRatio).unwrap_or_default();
 
     let rect = NonZeroRect::from_xywh(x, y, width, height);
-    let rect = rect.log_none(|| log::warn!("Image has an invalid size. Skipped."))?;
+    let rect = rect.log_none(|| {
+        node.warn(
+            DiagnosticCode::InvalidElement,
+            "Image has an invalid size. Skipped.",
+        )
+    })?;
 
     convert_inner(
         kind,
         id,
+        state.source_span(node),
+        state.metadata(node),
         visible,
         rendering_mode,
         aspect,
@@ -206,6 +232,8 @@ pub(crate) fn convert(
 pub(crate) fn convert_inner(
     kind: ImageKind,
     id: String,
+    source_span: Option<SourceSpan>,
+    metadata: Option<Box<Metadata>>,
     visible: bool,
     rendering_mode: ImageRendering,
     aspect: AspectRatio,
@@ -238,8 +266,12 @@ pub(crate) fn convert_inner(
 
     let mut g = Group::empty();
     g.id = id;
+    g.source_span = source_span.clone();
+    g.metadata = metadata;
     g.children.push(Node::Image(Box::new(Image {
         id: String::new(),
+        source_span,
+        metadata: None,
         visible,
         size: actual_size,
         rendering_mode,
@@ -272,6 +304,8 @@ pub(crate) fn convert_inner(
 
         let mut g2 = Group::empty();
         std::mem::swap(&mut g.id, &mut g2.id);
+        std::mem::swap(&mut g.metadata, &mut g2.metadata);
+        g2.source_span = g.source_span.clone();
         g2.abs_transform = parent.abs_transform;
         g2.clip_path = Some(Arc::new(clip));
         g2.children.push(Node::Group(Box::new(g)));
@@ -286,7 +320,7 @@ pub(crate) fn convert_inner(
 }
 
 pub(crate) fn get_href_data(href: &str, state: &converter::State) -> Option<ImageKind> {
-    if let Ok(url) = data_url::DataUrl::process(href) {
+    let kind = if let Ok(url) = data_url::DataUrl::process(href) {
         let (data, _) = url.decode_to_vec().ok()?;
 
         let mime = format!(
@@ -298,7 +332,26 @@ pub(crate) fn get_href_data(href: &str, state: &converter::State) -> Option<Imag
         (state.opt.image_href_resolver.resolve_data)(&mime, Arc::new(data), state.opt)
     } else {
         (state.opt.image_href_resolver.resolve_string)(href, state.opt)
+    }?;
+
+    // Check the size stored in the header, so huge images would never be decoded.
+    if !matches!(kind, ImageKind::SVG(_)) {
+        if let Some(size) = kind.actual_size() {
+            let pixels = size.width() as u64 * size.height() as u64;
+            if pixels > state.opt.limits.max_image_pixels {
+                state.opt.diagnostics.warn(
+                    DiagnosticCode::LimitExceeded,
+                    format!(
+                        "Image has {} pixels, which is more than allowed. Skipped.",
+                        pixels
+                    ),
+                );
+                return None;
+            }
+        }
     }
+
+    Some(kind)
 }
 
 /// Checks that file has a PNG, a GIF, a JPEG or a WebP magic bytes.
@@ -327,8 +380,18 @@ fn get_image_data_format(data: &[u8]) -> Option<ImageFormat> {
 pub(crate) fn load_sub_svg(data: &[u8], opt: &Options) -> Option<ImageKind> {
     match Tree::from_data_nested(data, opt) {
         Ok(tree) => Some(ImageKind::SVG(tree)),
+        Err(crate::Error::NestedImagesLimitReached) => {
+            opt.diagnostics.warn(
+                DiagnosticCode::LimitExceeded,
+                "Nested SVG images limit reached. Skipped.",
+            );
+            None
+        }
         Err(_) => {
-            log::warn!("Failed to load nested SVG image.");
+            opt.diagnostics.error(
+                DiagnosticCode::ResourceLoadingFailed,
+                "Failed to load nested SVG image.",
+            );
             None
         }
     }
@@ -1,13 +1,19 @@
 // Copyright 2018 the Resvg Authors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use std::str::FromStr;
 use std::sync::Arc;
 
 use svgtypes::{Length, LengthUnit as Unit};
 
+use super::css::{self, GeometryBox, PositionOffset, Token, parse_position, tokenize};
+use super::paint_server::{self, ServerOrColor};
 use super::svgtree::{AId, EId, SvgNode};
-use super::{OptionLog, converter};
-use crate::{Group, Mask, MaskType, Node, NonEmptyString, NonZeroRect, Transform, Units};
+use super::{OptionLog, converter, image};
+use crate::{
+    DiagnosticCode, Fill, Group, ImageKind, Mask, MaskComposite, MaskLayer, MaskType, Node,
+    NonEmptyString, NonZeroRect, Opacity, Paint, Path, Pattern, Size, Transform, Units,
+};
 
 pub(crate) fn convert(
     node: SvgNode,
@@ -46,8 +52,12 @@ pub(crate) fn convert(
         node.convert_length(AId::Width, units, state, Length::new(120.0, Unit::Percent)),
         node.convert_length(AId::Height, units, state, Length::new(120.0, Unit::Percent)),
     );
-    let mut rect =
-        rect.log_none(|| log::warn!("Mask '{}' has an invalid size. Skipped.", node.element_id()))?;
+    let mut rect = rect.log_none(|| {
+        node.warn(
+            DiagnosticCode::InvalidElement,
+            format!("Mask '{}' has an invalid size. Skipped.", node.element_id()),
+        )
+    })?;
 
     let mut mask_all = false;
     if units == Units::ObjectBoundingBox {
@@ -112,7 +122,10 @@ pub(crate) fn convert(
         let object_bbox = match object_bbox {
             Some(v) => v,
             None => {
-                log::warn!("Masking of zero-sized shapes is not allowed.");
+                node.warn(
+                    DiagnosticCode::InvalidElement,
+                    "Masking of zero-sized shapes is not allowed.",
+                );
                 return None;
             }
         };
@@ -148,3 +161,648 @@ pub(crate) fn convert(
     cache.masks.insert(id_copy, mask.clone());
     Some(mask)
 }
+
+/// A `mask-image` value.
+#[derive(Clone, Copy, Debug)]
+enum MaskImage<'a> {
+    None,
+    Url(&'a str),
+    Gradient(&'a str, &'a str),
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum MaskMode {
+    Alpha,
+    Luminance,
+    MatchSource,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum MaskSize {
+    Cover,
+    Contain,
+    /// `None` indicates `auto`.
+    Explicit(Option<Length>, Option<Length>),
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum RepeatStyle {
+    Repeat,
+    Space,
+    Round,
+    NoRepeat,
+}
+
+/// A single mask layer, as defined by the `mask` shorthand and the `mask-*` longhands.
+#[derive(Clone, Copy, Debug)]
+struct LayerProps<'a> {
+    image: MaskImage<'a>,
+    mode: MaskMode,
+    composite: MaskComposite,
+    size: MaskSize,
+    position: (PositionOffset, PositionOffset),
+    repeat: (RepeatStyle, RepeatStyle),
+    origin: GeometryBox,
+    /// `None` indicates `no-clip`.
+    clip: Option<GeometryBox>,
+}
+
+impl Default for LayerProps<'_> {
+    fn default() -> Self {
+        let zero = PositionOffset::start(Length::zero());
+        LayerProps {
+            image: MaskImage::None,
+            mode: MaskMode::MatchSource,
+            composite: MaskComposite::Add,
+            size: MaskSize::Explicit(None, None),
+            position: (zero, zero),
+            repeat: (RepeatStyle::Repeat, RepeatStyle::Repeat),
+            // `border-box` is the default, which is `stroke-box` for SVG elements.
+            origin: GeometryBox::Stroke,
+            clip: Some(GeometryBox::Stroke),
+        }
+    }
+}
+
+/// A mask layer content source.
+enum LayerSource<'a> {
+    Image(Box<ImageKind>, Size),
+    Paint(Paint, Opacity),
+    Gradient(&'a str, &'a str),
+}
+
+/// Converts `mask` and `mask-*` properties into mask layers.
+///
+/// Longhands take precedence over the `mask` shorthand.
+///
+/// Returns `None` when the element should not be rendered,
+/// which happens when it references an invalid `mask` element.
+pub(crate) fn convert_layers(
+    node: SvgNode,
+    state: &converter::State,
+    object_bbox: Option<NonZeroRect>,
+    group: &mut Group,
+    cache: &mut converter::Cache,
+) -> Option<Vec<MaskLayer>> {
+    let mut layers = Vec::new();
+    for props in resolve_layer_props(node) {
+        let layer = match props.image {
+            MaskImage::None => continue,
+            MaskImage::Url(url) => {
+                if let Some(id) = url.strip_prefix('#') {
+                    let Some(link) = node.document().element_by_id(id) else {
+                        node.warn(
+                            DiagnosticCode::InvalidReference,
+                            format!("Mask layer references a non-existing element '{}'.", id),
+                        );
+                        continue;
+                    };
+
+                    if link.tag_name() == Some(EId::Mask) {
+                        let mask = convert(link, state, object_bbox, cache)?;
+                        let mode = match props.mode {
+                            MaskMode::Alpha => MaskType::Alpha,
+                            MaskMode::Luminance => MaskType::Luminance,
+                            MaskMode::MatchSource => mask.kind,
+                        };
+
+                        layers.push(MaskLayer {
+                            mask,
+                            mode,
+                            composite: props.composite,
+                        });
+                        continue;
+                    } else if link.tag_name().is_some_and(|tag| tag.is_paint_server()) {
+                        match paint_server::convert(link, state, cache)? {
+                            ServerOrColor::Server(paint) => LayerSource::Paint(paint, Opacity::ONE),
+                            ServerOrColor::Color { color, opacity } => {
+                                LayerSource::Paint(Paint::Color(color), opacity)
+                            }
+                        }
+                    } else {
+                        // Just like with `mask` elements, an invalid link disables rendering.
+                        return None;
+                    }
+                } else {
+                    let Some(kind) = image::get_href_data(url, state) else {
+                        continue;
+                    };
+                    let Some(size) = kind.actual_size() else {
+                        continue;
+                    };
+                    LayerSource::Image(Box::new(kind), size)
+                }
+            }
+            MaskImage::Gradient(name, args) => LayerSource::Gradient(name, args),
+        };
+
+        if let Some(mask) =
+            convert_image_layer(layer, &props, node, state, group, object_bbox, cache)
+        {
+            let mode = match props.mode {
+                MaskMode::Luminance => MaskType::Luminance,
+                // Images and gradients always use alpha by default.
+                MaskMode::Alpha | MaskMode::MatchSource => MaskType::Alpha,
+            };
+
+            layers.push(MaskLayer {
+                mask: Arc::new(mask),
+                mode,
+                composite: props.composite,
+            });
+        }
+    }
+
+    Some(layers)
+}
+
+fn resolve_layer_props<'a>(node: SvgNode<'a, '_>) -> Vec<LayerProps<'a>> {
+    let mut layers = Vec::new();
+    if let Some(value) = node.attribute::<&str>(AId::Mask) {
+        match parse_list(value, parse_shorthand) {
+            Some(list) => layers = list,
+            None => node.warn(
+                DiagnosticCode::InvalidAttribute,
+                format!("Failed to parse {} value: '{}'.", AId::Mask, value),
+            ),
+        }
+    }
+
+    if let Some(images) = parse_longhand(node, AId::MaskImage, |t| match t {
+        [t] => parse_image(t),
+        _ => None,
+    }) {
+        layers.resize(images.len(), LayerProps::default());
+        for (layer, image) in layers.iter_mut().zip(images) {
+            layer.image = image;
+        }
+    }
+
+    macro_rules! apply_longhand {
+        ($aid:expr, $field:ident, $parser:expr) => {
+            if let Some(list) = parse_longhand(node, $aid, $parser) {
+                for (layer, value) in layers.iter_mut().zip(list.iter().cycle()) {
+                    layer.$field = *value;
+                }
+            }
+        };
+    }
+
+    apply_longhand!(AId::MaskMode, mode, |t| match t {
+        [Token::Word(s)] => parse_mode(s),
+        _ => None,
+    });
+    apply_longhand!(AId::MaskComposite, composite, |t| match t {
+        [Token::Word(s)] => parse_composite(s),
+        _ => None,
+    });
+    apply_longhand!(AId::MaskSize, size, parse_size);
+    apply_longhand!(AId::MaskPosition, position, parse_position);
+    apply_longhand!(AId::MaskRepeat, repeat, parse_repeat);
+    apply_longhand!(AId::MaskOrigin, origin, |t| match t {
+        [Token::Word(s)] => GeometryBox::parse(s),
+        _ => None,
+    });
+    apply_longhand!(AId::MaskClip, clip, |t| match t {
+        [Token::Word("no-clip")] => Some(None),
+        [Token::Word(s)] => GeometryBox::parse(s).map(Some),
+        _ => None,
+    });
+
+    layers
+}
+
+fn parse_longhand<'a, T>(
+    node: SvgNode<'a, '_>,
+    aid: AId,
+    f: impl Fn(&[Token<'a>]) -> Option<T>,
+) -> Option<Vec<T>> {
+    let value = node.attribute::<&str>(aid)?;
+    parse_list(value, f).log_none(|| {
+        node.warn(
+            DiagnosticCode::InvalidAttribute,
+            format!("Failed to parse {} value: '{}'.", aid, value),
+        )
+    })
+}
+
+/// Parses a comma-separated list.
+fn parse_list<'a, T>(value: &'a str, f: impl Fn(&[Token<'a>]) -> Option<T>) -> Option<Vec<T>> {
+    let tokens = tokenize(value)?;
+    tokens.split(|t| *t == Token::Comma).map(f).collect()
+}
+
+fn parse_shorthand<'a>(tokens: &[Token<'a>]) -> Option<LayerProps<'a>> {
+    let mut props = LayerProps::default();
+    let mut has_image = false;
+    let mut has_mode = false;
+    let mut has_composite = false;
+    let mut has_position = false;
+    let mut has_repeat = false;
+    let mut has_no_clip = false;
+    let mut boxes = Vec::new();
+
+    let mut i = 0;
+    while i < tokens.len() {
+        let Token::Word(word) = tokens[i] else {
+            return None;
+        };
+
+        if let Some(image) = parse_image(&tokens[i]) {
+            if std::mem::replace(&mut has_image, true) {
+                return None;
+            }
+
+            props.image = image;
+            i += 1;
+        } else if let Some(mode) = parse_mode(word) {
+            if std::mem::replace(&mut has_mode, true) {
+                return None;
+            }
+
+            props.mode = mode;
+            i += 1;
+        } else if let Some(composite) = parse_composite(word) {
+            if std::mem::replace(&mut has_composite, true) {
+                return None;
+            }
+
+            props.composite = composite;
+            i += 1;
+        } else if let Some(geometry_box) = GeometryBox::parse(word) {
+            boxes.push(geometry_box);
+            i += 1;
+        } else if word == "no-clip" {
+            if std::mem::replace(&mut has_no_clip, true) {
+                return None;
+            }
+
+            i += 1;
+        } else if parse_repeat_keyword(word).is_some() {
+            if std::mem::replace(&mut has_repeat, true) {
+                return None;
+            }
+
+            let len = tokens[i..]
+                .iter()
+                .take(2)
+                .take_while(|t| matches!(t, Token::Word(s) if parse_repeat_keyword(s).is_some()))
+                .count();
+            props.repeat = parse_repeat(&tokens[i..i + len])?;
+            i += len;
+        } else if is_position_word(word) {
+            if std::mem::replace(&mut has_position, true) {
+                return None;
+            }
+
+            let len = tokens[i..]
+                .iter()
+                .take(4)
+                .take_while(|t| matches!(t, Token::Word(s) if is_position_word(s)))
+                .count();
+            props.position = parse_position(&tokens[i..i + len])?;
+            i += len;
+
+            if tokens.get(i) == Some(&Token::Slash) {
+                i += 1;
+                let len = tokens[i..]
+                    .iter()
+                    .take(2)
+                    .take_while(|t| matches!(t, Token::Word(s) if is_size_word(s)))
+                    .count();
+                props.size = parse_size(&tokens[i..i + len])?;
+                i += len;
+            }
+        } else {
+            return None;
+        }
+    }
+
+    match (boxes.as_slice(), has_no_clip) {
+        ([], false) => {}
+        ([], true) => props.clip = None,
+        ([b], false) => {
+            props.origin = *b;
+            props.clip = Some(*b);
+        }
+        ([b], true) => {
+            props.origin = *b;
+            props.clip = None;
+        }
+        ([origin, clip], false) => {
+            props.origin = *origin;
+            props.clip = Some(*clip);
+        }
+        _ => return None,
+    }
+
+    Some(props)
+}
+
+fn parse_image<'a>(token: &Token<'a>) -> Option<MaskImage<'a>> {
+    if *token == Token::Word("none") {
+        return Some(MaskImage::None);
+    }
+
+    match token.function()? {
+        ("url", url) => {
+            let url = url.trim();
+            let url = url
+                .strip_prefix('"')
+                .and_then(|s| s.strip_suffix('"'))
+                .or_else(|| url.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')))
+                .unwrap_or(url);
+            Some(MaskImage::Url(url))
+        }
+        (name @ ("linear-gradient" | "radial-gradient"), args) => {
+            Some(MaskImage::Gradient(name, args))
+        }
+        _ => None,
+    }
+}
+
+fn parse_mode(text: &str) -> Option<MaskMode> {
+    match text {
+        "alpha" => Some(MaskMode::Alpha),
+        "luminance" => Some(MaskMode::Luminance),
+        "match-source" => Some(MaskMode::MatchSource),
+        _ => None,
+    }
+}
+
+fn parse_composite(text: &str) -> Option<MaskComposite> {
+    match text {
+        "add" => Some(MaskComposite::Add),
+        "subtract" => Some(MaskComposite::Subtract),
+        "intersect" => Some(MaskComposite::Intersect),
+        "exclude" => Some(MaskComposite::Exclude),
+        _ => None,
+    }
+}
+
+fn is_position_word(text: &str) -> bool {
+    matches!(text, "left" | "center" | "right" | "top" | "bottom") || Length::from_str(text).is_ok()
+}
+
+fn is_size_word(text: &str) -> bool {
+    matches!(text, "auto" | "cover" | "contain") || Length::from_str(text).is_ok()
+}
+
+fn parse_size(tokens: &[Token]) -> Option<MaskSize> {
+    let parse = |s: &str| match s {
+        "auto" => Some(None),
+        _ => {
+            let length = Length::from_str(s).ok()?;
+            (length.number >= 0.0).then_some(Some(length))
+        }
+    };
+
+    match *tokens {
+        [Token::Word("cover")] => Some(MaskSize::Cover),
+        [Token::Word("contain")] => Some(MaskSize::Contain),
+        [Token::Word(w)] => Some(MaskSize::Explicit(parse(w)?, None)),
+        [Token::Word(w), Token::Word(h)] => Some(MaskSize::Explicit(parse(w)?, parse(h)?)),
+        _ => None,
+    }
+}
+
+fn parse_repeat_keyword(text: &str) -> Option<RepeatStyle> {
+    match text {
+        "repeat" => Some(RepeatStyle::Repeat),
+        "space" => Some(RepeatStyle::Space),
+        "round" => Some(RepeatStyle::Round),
+        "no-repeat" => Some(RepeatStyle::NoRepeat),
+        _ => None,
+    }
+}
+
+fn parse_repeat(tokens: &[Token]) -> Option<(RepeatStyle, RepeatStyle)> {
+    match *tokens {
+        [Token::Word("repeat-x")] => Some((RepeatStyle::Repeat, RepeatStyle::NoRepeat)),
+        [Token::Word("repeat-y")] => Some((RepeatStyle::NoRepeat, RepeatStyle::Repeat)),
+        [Token::Word(s)] => {
+            let r = parse_repeat_keyword(s)?;
+            Some((r, r))
+        }
+        [Token::Word(x), Token::Word(y)] => {
+            Some((parse_repeat_keyword(x)?, parse_repeat_keyword(y)?))
+        }
+        _ => None,
+    }
+}
+
+/// Converts an image or a gradient mask layer into a mask with generated content.
+fn convert_image_layer(
+    source: LayerSource,
+    props: &LayerProps,
+    node: SvgNode,
+    state: &converter::State,
+    group: &mut Group,
+    object_bbox: Option<NonZeroRect>,
+    cache: &mut converter::Cache,
+) -> Option<Mask> {
+    let mut resolve_box = |geometry_box: GeometryBox| match geometry_box {
+        GeometryBox::Fill => object_bbox,
+        GeometryBox::Stroke => {
+            group.calculate_bounding_boxes();
+            group.stroke_bounding_box.to_non_zero_rect()
+        }
+        // The reference box is positioned at the origin of the viewBox coordinate system.
+        GeometryBox::View => {
+            NonZeroRect::from_xywh(0.0, 0.0, state.view_box.width(), state.view_box.height())
+        }
+    };
+
+    let area = resolve_box(props.origin)?;
+    let clip = match props.clip {
+        Some(geometry_box) => resolve_box(geometry_box)?,
+        // Everything outside the group layer is irrelevant anyway.
+        None => {
+            group.calculate_bounding_boxes();
+            group.layer_bounding_box
+        }
+    };
+
+    let natural_size = match source {
+        LayerSource::Image(_, size) => Some(size),
+        LayerSource::Paint(..) | LayerSource::Gradient(..) => None,
+    };
+
+    let len = |length: Length, base: f32| css::convert_length(length, base, node, state);
+    let (mut width, mut height) = match props.size {
+        MaskSize::Cover | MaskSize::Contain => match natural_size {
+            Some(size) => {
+                let sx = area.width() / size.width();
+                let sy = area.height() / size.height();
+                let scale = if matches!(props.size, MaskSize::Cover) {
+                    sx.max(sy)
+                } else {
+                    sx.min(sy)
+                };
+                (size.width() * scale, size.height() * scale)
+            }
+            None => (area.width(), area.height()),
+        },
+        MaskSize::Explicit(w, h) => {
+            let w = w.map(|w| len(w, area.width()));
+            let h = h.map(|h| len(h, area.height()));
+            match (w, h, natural_size) {
+                (Some(w), Some(h), _) => (w, h),
+                (Some(w), None, Some(size)) => (w, w * size.height() / size.width()),
+                (None, Some(h), Some(size)) => (h * size.width() / size.height(), h),
+                (None, None, Some(size)) => (size.width(), size.height()),
+                (w, h, None) => (w.unwrap_or(area.width()), h.unwrap_or(area.height())),
+            }
+        }
+    };
+
+    if !(width > 0.0 && height > 0.0) {
+        return None;
+    }
+
+    if props.repeat.0 == RepeatStyle::Round {
+        width = area.width() / (area.width() / width).round().max(1.0);
+    }
+    if props.repeat.1 == RepeatStyle::Round {
+        height = area.height() / (area.height() / height).round().max(1.0);
+    }
+
+    let x = props
+        .position
+        .0
+        .resolve(area.x(), area.width() - width, node, state);
+    let y = props
+        .position
+        .1
+        .resolve(area.y(), area.height() - height, node, state);
+    let (x, period_x, repeat_x) = resolve_tiling(props.repeat.0, area.x(), area.width(), width, x);
+    let (y, period_y, repeat_y) =
+        resolve_tiling(props.repeat.1, area.y(), area.height(), height, y);
+
+    let mut mask = Mask {
+        id: cache.gen_mask_id(),
+        rect: clip,
+        kind: MaskType::Alpha,
+        mask: None,
+        root: Group::empty(),
+    };
+
+    if !repeat_x && !repeat_y {
+        let tile = NonZeroRect::from_xywh(x, y, width, height)?;
+        convert_layer_source(source, tile, node, state, cache, &mut mask.root)?;
+    } else {
+        // A non-repeating axis still needs a period. Make sure that only one tile
+        // would be visible inside the clipping area.
+        let period_x = if repeat_x {
+            period_x
+        } else {
+            clip.right().max(x + width) - clip.left().min(x) + width
+        };
+        let period_y = if repeat_y {
+            period_y
+        } else {
+            clip.bottom().max(y + height) - clip.top().min(y) + height
+        };
+
+        let mut pattern = Pattern {
+            id: cache.gen_pattern_id(),
+            units: Units::UserSpaceOnUse,
+            content_units: Units::UserSpaceOnUse,
+            transform: Transform::default(),
+            rect: NonZeroRect::from_xywh(x, y, period_x, period_y)?,
+            view_box: None,
+            root: Group::empty(),
+        };
+
+        let tile = NonZeroRect::from_xywh(0.0, 0.0, width, height)?;
+        convert_layer_source(source, tile, node, state, cache, &mut pattern.root)?;
+        pattern.root.calculate_bounding_boxes();
+
+        let paint = Paint::Pattern(Arc::new(pattern));
+        mask.root
+            .children
+            .push(Node::Path(Box::new(rect_path(clip, paint, Opacity::ONE)?)));
+    }
+
+    mask.root.calculate_bounding_boxes();
+    Some(mask)
+}
+
+/// Returns a tile position, a tiling period and whether tiles should be repeated along an axis.
+fn resolve_tiling(
+    repeat: RepeatStyle,
+    start: f32,
+    size: f32,
+    tile: f32,
+    pos: f32,
+) -> (f32, f32, bool) {
+    match repeat {
+        RepeatStyle::Repeat | RepeatStyle::Round => (pos, tile, true),
+        RepeatStyle::NoRepeat => (pos, tile, false),
+        RepeatStyle::Space => {
+            let count = (size / tile).floor();
+            if count >= 2.0 {
+                let spacing = (size - count * tile) / (count - 1.0);
+                (start, tile + spacing, true)
+            } else {
+                (pos, tile, false)
+            }
+        }
+    }
+}
+
+fn convert_layer_source(
+    source: LayerSource,
+    tile: NonZeroRect,
+    node: SvgNode,
+    state: &converter::State,
+    cache: &mut converter::Cache,
+    parent: &mut Group,
+) -> Option<()> {
+    let (paint, opacity) = match source {
+        LayerSource::Image(kind, size) => {
+            let aspect = svgtypes::AspectRatio {
+                defer: false,
+                align: svgtypes::Align::None,
+                slice: false,
+            };
+
+            return image::convert_inner(
+                *kind,
+                String::new(),
+                None,
+                None,
+                true,
+                state.opt.image_rendering,
+                aspect,
+                size,
+                tile,
+                cache,
+                parent,
+            );
+        }
+        LayerSource::Paint(paint, opacity) => (paint, opacity),
+        LayerSource::Gradient(name, args) => {
+            match paint_server::convert_css_gradient(name, args, tile, node, state, cache)? {
+                ServerOrColor::Server(paint) => (paint, Opacity::ONE),
+                ServerOrColor::Color { color, opacity } => (Paint::Color(color), opacity),
+            }
+        }
+    };
+
+    parent
+        .children
+        .push(Node::Path(Box::new(rect_path(tile, paint, opacity)?)));
+    Some(())
+}
+
+fn rect_path(rect: NonZeroRect, paint: Paint, opacity: Opacity) -> Option<Path> {
+    let path = tiny_skia_path::PathBuilder::from_rect(rect.to_rect());
+    let mut path = Path::new_simple(Arc::new(path))?;
+    path.fill = Some(Fill {
+        paint,
+        opacity,
+        ..Fill::default()
+    });
+    Some(path)
+}
@@ -0,0 +1,565 @@
+// Copyright 2026 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Media queries evaluation.
+//!
+//! Supports a subset of [Media Queries Level 5](https://www.w3.org/TR/mediaqueries-5/)
+//! that makes sense for a static renderer.
+
+use crate::Size;
+
+/// A preferred color scheme.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum ColorScheme {
+    /// A light color scheme.
+    #[default]
+    Light,
+    /// A dark color scheme.
+    Dark,
+}
+
+impl std::str::FromStr for ColorScheme {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "light" => Ok(ColorScheme::Light),
+            "dark" => Ok(ColorScheme::Dark),
+            _ => Err("invalid"),
+        }
+    }
+}
+
+/// An environment that `@media` rules and `media` attributes are evaluated against.
+#[derive(Clone, Copy, Debug)]
+pub struct MediaContext {
+    /// The value of the `prefers-color-scheme` media feature.
+    ///
+    /// Default: `ColorScheme::Light`
+    pub color_scheme: ColorScheme,
+
+    /// The viewport size in pixels.
+    ///
+    /// Used by the `width`, `height`, `aspect-ratio` and `orientation` media features.
+    /// When not set, those features never match.
+    ///
+    /// Default: `None`
+    pub viewport_size: Option<Size>,
+
+    /// The value of the `resolution` media feature in dots per pixel.
+    ///
+    /// Default: 1.0
+    pub resolution: f32,
+
+    /// The value of the `prefers-reduced-motion` media feature.
+    ///
+    /// Default: `false`
+    pub prefers_reduced_motion: bool,
+}
+
+impl Default for MediaContext {
+    fn default() -> Self {
+        MediaContext {
+            color_scheme: ColorScheme::default(),
+            viewport_size: None,
+            resolution: 1.0,
+            prefers_reduced_motion: false,
+        }
+    }
+}
+
+impl MediaContext {
+    /// Checks that a comma-separated list of media queries matches this context.
+    ///
+    /// An empty list always matches. Invalid queries and queries with unknown features never match.
+    pub fn matches(&self, media_query_list: &str) -> bool {
+        let text = media_query_list.trim();
+        if text.is_empty() {
+            return true;
+        }
+
+        split_by_commas(text).any(|query| self.eval_query(query.trim()) == Some(true))
+    }
+
+    /// Evaluates a single media query.
+    ///
+    /// `None` indicates either an invalid query or an unknown feature.
+    fn eval_query(&self, text: &str) -> Option<bool> {
+        if text.starts_with('(') || starts_with_keyword(text, "not").is_some_and(is_condition) {
+            return self.eval_condition(text, true);
+        }
+
+        let (negate, text) = if let Some(rest) = starts_with_keyword(text, "not") {
+            (true, rest)
+        } else if let Some(rest) = starts_with_keyword(text, "only") {
+            (false, rest)
+        } else {
+            (false, text)
+        };
+
+        let (media_type, rest) = split_ident(text)?;
+        let mut result = match media_type.to_ascii_lowercase().as_str() {
+            "all" | "screen" => Some(true),
+            // `and`, `or`, `not`, `only` and `layer` are not valid media types.
+            "and" | "or" | "not" | "only" | "layer" => return None,
+            _ => Some(false),
+        };
+
+        let rest = rest.trim_start();
+        if !rest.is_empty() {
+            let condition = starts_with_keyword(rest, "and")?;
+            result = and(result, self.eval_condition(condition, false));
+        }
+
+        if negate { result.map(|v| !v) } else { result }
+    }
+
+    /// Evaluates `<media-condition>` or `<media-condition-without-or>`.
+    fn eval_condition(&self, text: &str, allow_or: bool) -> Option<bool> {
+        let text = text.trim();
+        if let Some(rest) = starts_with_keyword(text, "not") {
+            let (value, rest) = self.eval_in_parens(rest)?;
+            return if rest.trim().is_empty() {
+                value.map(|v| !v)
+            } else {
+                None
+            };
+        }
+
+        let (mut result, mut rest) = self.eval_in_parens(text)?;
+        let mut is_or = None;
+        loop {
+            rest = rest.trim_start();
+            if rest.is_empty() {
+                return result;
+            }
+
+            let (keyword, tail) = split_ident(rest)?;
+            let keyword_is_or = if keyword.eq_ignore_ascii_case("and") {
+                false
+            } else if keyword.eq_ignore_ascii_case("or") && allow_or {
+                true
+            } else {
+                return None;
+            };
+
+            // `and` and `or` cannot be mixed without parentheses.
+            if *is_or.get_or_insert(keyword_is_or) != keyword_is_or {
+                return None;
+            }
+
+            let (value, tail) = self.eval_in_parens(tail)?;
+            result = if keyword_is_or {
+                or(result, value)
+            } else {
+                and(result, value)
+            };
+            rest = tail;
+        }
+    }
+
+    /// Evaluates `<media-in-parens>` and returns the remaining text.
+    ///
+    /// The outer `Option` indicates a syntax error.
+    fn eval_in_parens<'a>(&self, text: &'a str) -> Option<(Option<bool>, &'a str)> {
+        let text = text.trim_start();
+        if !text.starts_with('(') {
+            // `<general-enclosed>` in a functional form, like `foo(bar)`.
+            let (_, rest) = split_ident(text)?;
+            let end = rest.strip_prefix('(').and_then(find_closing_paren)?;
+            return Some((None, &rest[end + 2..]));
+        }
+
+        let end = find_closing_paren(&text[1..])?;
+        let inner = text[1..end + 1].trim();
+        let rest = &text[end + 2..];
+
+        let value = if is_condition(inner) {
+            self.eval_condition(inner, true)
+        } else {
+            self.eval_feature(inner)
+        };
+
+        Some((value, rest))
+    }
+
+    /// Evaluates the content of `<media-feature>`.
+    fn eval_feature(&self, text: &str) -> Option<bool> {
+        if let Some((name, value)) = text.split_once(':') {
+            let name = name.trim().to_ascii_lowercase();
+            let (op, name) = if let Some(name) = name.strip_prefix("min-") {
+                (Op::Ge, name)
+            } else if let Some(name) = name.strip_prefix("max-") {
+                (Op::Le, name)
+            } else {
+                (Op::Eq, name.as_str())
+            };
+
+            let feature = Feature::from_name(name)?;
+            if op != Op::Eq && !feature.is_range() {
+                return None;
+            }
+
+            let expected = feature.parse_value(value.trim())?;
+            return Some(compare(self.feature_value(feature)?, op, expected));
+        }
+
+        if text.contains(['<', '>', '=']) {
+            return self.eval_range(text);
+        }
+
+        let feature = Feature::from_name(&text.to_ascii_lowercase())?;
+        match self.feature_value(feature)? {
+            Value::Number(n) => Some(n != 0.0),
+            Value::Ident(ident) => Some(match feature {
+                Feature::PrefersReducedMotion => ident != "no-preference",
+                _ => true,
+            }),
+        }
+    }
+
+    /// Evaluates `<mf-range>`, like `width >= 600px` or `400px < width < 800px`.
+    fn eval_range(&self, text: &str) -> Option<bool> {
+        let mut operands = Vec::new();
+        let mut ops = Vec::new();
+        let mut rest = text;
+        loop {
+            let idx = rest.find(['<', '>', '=']);
+            operands.push(rest[..idx.unwrap_or(rest.len())].trim());
+            let Some(idx) = idx else {
+                break;
+            };
+
+            let (op, len) = Op::parse(&rest[idx..])?;
+            ops.push(op);
+            rest = &rest[idx + len..];
+        }
+
+        match (operands.as_slice(), ops.as_slice()) {
+            ([a, b], [op]) => {
+                if let Some(feature) = Feature::from_name(&a.to_ascii_lowercase()) {
+                    self.eval_range_part(feature, *op, b)
+                } else {
+                    let feature = Feature::from_name(&b.to_ascii_lowercase())?;
+                    self.eval_range_part(feature, op.flip(), a)
+                }
+            }
+            ([a, name, b], [op1, op2]) => {
+                // Only `a < name < b` and `a > name > b` forms are allowed.
+                let is_lt = |op: &Op| matches!(op, Op::Lt | Op::Le);
+                let is_gt = |op: &Op| matches!(op, Op::Gt | Op::Ge);
+                if !((is_lt(op1) && is_lt(op2)) || (is_gt(op1) && is_gt(op2))) {
+                    return None;
+                }
+
+                let feature = Feature::from_name(&name.to_ascii_lowercase())?;
+                Some(
+                    self.eval_range_part(feature, op1.flip(), a)?
+                        && self.eval_range_part(feature, *op2, b)?,
+                )
+            }
+            _ => None,
+        }
+    }
+
+    fn eval_range_part(&self, feature: Feature, op: Op, value: &str) -> Option<bool> {
+        if !feature.is_range() {
+            return None;
+        }
+
+        let expected = feature.parse_value(value)?;
+        Some(compare(self.feature_value(feature)?, op, expected))
+    }
+
+    fn feature_value(&self, feature: Feature) -> Option<Value<'static>> {
+        let value = match feature {
+            Feature::Width => Value::Number(self.viewport_size?.width() as f64),
+            Feature::Height => Value::Number(self.viewport_size?.height() as f64),
+            Feature::AspectRatio => {
+                let size = self.viewport_size?;
+                Value::Number(size.width() as f64 / size.height() as f64)
+            }
+            Feature::Orientation => {
+                let size = self.viewport_size?;
+                Value::Ident(if size.height() >= size.width() {
+                    "portrait"
+                } else {
+                    "landscape"
+                })
+            }
+            Feature::Resolution => Value::Number(self.resolution as f64),
+            Feature::Color => Value::Number(8.0),
+            Feature::Monochrome => Value::Number(0.0),
+            Feature::PrefersColorScheme => Value::Ident(match self.color_scheme {
+                ColorScheme::Light => "light",
+                ColorScheme::Dark => "dark",
+            }),
+            Feature::PrefersReducedMotion => Value::Ident(if self.prefers_reduced_motion {
+                "reduce"
+            } else {
+                "no-preference"
+            }),
+        };
+
+        Some(value)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Feature {
+    Width,
+    Height,
+    AspectRatio,
+    Orientation,
+    Resolution,
+    Color,
+    Monochrome,
+    PrefersColorScheme,
+    PrefersReducedMotion,
+}
+
+impl Feature {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "width" => Some(Feature::Width),
+            "height" => Some(Feature::Height),
+            "aspect-ratio" => Some(Feature::AspectRatio),
+            "orientation" => Some(Feature::Orientation),
+            "resolution" => Some(Feature::Resolution),
+            "color" => Some(Feature::Color),
+            "monochrome" => Some(Feature::Monochrome),
+            "prefers-color-scheme" => Some(Feature::PrefersColorScheme),
+            "prefers-reduced-motion" => Some(Feature::PrefersReducedMotion),
+            _ => None,
+        }
+    }
+
+    fn is_range(self) -> bool {
+        !matches!(
+            self,
+            Feature::Orientation | Feature::PrefersColorScheme | Feature::PrefersReducedMotion
+        )
+    }
+
+    fn parse_value(self, text: &str) -> Option<Value<'_>> {
+        let number = match self {
+            Feature::Width | Feature::Height => parse_length(text)?,
+            Feature::AspectRatio => parse_ratio(text)?,
+            Feature::Resolution => parse_resolution(text)?,
+            Feature::Color | Feature::Monochrome => {
+                let n = text.parse::<u32>().ok()?;
+                n as f64
+            }
+            Feature::Orientation | Feature::PrefersColorScheme | Feature::PrefersReducedMotion => {
+                return Some(Value::Ident(text));
+            }
+        };
+
+        Some(Value::Number(number))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Value<'a> {
+    Number(f64),
+    Ident(&'a str),
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Op {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+}
+
+impl Op {
+    fn parse(text: &str) -> Option<(Self, usize)> {
+        if text.starts_with("<=") {
+            Some((Op::Le, 2))
+        } else if text.starts_with(">=") {
+            Some((Op::Ge, 2))
+        } else if text.starts_with('<') {
+            Some((Op::Lt, 1))
+        } else if text.starts_with('>') {
+            Some((Op::Gt, 1))
+        } else if text.starts_with('=') {
+            Some((Op::Eq, 1))
+        } else {
+            None
+        }
+    }
+
+    /// Returns an operator for swapped operands.
+    fn flip(self) -> Self {
+        match self {
+            Op::Lt => Op::Gt,
+            Op::Le => Op::Ge,
+            Op::Gt => Op::Lt,
+            Op::Ge => Op::Le,
+            Op::Eq => Op::Eq,
+        }
+    }
+}
+
+fn compare(actual: Value, op: Op, expected: Value) -> bool {
+    match (actual, expected) {
+        (Value::Number(a), Value::Number(b)) => {
+            let eq = (a - b).abs() < 1e-6;
+            match op {
+                Op::Lt => a < b && !eq,
+                Op::Le => a < b || eq,
+                Op::Gt => a > b && !eq,
+                Op::Ge => a > b || eq,
+                Op::Eq => eq,
+            }
+        }
+        (Value::Ident(a), Value::Ident(b)) => op == Op::Eq && a.eq_ignore_ascii_case(b),
+        _ => false,
+    }
+}
+
+/// Parses a length in pixels.
+///
+/// Relative units are resolved using the initial font size of 16px.
+fn parse_length(text: &str) -> Option<f64> {
+    let (n, unit) = split_dimension(text)?;
+    let scale = match unit.to_ascii_lowercase().as_str() {
+        "" if n == 0.0 => 1.0,
+        "px" => 1.0,
+        "em" | "rem" => 16.0,
+        "ex" => 8.0,
+        "in" => 96.0,
+        "cm" => 96.0 / 2.54,
+        "mm" => 96.0 / 25.4,
+        "q" => 96.0 / 101.6,
+        "pt" => 96.0 / 72.0,
+        "pc" => 16.0,
+        _ => return None,
+    };
+
+    Some(n * scale)
+}
+
+/// Parses a resolution in dots per pixel.
+fn parse_resolution(text: &str) -> Option<f64> {
+    let (n, unit) = split_dimension(text)?;
+    let scale = match unit.to_ascii_lowercase().as_str() {
+        "dppx" | "x" => 1.0,
+        "dpi" => 1.0 / 96.0,
+        "dpcm" => 2.54 / 96.0,
+        _ => return None,
+    };
+
+    Some(n * scale)
+}
+
+/// Parses a `<ratio>`, like `16/9`.
+fn parse_ratio(text: &str) -> Option<f64> {
+    let (a, b) = match text.split_once('/') {
+        Some((a, b)) => (a.trim().parse::<f64>().ok()?, b.trim().parse::<f64>().ok()?),
+        None => (text.parse::<f64>().ok()?, 1.0),
+    };
+
+    if !(a.is_finite() && b.is_finite()) || a < 0.0 || b < 0.0 {
+        return None;
+    }
+
+    // Degenerate ratios like `0/1` and `1/0` are treated as infinite.
+    if a == 0.0 || b == 0.0 {
+        return Some(f64::INFINITY);
+    }
+
+    Some(a / b)
+}
+
+fn split_dimension(text: &str) -> Option<(f64, &str)> {
+    // Exponents are not supported, since they are ambiguous with units like `em`.
+    let idx = text
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '+' | '-')))
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(idx);
+    let n = number.parse::<f64>().ok()?;
+    n.is_finite().then_some((n, unit))
+}
+
+/// Checks that the text is a `<media-condition>` and not a `<media-feature>`.
+fn is_condition(text: &str) -> bool {
+    let text = text.trim_start();
+    text.starts_with('(') || starts_with_keyword(text, "not").is_some()
+}
+
+/// Strips an ASCII case-insensitive keyword followed by a space or a parenthesis.
+fn starts_with_keyword<'a>(text: &'a str, keyword: &str) -> Option<&'a str> {
+    let (ident, rest) = split_ident(text)?;
+    if ident.eq_ignore_ascii_case(keyword)
+        && (rest.starts_with(char::is_whitespace) || rest.starts_with('('))
+    {
+        Some(rest)
+    } else {
+        None
+    }
+}
+
+fn split_ident(text: &str) -> Option<(&str, &str)> {
+    let text = text.trim_start();
+    let end = text
+        .find(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_'))
+        .unwrap_or(text.len());
+    if end == 0 {
+        return None;
+    }
+
+    Some(text.split_at(end))
+}
+
+/// Returns the index of a closing parenthesis, assuming that the opening one was already consumed.
+fn find_closing_paren(text: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (idx, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return Some(idx),
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+
+    None
+}
+
+fn split_by_commas(text: &str) -> impl Iterator<Item = &str> {
+    let mut depth = 0usize;
+    let mut start = 0;
+    let mut parts = Vec::new();
+    for (idx, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(&text[start..idx]);
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts.into_iter()
+}
+
+fn and(a: Option<bool>, b: Option<bool>) -> Option<bool> {
+    match (a, b) {
+        (Some(false), _) | (_, Some(false)) => Some(false),
+        (Some(true), Some(true)) => Some(true),
+        _ => None,
+    }
+}
+
+fn or(a: Option<bool>, b: Option<bool>) -> Option<bool> {
+    match (a, b) {
+        (Some(true), _) | (_, Some(true)) => Some(true),
+        (Some(false), Some(false)) => Some(false),
+        _ => None,
+    }
+}
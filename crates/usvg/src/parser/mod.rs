@@ -2,15 +2,28 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 mod clippath;
+mod color;
 mod converter;
+mod css;
+mod deferred;
+mod dependencies;
+mod embedded;
+mod encoding;
 mod filter;
+mod foreign_object;
+mod fragment;
 mod image;
 mod marker;
 mod mask;
+mod media;
 mod options;
 mod paint_server;
+mod recovery;
+mod resource_policy;
 mod shapes;
+mod smil;
 mod style;
+mod style_sheet;
 mod svgtree;
 mod switch;
 mod units;
@@ -20,26 +33,43 @@ mod use_node;
 mod text;
 #[cfg(feature = "text")]
 pub(crate) use converter::Cache;
+pub use deferred::{PendingTree, ResourceRequest};
+pub use dependencies::{Dependency, DependencyKind, collect_dependencies};
+pub use embedded::{EmbeddedSvg, parse_embedded_svgs};
+pub use encoding::decode_text;
+pub use foreign_object::{ForeignObject, ForeignObjectResolver, ForeignObjectResolverFn};
 pub use image::{ImageHrefDataResolverFn, ImageHrefResolver, ImageHrefStringResolverFn};
-pub use options::Options;
+pub use media::{ColorScheme, MediaContext};
+pub use options::{Limits, MetadataOptions, Options};
+pub use resource_policy::ResourcePolicy;
+pub use style_sheet::{StyleSheetImportResolverFn, StyleSheetResolver};
 #[cfg(feature = "writer")]
 pub(crate) use svgtree::{AId, EId};
+pub use switch::{ConditionExtensionFn, ConditionFeatureFn, ConditionResolver, ConditionalElement};
 
 /// List of all errors.
 #[derive(Debug)]
 pub enum Error {
-    /// Only UTF-8 content are supported.
+    /// Provided data is not a valid text in the detected encoding.
     NotAnUtf8Str,
 
+    /// SVG data uses an encoding that is not supported by [`decode_text`].
+    ///
+    /// Contains the encoding name in lowercase.
+    UnsupportedEncoding(String),
+
     /// `svgz` feature is required to parse SVGZ data.
     SvgzFeatureNotEnabled,
 
     /// Compressed SVG must use the GZip algorithm.
     MalformedGZip,
 
-    /// We do not allow SVG with more than 1_000_000 elements for security reasons.
+    /// SVG has more elements than allowed by [`Limits::max_elements`].
     ElementsLimitReached,
 
+    /// SVG images are nested deeper than allowed by [`Limits::max_nested_images_depth`].
+    NestedImagesLimitReached,
+
     /// SVG doesn't have a valid size.
     ///
     /// Occurs when width and/or height are <= 0.
@@ -47,6 +77,12 @@ pub enum Error {
     /// Also occurs if width, height and viewBox are not set.
     InvalidSize,
 
+    /// SVG data is bigger than allowed by [`Limits::max_input_size`].
+    InputSizeLimitReached,
+
+    /// Failed to read an SVG data.
+    ReadingFailed(std::io::Error),
+
     /// Failed to parse an SVG data.
     ParsingFailed(roxmltree::Error),
 }
@@ -63,6 +99,9 @@ impl std::fmt::Display for Error {
             Error::NotAnUtf8Str => {
                 write!(f, "provided data has not an UTF-8 encoding")
             }
+            Error::UnsupportedEncoding(ref encoding) => {
+                write!(f, "'{}' encoding is not supported", encoding)
+            }
             Self::SvgzFeatureNotEnabled => {
                 write!(f, "enable svgz cargo feature to decode SVGZ data")
             }
@@ -72,9 +111,21 @@ impl std::fmt::Display for Error {
             Error::ElementsLimitReached => {
                 write!(f, "the maximum number of SVG elements has been reached")
             }
+            Error::NestedImagesLimitReached => {
+                write!(
+                    f,
+                    "the maximum nesting depth of SVG images has been reached"
+                )
+            }
             Error::InvalidSize => {
                 write!(f, "SVG has an invalid size")
             }
+            Error::InputSizeLimitReached => {
+                write!(f, "the maximum SVG data size has been reached")
+            }
+            Error::ReadingFailed(ref e) => {
+                write!(f, "SVG data reading failed cause {}", e)
+            }
             Error::ParsingFailed(ref e) => {
                 write!(f, "SVG data parsing failed cause {}", e)
             }
@@ -102,29 +153,39 @@ impl crate::Tree {
     /// Parses `Tree` from an SVG data.
     ///
     /// Can contain an SVG string or a gzip compressed data.
+    /// Non-UTF-8 text is converted using [`decode_text`](crate::decode_text).
     pub fn from_data(data: &[u8], opt: &Options) -> Result<Self, Error> {
         if data.starts_with(&[0x1f, 0x8b]) {
-            #[cfg(feature = "svgz")]
-            {
-                let data = decompress_svgz(data)?;
-                let text = std::str::from_utf8(&data).map_err(|_| Error::NotAnUtf8Str)?;
-                Self::from_str(text, opt)
-            }
-
-            #[cfg(not(feature = "svgz"))]
-            Err(Error::SvgzFeatureNotEnabled)
+            let data = read_svg_data(data, opt.limits.max_input_size)?;
+            Self::from_str(&decode_text(&data)?, opt)
+        } else if data.len() as u64 > opt.limits.max_input_size {
+            Err(Error::InputSizeLimitReached)
         } else {
-            let text = std::str::from_utf8(data).map_err(|_| Error::NotAnUtf8Str)?;
-            Self::from_str(text, opt)
+            Self::from_str(&decode_text(data)?, opt)
         }
     }
 
+    /// Parses `Tree` from a reader.
+    ///
+    /// Like `from_data`, accepts an SVG string or a gzip compressed data.
+    /// Gzip data is decompressed while reading and reading stops as soon as
+    /// [`Limits::max_input_size`] is exceeded.
+    pub fn from_reader<R: std::io::Read>(reader: R, opt: &Options) -> Result<Self, Error> {
+        let data = read_svg_data(reader, opt.limits.max_input_size)?;
+        Self::from_str(&decode_text(&data)?, opt)
+    }
+
     /// Similar to the `from_data` method, except that it ignores all `image` elements linking to
     /// external files, as required by the SVG specification when SVG files are loaded
     /// for `<image href="..." />` tags.
     pub fn from_data_nested(data: &[u8], opt: &Options) -> Result<Self, Error> {
+        if opt.limits.max_nested_images_depth == 0 {
+            return Err(Error::NestedImagesLimitReached);
+        }
+
         let nested_opt = Options {
             resources_dir: None,
+            resource_policy: opt.resource_policy.clone(),
             dpi: opt.dpi,
             font_size: opt.font_size,
             languages: opt.languages.clone(),
@@ -149,6 +210,28 @@ impl crate::Tree {
                     (opt.font_resolver.select_fallback)(c, used_fonts, db)
                 }),
             },
+            diagnostics: crate::DiagnosticSink {
+                report: Box::new(|diagnostic| opt.diagnostics.report(diagnostic)),
+            },
+            condition_resolver: crate::ConditionResolver {
+                is_feature_supported: Box::new(|a, b, c| {
+                    (opt.condition_resolver.is_feature_supported)(a, b, c)
+                }),
+                is_extension_supported: Box::new(|a, b, c| {
+                    (opt.condition_resolver.is_extension_supported)(a, b, c)
+                }),
+            },
+            foreign_object_resolver: crate::ForeignObjectResolver {
+                resolve: Box::new(|a, b| (opt.foreign_object_resolver.resolve)(a, b)),
+            },
+            // External style sheets should be ignored as well.
+            style_sheet_resolver: StyleSheetResolver {
+                resolve_import: Box::new(|_, _| None),
+            },
+            limits: Limits {
+                max_nested_images_depth: opt.limits.max_nested_images_depth - 1,
+                ..opt.limits
+            },
             ..Options::default()
         };
 
@@ -157,24 +240,71 @@ impl crate::Tree {
 
     /// Parses `Tree` from an SVG string.
     pub fn from_str(text: &str, opt: &Options) -> Result<Self, Error> {
-        let xml_opt = roxmltree::ParsingOptions {
-            allow_dtd: true,
-            ..Default::default()
+        let doc = match roxmltree::Document::parse_with_options(text, xml_options()) {
+            Ok(doc) => doc,
+            Err(e) => return Self::from_str(&recovery::recover(text, e, opt)?, opt),
         };
 
-        let doc =
-            roxmltree::Document::parse_with_options(text, xml_opt).map_err(Error::ParsingFailed)?;
-
         Self::from_xmltree(&doc, opt)
     }
 
     /// Parses `Tree` from `roxmltree::Document`.
     pub fn from_xmltree(doc: &roxmltree::Document, opt: &Options) -> Result<Self, Error> {
-        let doc = svgtree::Document::parse_tree(doc, opt.style_sheet.as_deref())?;
+        let doc = svgtree::Document::parse_tree(doc, opt)?;
         self::converter::convert_doc(&doc, opt)
     }
 }
 
+/// Returns XML parsing options used for all SVG documents.
+pub(crate) fn xml_options<'a>() -> roxmltree::ParsingOptions<'a> {
+    roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    }
+}
+
+/// Reads an SVG data, decompressing it when needed.
+///
+/// Gzip data is decompressed while reading and reading stops as soon as
+/// the data, after decompression, is bigger than `limit`.
+/// Usually, `limit` is [`Limits::max_input_size`].
+pub fn read_svg_data<R: std::io::Read>(reader: R, limit: u64) -> Result<Vec<u8>, Error> {
+    use std::io::{BufRead, Read};
+
+    let mut reader = std::io::BufReader::new(reader);
+    let is_gzip = reader
+        .fill_buf()
+        .map_err(Error::ReadingFailed)?
+        .starts_with(&[0x1f, 0x8b]);
+
+    // Read one extra byte to detect that the limit was exceeded.
+    let limit = limit.saturating_add(1);
+    let mut data = Vec::new();
+    if is_gzip {
+        #[cfg(feature = "svgz")]
+        {
+            flate2::read::GzDecoder::new(reader)
+                .take(limit)
+                .read_to_end(&mut data)
+                .map_err(|_| Error::MalformedGZip)?;
+        }
+
+        #[cfg(not(feature = "svgz"))]
+        return Err(Error::SvgzFeatureNotEnabled);
+    } else {
+        reader
+            .take(limit)
+            .read_to_end(&mut data)
+            .map_err(Error::ReadingFailed)?;
+    }
+
+    if data.len() as u64 == limit {
+        return Err(Error::InputSizeLimitReached);
+    }
+
+    Ok(data)
+}
+
 /// Decompresses an SVGZ file.
 #[cfg(feature = "svgz")]
 pub fn decompress_svgz(data: &[u8]) -> Result<Vec<u8>, Error> {
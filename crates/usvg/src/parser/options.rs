@@ -1,12 +1,17 @@
 // Copyright 2018 the Resvg Authors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use std::collections::HashMap;
 #[cfg(feature = "text")]
 use std::sync::Arc;
 
 #[cfg(feature = "text")]
 use crate::FontResolver;
-use crate::{ImageHrefResolver, ImageRendering, ShapeRendering, Size, TextRendering};
+use crate::{
+    ConditionResolver, DiagnosticCode, DiagnosticSink, ForeignObjectResolver, ImageHrefResolver,
+    ImageRendering, MediaContext, ResourcePolicy, ShapeRendering, Size, StyleSheetResolver,
+    TextRendering,
+};
 
 /// Processing options.
 #[derive(Debug)]
@@ -19,6 +24,13 @@ pub struct Options<'a> {
     /// Default: `None`
     pub resources_dir: Option<std::path::PathBuf>,
 
+    /// Specifies which local files referenced by an SVG can be loaded.
+    ///
+    /// Should be restricted when processing untrusted files.
+    ///
+    /// Default: `ResourcePolicy::AllowAll`
+    pub resource_policy: ResourcePolicy,
+
     /// Target DPI.
     ///
     /// Impacts units conversion.
@@ -44,7 +56,9 @@ pub struct Options<'a> {
     ///
     /// Will be used to resolve a `systemLanguage` conditional attribute.
     ///
-    /// Format: en, en-US.
+    /// Format: BCP 47 language ranges, like en, en-US or `*`.
+    /// A range matches both more and less specific tags,
+    /// so `en` matches `en-US` and `en-US` matches `en`.
     ///
     /// Default: `[en]`
     pub languages: Vec<String>,
@@ -98,12 +112,111 @@ pub struct Options<'a> {
     /// A CSS stylesheet that should be injected into the SVG. Can be used to overwrite
     /// certain attributes.
     pub style_sheet: Option<String>,
+
+    /// Specifies how `requiredFeatures` and `requiredExtensions` conditional attributes
+    /// should be evaluated.
+    ///
+    /// Default: see type's documentation for details
+    pub condition_resolver: ConditionResolver<'a>,
+
+    /// Specifies how `foreignObject` elements should be rendered.
+    ///
+    /// Default: see type's documentation for details
+    pub foreign_object_resolver: ForeignObjectResolver<'a>,
+
+    /// Specifies how `@import` rules in style sheets should be handled.
+    ///
+    /// Applies to both embedded style sheets and `style_sheet`.
+    ///
+    /// Default: see type's documentation for details
+    pub style_sheet_resolver: StyleSheetResolver<'a>,
+
+    /// An environment that `@media` rules and `media` attributes of `style` elements
+    /// are evaluated against.
+    ///
+    /// Applies to both embedded style sheets and `style_sheet`.
+    ///
+    /// Default: see type's documentation for details
+    pub media: MediaContext,
+
+    /// CSS custom properties that should be defined on the root element.
+    ///
+    /// Names must include the leading `--`, like `--accent`.
+    /// Can be used to theme an SVG without modifying it.
+    ///
+    /// Default: empty
+    pub custom_properties: HashMap<String, String>,
+
+    /// Specifies where parsing diagnostics, like invalid attributes or missing fonts, should go.
+    ///
+    /// Default: forwards diagnostics to the `log` crate
+    pub diagnostics: DiagnosticSink<'a>,
+
+    /// Keep elements location in the source SVG.
+    ///
+    /// When enabled, groups, paths, images and text nodes will point to the elements
+    /// they were created from via `source_span()`. Nodes created from `use` and `marker`
+    /// point to the referenced elements. Text outlines point to the `text` element.
+    ///
+    /// Default: false
+    pub source_spans: bool,
+
+    /// Specifies which non-rendering data, like `title` elements or `class` attributes,
+    /// should be preserved in the tree.
+    ///
+    /// Default: nothing is preserved
+    pub metadata: MetadataOptions,
+
+    /// Resource limits.
+    ///
+    /// Useful when processing untrusted files.
+    ///
+    /// Default: see type's documentation for details
+    pub limits: Limits,
+
+    /// Build a tree from a truncated or malformed XML instead of failing.
+    ///
+    /// Everything starting from the first XML error is ignored
+    /// and unclosed elements are closed.
+    /// Reported as a [`DiagnosticCode::MalformedXml`] diagnostic.
+    ///
+    /// Default: false
+    pub recover_malformed_xml: bool,
+
+    /// A fragment identifier that selects a part of the document to render, without `#`.
+    ///
+    /// Can be:
+    ///
+    /// - An ID of a `view` element, like `icon-x`.
+    ///   The root viewport uses the `viewBox` and `preserveAspectRatio` of the view.
+    /// - An SVG view specification, like `svgView(viewBox(0,0,10,10))`.
+    ///   `viewBox`, `preserveAspectRatio` and `transform` are supported.
+    /// - An ID of any other element.
+    ///   The root viewport is set to the element's layer bounding box.
+    ///
+    /// Unknown fragments are reported and ignored.
+    ///
+    /// Default: None
+    pub fragment: Option<String>,
+
+    /// A time in seconds at which SMIL animations should be sampled.
+    ///
+    /// When set, `animate`, `set`, `animateTransform` and `animateMotion` elements
+    /// are evaluated at this time and the resulting static tree is built.
+    /// Event-based timing, like `begin="click"`, never starts an animation.
+    /// CSS animations defined via `@keyframes` are sampled as well.
+    ///
+    /// When `None`, animations are ignored and the tree is built from the base values.
+    ///
+    /// Default: None
+    pub animation_time: Option<f32>,
 }
 
 impl Default for Options<'_> {
     fn default() -> Options<'static> {
         Options {
             resources_dir: None,
+            resource_policy: ResourcePolicy::default(),
             dpi: 96.0,
             // Default font is user-agent dependent so we can use whichever we like.
             font_family: "Times New Roman".to_owned(),
@@ -119,6 +232,18 @@ impl Default for Options<'_> {
             #[cfg(feature = "text")]
             fontdb: Arc::new(fontdb::Database::new()),
             style_sheet: None,
+            condition_resolver: ConditionResolver::default(),
+            foreign_object_resolver: ForeignObjectResolver::default(),
+            style_sheet_resolver: StyleSheetResolver::default(),
+            media: MediaContext::default(),
+            custom_properties: HashMap::new(),
+            diagnostics: DiagnosticSink::default(),
+            source_spans: false,
+            metadata: MetadataOptions::default(),
+            limits: Limits::default(),
+            recover_malformed_xml: false,
+            fragment: None,
+            animation_time: None,
         }
     }
 }
@@ -134,6 +259,93 @@ impl Options<'_> {
         }
     }
 
+    /// Converts a path referenced by an SVG into absolute one
+    /// and checks it against [`Options::resource_policy`].
+    ///
+    /// Returns the path that was checked, which is canonical unless the policy is
+    /// `ResourcePolicy::AllowAll`. Only the returned path should be opened.
+    ///
+    /// Returns `None` and reports a diagnostic when access is denied.
+    pub fn resolve_resource_path(&self, href: &str) -> Option<std::path::PathBuf> {
+        let path = self.get_abs_path(std::path::Path::new(href));
+        let path = self
+            .resource_policy
+            .resolve(&path, self.resources_dir.as_deref());
+        if path.is_none() {
+            self.diagnostics.warn(
+                DiagnosticCode::AccessDenied,
+                format!("Access to '{}' is denied by the resource policy.", href),
+            );
+        }
+
+        path
+    }
+
+    /// Creates options that share settings with these ones and forward resolvers to them.
+    ///
+    /// Used to override some of the resolvers for a single parsing pass.
+    pub(crate) fn forwarding(&self) -> Options<'_> {
+        Options {
+            resources_dir: self.resources_dir.clone(),
+            resource_policy: self.resource_policy.clone(),
+            dpi: self.dpi,
+            font_family: self.font_family.clone(),
+            font_size: self.font_size,
+            languages: self.languages.clone(),
+            shape_rendering: self.shape_rendering,
+            text_rendering: self.text_rendering,
+            image_rendering: self.image_rendering,
+            default_size: self.default_size,
+            image_href_resolver: ImageHrefResolver {
+                resolve_data: Box::new(|mime, data, opt| {
+                    (self.image_href_resolver.resolve_data)(mime, data, opt)
+                }),
+                resolve_string: Box::new(|href, opt| {
+                    (self.image_href_resolver.resolve_string)(href, opt)
+                }),
+            },
+            #[cfg(feature = "text")]
+            font_resolver: FontResolver {
+                select_font: Box::new(|font, db| (self.font_resolver.select_font)(font, db)),
+                select_fallback: Box::new(|c, used_fonts, db| {
+                    (self.font_resolver.select_fallback)(c, used_fonts, db)
+                }),
+            },
+            #[cfg(feature = "text")]
+            fontdb: self.fontdb.clone(),
+            style_sheet: self.style_sheet.clone(),
+            condition_resolver: ConditionResolver {
+                is_feature_supported: Box::new(|feature, element, opt| {
+                    (self.condition_resolver.is_feature_supported)(feature, element, opt)
+                }),
+                is_extension_supported: Box::new(|extension, element, opt| {
+                    (self.condition_resolver.is_extension_supported)(extension, element, opt)
+                }),
+            },
+            foreign_object_resolver: ForeignObjectResolver {
+                resolve: Box::new(|object, opt| {
+                    (self.foreign_object_resolver.resolve)(object, opt)
+                }),
+            },
+            style_sheet_resolver: StyleSheetResolver {
+                resolve_import: Box::new(|url, opt| {
+                    (self.style_sheet_resolver.resolve_import)(url, opt)
+                }),
+            },
+            media: self.media,
+            custom_properties: self.custom_properties.clone(),
+            diagnostics: DiagnosticSink {
+                report: Box::new(|diagnostic| self.diagnostics.report(diagnostic)),
+            },
+            source_spans: self.source_spans,
+            metadata: self.metadata.clone(),
+            limits: self.limits,
+            recover_malformed_xml: self.recover_malformed_xml,
+            fragment: self.fragment.clone(),
+            animation_time: self.animation_time,
+        }
+    }
+
     /// Mutably acquires the database.
     ///
     /// This clones the database if it is currently shared.
@@ -142,3 +354,118 @@ impl Options<'_> {
         Arc::make_mut(&mut self.fontdb)
     }
 }
+
+/// Specifies which non-rendering data should be preserved in the tree.
+///
+/// Preserved data is available via `metadata()` methods of groups, paths, images
+/// and text nodes and is written back by `usvg::Tree::to_string`.
+#[derive(Clone, Default, Debug)]
+pub struct MetadataOptions {
+    /// Preserve `title` and `desc` child elements.
+    pub title_and_desc: bool,
+
+    /// Preserve `metadata` child elements.
+    pub metadata_elements: bool,
+
+    /// Preserve `class` attributes.
+    pub classes: bool,
+
+    /// Preserve `data-*` attributes.
+    pub data_attributes: bool,
+
+    /// Namespace URIs of attributes that should be preserved as is.
+    ///
+    /// For example, `http://www.inkscape.org/namespaces/inkscape`.
+    pub attribute_namespaces: Vec<String>,
+}
+
+impl MetadataOptions {
+    /// Creates options that preserve titles, descriptions, `metadata` elements,
+    /// classes and `data-*` attributes.
+    pub fn all() -> Self {
+        MetadataOptions {
+            title_and_desc: true,
+            metadata_elements: true,
+            classes: true,
+            data_attributes: true,
+            attribute_namespaces: Vec::new(),
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.title_and_desc
+            || self.metadata_elements
+            || self.classes
+            || self.data_attributes
+            || !self.attribute_namespaces.is_empty()
+    }
+}
+
+/// Resource limits.
+///
+/// Exceeding `max_elements` is an error. Elements exceeding other limits are skipped
+/// with a [`DiagnosticCode::LimitExceeded`](crate::DiagnosticCode::LimitExceeded) diagnostic.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Limits {
+    /// The maximum number of SVG elements, including ones created by `use` elements.
+    ///
+    /// Exceeding it results in [`Error::ElementsLimitReached`](crate::Error::ElementsLimitReached).
+    ///
+    /// Default: 1_000_000
+    pub max_elements: usize,
+
+    /// The maximum nesting depth of `use` elements.
+    ///
+    /// Default: 64
+    pub max_use_depth: u32,
+
+    /// The maximum number of pixels in a raster image.
+    ///
+    /// Checked before decoding, using the size stored in the image header.
+    ///
+    /// Default: 100_000_000
+    pub max_image_pixels: u64,
+
+    /// The maximum nesting depth of SVG images.
+    ///
+    /// `0` disables SVG images completely.
+    ///
+    /// Default: 8
+    pub max_nested_images_depth: u32,
+
+    /// The maximum number of characters in a `text` element
+    /// or in a `foreignObject` rendered by the default resolver.
+    ///
+    /// Default: 100_000
+    pub max_text_length: usize,
+
+    /// The maximum area of a filter region in pixels.
+    ///
+    /// Only the part of the region inside the canvas is taken into account,
+    /// before the canvas scale is applied.
+    ///
+    /// Default: 100_000_000
+    pub max_filter_region_pixels: u64,
+
+    /// The maximum size of an SVG data in bytes, after decompression.
+    ///
+    /// Exceeding it results in [`Error::InputSizeLimitReached`](crate::Error::InputSizeLimitReached).
+    /// Applies only to `Tree::from_data` and `Tree::from_reader`.
+    ///
+    /// Default: 268_435_456 (256 MiB)
+    pub max_input_size: u64,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_elements: 1_000_000,
+            max_use_depth: 64,
+            max_image_pixels: 100_000_000,
+            max_nested_images_depth: 8,
+            max_text_length: 100_000,
+            max_filter_region_pixels: 100_000_000,
+            max_input_size: 268_435_456,
+        }
+    }
+}
@@ -9,7 +9,9 @@ use svgtypes::{Length, LengthUnit as Unit};
 
 use super::OptionLog;
 use super::converter::{self, Cache, SvgColorExt};
+use super::css::{self, PositionOffset, Token};
 use super::svgtree::{AId, EId, SvgNode};
+use super::units;
 use crate::*;
 
 pub(crate) enum ServerOrColor {
@@ -72,6 +74,7 @@ fn convert_linear(node: SvgNode, state: &converter::State) -> Option<ServerOrCol
             units,
             transform,
             spread_method: convert_spread_method(node),
+            color_interpolation: convert_color_interpolation(node),
             stops,
         },
     };
@@ -137,6 +140,7 @@ fn convert_radial(node: SvgNode, state: &converter::State) -> Option<ServerOrCol
             units,
             transform,
             spread_method,
+            color_interpolation: convert_color_interpolation(node),
             stops,
         },
     };
@@ -177,9 +181,12 @@ fn convert_pattern(
         resolve_number(node, AId::Height, units, state, Length::zero()),
     );
     let rect = rect.log_none(|| {
-        log::warn!(
-            "Pattern '{}' has an invalid size. Skipped.",
-            node.element_id()
+        node.warn(
+            DiagnosticCode::InvalidElement,
+            format!(
+                "Pattern '{}' has an invalid size. Skipped.",
+                node.element_id()
+            ),
         );
     })?;
 
@@ -222,11 +229,371 @@ fn convert_pattern(
     Some(ServerOrColor::Server(Paint::Pattern(Arc::new(patt))))
 }
 
+/// Converts a CSS `linear-gradient()` or `radial-gradient()` function
+/// into a user space gradient that covers `rect`.
+pub(crate) fn convert_css_gradient(
+    name: &str,
+    args: &str,
+    rect: NonZeroRect,
+    node: SvgNode,
+    state: &converter::State,
+    cache: &mut converter::Cache,
+) -> Option<ServerOrColor> {
+    let tokens = css::tokenize(args)?;
+    let args: Vec<_> = tokens.split(|t| *t == Token::Comma).collect();
+    let (config, stop_args) = args.split_first()?;
+
+    match name {
+        "linear-gradient" => {
+            let (dx, dy) = match parse_css_linear_direction(config, rect) {
+                Some(dir) => dir,
+                // The direction is optional.
+                None => return convert_css_linear(&args, (0.0, 1.0), rect, node, state, cache),
+            };
+            convert_css_linear(stop_args, (dx, dy), rect, node, state, cache)
+        }
+        "radial-gradient" => match parse_css_radial_shape(config, rect, node, state) {
+            Some(shape) => convert_css_radial(stop_args, shape, node, state, cache),
+            None => {
+                let shape = parse_css_radial_shape(&[], rect, node, state)?;
+                convert_css_radial(&args, shape, node, state, cache)
+            }
+        },
+        _ => None,
+    }
+}
+
+/// Returns a normalized gradient line direction.
+fn parse_css_linear_direction(tokens: &[Token], rect: NonZeroRect) -> Option<(f32, f32)> {
+    let (w, h) = (rect.width(), rect.height());
+    match *tokens {
+        [Token::Word(angle)] => {
+            let angle = svgtypes::Angle::from_str(angle).ok()?;
+            let angle = (angle.to_degrees() as f32).to_radians();
+            // `0deg` points up and angles increase clockwise.
+            Some((angle.sin(), -angle.cos()))
+        }
+        [Token::Word("to"), ref sides @ ..] => {
+            let mut dx = 0.0;
+            let mut dy = 0.0;
+            for side in sides {
+                let (x, y) = match *side {
+                    Token::Word("left") => (-1.0, 0.0),
+                    Token::Word("right") => (1.0, 0.0),
+                    Token::Word("top") => (0.0, -1.0),
+                    Token::Word("bottom") => (0.0, 1.0),
+                    _ => return None,
+                };
+
+                if (x != 0.0 && dx != 0.0) || (y != 0.0 && dy != 0.0) {
+                    return None;
+                }
+
+                dx += x;
+                dy += y;
+            }
+
+            match sides.len() {
+                1 => Some((dx, dy)),
+                // The gradient line must be perpendicular to the diagonal
+                // connecting the two neighboring corners.
+                2 => {
+                    let (x, y) = (dx * h, dy * w);
+                    let len = x.hypot(y);
+                    Some((x / len, y / len))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn convert_css_linear(
+    stop_args: &[&[Token]],
+    (dx, dy): (f32, f32),
+    rect: NonZeroRect,
+    node: SvgNode,
+    state: &converter::State,
+    cache: &mut converter::Cache,
+) -> Option<ServerOrColor> {
+    let length = (rect.width() * dx).abs() + (rect.height() * dy).abs();
+    let stops = convert_css_stops(stop_args, length, node, state)?;
+    if stops.len() < 2 {
+        return stops_to_color(&stops);
+    }
+
+    let cx = rect.x() + rect.width() / 2.0;
+    let cy = rect.y() + rect.height() / 2.0;
+    let gradient = LinearGradient {
+        x1: cx - dx * length / 2.0,
+        y1: cy - dy * length / 2.0,
+        x2: cx + dx * length / 2.0,
+        y2: cy + dy * length / 2.0,
+        base: BaseGradient {
+            id: cache.gen_linear_gradient_id(),
+            units: Units::UserSpaceOnUse,
+            transform: Transform::default(),
+            spread_method: SpreadMethod::Pad,
+            color_interpolation: filter::ColorInterpolation::SRGB,
+            stops,
+        },
+    };
+
+    Some(ServerOrColor::Server(Paint::LinearGradient(Arc::new(
+        gradient,
+    ))))
+}
+
+/// A resolved radial gradient ending shape: a center and radii.
+struct CssRadialShape {
+    cx: f32,
+    cy: f32,
+    rx: f32,
+    ry: f32,
+}
+
+fn parse_css_radial_shape(
+    tokens: &[Token],
+    rect: NonZeroRect,
+    node: SvgNode,
+    state: &converter::State,
+) -> Option<CssRadialShape> {
+    let (shape_tokens, position) = match tokens.iter().position(|t| *t == Token::Word("at")) {
+        Some(idx) => (&tokens[..idx], css::parse_position(&tokens[idx + 1..])?),
+        None => (tokens, (PositionOffset::CENTER, PositionOffset::CENTER)),
+    };
+
+    let cx = position.0.resolve(rect.x(), rect.width(), node, state);
+    let cy = position.1.resolve(rect.y(), rect.height(), node, state);
+
+    let mut circle = None;
+    let mut extent = None;
+    let mut lengths = Vec::new();
+    for token in shape_tokens {
+        let Token::Word(word) = *token else {
+            return None;
+        };
+
+        match word {
+            "circle" if circle.is_none() => circle = Some(true),
+            "ellipse" if circle.is_none() => circle = Some(false),
+            "closest-side" | "farthest-side" | "closest-corner" | "farthest-corner"
+                if extent.is_none() && lengths.is_empty() =>
+            {
+                extent = Some(word)
+            }
+            _ if extent.is_none() => lengths.push(Length::from_str(word).ok()?),
+            _ => return None,
+        }
+    }
+
+    // A single length implies a circle.
+    let circle = circle.unwrap_or(lengths.len() == 1);
+
+    let (rx, ry) = match (circle, lengths.as_slice()) {
+        (_, []) => {
+            let left = cx - rect.left();
+            let right = rect.right() - cx;
+            let top = cy - rect.top();
+            let bottom = rect.bottom() - cy;
+
+            let (sx, sy) = match extent.unwrap_or("farthest-corner") {
+                "closest-side" | "closest-corner" => {
+                    (left.abs().min(right.abs()), top.abs().min(bottom.abs()))
+                }
+                _ => (left.abs().max(right.abs()), top.abs().max(bottom.abs())),
+            };
+
+            match (extent.unwrap_or("farthest-corner"), circle) {
+                ("closest-side", true) => (sx.min(sy), sx.min(sy)),
+                ("farthest-side", true) => (sx.max(sy), sx.max(sy)),
+                (_, true) => (sx.hypot(sy), sx.hypot(sy)),
+                ("closest-side" | "farthest-side", false) => (sx, sy),
+                // An ellipse with the same aspect ratio as the side one,
+                // which passes through the corner.
+                (_, false) => (sx * std::f32::consts::SQRT_2, sy * std::f32::consts::SQRT_2),
+            }
+        }
+        (true, [r]) if r.unit != Unit::Percent => {
+            let r = units::convert_user_length(*r, node, AId::R, state);
+            (r, r)
+        }
+        (false, [rx, ry]) => (
+            css::convert_length(*rx, rect.width(), node, state),
+            css::convert_length(*ry, rect.height(), node, state),
+        ),
+        _ => return None,
+    };
+
+    if rx < 0.0 || ry < 0.0 {
+        return None;
+    }
+
+    Some(CssRadialShape { cx, cy, rx, ry })
+}
+
+fn convert_css_radial(
+    stop_args: &[&[Token]],
+    shape: CssRadialShape,
+    node: SvgNode,
+    state: &converter::State,
+    cache: &mut converter::Cache,
+) -> Option<ServerOrColor> {
+    let stops = convert_css_stops(stop_args, shape.rx, node, state)?;
+    if stops.len() < 2 {
+        return stops_to_color(&stops);
+    }
+
+    // A degenerate gradient is painted using the last stop color.
+    if !(shape.rx > 0.0 && shape.ry > 0.0) {
+        let stop = stops.last().unwrap();
+        return Some(ServerOrColor::Color {
+            color: stop.color,
+            opacity: stop.opacity,
+        });
+    }
+
+    // An ellipse is a circle scaled vertically around its center.
+    let k = shape.ry / shape.rx;
+    let gradient = RadialGradient {
+        cx: shape.cx,
+        cy: shape.cy,
+        r: PositiveF32::new(shape.rx)?,
+        fx: shape.cx,
+        fy: shape.cy,
+        fr: PositiveF32::ZERO,
+        base: BaseGradient {
+            id: cache.gen_radial_gradient_id(),
+            units: Units::UserSpaceOnUse,
+            transform: Transform::from_row(1.0, 0.0, 0.0, k, 0.0, shape.cy * (1.0 - k)),
+            spread_method: SpreadMethod::Pad,
+            color_interpolation: filter::ColorInterpolation::SRGB,
+            stops,
+        },
+    };
+
+    Some(ServerOrColor::Server(Paint::RadialGradient(Arc::new(
+        gradient,
+    ))))
+}
+
+/// Converts CSS color stops.
+///
+/// Positions are resolved against the gradient line `length`.
+/// Color hints are not supported.
+fn convert_css_stops(
+    args: &[&[Token]],
+    length: f32,
+    node: SvgNode,
+    state: &converter::State,
+) -> Option<Vec<Stop>> {
+    let mut colors = Vec::new();
+    let mut positions = Vec::new();
+    for arg in args {
+        let (color, stop_positions) = arg.split_first()?;
+        let Token::Word(color) = *color else {
+            return None;
+        };
+
+        let color = if color == "currentColor" {
+            node.find_attribute(AId::Color)
+                .unwrap_or_else(svgtypes::Color::black)
+        } else {
+            super::color::parse_color(color)?
+        };
+
+        if stop_positions.len() > 2 {
+            return None;
+        }
+
+        // A stop with two positions is the same as two stops with the same color.
+        let mut has_position = false;
+        for position in stop_positions.iter() {
+            let Token::Word(position) = *position else {
+                return None;
+            };
+
+            let position = Length::from_str(position).ok()?;
+            colors.push(color);
+            positions.push(Some(
+                css::convert_length(position, length, node, state) / length,
+            ));
+            has_position = true;
+        }
+
+        if !has_position {
+            colors.push(color);
+            positions.push(None);
+        }
+    }
+
+    if colors.is_empty() {
+        return None;
+    }
+
+    // Resolve missing positions.
+    if positions[0].is_none() {
+        positions[0] = Some(0.0);
+    }
+    if let Some(last) = positions.last_mut() {
+        last.get_or_insert(1.0);
+    }
+
+    let mut resolved = Vec::with_capacity(positions.len());
+    let mut max = 0.0f32;
+    let mut i = 0;
+    while i < positions.len() {
+        match positions[i] {
+            Some(pos) => {
+                // A stop position cannot be smaller than the previous one.
+                max = max.max(pos);
+                resolved.push(max);
+                i += 1;
+            }
+            None => {
+                // Distribute stops without a position evenly.
+                let start = max;
+                let end_idx = (i..positions.len()).find(|idx| positions[*idx].is_some())?;
+                let end = positions[end_idx]?.max(start);
+                let count = (end_idx - i + 1) as f32;
+                for n in i..end_idx {
+                    resolved.push(start + (end - start) * (n - i + 1) as f32 / count);
+                }
+                i = end_idx;
+            }
+        }
+    }
+
+    let mut stops: Vec<_> = colors
+        .into_iter()
+        .zip(resolved)
+        .map(|(color, offset)| {
+            let (color, opacity) = color.split_alpha();
+            Stop {
+                offset: StopOffset::new_clamped(offset),
+                color,
+                opacity,
+            }
+        })
+        .collect();
+
+    fix_stop_offsets(&mut stops);
+    Some(stops)
+}
+
 fn convert_spread_method(node: SvgNode) -> SpreadMethod {
     let node = resolve_attr(node, AId::SpreadMethod);
     node.attribute(AId::SpreadMethod).unwrap_or_default()
 }
 
+fn convert_color_interpolation(node: SvgNode) -> filter::ColorInterpolation {
+    // Unlike gradient attributes, `color-interpolation` is a property
+    // and is not inherited via `xlink:href`.
+    node.find_attribute(AId::ColorInterpolation)
+        .unwrap_or(filter::ColorInterpolation::SRGB)
+}
+
 pub(crate) fn convert_units(node: SvgNode, name: AId, def: Units) -> Units {
     let node = resolve_attr(node, name);
     node.attribute(name).unwrap_or(def)
@@ -237,10 +604,13 @@ fn find_gradient_with_stops<'a, 'input: 'a>(
 ) -> Option<SvgNode<'a, 'input>> {
     for link in node.href_iter() {
         if !link.tag_name().unwrap().is_gradient() {
-            log::warn!(
-                "Gradient '{}' cannot reference '{}' via 'xlink:href'.",
-                node.element_id(),
-                link.tag_name().unwrap()
+            node.warn(
+                DiagnosticCode::InvalidReference,
+                format!(
+                    "Gradient '{}' cannot reference '{}' via 'xlink:href'.",
+                    node.element_id(),
+                    link.tag_name().unwrap()
+                ),
             );
             return None;
         }
@@ -258,10 +628,13 @@ fn find_pattern_with_children<'a, 'input: 'a>(
 ) -> Option<SvgNode<'a, 'input>> {
     for link in node.href_iter() {
         if link.tag_name() != Some(EId::Pattern) {
-            log::warn!(
-                "Pattern '{}' cannot reference '{}' via 'xlink:href'.",
-                node.element_id(),
-                link.tag_name().unwrap()
+            node.warn(
+                DiagnosticCode::InvalidReference,
+                format!(
+                    "Pattern '{}' cannot reference '{}' via 'xlink:href'.",
+                    node.element_id(),
+                    link.tag_name().unwrap()
+                ),
             );
             return None;
         }
@@ -281,7 +654,10 @@ fn convert_stops(grad: SvgNode) -> Vec<Stop> {
         let mut prev_offset = Length::zero();
         for stop in grad.children() {
             if stop.tag_name() != Some(EId::Stop) {
-                log::warn!("Invalid gradient child: '{:?}'.", stop.tag_name().unwrap());
+                grad.warn(
+                    DiagnosticCode::InvalidElement,
+                    format!("Invalid gradient child: '{:?}'.", stop.tag_name().unwrap()),
+                );
                 continue;
             }
 
@@ -300,10 +676,13 @@ fn convert_stops(grad: SvgNode) -> Vec<Stop> {
                     .find_attribute(AId::Color)
                     .unwrap_or_else(svgtypes::Color::black),
                 Some(value) => {
-                    if let Ok(c) = svgtypes::Color::from_str(value) {
+                    if let Some(c) = super::color::parse_color(value) {
                         c
                     } else {
-                        log::warn!("Failed to parse stop-color value: '{}'.", value);
+                        stop.warn(
+                            DiagnosticCode::InvalidAttribute,
+                            format!("Failed to parse stop-color value: '{}'.", value),
+                        );
                         svgtypes::Color::black()
                     }
                 }
@@ -322,6 +701,11 @@ fn convert_stops(grad: SvgNode) -> Vec<Stop> {
         }
     }
 
+    fix_stop_offsets(&mut stops);
+    stops
+}
+
+fn fix_stop_offsets(stops: &mut Vec<Stop>) {
     // Remove stops with equal offset.
     //
     // Example:
@@ -399,8 +783,6 @@ fn convert_stops(grad: SvgNode) -> Vec<Stop> {
             i += 1;
         }
     }
-
-    stops
 }
 
 #[inline(never)]
@@ -589,8 +971,8 @@ fn node_to_user_coordinates(
     match node {
         Node::Group(g) => {
             // No need to check clip paths, because they cannot have paint servers.
-            if let Some(mask) = &mut g.mask {
-                if let Some(mask) = Arc::get_mut(mask) {
+            for layer in &mut g.masks {
+                if let Some(mask) = Arc::get_mut(&mut layer.mask) {
                     update_paint_servers(
                         &mut mask.root,
                         context_transform,
@@ -846,6 +1228,7 @@ fn process_context_paint(
                     units: lg.units,
                     transform,
                     spread_method: lg.spread_method,
+                    color_interpolation: lg.color_interpolation,
                     stops: lg.stops.clone(),
                 },
             }));
@@ -864,6 +1247,7 @@ fn process_context_paint(
                     units: rg.units,
                     transform,
                     spread_method: rg.spread_method,
+                    color_interpolation: rg.color_interpolation,
                     stops: rg.stops.clone(),
                 },
             }));
@@ -952,9 +1336,12 @@ impl Paint {
         } else {
             "Gradient"
         };
-        let bbox = bbox
-            .to_non_zero_rect()
-            .log_none(|| log::warn!("{} on zero-sized shapes is not allowed.", name))?;
+        let bbox = bbox.to_non_zero_rect().log_none(|| {
+            cache.diagnostics.warn(
+                DiagnosticCode::InvalidElement,
+                format!("{} on zero-sized shapes is not allowed.", name),
+            )
+        })?;
 
         // `Arc::get_mut()` allow us to modify some paint servers in-place.
         // This reduces the amount of cloning and preserves the original ID as well.
@@ -976,6 +1363,7 @@ impl Paint {
                             units: Units::UserSpaceOnUse,
                             transform,
                             spread_method: lg.spread_method,
+                            color_interpolation: lg.color_interpolation,
                             stops: lg.stops.clone(),
                         },
                     });
@@ -999,6 +1387,7 @@ impl Paint {
                             units: Units::UserSpaceOnUse,
                             transform,
                             spread_method: rg.spread_method,
+                            color_interpolation: rg.color_interpolation,
                             stops: rg.stops.clone(),
                         },
                     });
@@ -0,0 +1,173 @@
+// Copyright 2026 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use super::{Error, Options, xml_options};
+use crate::DiagnosticCode;
+
+/// The maximum number of repair attempts.
+///
+/// Each attempt removes at least one markup construct, so it's usually enough.
+const MAX_ATTEMPTS: usize = 8;
+
+/// Repairs a malformed XML when allowed by [`Options::recover_malformed_xml`].
+///
+/// Returns the original error otherwise or when the document cannot be repaired.
+pub(crate) fn recover(text: &str, error: roxmltree::Error, opt: &Options) -> Result<String, Error> {
+    if !opt.recover_malformed_xml {
+        return Err(Error::ParsingFailed(error));
+    }
+
+    match repair(text, &error) {
+        Some(repaired) => {
+            opt.diagnostics.warn(
+                DiagnosticCode::MalformedXml,
+                format!("{}. The rest of the document is ignored.", error),
+            );
+            Ok(repaired)
+        }
+        None => Err(Error::ParsingFailed(error)),
+    }
+}
+
+/// Repairs a truncated or malformed XML, so it can be parsed.
+///
+/// Everything starting from the markup that caused an error is removed
+/// and all unclosed elements are closed, like a truncated download would be.
+///
+/// Returns `None` when the document cannot be repaired, like when the root element is missing.
+fn repair(text: &str, error: &roxmltree::Error) -> Option<String> {
+    let mut error = error.clone();
+    let mut end = error_offset(text, &error)?;
+    for _ in 0..MAX_ATTEMPTS {
+        let (cut, open_elements) = scan(&text[..end]);
+        end = cut;
+
+        let mut repaired = text[..end].to_string();
+        for name in open_elements.iter().rev() {
+            repaired.push_str("</");
+            repaired.push_str(name);
+            repaired.push('>');
+        }
+
+        error = match roxmltree::Document::parse_with_options(&repaired, xml_options()) {
+            Ok(_) => return Some(repaired),
+            Err(e) => e,
+        };
+
+        // When an error is inside the kept text, cut it too.
+        // Otherwise, remove the last markup construct, which closes a wrong element.
+        let offset = error_offset(&repaired, &error)?;
+        end = if offset < end {
+            offset
+        } else {
+            text[..end].rfind('<')?
+        };
+    }
+
+    None
+}
+
+/// Returns a byte offset of an error.
+fn error_offset(text: &str, error: &roxmltree::Error) -> Option<usize> {
+    match error {
+        // Positions of these errors are unknown, but they occur only at the end of the text.
+        roxmltree::Error::UnclosedRootNode | roxmltree::Error::UnexpectedEndOfStream => {
+            Some(text.len())
+        }
+        roxmltree::Error::NoRootNode
+        | roxmltree::Error::DtdDetected
+        | roxmltree::Error::NodesLimitReached
+        | roxmltree::Error::AttributesLimitReached
+        | roxmltree::Error::NamespacesLimitReached => None,
+        _ => {
+            let pos = error.pos();
+            let line_start = if pos.row > 1 {
+                text.match_indices('\n').nth(pos.row as usize - 2)?.0 + 1
+            } else {
+                0
+            };
+
+            let col = text[line_start..]
+                .char_indices()
+                .nth(pos.col.saturating_sub(1) as usize)
+                .map(|(idx, _)| idx)
+                .unwrap_or(text.len() - line_start);
+            Some(line_start + col)
+        }
+    }
+}
+
+/// Finds elements that are not closed at the end of the text.
+///
+/// Also returns an offset of the last markup construct, when it is not terminated,
+/// or the text length otherwise.
+fn scan(text: &str) -> (usize, Vec<&str>) {
+    let mut open_elements = Vec::new();
+    let mut pos = 0;
+    while let Some(idx) = text[pos..].find('<') {
+        let start = pos + idx;
+        let rest = &text[start..];
+
+        let len = if rest.starts_with("<!--") {
+            rest.find("-->").map(|i| i + 3)
+        } else if rest.starts_with("<![CDATA[") {
+            rest.find("]]>").map(|i| i + 3)
+        } else if rest.starts_with("<?") {
+            rest.find("?>").map(|i| i + 2)
+        } else if rest.starts_with("<!") {
+            doctype_len(rest)
+        } else if rest.starts_with("</") {
+            let len = rest.find('>').map(|i| i + 1);
+            if len.is_some() {
+                open_elements.pop();
+            }
+            len
+        } else {
+            let len = start_tag_len(rest);
+            if len.is_some_and(|len| !rest[..len].ends_with("/>")) {
+                let name_end = rest[1..]
+                    .find(|c: char| c.is_ascii_whitespace() || c == '/' || c == '>')
+                    .map_or(rest.len(), |i| i + 1);
+                open_elements.push(&rest[1..name_end]);
+            }
+            len
+        };
+
+        match len {
+            Some(len) => pos = start + len,
+            None => return (start, open_elements),
+        }
+    }
+
+    (text.len(), open_elements)
+}
+
+/// Returns the length of a start tag, skipping `>` inside attribute values.
+pub(crate) fn start_tag_len(text: &str) -> Option<usize> {
+    let mut quote = None;
+    for (idx, c) in text.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if q == c => quote = None,
+            (None, '>') => return Some(idx + 1),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Returns the length of a `DOCTYPE`, including an internal subset.
+fn doctype_len(text: &str) -> Option<usize> {
+    let mut in_subset = false;
+    for (idx, c) in text.char_indices() {
+        match c {
+            '[' => in_subset = true,
+            ']' => in_subset = false,
+            '>' if !in_subset => return Some(idx + 1),
+            _ => {}
+        }
+    }
+
+    None
+}
@@ -0,0 +1,83 @@
+// Copyright 2026 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::path::{Path, PathBuf};
+
+/// A policy for accessing local files referenced by an SVG.
+///
+/// Applies to images and style sheets loaded by the default resolvers.
+/// Custom resolvers can use [`Options::resolve_resource_path`](crate::Options::resolve_resource_path)
+/// to follow the same policy.
+///
+/// External `use` targets and fonts are never loaded from paths referenced by an SVG,
+/// so they are not affected. Fonts are loaded only via [`Options::fontdb`](crate::Options::fontdb).
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub enum ResourcePolicy {
+    /// Any file can be loaded.
+    #[default]
+    AllowAll,
+    /// No files can be loaded.
+    DenyAll,
+    /// Only files inside [`Options::resources_dir`](crate::Options::resources_dir)
+    /// can be loaded.
+    ///
+    /// No files can be loaded when `resources_dir` is not set.
+    ResourcesDir,
+    /// Only the listed files and files inside the listed directories can be loaded.
+    Allowlist(Vec<PathBuf>),
+}
+
+impl ResourcePolicy {
+    /// Checks that a file can be loaded.
+    ///
+    /// A shorthand for [`ResourcePolicy::resolve`].
+    pub fn allows(&self, path: &Path, resources_dir: Option<&Path>) -> bool {
+        self.resolve(path, resources_dir).is_some()
+    }
+
+    /// Checks that a file can be loaded and returns the path that should be opened.
+    ///
+    /// Paths are canonicalized before checking and the canonical path is returned,
+    /// so symlinks and `..` components cannot be used to escape allowed directories,
+    /// as long as only the returned path is opened.
+    /// Files that do not exist are never allowed, unless the policy is `AllowAll`,
+    /// in which case the path is returned as is.
+    pub fn resolve(&self, path: &Path, resources_dir: Option<&Path>) -> Option<PathBuf> {
+        let is_inside = |path: &Path, dir: &Path| -> bool {
+            dir.canonicalize().is_ok_and(|dir| path.starts_with(dir))
+        };
+
+        let resolve = |is_allowed: &dyn Fn(&Path) -> bool| -> Option<PathBuf> {
+            let path = path.canonicalize().ok()?;
+            is_allowed(&path).then_some(path)
+        };
+
+        match self {
+            ResourcePolicy::AllowAll => Some(path.to_path_buf()),
+            ResourcePolicy::DenyAll => None,
+            ResourcePolicy::ResourcesDir => {
+                let dir = resources_dir?;
+                resolve(&|path| is_inside(path, dir))
+            }
+            ResourcePolicy::Allowlist(list) => {
+                resolve(&|path| list.iter().any(|dir| is_inside(path, dir)))
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for ResourcePolicy {
+    type Err = &'static str;
+
+    /// Parses a policy name.
+    ///
+    /// `Allowlist` cannot be parsed, since it requires a list of paths.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow-all" => Ok(ResourcePolicy::AllowAll),
+            "deny-all" => Ok(ResourcePolicy::DenyAll),
+            "resources-dir" => Ok(ResourcePolicy::ResourcesDir),
+            _ => Err("invalid"),
+        }
+    }
+}
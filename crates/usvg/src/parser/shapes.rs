@@ -8,7 +8,7 @@ use tiny_skia_path::Path;
 
 use super::svgtree::{AId, EId, SvgNode};
 use super::{converter, units};
-use crate::{ApproxEqUlps, IsValidLength, Rect};
+use crate::{ApproxEqUlps, DiagnosticCode, IsValidLength, Rect};
 
 pub(crate) fn convert(node: SvgNode, state: &converter::State) -> Option<Arc<Path>> {
     match node.tag_name()? {
@@ -25,12 +25,19 @@ pub(crate) fn convert(node: SvgNode, state: &converter::State) -> Option<Arc<Pat
 
 pub(crate) fn convert_path(node: SvgNode) -> Option<Arc<Path>> {
     let value: &str = node.attribute(AId::D)?;
+    parse_path_data(node, value).map(Arc::new)
+}
+
+pub(crate) fn parse_path_data(node: SvgNode, value: &str) -> Option<Path> {
     let mut builder = tiny_skia_path::PathBuilder::new();
     for segment in svgtypes::SimplifyingPathParser::from(value) {
         let segment = match segment {
             Ok(v) => v,
             Err(e) => {
-                log::warn!("Error during path parsing: {e}");
+                node.warn(
+                    DiagnosticCode::InvalidAttribute,
+                    format!("Error during path parsing: {e}"),
+                );
                 break;
             }
         };
@@ -63,7 +70,7 @@ pub(crate) fn convert_path(node: SvgNode) -> Option<Arc<Path>> {
         }
     }
 
-    builder.finish().map(Arc::new)
+    builder.finish()
 }
 
 fn convert_rect(node: SvgNode, state: &converter::State) -> Option<Arc<Path>> {
@@ -71,16 +78,22 @@ fn convert_rect(node: SvgNode, state: &converter::State) -> Option<Arc<Path>> {
     let width = node.convert_user_length(AId::Width, state, Length::zero());
     let height = node.convert_user_length(AId::Height, state, Length::zero());
     if !width.is_valid_length() {
-        log::warn!(
-            "Rect '{}' has an invalid 'width' value. Skipped.",
-            node.element_id()
+        node.warn(
+            DiagnosticCode::InvalidElement,
+            format!(
+                "Rect '{}' has an invalid 'width' value. Skipped.",
+                node.element_id()
+            ),
         );
         return None;
     }
     if !height.is_valid_length() {
-        log::warn!(
-            "Rect '{}' has an invalid 'height' value. Skipped.",
-            node.element_id()
+        node.warn(
+            DiagnosticCode::InvalidElement,
+            format!(
+                "Rect '{}' has an invalid 'height' value. Skipped.",
+                node.element_id()
+            ),
         );
         return None;
     }
@@ -200,10 +213,13 @@ fn points_to_path(node: SvgNode, eid: &str) -> Option<tiny_skia_path::PathBuilde
             }
         }
         _ => {
-            log::warn!(
-                "{} '{}' has an invalid 'points' value. Skipped.",
-                eid,
-                node.element_id()
+            node.warn(
+                DiagnosticCode::InvalidElement,
+                format!(
+                    "{} '{}' has an invalid 'points' value. Skipped.",
+                    eid,
+                    node.element_id()
+                ),
             );
             return None;
         }
@@ -211,10 +227,13 @@ fn points_to_path(node: SvgNode, eid: &str) -> Option<tiny_skia_path::PathBuilde
 
     // 'polyline' and 'polygon' elements must contain at least 2 points.
     if builder.len() < 2 {
-        log::warn!(
-            "{} '{}' has less than 2 points. Skipped.",
-            eid,
-            node.element_id()
+        node.warn(
+            DiagnosticCode::InvalidElement,
+            format!(
+                "{} '{}' has less than 2 points. Skipped.",
+                eid,
+                node.element_id()
+            ),
         );
         return None;
     }
@@ -228,9 +247,12 @@ fn convert_circle(node: SvgNode, state: &converter::State) -> Option<Arc<Path>>
     let r = node.convert_user_length(AId::R, state, Length::zero());
 
     if !r.is_valid_length() {
-        log::warn!(
-            "Circle '{}' has an invalid 'r' value. Skipped.",
-            node.element_id()
+        node.warn(
+            DiagnosticCode::InvalidElement,
+            format!(
+                "Circle '{}' has an invalid 'r' value. Skipped.",
+                node.element_id()
+            ),
         );
         return None;
     }
@@ -244,17 +266,23 @@ fn convert_ellipse(node: SvgNode, state: &converter::State) -> Option<Arc<Path>>
     let (rx, ry) = resolve_rx_ry(node, state);
 
     if !rx.is_valid_length() {
-        log::warn!(
-            "Ellipse '{}' has an invalid 'rx' value. Skipped.",
-            node.element_id()
+        node.warn(
+            DiagnosticCode::InvalidElement,
+            format!(
+                "Ellipse '{}' has an invalid 'rx' value. Skipped.",
+                node.element_id()
+            ),
         );
         return None;
     }
 
     if !ry.is_valid_length() {
-        log::warn!(
-            "Ellipse '{}' has an invalid 'ry' value. Skipped.",
-            node.element_id()
+        node.warn(
+            DiagnosticCode::InvalidElement,
+            format!(
+                "Ellipse '{}' has an invalid 'ry' value. Skipped.",
+                node.element_id()
+            ),
         );
         return None;
     }
@@ -262,7 +290,7 @@ fn convert_ellipse(node: SvgNode, state: &converter::State) -> Option<Arc<Path>>
     ellipse_to_path(cx, cy, rx, ry)
 }
 
-fn ellipse_to_path(cx: f32, cy: f32, rx: f32, ry: f32) -> Option<Arc<Path>> {
+pub(crate) fn ellipse_to_path(cx: f32, cy: f32, rx: f32, ry: f32) -> Option<Arc<Path>> {
     let mut builder = tiny_skia_path::PathBuilder::new();
     builder.move_to(cx + rx, cy);
     builder.arc_to(rx, ry, 0.0, false, true, cx, cy + ry);
@@ -273,7 +301,7 @@ fn ellipse_to_path(cx: f32, cy: f32, rx: f32, ry: f32) -> Option<Arc<Path>> {
     builder.finish().map(Arc::new)
 }
 
-trait PathBuilderExt {
+pub(crate) trait PathBuilderExt {
     fn arc_to(
         &mut self,
         rx: f32,
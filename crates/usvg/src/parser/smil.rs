@@ -0,0 +1,1475 @@
+// Copyright 2026 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! SMIL animations sampling.
+//!
+//! Animations are evaluated using the source XML before [`svgtree`](super::svgtree) is built.
+//! The resulting values override element attributes and CSS.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use super::svgtree::{AId, SVG_NS, XLINK_NS};
+use crate::{Diagnostic, DiagnosticCode, Options, Severity};
+
+/// Limits the number of intervals created by cyclic timing, like `begin="0s;other.end"`.
+const MAX_INTERVALS: usize = 1000;
+
+/// The number of line segments used to approximate a curve of a motion path.
+const CURVE_STEPS: usize = 16;
+
+/// Animated attribute values of the source XML elements.
+pub(crate) type AnimatedValues = HashMap<roxmltree::NodeId, Vec<(AId, String)>>;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Kind {
+    Animate,
+    Set,
+    Transform,
+    Motion,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum CalcMode {
+    Discrete,
+    Linear,
+    Paced,
+    Spline,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Restart {
+    Always,
+    WhenNotActive,
+    Never,
+}
+
+/// A resolvable `begin` or `end` value.
+#[derive(Clone, Copy, Debug)]
+enum TimeValue {
+    Offset(f64),
+    /// Interval begins of an animation with the specified index, with an offset.
+    SyncBegin(usize, f64),
+    /// Interval ends of an animation with the specified index, with an offset.
+    SyncEnd(usize, f64),
+}
+
+/// An animation state at the sampled time.
+#[derive(Clone, Copy, Debug)]
+struct Sample {
+    /// The current interval begin. Defines the animation priority.
+    begin: f64,
+    /// The number of completed iterations.
+    iteration: f64,
+    /// The simple time divided by the simple duration.
+    progress: f64,
+}
+
+struct Animation<'a, 'input> {
+    node: roxmltree::Node<'a, 'input>,
+    kind: Kind,
+    target: roxmltree::Node<'a, 'input>,
+    /// An animated attribute. Always `transform` for `animateMotion`.
+    attribute: AId,
+    begin: Vec<TimeValue>,
+    end: Option<Vec<TimeValue>>,
+    /// A simple duration. Infinite when indefinite or not set.
+    dur: f64,
+    /// An active duration, ignoring `end`.
+    active_dur: f64,
+    restart: Restart,
+    freeze: bool,
+    calc_mode: CalcMode,
+    key_times: Option<Vec<f64>>,
+    key_splines: Vec<[f64; 4]>,
+    additive: bool,
+    accumulate: bool,
+    /// A motion path data from `mpath` or `path`.
+    motion_path: Option<&'a str>,
+}
+
+/// A parsed animation value.
+#[derive(Clone, PartialEq, Debug)]
+pub(crate) enum Value {
+    /// Numbers and the text around them, like `10px` or `translate(10 20)`.
+    ///
+    /// Values with the same text can be interpolated.
+    Numbers(Vec<f64>, Vec<String>),
+    /// An RGBA color with an alpha in the 0..1 range.
+    Color([f64; 4]),
+    /// A value that cannot be interpolated.
+    Other(String),
+}
+
+/// Evaluates animations of the document at the specified time in seconds.
+pub(crate) fn sample(xml: &roxmltree::Document, time: f32, opt: &Options) -> AnimatedValues {
+    let time = time as f64;
+    let animations = collect(xml, opt);
+    if animations.is_empty() {
+        return AnimatedValues::new();
+    }
+
+    let intervals = resolve_intervals(&animations, time);
+
+    let mut active: Vec<(Sample, &Animation)> = animations
+        .iter()
+        .zip(&intervals)
+        .filter_map(|(animation, intervals)| Some((animation.sample(intervals, time)?, animation)))
+        .collect();
+    // Animations that begin later have a higher priority.
+    // The sort is stable, therefore the document order is preserved otherwise.
+    active.sort_by(|(a, _), (b, _)| a.begin.total_cmp(&b.begin));
+
+    let mut values = AnimatedValues::new();
+    let mut motions: HashMap<roxmltree::NodeId, (f64, f64, f64)> = HashMap::new();
+    for (sample, animation) in active {
+        if animation.kind == Kind::Motion {
+            let Some((x, y, angle)) = animation.motion(sample, opt) else {
+                continue;
+            };
+
+            let motion = motions
+                .entry(animation.target.id())
+                .or_insert((0.0, 0.0, 0.0));
+            if animation.additive {
+                *motion = (motion.0 + x, motion.1 + y, angle);
+            } else {
+                *motion = (x, y, angle);
+            }
+
+            continue;
+        }
+
+        let node_id = animation.target.id();
+        let underlying = animated_value(&values, node_id, animation.attribute)
+            .or_else(|| base_value(animation.target, animation.attribute))
+            .map(str::to_string);
+        let Some((value, additive)) = animation.value(sample, underlying.as_deref(), opt) else {
+            continue;
+        };
+
+        let value = match underlying {
+            // Transforms are post-multiplied.
+            Some(underlying) if additive && animation.kind == Kind::Transform => {
+                format!("{} {}", underlying, value)
+            }
+            Some(ref underlying) if additive => {
+                parse_value(underlying, animation.attribute, opt.dpi)
+                    .add(&value)
+                    .unwrap_or(value)
+                    .to_string()
+            }
+            _ => value.to_string(),
+        };
+        set_animated_value(&mut values, node_id, animation.attribute, value);
+    }
+
+    // A motion is applied before the `transform` attribute.
+    for (node_id, (x, y, angle)) in motions {
+        let transform = animated_value(&values, node_id, AId::Transform)
+            .or_else(|| base_value(xml.get_node(node_id).unwrap(), AId::Transform))
+            .unwrap_or_default();
+        let motion = format!(
+            "translate({} {}) rotate({}) {}",
+            fmt_number(x),
+            fmt_number(y),
+            fmt_number(angle),
+            transform
+        );
+        set_animated_value(&mut values, node_id, AId::Transform, motion);
+    }
+
+    values
+}
+
+fn animated_value(values: &AnimatedValues, node_id: roxmltree::NodeId, aid: AId) -> Option<&str> {
+    values
+        .get(&node_id)?
+        .iter()
+        .find(|(name, _)| *name == aid)
+        .map(|(_, value)| value.as_str())
+}
+
+fn set_animated_value(
+    values: &mut AnimatedValues,
+    node_id: roxmltree::NodeId,
+    aid: AId,
+    value: String,
+) {
+    let values = values.entry(node_id).or_default();
+    match values.iter_mut().find(|(name, _)| *name == aid) {
+        Some(prev) => prev.1 = value,
+        None => values.push((aid, value)),
+    }
+}
+
+fn collect<'a, 'input>(
+    xml: &'a roxmltree::Document<'input>,
+    opt: &Options,
+) -> Vec<Animation<'a, 'input>> {
+    let mut ids = HashMap::new();
+    for node in xml.descendants() {
+        if let Some(id) = node.attribute("id") {
+            ids.entry(id).or_insert(node);
+        }
+    }
+
+    let mut elements = Vec::new();
+    for node in xml.descendants() {
+        if !matches!(node.tag_name().namespace(), None | Some(SVG_NS)) {
+            continue;
+        }
+
+        let kind = match node.tag_name().name() {
+            "animate" | "animateColor" => Kind::Animate,
+            "set" => Kind::Set,
+            "animateTransform" => Kind::Transform,
+            "animateMotion" => Kind::Motion,
+            _ => continue,
+        };
+
+        let target = match href_value(node) {
+            Some(href) => href
+                .trim()
+                .strip_prefix('#')
+                .and_then(|id| ids.get(id).copied()),
+            None => node.parent_element(),
+        };
+        let Some(target) = target else {
+            warn(
+                node,
+                DiagnosticCode::InvalidReference,
+                "An animation target doesn't exist. Skipped.".to_string(),
+                opt,
+            );
+            continue;
+        };
+
+        let attribute = match (kind, node.attribute("attributeName")) {
+            (Kind::Motion, _) | (Kind::Transform, None) => Some(AId::Transform),
+            (_, Some(name)) => {
+                let name = name.trim();
+                AId::from_str(name.strip_prefix("xlink:").unwrap_or(name))
+            }
+            (_, None) => None,
+        };
+        let Some(attribute) = attribute else {
+            warn(
+                node,
+                DiagnosticCode::Unsupported,
+                format!(
+                    "Animation of '{}' is not supported. Skipped.",
+                    node.attribute("attributeName").unwrap_or_default()
+                ),
+                opt,
+            );
+            continue;
+        };
+
+        elements.push((node, kind, target, attribute));
+    }
+
+    // Syncbase values can reference animations that come later.
+    let indices: HashMap<&str, usize> = elements
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, (node, ..))| Some((node.attribute("id")?, idx)))
+        .collect();
+
+    let mut animations = Vec::new();
+    for (node, kind, target, attribute) in elements {
+        let dur = node
+            .attribute("dur")
+            .and_then(parse_clock_value)
+            .filter(|dur| *dur > 0.0)
+            .unwrap_or(f64::INFINITY);
+        let repeat_count = node.attribute("repeatCount").and_then(|v| match v.trim() {
+            "indefinite" => Some(f64::INFINITY),
+            v => f64::from_str(v).ok().filter(|n| *n > 0.0),
+        });
+        let repeat_dur = node.attribute("repeatDur").and_then(|v| match v.trim() {
+            "indefinite" => Some(f64::INFINITY),
+            v => parse_clock_value(v),
+        });
+        let active_dur = if repeat_count.is_none() && repeat_dur.is_none() {
+            dur
+        } else {
+            let count_dur = repeat_count.map_or(f64::INFINITY, |count| count * dur);
+            count_dur.min(repeat_dur.unwrap_or(f64::INFINITY))
+        };
+
+        let begin = parse_time_list(node, node.attribute("begin").unwrap_or("0s"), &indices, opt);
+        let end = node
+            .attribute("end")
+            .map(|end| parse_time_list(node, end, &indices, opt));
+
+        let restart = match node.attribute("restart") {
+            Some("whenNotActive") => Restart::WhenNotActive,
+            Some("never") => Restart::Never,
+            _ => Restart::Always,
+        };
+
+        let calc_mode = match node.attribute("calcMode") {
+            Some("discrete") => CalcMode::Discrete,
+            Some("linear") => CalcMode::Linear,
+            Some("paced") => CalcMode::Paced,
+            Some("spline") => CalcMode::Spline,
+            _ if kind == Kind::Motion => CalcMode::Paced,
+            _ => CalcMode::Linear,
+        };
+
+        let key_times = match node.attribute("keyTimes") {
+            Some(text) => match parse_number_list(text, ';') {
+                Some(list) => Some(list),
+                None => {
+                    warn(
+                        node,
+                        DiagnosticCode::InvalidAttribute,
+                        format!("Failed to parse keyTimes value: '{}'. Skipped.", text),
+                        opt,
+                    );
+                    continue;
+                }
+            },
+            None => None,
+        };
+
+        let key_splines = match node.attribute("keySplines") {
+            Some(text) if calc_mode == CalcMode::Spline => match parse_key_splines(text) {
+                Some(splines) => splines,
+                None => {
+                    warn(
+                        node,
+                        DiagnosticCode::InvalidAttribute,
+                        format!("Failed to parse keySplines value: '{}'. Skipped.", text),
+                        opt,
+                    );
+                    continue;
+                }
+            },
+            _ => Vec::new(),
+        };
+
+        let motion_path = if kind == Kind::Motion {
+            let mpath = node
+                .children()
+                .find(|n| n.has_tag_name((SVG_NS, "mpath")))
+                .and_then(href_value)
+                .and_then(|href| href.trim().strip_prefix('#'))
+                .and_then(|id| ids.get(id))
+                .and_then(|path| path.attribute("d"));
+            mpath.or_else(|| node.attribute("path"))
+        } else {
+            None
+        };
+
+        animations.push(Animation {
+            node,
+            kind,
+            target,
+            attribute,
+            begin,
+            end,
+            dur,
+            active_dur,
+            restart,
+            freeze: node.attribute("fill") == Some("freeze"),
+            calc_mode,
+            key_times,
+            key_splines,
+            additive: node.attribute("additive") == Some("sum"),
+            accumulate: node.attribute("accumulate") == Some("sum"),
+            motion_path,
+        });
+    }
+
+    animations
+}
+
+/// Resolves animation intervals that begin before the specified time.
+///
+/// Each pass resolves one more step of syncbase dependencies,
+/// so cyclic timing is resolved up to [`MAX_INTERVALS`] steps.
+fn resolve_intervals(animations: &[Animation], time: f64) -> Vec<Vec<(f64, f64)>> {
+    let mut intervals = vec![Vec::new(); animations.len()];
+    for _ in 0..MAX_INTERVALS {
+        let next: Vec<_> = animations
+            .iter()
+            .map(|animation| animation.intervals(&intervals, time))
+            .collect();
+        if next == intervals {
+            break;
+        }
+
+        intervals = next;
+    }
+
+    intervals
+}
+
+fn resolve_times(values: &[TimeValue], intervals: &[Vec<(f64, f64)>]) -> Vec<f64> {
+    let mut times = Vec::new();
+    for value in values {
+        match *value {
+            TimeValue::Offset(time) => times.push(time),
+            TimeValue::SyncBegin(idx, offset) => {
+                times.extend(intervals[idx].iter().map(|(begin, _)| begin + offset));
+            }
+            TimeValue::SyncEnd(idx, offset) => times.extend(
+                intervals[idx]
+                    .iter()
+                    .map(|(_, end)| end + offset)
+                    .filter(|end| end.is_finite()),
+            ),
+        }
+    }
+
+    times
+}
+
+impl Animation<'_, '_> {
+    fn intervals(&self, resolved: &[Vec<(f64, f64)>], time: f64) -> Vec<(f64, f64)> {
+        let mut begins = resolve_times(&self.begin, resolved);
+        begins.retain(|begin| *begin <= time);
+        begins.sort_by(f64::total_cmp);
+        begins.dedup();
+
+        let ends = self.end.as_ref().map(|end| resolve_times(end, resolved));
+
+        let mut intervals: Vec<(f64, f64)> = Vec::new();
+        for (idx, &begin) in begins.iter().enumerate() {
+            if intervals.len() == MAX_INTERVALS {
+                break;
+            }
+
+            if let Some(&(_, prev_end)) = intervals.last() {
+                match self.restart {
+                    Restart::Never => break,
+                    Restart::WhenNotActive if begin < prev_end => continue,
+                    _ => {}
+                }
+            }
+
+            let mut end = begin + self.active_dur;
+            if let Some(ends) = &ends {
+                let first_end = ends
+                    .iter()
+                    .copied()
+                    .filter(|end| *end >= begin)
+                    .min_by(f64::total_cmp);
+                if let Some(first_end) = first_end {
+                    end = end.min(first_end);
+                }
+            }
+
+            // A new interval ends the current one.
+            if self.restart == Restart::Always {
+                if let Some(&next) = begins.get(idx + 1) {
+                    end = end.min(next);
+                }
+            }
+
+            intervals.push((begin, end));
+        }
+
+        intervals
+    }
+
+    fn sample(&self, intervals: &[(f64, f64)], time: f64) -> Option<Sample> {
+        let &(begin, end) = intervals.iter().rev().find(|(begin, _)| *begin <= time)?;
+        let (elapsed, frozen) = if time < end {
+            (time - begin, false)
+        } else if self.freeze {
+            (end - begin, true)
+        } else {
+            return None;
+        };
+
+        // The simple duration is indefinite, so the first value is used.
+        if !self.dur.is_finite() {
+            return Some(Sample {
+                begin,
+                iteration: 0.0,
+                progress: 0.0,
+            });
+        }
+
+        let mut iteration = (elapsed / self.dur).floor();
+        let mut progress = elapsed / self.dur - iteration;
+        // A value frozen at the end of an iteration is the last value of this iteration.
+        if frozen && iteration > 0.0 && progress < 1e-9 {
+            iteration -= 1.0;
+            progress = 1.0;
+        }
+
+        Some(Sample {
+            begin,
+            iteration,
+            progress,
+        })
+    }
+
+    /// Returns an animation value and whether it should be added to the underlying one.
+    fn value(
+        &self,
+        sample: Sample,
+        underlying: Option<&str>,
+        opt: &Options,
+    ) -> Option<(Value, bool)> {
+        let node = self.node;
+        let parse = |text: &str| -> Option<Value> {
+            if self.kind == Kind::Transform {
+                let kind = node.attribute("type").unwrap_or("translate");
+                transform_value(kind, text)
+            } else {
+                Some(parse_value(text, self.attribute, opt.dpi))
+            }
+        };
+
+        let mut additive = self.additive;
+        let mut to_animation = false;
+        let values: Option<Vec<Value>> = if self.kind == Kind::Set {
+            additive = false;
+            node.attribute("to")
+                .map(|to| vec![Value::Other(to.trim().to_string())])
+        } else if let Some(values) = node.attribute("values") {
+            values
+                .split(';')
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(parse)
+                .collect()
+        } else {
+            let from = node.attribute("from").map(parse);
+            let to = node.attribute("to").map(parse);
+            let by = node.attribute("by").map(parse);
+            match (from, to, by) {
+                (Some(from), Some(to), _) => Some(vec![from?, to?]),
+                (Some(from), None, Some(by)) => {
+                    let from = from?;
+                    let to = from.add(&by?)?;
+                    Some(vec![from, to])
+                }
+                (None, None, Some(by)) => {
+                    // A by animation is always additive.
+                    additive = true;
+                    let by = by?;
+                    Some(vec![by.scale(0.0)?, by])
+                }
+                (None, Some(to), None) => {
+                    // A to animation starts from the underlying value and is never additive.
+                    additive = false;
+                    to_animation = true;
+                    let to = to?;
+                    let from = match underlying {
+                        _ if self.kind == Kind::Transform => identity_transform(&to),
+                        Some(underlying) => parse(underlying),
+                        None => initial_value(self.attribute).and_then(parse),
+                    };
+                    Some(vec![from.unwrap_or_else(|| to.clone()), to])
+                }
+                _ => Some(Vec::new()),
+            }
+        };
+
+        let Some(values) = values.filter(|v| !v.is_empty()) else {
+            warn(
+                node,
+                DiagnosticCode::InvalidAttribute,
+                "An animation has invalid values. Skipped.".to_string(),
+                opt,
+            );
+            return None;
+        };
+
+        // Values that cannot be interpolated are animated discretely.
+        let mut calc_mode = self.calc_mode;
+        if values
+            .windows(2)
+            .any(|w| w[0].interpolate(&w[1], 0.0).is_none())
+        {
+            calc_mode = CalcMode::Discrete;
+        }
+
+        if !self.is_timing_valid(calc_mode, values.len(), opt) {
+            return None;
+        }
+
+        let distances = if calc_mode == CalcMode::Paced {
+            cumulative(values.windows(2).map(|w| w[0].distance(&w[1])))
+        } else {
+            None
+        };
+
+        let (idx, t) = self.segment(
+            calc_mode,
+            sample.progress,
+            values.len(),
+            distances.as_deref(),
+        );
+        let mut value = match values.get(idx + 1) {
+            Some(next) if t > 0.0 => values[idx].interpolate(next, t)?,
+            _ => values[idx].clone(),
+        };
+
+        if self.accumulate && !to_animation && sample.iteration > 0.0 {
+            let accumulated = values
+                .last()
+                .and_then(|last| last.scale(sample.iteration))
+                .and_then(|offset| value.add(&offset));
+            if let Some(accumulated) = accumulated {
+                value = accumulated;
+            }
+        }
+
+        Some((value, additive))
+    }
+
+    /// Returns a translation and a rotation angle of an `animateMotion`.
+    fn motion(&self, sample: Sample, opt: &Options) -> Option<(f64, f64, f64)> {
+        let node = self.node;
+
+        let (mut x, mut y, direction, end) = if let Some(path) = self.motion_path {
+            let polyline = Polyline::from_path(path)?;
+            let fraction = match node.attribute("keyPoints") {
+                Some(text) if self.calc_mode != CalcMode::Paced => {
+                    let Some(points) = parse_number_list(text, ';') else {
+                        warn(
+                            node,
+                            DiagnosticCode::InvalidAttribute,
+                            format!("Failed to parse keyPoints value: '{}'. Skipped.", text),
+                            opt,
+                        );
+                        return None;
+                    };
+
+                    if !self.is_timing_valid(self.calc_mode, points.len(), opt) {
+                        return None;
+                    }
+
+                    let (idx, t) =
+                        self.segment(self.calc_mode, sample.progress, points.len(), None);
+                    match points.get(idx + 1) {
+                        Some(next) => points[idx] + (next - points[idx]) * t,
+                        None => points[idx],
+                    }
+                }
+                _ => sample.progress,
+            };
+
+            let (x, y, direction) = polyline.at(fraction * polyline.length());
+            let (end_x, end_y, _) = polyline.at(polyline.length());
+            (x, y, direction, (end_x, end_y))
+        } else {
+            let points = self.motion_points()?;
+            let calc_mode = self.calc_mode;
+            if !self.is_timing_valid(calc_mode, points.len(), opt) {
+                return None;
+            }
+
+            let distances = if calc_mode == CalcMode::Paced {
+                cumulative(
+                    points
+                        .windows(2)
+                        .map(|w| Some((w[1].0 - w[0].0).hypot(w[1].1 - w[0].1))),
+                )
+            } else {
+                None
+            };
+
+            let (idx, t) = self.segment(
+                calc_mode,
+                sample.progress,
+                points.len(),
+                distances.as_deref(),
+            );
+            let (x, y, direction) = match points.get(idx + 1) {
+                Some(next) => {
+                    let prev = points[idx];
+                    let direction = (next.1 - prev.1).atan2(next.0 - prev.0).to_degrees();
+                    (
+                        prev.0 + (next.0 - prev.0) * t,
+                        prev.1 + (next.1 - prev.1) * t,
+                        direction,
+                    )
+                }
+                None => (points[idx].0, points[idx].1, 0.0),
+            };
+            (x, y, direction, *points.last()?)
+        };
+
+        if self.accumulate && sample.iteration > 0.0 {
+            x += end.0 * sample.iteration;
+            y += end.1 * sample.iteration;
+        }
+
+        let angle = match node.attribute("rotate").map(str::trim) {
+            Some("auto") => direction,
+            Some("auto-reverse") => direction + 180.0,
+            Some(angle) => f64::from_str(angle).unwrap_or(0.0),
+            None => 0.0,
+        };
+
+        Some((x, y, angle))
+    }
+
+    /// Returns `animateMotion` points from `values`, `from`, `to` or `by`.
+    fn motion_points(&self) -> Option<Vec<(f64, f64)>> {
+        let node = self.node;
+        let parse = |text: &str| -> Option<(f64, f64)> {
+            match parse_number_list(text, ',')?.as_slice() {
+                &[x, y] => Some((x, y)),
+                _ => None,
+            }
+        };
+
+        if let Some(values) = node.attribute("values") {
+            return values
+                .split(';')
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(parse)
+                .collect::<Option<Vec<_>>>()
+                .filter(|points| !points.is_empty());
+        }
+
+        let from = node.attribute("from").map(parse);
+        let to = node.attribute("to").map(parse);
+        let by = node.attribute("by").map(parse);
+        match (from, to, by) {
+            (Some(from), Some(to), _) => Some(vec![from?, to?]),
+            (from, None, Some(by)) => {
+                let from = from.unwrap_or(Some((0.0, 0.0)))?;
+                let by = by?;
+                Some(vec![from, (from.0 + by.0, from.1 + by.1)])
+            }
+            (None, Some(to), None) => Some(vec![(0.0, 0.0), to?]),
+            _ => None,
+        }
+    }
+
+    /// Checks that `keyTimes` and `keySplines` match the number of values.
+    fn is_timing_valid(&self, calc_mode: CalcMode, count: usize, opt: &Options) -> bool {
+        let mut is_valid = true;
+        if let Some(key_times) = self
+            .key_times
+            .as_ref()
+            .filter(|_| calc_mode != CalcMode::Paced)
+        {
+            is_valid &= key_times.len() == count
+                && key_times.first() == Some(&0.0)
+                && key_times.windows(2).all(|w| w[0] <= w[1])
+                && key_times.iter().all(|t| (0.0..=1.0).contains(t));
+            if calc_mode != CalcMode::Discrete {
+                is_valid &= key_times.last() == Some(&1.0);
+            }
+        }
+
+        if calc_mode == CalcMode::Spline {
+            is_valid &= self.key_splines.len() + 1 == count;
+        }
+
+        if !is_valid {
+            warn(
+                self.node,
+                DiagnosticCode::InvalidAttribute,
+                "Animation keyTimes or keySplines don't match its values. Skipped.".to_string(),
+                opt,
+            );
+        }
+
+        is_valid
+    }
+
+    /// Returns a value index and a progress between it and the next one.
+    fn segment(
+        &self,
+        calc_mode: CalcMode,
+        progress: f64,
+        count: usize,
+        distances: Option<&[f64]>,
+    ) -> (usize, f64) {
+        if count < 2 {
+            return (0, 0.0);
+        }
+
+        let last = count - 1;
+        match (calc_mode, distances) {
+            (CalcMode::Discrete, _) => {
+                let idx = match &self.key_times {
+                    Some(key_times) => key_times.iter().rposition(|t| *t <= progress).unwrap_or(0),
+                    None => (progress * count as f64) as usize,
+                };
+                (idx.min(last), 0.0)
+            }
+            (CalcMode::Paced, Some(distances)) => {
+                let total = distances[last];
+                if total <= 0.0 {
+                    return (0, 0.0);
+                }
+
+                let distance = progress * total;
+                let idx = distances[1..]
+                    .iter()
+                    .position(|d| distance <= *d)
+                    .unwrap_or(last - 1);
+                let len = distances[idx + 1] - distances[idx];
+                let t = if len > 0.0 {
+                    (distance - distances[idx]) / len
+                } else {
+                    0.0
+                };
+                (idx, t.clamp(0.0, 1.0))
+            }
+            _ => {
+                let default_times: Vec<f64>;
+                let key_times = match &self.key_times {
+                    Some(key_times) if calc_mode != CalcMode::Paced => key_times.as_slice(),
+                    _ => {
+                        default_times = (0..count).map(|i| i as f64 / last as f64).collect();
+                        &default_times
+                    }
+                };
+
+                let idx = key_times[1..]
+                    .iter()
+                    .position(|t| progress < *t)
+                    .unwrap_or(last - 1);
+                let span = key_times[idx + 1] - key_times[idx];
+                let mut t = if span > 0.0 {
+                    ((progress - key_times[idx]) / span).clamp(0.0, 1.0)
+                } else {
+                    1.0
+                };
+                if calc_mode == CalcMode::Spline {
+                    t = cubic_bezier(self.key_splines[idx], t);
+                }
+                (idx, t)
+            }
+        }
+    }
+}
+
+impl Value {
+    fn combine(&self, other: &Value, f: impl Fn(f64, f64) -> f64) -> Option<Value> {
+        match (self, other) {
+            (Value::Numbers(a, text_a), Value::Numbers(b, text_b)) if text_a == text_b => {
+                Some(Value::Numbers(
+                    a.iter().zip(b).map(|(a, b)| f(*a, *b)).collect(),
+                    text_a.clone(),
+                ))
+            }
+            (Value::Color(a), Value::Color(b)) => {
+                Some(Value::Color(std::array::from_fn(|i| f(a[i], b[i]))))
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn interpolate(&self, to: &Value, t: f64) -> Option<Value> {
+        if self == to {
+            return Some(self.clone());
+        }
+
+        self.combine(to, |a, b| a + (b - a) * t)
+    }
+
+    fn add(&self, other: &Value) -> Option<Value> {
+        self.combine(other, |a, b| a + b)
+    }
+
+    fn scale(&self, factor: f64) -> Option<Value> {
+        self.combine(self, |a, _| a * factor)
+    }
+
+    fn distance(&self, to: &Value) -> Option<f64> {
+        match (self, to) {
+            (Value::Numbers(a, text_a), Value::Numbers(b, text_b)) if text_a == text_b => Some(
+                a.iter()
+                    .zip(b)
+                    .map(|(a, b)| (b - a) * (b - a))
+                    .sum::<f64>()
+                    .sqrt(),
+            ),
+            (Value::Color(a), Value::Color(b)) => Some(
+                (0..3)
+                    .map(|i| (b[i] - a[i]) * (b[i] - a[i]))
+                    .sum::<f64>()
+                    .sqrt(),
+            ),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Numbers(numbers, text) => {
+                let mut value = text[0].clone();
+                for (number, text) in numbers.iter().zip(&text[1..]) {
+                    // Keep adjacent numbers separated, like in `10-5`.
+                    if *number >= 0.0 && value.ends_with(|c: char| c.is_ascii_digit() || c == '.') {
+                        value.push(' ');
+                    }
+                    value.push_str(&fmt_number(*number));
+                    value.push_str(text);
+                }
+                f.write_str(&value)
+            }
+            Value::Color(c) => {
+                let channel = |n: f64| n.round().clamp(0.0, 255.0);
+                let alpha = c[3].clamp(0.0, 1.0);
+                if alpha >= 1.0 {
+                    write!(
+                        f,
+                        "rgb({}, {}, {})",
+                        channel(c[0]),
+                        channel(c[1]),
+                        channel(c[2])
+                    )
+                } else {
+                    write!(
+                        f,
+                        "rgba({}, {}, {}, {})",
+                        channel(c[0]),
+                        channel(c[1]),
+                        channel(c[2]),
+                        fmt_number(alpha)
+                    )
+                }
+            }
+            Value::Other(text) => f.write_str(text),
+        }
+    }
+}
+
+/// Returns the initial value of commonly animated attributes and properties.
+///
+/// Used when an animated attribute is not set.
+pub(crate) fn initial_value(aid: AId) -> Option<&'static str> {
+    match aid {
+        AId::Transform | AId::StrokeDasharray => Some("none"),
+        AId::Opacity | AId::FillOpacity | AId::StrokeOpacity | AId::StrokeWidth => Some("1"),
+        AId::StopOpacity | AId::FloodOpacity => Some("1"),
+        AId::StrokeDashoffset => Some("0"),
+        AId::X | AId::Y | AId::Cx | AId::Cy | AId::X1 | AId::Y1 | AId::X2 | AId::Y2 => Some("0"),
+        AId::Dx | AId::Dy => Some("0"),
+        AId::Fill | AId::StopColor | AId::FloodColor => Some("black"),
+        AId::Stroke => Some("none"),
+        _ => None,
+    }
+}
+
+/// Parses an animation value.
+///
+/// Lengths in absolute units are converted to user units, so they can be interpolated
+/// with unitless numbers.
+pub(crate) fn parse_value(text: &str, aid: AId, dpi: f32) -> Value {
+    let text = text.trim();
+    // Lists can be separated by commas and spaces.
+    let normalized;
+    let text = if aid == AId::StrokeDasharray {
+        normalized = parse_list_items(text).join(" ");
+        normalized.as_str()
+    } else {
+        text
+    };
+    if matches!(
+        aid,
+        AId::Fill
+            | AId::Stroke
+            | AId::StopColor
+            | AId::FloodColor
+            | AId::LightingColor
+            | AId::Color
+    ) {
+        if let Ok(c) = svgtypes::Color::from_str(text) {
+            return Value::Color([
+                c.red as f64,
+                c.green as f64,
+                c.blue as f64,
+                c.alpha as f64 / 255.0,
+            ]);
+        }
+    }
+
+    // Arc flags cannot be interpolated.
+    if aid == AId::D && text.contains(['a', 'A']) {
+        return Value::Other(text.to_string());
+    }
+
+    // Numbers inside identifiers, like in `url(#grad1)`, are kept as is.
+    // Except path data, where letters are commands.
+    let allow_after_letter = aid == AId::D;
+
+    let bytes = text.as_bytes();
+    let mut numbers = Vec::new();
+    let mut parts = Vec::new();
+    let mut part_start = 0;
+    let mut idx = 0;
+    while idx < bytes.len() {
+        let is_identifier_start =
+            bytes[idx].is_ascii_alphabetic() || matches!(bytes[idx], b'#' | b'_');
+        if is_identifier_start && !allow_after_letter {
+            idx += 1;
+            while idx < bytes.len()
+                && (bytes[idx].is_ascii_alphanumeric() || matches!(bytes[idx], b'-' | b'_'))
+            {
+                idx += 1;
+            }
+            continue;
+        }
+
+        let number = number_len(&bytes[idx..])
+            .and_then(|len| Some((len, f64::from_str(&text[idx..idx + len]).ok()?)));
+        match number {
+            Some((len, mut number)) => {
+                parts.push(normalize_separators(&text[part_start..idx], aid));
+                idx += len;
+                if aid != AId::D {
+                    if let Some(scale) = unit_scale(&bytes[idx..], dpi as f64) {
+                        number *= scale;
+                        idx += 2;
+                    }
+                }
+                numbers.push(number);
+                part_start = idx;
+            }
+            None => idx += 1,
+        }
+    }
+    parts.push(normalize_separators(&text[part_start..], aid));
+
+    Value::Numbers(numbers, parts)
+}
+
+/// Returns a scale to user units of an absolute length unit at the start of the text.
+fn unit_scale(bytes: &[u8], dpi: f64) -> Option<f64> {
+    let scale = match bytes.get(..2)? {
+        b"px" => 1.0,
+        b"in" => dpi,
+        b"cm" => dpi / 2.54,
+        b"mm" => dpi / 25.4,
+        b"pt" => dpi / 72.0,
+        b"pc" => dpi / 6.0,
+        _ => return None,
+    };
+
+    // Not a part of an identifier.
+    match bytes.get(2) {
+        Some(c) if c.is_ascii_alphanumeric() || matches!(c, b'-' | b'_' | b'%') => None,
+        _ => Some(scale),
+    }
+}
+
+/// Makes values that differ only in separators, like `0,0` and `0 0`, comparable.
+///
+/// Adjacent numbers are separated again when a value is formatted.
+fn normalize_separators(part: &str, aid: AId) -> String {
+    let is_separator = |c: char| c.is_ascii_whitespace() || c == ',';
+    if aid == AId::D {
+        // Path commands are single letters, so separators are never required.
+        part.chars().filter(|c| !is_separator(*c)).collect()
+    } else if !part.is_empty() && part.chars().all(is_separator) {
+        " ".to_string()
+    } else {
+        part.to_string()
+    }
+}
+
+/// Returns a length of a number at the start of the text.
+fn number_len(bytes: &[u8]) -> Option<usize> {
+    let digits = |start: usize| {
+        bytes[start.min(bytes.len())..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .count()
+    };
+
+    let mut len = 0;
+    if matches!(bytes.first(), Some(b'+' | b'-')) {
+        len += 1;
+    }
+
+    let integer = digits(len);
+    len += integer;
+
+    let mut fraction = 0;
+    if bytes.get(len) == Some(&b'.') {
+        fraction = digits(len + 1);
+        if fraction > 0 || integer > 0 {
+            len += 1 + fraction;
+        }
+    }
+
+    if integer == 0 && fraction == 0 {
+        return None;
+    }
+
+    if matches!(bytes.get(len), Some(b'e' | b'E')) {
+        let sign = matches!(bytes.get(len + 1), Some(b'+' | b'-')) as usize;
+        let exponent = digits(len + 1 + sign);
+        if exponent > 0 {
+            len += 1 + sign + exponent;
+        }
+    }
+
+    Some(len)
+}
+
+/// Parses an `animateTransform` value, like `45 10 10` for `rotate`.
+fn transform_value(kind: &str, text: &str) -> Option<Value> {
+    let mut numbers = parse_number_list(text, ',')?;
+    match (kind, numbers.len()) {
+        ("translate", 1) => numbers.push(0.0),
+        ("scale", 1) => numbers.push(numbers[0]),
+        ("rotate", 1) => numbers.extend([0.0, 0.0]),
+        ("translate" | "scale", 2) | ("rotate", 3) | ("skewX" | "skewY", 1) => {}
+        _ => return None,
+    }
+
+    let mut parts = vec![format!("{}(", kind)];
+    parts.extend(std::iter::repeat_n(" ".to_string(), numbers.len() - 1));
+    parts.push(")".to_string());
+    Some(Value::Numbers(numbers, parts))
+}
+
+/// Returns a transform list that doesn't change anything, with the same functions as the specified one.
+pub(crate) fn identity_transform(value: &Value) -> Option<Value> {
+    let Value::Numbers(numbers, parts) = value else {
+        return None;
+    };
+
+    let mut function = "";
+    let mut arg = 0;
+    let mut identity = Vec::with_capacity(numbers.len());
+    for part in &parts[..numbers.len()] {
+        match part.rfind('(') {
+            Some(open) => {
+                function = part[..open]
+                    .rsplit(|c: char| c.is_ascii_whitespace() || c == ')' || c == ',')
+                    .next()
+                    .unwrap_or_default();
+                arg = 0;
+            }
+            None => arg += 1,
+        }
+
+        identity.push(match function {
+            "scale" => 1.0,
+            "matrix" => [1.0, 0.0, 0.0, 1.0, 0.0, 0.0]
+                .get(arg)
+                .copied()
+                .unwrap_or(0.0),
+            _ => 0.0,
+        });
+    }
+
+    Some(Value::Numbers(identity, parts.clone()))
+}
+
+/// A flattened motion path.
+struct Polyline {
+    start: (f64, f64),
+    segments: Vec<((f64, f64), (f64, f64))>,
+    /// Cumulative lengths of segments, starting with zero.
+    lengths: Vec<f64>,
+}
+
+impl Polyline {
+    fn from_path(text: &str) -> Option<Self> {
+        let mut segments = Vec::new();
+        let mut start = None;
+        let mut subpath_start = (0.0, 0.0);
+        let mut prev = (0.0, 0.0);
+        let line_to = |segments: &mut Vec<_>, from: (f64, f64), to: (f64, f64)| {
+            // Zero-length segments have no direction.
+            if from != to {
+                segments.push((from, to));
+            }
+        };
+
+        for segment in svgtypes::SimplifyingPathParser::from(text) {
+            let Ok(segment) = segment else {
+                break;
+            };
+
+            match segment {
+                svgtypes::SimplePathSegment::MoveTo { x, y } => {
+                    start.get_or_insert((x, y));
+                    subpath_start = (x, y);
+                    prev = (x, y);
+                }
+                svgtypes::SimplePathSegment::LineTo { x, y } => {
+                    line_to(&mut segments, prev, (x, y));
+                    prev = (x, y);
+                }
+                svgtypes::SimplePathSegment::CurveTo {
+                    x1,
+                    y1,
+                    x2,
+                    y2,
+                    x,
+                    y,
+                } => {
+                    let p0 = prev;
+                    for i in 1..=CURVE_STEPS {
+                        let t = i as f64 / CURVE_STEPS as f64;
+                        let mt = 1.0 - t;
+                        let point = |a: f64, b: f64, c: f64, d: f64| {
+                            mt * mt * mt * a
+                                + 3.0 * mt * mt * t * b
+                                + 3.0 * mt * t * t * c
+                                + t * t * t * d
+                        };
+                        let next = (point(p0.0, x1, x2, x), point(p0.1, y1, y2, y));
+                        line_to(&mut segments, prev, next);
+                        prev = next;
+                    }
+                }
+                svgtypes::SimplePathSegment::Quadratic { x1, y1, x, y } => {
+                    let p0 = prev;
+                    for i in 1..=CURVE_STEPS {
+                        let t = i as f64 / CURVE_STEPS as f64;
+                        let mt = 1.0 - t;
+                        let point =
+                            |a: f64, b: f64, c: f64| mt * mt * a + 2.0 * mt * t * b + t * t * c;
+                        let next = (point(p0.0, x1, x), point(p0.1, y1, y));
+                        line_to(&mut segments, prev, next);
+                        prev = next;
+                    }
+                }
+                svgtypes::SimplePathSegment::ClosePath => {
+                    line_to(&mut segments, prev, subpath_start);
+                    prev = subpath_start;
+                }
+            }
+        }
+
+        let lengths = cumulative(
+            segments
+                .iter()
+                .map(|(a, b)| Some((b.0 - a.0).hypot(b.1 - a.1))),
+        )?;
+        Some(Polyline {
+            start: start?,
+            segments,
+            lengths,
+        })
+    }
+
+    fn length(&self) -> f64 {
+        self.lengths.last().copied().unwrap_or(0.0)
+    }
+
+    /// Returns a point and a direction angle at the specified distance.
+    fn at(&self, distance: f64) -> (f64, f64, f64) {
+        if self.segments.is_empty() {
+            return (self.start.0, self.start.1, 0.0);
+        }
+
+        let idx = self.lengths[1..]
+            .iter()
+            .position(|len| distance <= *len)
+            .unwrap_or(self.segments.len() - 1);
+        let (from, to) = self.segments[idx];
+        let len = self.lengths[idx + 1] - self.lengths[idx];
+        let t = ((distance - self.lengths[idx]) / len).clamp(0.0, 1.0);
+        let direction = (to.1 - from.1).atan2(to.0 - from.0).to_degrees();
+        (
+            from.0 + (to.0 - from.0) * t,
+            from.1 + (to.1 - from.1) * t,
+            direction,
+        )
+    }
+}
+
+/// Converts distances into cumulative ones, starting with zero.
+fn cumulative(distances: impl Iterator<Item = Option<f64>>) -> Option<Vec<f64>> {
+    let mut total = 0.0;
+    let mut cumulative = vec![0.0];
+    for distance in distances {
+        total += distance?;
+        cumulative.push(total);
+    }
+
+    Some(cumulative)
+}
+
+pub(crate) fn cubic_bezier([x1, y1, x2, y2]: [f64; 4], x: f64) -> f64 {
+    let point = |p1: f64, p2: f64, t: f64| {
+        let mt = 1.0 - t;
+        3.0 * mt * mt * t * p1 + 3.0 * mt * t * t * p2 + t * t * t
+    };
+
+    // The x coordinate is monotonic, so the curve parameter can be found by bisection.
+    let (mut low, mut high) = (0.0, 1.0);
+    for _ in 0..32 {
+        let mid = (low + high) / 2.0;
+        if point(x1, x2, mid) < x {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    point(y1, y2, (low + high) / 2.0)
+}
+
+fn parse_key_splines(text: &str) -> Option<Vec<[f64; 4]>> {
+    text.split(';')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|spline| match parse_number_list(spline, ',')?.as_slice() {
+            &[x1, y1, x2, y2] if [x1, y1, x2, y2].iter().all(|n| (0.0..=1.0).contains(n)) => {
+                Some([x1, y1, x2, y2])
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn parse_list_items(text: &str) -> Vec<&str> {
+    text.split(|c: char| c.is_ascii_whitespace() || c == ',')
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Parses a list of numbers separated by whitespace and the specified separator.
+fn parse_number_list(text: &str, separator: char) -> Option<Vec<f64>> {
+    text.split(|c: char| c.is_ascii_whitespace() || c == separator)
+        .filter(|s| !s.is_empty())
+        .map(|s| f64::from_str(s).ok().filter(|n| n.is_finite()))
+        .collect()
+}
+
+fn parse_time_list(
+    node: roxmltree::Node,
+    text: &str,
+    indices: &HashMap<&str, usize>,
+    opt: &Options,
+) -> Vec<TimeValue> {
+    let mut values = Vec::new();
+    for value in text.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+        if value == "indefinite" {
+            continue;
+        }
+
+        match parse_time_value(value, indices) {
+            Some(value) => values.push(value),
+            None => warn(
+                node,
+                DiagnosticCode::Unsupported,
+                format!("Animation timing '{}' is not supported.", value),
+                opt,
+            ),
+        }
+    }
+
+    values
+}
+
+/// Parses an offset or a syncbase value, like `1s` or `other.end+1s`.
+fn parse_time_value(text: &str, indices: &HashMap<&str, usize>) -> Option<TimeValue> {
+    if let Some(offset) = parse_offset(text) {
+        return Some(TimeValue::Offset(offset));
+    }
+
+    for (suffix, is_begin) in [(".begin", true), (".end", false)] {
+        let Some(pos) = text.rfind(suffix) else {
+            continue;
+        };
+
+        let rest = text[pos + suffix.len()..].trim();
+        let offset = if rest.is_empty() {
+            0.0
+        } else {
+            parse_offset(rest)?
+        };
+        let idx = *indices.get(text[..pos].trim())?;
+        return Some(if is_begin {
+            TimeValue::SyncBegin(idx, offset)
+        } else {
+            TimeValue::SyncEnd(idx, offset)
+        });
+    }
+
+    None
+}
+
+fn parse_offset(text: &str) -> Option<f64> {
+    let text = text.trim();
+    let (sign, text) = match text.strip_prefix('-') {
+        Some(text) => (-1.0, text),
+        None => (1.0, text.strip_prefix('+').unwrap_or(text)),
+    };
+
+    Some(sign * parse_clock_value(text.trim_start())?)
+}
+
+/// Parses a SMIL clock value, like `1.5s`, `500ms` or `01:30`.
+fn parse_clock_value(text: &str) -> Option<f64> {
+    let text = text.trim();
+    if text.contains(':') {
+        let parts: Option<Vec<f64>> = text.split(':').map(|s| f64::from_str(s).ok()).collect();
+        let value = match *parts?.as_slice() {
+            [minutes, seconds] => minutes * 60.0 + seconds,
+            [hours, minutes, seconds] => hours * 3600.0 + minutes * 60.0 + seconds,
+            _ => return None,
+        };
+        return Some(value).filter(|v| v.is_finite() && *v >= 0.0);
+    }
+
+    let (number, scale) = if let Some(number) = text.strip_suffix("ms") {
+        (number, 0.001)
+    } else if let Some(number) = text.strip_suffix("min") {
+        (number, 60.0)
+    } else if let Some(number) = text.strip_suffix('h') {
+        (number, 3600.0)
+    } else if let Some(number) = text.strip_suffix('s') {
+        (number, 1.0)
+    } else {
+        (text, 1.0)
+    };
+
+    let number = f64::from_str(number).ok()?;
+    Some(number * scale).filter(|v| v.is_finite() && *v >= 0.0)
+}
+
+pub(crate) fn fmt_number(n: f64) -> String {
+    let n = (n * 1e6).round() / 1e6;
+    // Avoid `-0`.
+    if n == 0.0 {
+        "0".to_string()
+    } else {
+        n.to_string()
+    }
+}
+
+/// Returns an attribute value of the source element.
+fn base_value<'a>(node: roxmltree::Node<'a, '_>, aid: AId) -> Option<&'a str> {
+    node.attributes()
+        .find(|a| {
+            a.name() == aid.to_str()
+                && matches!(a.namespace(), None | Some(SVG_NS) | Some(XLINK_NS))
+        })
+        .map(|a| a.value())
+}
+
+fn href_value<'a>(node: roxmltree::Node<'a, '_>) -> Option<&'a str> {
+    // An unprefixed `href` takes precedence.
+    node.attributes()
+        .find(|a| a.name() == "href" && a.namespace().is_none())
+        .or_else(|| {
+            node.attributes()
+                .find(|a| a.name() == "href" && a.namespace() == Some(XLINK_NS))
+        })
+        .map(|a| a.value())
+}
+
+fn warn(node: roxmltree::Node, code: DiagnosticCode, message: String, opt: &Options) {
+    let diagnostic = Diagnostic::new(code, Severity::Warning, message)
+        .with_element_id(node.attribute("id").unwrap_or_default());
+    opt.diagnostics.report(diagnostic);
+}
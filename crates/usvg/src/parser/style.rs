@@ -6,7 +6,7 @@ use super::paint_server;
 use super::svgtree::{AId, FromValue, SvgNode};
 use crate::tree::ContextElement;
 use crate::{
-    ApproxEqUlps, Color, Fill, FillRule, LineCap, LineJoin, Opacity, Paint, Stroke,
+    ApproxEqUlps, Color, DiagnosticCode, Fill, FillRule, LineCap, LineJoin, Opacity, Paint, Stroke,
     StrokeMiterlimit, Units,
 };
 
@@ -28,6 +28,7 @@ impl<'a, 'input: 'a> FromValue<'a, 'input> for LineJoin {
             "miter-clip" => Some(LineJoin::MiterClip),
             "round" => Some(LineJoin::Round),
             "bevel" => Some(LineJoin::Bevel),
+            "arcs" => Some(LineJoin::Arcs),
             _ => None,
         }
     }
@@ -152,19 +153,25 @@ fn convert_paint(
     opacity: &mut Opacity,
     cache: &mut converter::Cache,
 ) -> Option<(Paint, Option<ContextElement>)> {
-    let paint = match svgtypes::Paint::from_str(value) {
-        Ok(v) => v,
-        Err(_) => {
+    let paint = match super::color::parse_paint(value) {
+        Some(v) => v,
+        None => {
             if aid == AId::Fill {
-                log::warn!(
-                    "Failed to parse fill value: '{}'. Fallback to black.",
-                    value
+                node.warn(
+                    DiagnosticCode::InvalidAttribute,
+                    format!(
+                        "Failed to parse fill value: '{}'. Fallback to black.",
+                        value
+                    ),
                 );
                 svgtypes::Paint::Color(svgtypes::Color::black())
             } else if aid == AId::Stroke {
-                log::warn!(
-                    "Failed to parse stroke value: '{}'. Fallback to no stroke.",
-                    value
+                node.warn(
+                    DiagnosticCode::InvalidAttribute,
+                    format!(
+                        "Failed to parse stroke value: '{}'. Fallback to no stroke.",
+                        value
+                    ),
                 );
                 return None;
             } else {
@@ -223,7 +230,10 @@ fn convert_paint(
                         None => from_fallback(node, fallback, opacity).map(|p| (p, None)),
                     }
                 } else {
-                    log::warn!("'{}' cannot be used to {} a shape.", tag_name, aid);
+                    node.warn(
+                        DiagnosticCode::InvalidReference,
+                        format!("'{}' cannot be used to {} a shape.", tag_name, aid),
+                    );
                     None
                 }
             } else {
@@ -0,0 +1,75 @@
+// Copyright 2026 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use super::Options;
+use crate::DiagnosticCode;
+
+/// A shorthand for [StyleSheetResolver]'s import function.
+pub type StyleSheetImportResolverFn<'a> =
+    Box<dyn Fn(&str, &Options) -> Option<String> + Send + Sync + 'a>;
+
+/// An `@import` rules resolver.
+///
+/// This type can be useful if you want to have an alternative `@import` handling
+/// to the default one. For example, you can forbid access to local files (which is allowed by default)
+/// or load style sheets from a network or an in-memory storage (usvg doesn't do any network requests).
+pub struct StyleSheetResolver<'a> {
+    /// Resolver function that will be used to load a style sheet referenced by an `@import` rule.
+    ///
+    /// A function would be called with a URL and parsing options and must return
+    /// the style sheet text.
+    ///
+    /// Relative URLs inside imported style sheets are already resolved against
+    /// the URL of the importing style sheet. URLs inside the SVG itself are passed as is.
+    pub resolve_import: StyleSheetImportResolverFn<'a>,
+}
+
+impl Default for StyleSheetResolver<'_> {
+    fn default() -> Self {
+        StyleSheetResolver {
+            resolve_import: StyleSheetResolver::default_import_resolver(),
+        }
+    }
+}
+
+impl StyleSheetResolver<'_> {
+    /// Creates a default import resolver.
+    ///
+    /// The default implementation treats an URL as a file path and tries to read it.
+    /// If an URL is not a path to an existing file it would be ignored.
+    ///
+    /// Paths have to be absolute or relative to the input SVG file or relative to
+    /// [Options::resources_dir](crate::Options::resources_dir).
+    ///
+    /// Follows [Options::resource_policy](crate::Options::resource_policy).
+    pub fn default_import_resolver() -> StyleSheetImportResolverFn<'static> {
+        Box::new(move |url: &str, opts: &Options| {
+            let path = opts.resolve_resource_path(url)?;
+
+            if path.is_file() {
+                match std::fs::read_to_string(&path) {
+                    Ok(text) => Some(text),
+                    Err(_) => {
+                        opts.diagnostics.error(
+                            DiagnosticCode::ResourceLoadingFailed,
+                            format!("Failed to load '{}'. Skipped.", url),
+                        );
+                        None
+                    }
+                }
+            } else {
+                opts.diagnostics.error(
+                    DiagnosticCode::ResourceLoadingFailed,
+                    format!("'{}' is not a path to a style sheet.", url),
+                );
+                None
+            }
+        })
+    }
+}
+
+impl std::fmt::Debug for StyleSheetResolver<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("StyleSheetResolver { .. }")
+    }
+}
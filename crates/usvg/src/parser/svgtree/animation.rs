@@ -0,0 +1,636 @@
+// Copyright 2026 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! CSS animations sampling.
+//!
+//! Transitions are never started in a static document, so they are ignored.
+
+use super::AId;
+use super::css::{Declaration, StyleSheet, split_top_level};
+use crate::parser::smil::{
+    Value, cubic_bezier, fmt_number, identity_transform, initial_value, parse_value,
+};
+
+const EASE: TimingFunction = TimingFunction::CubicBezier([0.25, 0.1, 0.25, 1.0]);
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum TimingFunction {
+    CubicBezier([f64; 4]),
+    Steps(u32, StepPosition),
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum StepPosition {
+    Start,
+    End,
+    None,
+    Both,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Direction {
+    Normal,
+    Reverse,
+    Alternate,
+    AlternateReverse,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum FillMode {
+    None,
+    Forwards,
+    Backwards,
+    Both,
+}
+
+/// An animation defined by the `animation-*` properties.
+#[derive(Clone, Copy, Debug)]
+struct Animation<'a> {
+    name: &'a str,
+    duration: f64,
+    timing_function: TimingFunction,
+    delay: f64,
+    iteration_count: f64,
+    direction: Direction,
+    fill_mode: FillMode,
+    paused: bool,
+}
+
+impl Default for Animation<'_> {
+    fn default() -> Self {
+        Animation {
+            name: "none",
+            duration: 0.0,
+            timing_function: EASE,
+            delay: 0.0,
+            iteration_count: 1.0,
+            direction: Direction::Normal,
+            fill_mode: FillMode::None,
+            paused: false,
+        }
+    }
+}
+
+/// Computed `animation-*` properties, one value per animation.
+///
+/// Lists shorter than the list of names are repeated.
+struct Properties<'a> {
+    names: Vec<&'a str>,
+    durations: Vec<f64>,
+    timing_functions: Vec<TimingFunction>,
+    delays: Vec<f64>,
+    iteration_counts: Vec<f64>,
+    directions: Vec<Direction>,
+    fill_modes: Vec<FillMode>,
+    play_states: Vec<bool>,
+}
+
+impl<'a> Properties<'a> {
+    fn from_animations(animations: &[Animation<'a>]) -> Self {
+        Properties {
+            names: animations.iter().map(|a| a.name).collect(),
+            durations: animations.iter().map(|a| a.duration).collect(),
+            timing_functions: animations.iter().map(|a| a.timing_function).collect(),
+            delays: animations.iter().map(|a| a.delay).collect(),
+            iteration_counts: animations.iter().map(|a| a.iteration_count).collect(),
+            directions: animations.iter().map(|a| a.direction).collect(),
+            fill_modes: animations.iter().map(|a| a.fill_mode).collect(),
+            play_states: animations.iter().map(|a| a.paused).collect(),
+        }
+    }
+
+    fn animations(&self) -> Vec<Animation<'a>> {
+        fn get<T: Copy>(list: &[T], idx: usize) -> T {
+            list[idx % list.len()]
+        }
+
+        (0..self.names.len())
+            .map(|idx| Animation {
+                name: self.names[idx],
+                duration: get(&self.durations, idx),
+                timing_function: get(&self.timing_functions, idx),
+                delay: get(&self.delays, idx),
+                iteration_count: get(&self.iteration_counts, idx),
+                direction: get(&self.directions, idx),
+                fill_mode: get(&self.fill_modes, idx),
+                paused: get(&self.play_states, idx),
+            })
+            .filter(|a| a.name != "none")
+            .collect()
+    }
+}
+
+/// Returns animated property values of an element at the specified time in seconds.
+///
+/// `declarations` must be sorted in the cascade order.
+/// `underlying` returns a property value without animations.
+/// `dpi` is used to convert lengths in absolute units.
+pub(crate) fn sample(
+    declarations: &[&Declaration],
+    style_sheet: &StyleSheet,
+    time: f32,
+    dpi: f32,
+    underlying: impl Fn(AId) -> Option<String>,
+) -> Vec<(AId, String)> {
+    let animations = collect_animations(declarations);
+
+    let mut values: Vec<(AId, String)> = Vec::new();
+    for animation in animations {
+        let Some(keyframes) = style_sheet.keyframes(animation.name) else {
+            continue;
+        };
+
+        let Some(progress) = animation.progress(time as f64) else {
+            continue;
+        };
+
+        let mut properties: Vec<(&str, AId)> = Vec::new();
+        for declaration in keyframes.keyframes.iter().flat_map(|k| &k.declarations) {
+            let Some(aid) = AId::from_str(declaration.name) else {
+                continue;
+            };
+
+            if (aid.is_presentation() || aid == AId::D)
+                && !properties.iter().any(|(name, _)| *name == declaration.name)
+            {
+                properties.push((declaration.name, aid));
+            }
+        }
+
+        for (name, aid) in properties {
+            let mut frames: Vec<(f64, Value, TimingFunction)> = Vec::new();
+            for keyframe in &keyframes.keyframes {
+                // The last declaration wins.
+                let find = |name: &str| keyframe.declarations.iter().rev().find(|d| d.name == name);
+                let Some(value) = find(name).and_then(|d| normalize(aid, d.value)) else {
+                    continue;
+                };
+
+                let timing_function = find("animation-timing-function")
+                    .and_then(|d| parse_timing_function(d.value))
+                    .unwrap_or(animation.timing_function);
+                frames.push((
+                    keyframe.offset,
+                    parse_value(&value, aid, dpi),
+                    timing_function,
+                ));
+            }
+
+            // Missing `from` and `to` keyframes use the underlying value.
+            let base = underlying(aid)
+                .or_else(|| initial_value(aid).map(str::to_string))
+                .and_then(|value| normalize(aid, &value))
+                .map(|value| parse_value(&value, aid, dpi));
+            if let Some(first) = frames.first().filter(|f| f.0 > 0.0) {
+                let value = base.clone().unwrap_or_else(|| first.1.clone());
+                frames.insert(0, (0.0, value, animation.timing_function));
+            }
+            if let Some(last) = frames.last().filter(|f| f.0 < 1.0) {
+                let value = base.clone().unwrap_or_else(|| last.1.clone());
+                frames.push((1.0, value, animation.timing_function));
+            }
+
+            if frames.len() < 2 {
+                continue;
+            }
+
+            let idx = frames
+                .iter()
+                .rposition(|f| f.0 <= progress)
+                .unwrap_or(0)
+                .min(frames.len() - 2);
+            let (from, to) = (&frames[idx], &frames[idx + 1]);
+            let span = to.0 - from.0;
+            let t = if span > 0.0 {
+                ((progress - from.0) / span).clamp(0.0, 1.0)
+            } else {
+                1.0
+            };
+            let t = from.2.apply(t);
+
+            let value = interpolate(aid, &from.1, &to.1, t)
+                // Values that cannot be interpolated flip in the middle.
+                .unwrap_or_else(|| {
+                    if t < 0.5 {
+                        from.1.clone()
+                    } else {
+                        to.1.clone()
+                    }
+                });
+
+            // Animations later in the list win.
+            let value = value.to_string();
+            match values.iter_mut().find(|(a, _)| *a == aid) {
+                Some(prev) => prev.1 = value,
+                None => values.push((aid, value)),
+            }
+        }
+    }
+
+    values
+}
+
+fn interpolate(aid: AId, from: &Value, to: &Value, t: f64) -> Option<Value> {
+    if aid == AId::Transform {
+        // `none` is interpolated as an identity transform of the same functions.
+        let is_none = |v: &Value| matches!(v, Value::Numbers(numbers, _) if numbers.is_empty());
+        if is_none(from) && !is_none(to) {
+            return identity_transform(to)?.interpolate(to, t);
+        } else if is_none(to) && !is_none(from) {
+            return from.interpolate(&identity_transform(from)?, t);
+        }
+    }
+
+    from.interpolate(to, t)
+}
+
+/// Converts a CSS property value into an attribute value.
+fn normalize(aid: AId, value: &str) -> Option<String> {
+    let value = value.trim();
+    match aid {
+        AId::Transform => css_transform(value),
+        AId::D => {
+            let data = value.strip_prefix("path(")?.strip_suffix(')')?.trim();
+            let quote = data.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+            Some(data.strip_prefix(quote)?.strip_suffix(quote)?.to_string())
+        }
+        _ => Some(value.to_string()),
+    }
+}
+
+/// Converts a CSS transform, like `rotate(45deg) translateX(10px)`, into the SVG syntax.
+///
+/// Functions with a single argument are expanded, so they could be interpolated
+/// with their two arguments forms.
+fn css_transform(text: &str) -> Option<String> {
+    if text == "none" {
+        return Some(String::new());
+    }
+
+    let mut functions = Vec::new();
+    let mut rest = text.trim();
+    while !rest.is_empty() {
+        let (function, tail) = rest.split_once(')')?;
+        let (name, args) = function.split_once('(')?;
+        rest = tail.trim_start();
+
+        let args: Vec<&str> = args
+            .split(|c: char| c.is_ascii_whitespace() || c == ',')
+            .filter(|s| !s.is_empty())
+            .collect();
+        let lengths = || {
+            args.iter()
+                .map(|a| parse_length(a))
+                .collect::<Option<Vec<_>>>()
+        };
+        let numbers = || {
+            args.iter()
+                .map(|a| a.parse().ok())
+                .collect::<Option<Vec<f64>>>()
+        };
+        let angle = || parse_angle(args.first()?);
+
+        let (name, values) = match (name.trim(), args.len()) {
+            ("translate", 1) => ("translate", vec![lengths()?[0], 0.0]),
+            ("translate", 2) => ("translate", lengths()?),
+            ("translateX", 1) => ("translate", vec![lengths()?[0], 0.0]),
+            ("translateY", 1) => ("translate", vec![0.0, lengths()?[0]]),
+            ("scale", 1) => ("scale", vec![numbers()?[0]; 2]),
+            ("scale", 2) => ("scale", numbers()?),
+            ("scaleX", 1) => ("scale", vec![numbers()?[0], 1.0]),
+            ("scaleY", 1) => ("scale", vec![1.0, numbers()?[0]]),
+            ("rotate", 1) => ("rotate", vec![angle()?]),
+            ("rotate", 3) => ("rotate", numbers()?),
+            ("skewX", 1) => ("skewX", vec![angle()?]),
+            ("skewY", 1) => ("skewY", vec![angle()?]),
+            ("matrix", 6) => ("matrix", numbers()?),
+            _ => return None,
+        };
+
+        let values: Vec<_> = values.into_iter().map(fmt_number).collect();
+        functions.push(format!("{}({})", name, values.join(" ")));
+    }
+
+    Some(functions.join(" "))
+}
+
+fn parse_length(text: &str) -> Option<f64> {
+    text.strip_suffix("px").unwrap_or(text).parse().ok()
+}
+
+/// Parses an angle in degrees.
+fn parse_angle(text: &str) -> Option<f64> {
+    let (number, scale) = if let Some(number) = text.strip_suffix("deg") {
+        (number, 1.0)
+    } else if let Some(number) = text.strip_suffix("grad") {
+        (number, 0.9)
+    } else if let Some(number) = text.strip_suffix("rad") {
+        (number, 180.0 / std::f64::consts::PI)
+    } else if let Some(number) = text.strip_suffix("turn") {
+        (number, 360.0)
+    } else {
+        (text, 1.0)
+    };
+
+    number.parse::<f64>().ok().map(|n| n * scale)
+}
+
+fn collect_animations<'a>(declarations: &[&Declaration<'a>]) -> Vec<Animation<'a>> {
+    let mut properties = Properties::from_animations(&[Animation::default()]);
+
+    // Declarations are sorted in the cascade order, so the last one wins.
+    // Invalid declarations are ignored.
+    for declaration in declarations {
+        let value = declaration.value;
+        match declaration.name {
+            "animation" => {
+                let animations: Option<Vec<_>> =
+                    split_top_level(value, b',').map(parse_shorthand).collect();
+                if let Some(animations) = animations {
+                    properties = Properties::from_animations(&animations);
+                }
+            }
+            "animation-name" => {
+                if let Some(names) = parse_list(value, |v| Some(unquote(v))) {
+                    properties.names = names;
+                }
+            }
+            "animation-duration" => {
+                if let Some(list) = parse_list(value, |v| parse_time(v).filter(|t| *t >= 0.0)) {
+                    properties.durations = list;
+                }
+            }
+            "animation-timing-function" => {
+                if let Some(list) = parse_list(value, parse_timing_function) {
+                    properties.timing_functions = list;
+                }
+            }
+            "animation-delay" => {
+                if let Some(list) = parse_list(value, parse_time) {
+                    properties.delays = list;
+                }
+            }
+            "animation-iteration-count" => {
+                if let Some(list) = parse_list(value, parse_iteration_count) {
+                    properties.iteration_counts = list;
+                }
+            }
+            "animation-direction" => {
+                if let Some(list) = parse_list(value, parse_direction) {
+                    properties.directions = list;
+                }
+            }
+            "animation-fill-mode" => {
+                if let Some(list) = parse_list(value, parse_fill_mode) {
+                    properties.fill_modes = list;
+                }
+            }
+            "animation-play-state" => {
+                if let Some(list) = parse_list(value, parse_play_state) {
+                    properties.play_states = list;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    properties.animations()
+}
+
+/// Parses a single animation of the `animation` shorthand, like `spin 2s linear infinite`.
+fn parse_shorthand(text: &str) -> Option<Animation<'_>> {
+    let mut animation = Animation::default();
+    let mut times = 0;
+    let mut has_timing_function = false;
+    let mut has_iteration_count = false;
+    let mut has_direction = false;
+    let mut has_fill_mode = false;
+    let mut has_play_state = false;
+    let mut has_name = false;
+
+    for token in tokens(text) {
+        if let Some(time) = parse_time(token) {
+            // The first time is a duration and the second one is a delay.
+            match times {
+                0 if time >= 0.0 => animation.duration = time,
+                1 => animation.delay = time,
+                _ => return None,
+            }
+            times += 1;
+        } else if let Some(f) = parse_timing_function(token).filter(|_| !has_timing_function) {
+            animation.timing_function = f;
+            has_timing_function = true;
+        } else if let Some(n) = parse_iteration_count(token).filter(|_| !has_iteration_count) {
+            animation.iteration_count = n;
+            has_iteration_count = true;
+        } else if let Some(d) = parse_direction(token).filter(|_| !has_direction) {
+            animation.direction = d;
+            has_direction = true;
+        } else if let Some(f) = parse_fill_mode(token).filter(|_| !has_fill_mode) {
+            animation.fill_mode = f;
+            has_fill_mode = true;
+        } else if let Some(p) = parse_play_state(token).filter(|_| !has_play_state) {
+            animation.paused = p;
+            has_play_state = true;
+        } else if !has_name {
+            animation.name = unquote(token);
+            has_name = true;
+        } else {
+            return None;
+        }
+    }
+
+    Some(animation)
+}
+
+/// Splits text by whitespace that is not inside brackets.
+fn tokens(text: &str) -> impl Iterator<Item = &str> {
+    let mut depth = 0;
+    text.split(move |c: char| {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+        depth == 0 && c.is_ascii_whitespace()
+    })
+    .filter(|s| !s.is_empty())
+}
+
+fn parse_list<'a, T>(text: &'a str, f: impl Fn(&'a str) -> Option<T>) -> Option<Vec<T>> {
+    split_top_level(text, b',').map(|v| f(v.trim())).collect()
+}
+
+fn unquote(text: &str) -> &str {
+    text.strip_prefix(['"', '\''])
+        .and_then(|t| t.strip_suffix(['"', '\'']))
+        .unwrap_or(text)
+}
+
+/// Parses a CSS time, like `1s` or `200ms`.
+fn parse_time(text: &str) -> Option<f64> {
+    let (number, scale) = if let Some(number) = text.strip_suffix("ms") {
+        (number, 0.001)
+    } else {
+        (text.strip_suffix('s')?, 1.0)
+    };
+
+    let n: f64 = number.parse().ok()?;
+    Some(n * scale).filter(|n| n.is_finite())
+}
+
+fn parse_timing_function(text: &str) -> Option<TimingFunction> {
+    let f = match text.trim() {
+        "linear" => TimingFunction::CubicBezier([0.0, 0.0, 1.0, 1.0]),
+        "ease" => EASE,
+        "ease-in" => TimingFunction::CubicBezier([0.42, 0.0, 1.0, 1.0]),
+        "ease-out" => TimingFunction::CubicBezier([0.0, 0.0, 0.58, 1.0]),
+        "ease-in-out" => TimingFunction::CubicBezier([0.42, 0.0, 0.58, 1.0]),
+        "step-start" => TimingFunction::Steps(1, StepPosition::Start),
+        "step-end" => TimingFunction::Steps(1, StepPosition::End),
+        text => {
+            let (name, args) = text.strip_suffix(')')?.split_once('(')?;
+            let args: Vec<&str> = args.split(',').map(str::trim).collect();
+            match (name.trim(), args.as_slice()) {
+                ("cubic-bezier", &[x1, y1, x2, y2]) => {
+                    let n = |s: &str| s.parse::<f64>().ok().filter(|n| n.is_finite());
+                    let (x1, y1, x2, y2) = (n(x1)?, n(y1)?, n(x2)?, n(y2)?);
+                    if !(0.0..=1.0).contains(&x1) || !(0.0..=1.0).contains(&x2) {
+                        return None;
+                    }
+                    TimingFunction::CubicBezier([x1, y1, x2, y2])
+                }
+                ("steps", [count, position @ ..]) if position.len() <= 1 => {
+                    let position = match position.first().copied() {
+                        None | Some("end" | "jump-end") => StepPosition::End,
+                        Some("start" | "jump-start") => StepPosition::Start,
+                        Some("jump-none") => StepPosition::None,
+                        Some("jump-both") => StepPosition::Both,
+                        Some(_) => return None,
+                    };
+                    let count: u32 = count.parse().ok()?;
+                    let min_count = if position == StepPosition::None { 2 } else { 1 };
+                    if count < min_count {
+                        return None;
+                    }
+                    TimingFunction::Steps(count, position)
+                }
+                _ => return None,
+            }
+        }
+    };
+
+    Some(f)
+}
+
+fn parse_iteration_count(text: &str) -> Option<f64> {
+    match text {
+        "infinite" => Some(f64::INFINITY),
+        _ => text
+            .parse::<f64>()
+            .ok()
+            .filter(|n| *n >= 0.0 && n.is_finite()),
+    }
+}
+
+fn parse_direction(text: &str) -> Option<Direction> {
+    match text {
+        "normal" => Some(Direction::Normal),
+        "reverse" => Some(Direction::Reverse),
+        "alternate" => Some(Direction::Alternate),
+        "alternate-reverse" => Some(Direction::AlternateReverse),
+        _ => None,
+    }
+}
+
+fn parse_fill_mode(text: &str) -> Option<FillMode> {
+    match text {
+        "none" => Some(FillMode::None),
+        "forwards" => Some(FillMode::Forwards),
+        "backwards" => Some(FillMode::Backwards),
+        "both" => Some(FillMode::Both),
+        _ => None,
+    }
+}
+
+/// Returns `true` for `paused`.
+fn parse_play_state(text: &str) -> Option<bool> {
+    match text {
+        "running" => Some(false),
+        "paused" => Some(true),
+        _ => None,
+    }
+}
+
+impl TimingFunction {
+    fn apply(self, t: f64) -> f64 {
+        match self {
+            // Linear curves are not solved, to avoid rounding errors.
+            TimingFunction::CubicBezier([x1, y1, x2, y2]) if x1 == y1 && x2 == y2 => t,
+            TimingFunction::CubicBezier(points) => cubic_bezier(points, t),
+            TimingFunction::Steps(count, position) => {
+                let count = count as f64;
+                let mut step = (t * count).floor();
+                if matches!(position, StepPosition::Start | StepPosition::Both) {
+                    step += 1.0;
+                }
+
+                let jumps = match position {
+                    StepPosition::None => count - 1.0,
+                    StepPosition::Both => count + 1.0,
+                    _ => count,
+                };
+                step.clamp(0.0, jumps) / jumps
+            }
+        }
+    }
+}
+
+impl Animation<'_> {
+    /// Returns a directed progress of the current iteration,
+    /// or `None` when the animation has no effect.
+    fn progress(&self, time: f64) -> Option<f64> {
+        // A paused animation never advances.
+        let time = if self.paused { 0.0 } else { time };
+        let local = time - self.delay;
+        let active_duration = if self.duration > 0.0 {
+            self.duration * self.iteration_count
+        } else {
+            0.0
+        };
+
+        let (iteration, progress) = if local < 0.0 {
+            if !matches!(self.fill_mode, FillMode::Backwards | FillMode::Both) {
+                return None;
+            }
+
+            (0.0, 0.0)
+        } else if local >= active_duration {
+            if !matches!(self.fill_mode, FillMode::Forwards | FillMode::Both) {
+                return None;
+            }
+
+            // The last iteration ends with the last keyframe, unless it's a partial one.
+            let iteration = self.iteration_count.floor();
+            let fraction = self.iteration_count - iteration;
+            if fraction == 0.0 && iteration > 0.0 {
+                (iteration - 1.0, 1.0)
+            } else {
+                (iteration, fraction)
+            }
+        } else {
+            let iteration = (local / self.duration).floor();
+            (iteration, local / self.duration - iteration)
+        };
+
+        let is_odd = iteration % 2.0 == 1.0;
+        let reversed = match self.direction {
+            Direction::Normal => false,
+            Direction::Reverse => true,
+            Direction::Alternate => is_odd,
+            Direction::AlternateReverse => !is_odd,
+        };
+
+        Some(if reversed { 1.0 - progress } else { progress })
+    }
+}
@@ -0,0 +1,664 @@
+// Copyright 2026 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Style sheets and declaration blocks parsing.
+
+use std::collections::{HashMap, HashSet};
+
+use super::selector::{Selector, Specificity};
+use crate::{DiagnosticCode, Options};
+
+/// A list of rules in the source order.
+#[derive(Default, Debug)]
+pub(crate) struct StyleSheet<'a> {
+    pub rules: Vec<Rule<'a>>,
+    /// `@keyframes` rules in the source order.
+    pub keyframes: Vec<Keyframes<'a>>,
+}
+
+impl<'a> StyleSheet<'a> {
+    /// Returns keyframes by name.
+    ///
+    /// When there are multiple rules with the same name, the last one is used.
+    pub(crate) fn keyframes(&self, name: &str) -> Option<&Keyframes<'a>> {
+        self.keyframes.iter().rev().find(|k| k.name == name)
+    }
+}
+
+/// A style rule with a single complex selector.
+#[derive(Debug)]
+pub(crate) struct Rule<'a> {
+    pub selector: Selector,
+    pub declarations: Vec<Declaration<'a>>,
+    pub origin: Origin,
+}
+
+/// A `@keyframes` rule.
+#[derive(Debug)]
+pub(crate) struct Keyframes<'a> {
+    pub name: &'a str,
+    /// Keyframes sorted by offset.
+    pub keyframes: Vec<Keyframe<'a>>,
+}
+
+/// A keyframe, like `50% { opacity: 0 }`.
+#[derive(Debug)]
+pub(crate) struct Keyframe<'a> {
+    /// An offset in the 0..1 range.
+    pub offset: f64,
+    pub declarations: Vec<Declaration<'a>>,
+}
+
+/// A property declaration, like `fill: red`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) struct Declaration<'a> {
+    pub name: &'a str,
+    pub value: &'a str,
+    pub important: bool,
+}
+
+/// A style sheet origin.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Origin {
+    /// `Options::style_sheet`.
+    User,
+    /// Style sheets and `style` attributes inside the document.
+    Author,
+}
+
+/// A declaration precedence in the cascade.
+///
+/// Declarations with a greater precedence override the ones with a lower precedence.
+/// Declarations with the same precedence are ordered by their source order.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub(crate) struct Precedence {
+    /// Origin and importance.
+    ///
+    /// Important declarations override normal ones and, unlike normal ones,
+    /// user important declarations override author important ones.
+    level: u8,
+    /// Declarations from a `style` attribute override the ones from style sheets.
+    is_inline: bool,
+    specificity: Specificity,
+}
+
+impl Precedence {
+    pub(crate) fn new(origin: Origin, important: bool, specificity: Specificity) -> Self {
+        let level = match (origin, important) {
+            (Origin::User, false) => 0,
+            (Origin::Author, false) => 1,
+            (Origin::Author, true) => 2,
+            (Origin::User, true) => 3,
+        };
+
+        Precedence {
+            level,
+            is_inline: false,
+            specificity,
+        }
+    }
+
+    /// Returns a precedence of a declaration inside a `style` attribute.
+    pub(crate) fn inline(important: bool) -> Self {
+        Precedence {
+            is_inline: true,
+            ..Precedence::new(Origin::Author, important, [0; 3])
+        }
+    }
+}
+
+/// Style sheets loaded by `@import` rules, by their resolved URLs.
+pub(crate) type ImportedStyleSheets = HashMap<String, String>;
+
+/// The maximum nesting level of `@import` rules.
+const MAX_IMPORT_DEPTH: usize = 16;
+
+/// The maximum number of style sheets loaded by `@import` rules per document.
+const MAX_IMPORTS: usize = 256;
+
+/// An `@import` rule.
+struct Import<'a> {
+    /// A URL resolved against the importing style sheet URL.
+    url: String,
+    media: &'a str,
+}
+
+/// Loads all style sheets imported by `sources`, recursively.
+///
+/// Imports are loaded beforehand, so the parsed rules could reference the imported text.
+/// Each URL is loaded at most once per document.
+pub(crate) fn load_imports(sources: &[(&str, Origin)], opt: &Options) -> ImportedStyleSheets {
+    let mut imported = ImportedStyleSheets::new();
+    let mut visited = HashSet::new();
+    for (text, _) in sources {
+        load_imports_impl(
+            text,
+            None,
+            opt,
+            &mut Vec::new(),
+            &mut visited,
+            &mut imported,
+        );
+    }
+
+    imported
+}
+
+fn load_imports_impl(
+    text: &str,
+    base_url: Option<&str>,
+    opt: &Options,
+    stack: &mut Vec<String>,
+    visited: &mut HashSet<String>,
+    imported: &mut ImportedStyleSheets,
+) {
+    let (imports, _) = parse_imports(text, base_url);
+    for import in imports.into_iter().flatten() {
+        if !opt.media.matches(import.media) {
+            continue;
+        }
+
+        if stack.contains(&import.url) {
+            opt.diagnostics.warn(
+                DiagnosticCode::InvalidStyleSheet,
+                format!("'{}' style sheet imports itself. Skipped.", import.url),
+            );
+            continue;
+        }
+
+        if stack.len() >= MAX_IMPORT_DEPTH {
+            opt.diagnostics.warn(
+                DiagnosticCode::InvalidStyleSheet,
+                format!(
+                    "Style sheet imports are nested too deep. '{}' skipped.",
+                    import.url
+                ),
+            );
+            continue;
+        }
+
+        // Duplicated imports are applied only once.
+        if visited.contains(&import.url) {
+            continue;
+        }
+
+        if visited.len() >= MAX_IMPORTS {
+            opt.diagnostics.warn(
+                DiagnosticCode::InvalidStyleSheet,
+                format!("Too many style sheet imports. '{}' skipped.", import.url),
+            );
+            continue;
+        }
+
+        visited.insert(import.url.clone());
+        let Some(text) = (opt.style_sheet_resolver.resolve_import)(&import.url, opt) else {
+            continue;
+        };
+        imported.insert(import.url.clone(), text.clone());
+
+        stack.push(import.url.clone());
+        load_imports_impl(&text, Some(&import.url), opt, stack, visited, imported);
+        stack.pop();
+    }
+}
+
+/// Parses style sheets in the order they are provided.
+///
+/// `imported` must contain style sheets loaded by [`load_imports`] for the same `sources`.
+pub(crate) fn parse_style_sheets<'a>(
+    sources: &[(&'a str, Origin)],
+    imported: &'a ImportedStyleSheets,
+    opt: &Options,
+) -> StyleSheet<'a> {
+    let mut parser = Parser {
+        opt,
+        imported,
+        stack: Vec::new(),
+        parsed: HashSet::new(),
+        sheet: StyleSheet::default(),
+    };
+
+    for (text, origin) in sources {
+        parser.parse(text, None, *origin);
+    }
+
+    parser.sheet
+}
+
+struct Parser<'a, 'o> {
+    opt: &'o Options<'o>,
+    imported: &'a ImportedStyleSheets,
+    stack: Vec<&'a str>,
+    /// Imported style sheets that were already parsed.
+    parsed: HashSet<&'a str>,
+    sheet: StyleSheet<'a>,
+}
+
+impl<'a> Parser<'a, '_> {
+    /// Parses a style sheet and appends its rules to the resulting sheet.
+    ///
+    /// Imported rules are added before the rules of the importing style sheet,
+    /// so they have a lower precedence at the same specificity.
+    fn parse(&mut self, text: &'a str, base_url: Option<&str>, origin: Origin) {
+        let (imports, end) = parse_imports(text, base_url);
+        for import in imports {
+            let Some(import) = import else {
+                self.warn("Failed to parse an @import rule. Skipped.");
+                continue;
+            };
+
+            if !self.opt.media.matches(import.media) {
+                continue;
+            }
+
+            // Missing style sheets were already reported by `load_imports`.
+            let Some((url, text)) = self.imported.get_key_value(&import.url) else {
+                continue;
+            };
+
+            if self.stack.contains(&url.as_str())
+                || self.stack.len() >= MAX_IMPORT_DEPTH
+                || !self.parsed.insert(url)
+            {
+                continue;
+            }
+
+            self.stack.push(url);
+            self.parse(text, Some(url), origin);
+            self.stack.pop();
+        }
+
+        self.parse_rules(&text[end..], origin);
+    }
+
+    /// Parses style rules.
+    ///
+    /// Rules inside `@media` blocks are added only when they match the media context.
+    fn parse_rules(&mut self, text: &'a str, origin: Origin) {
+        let mut pos = 0;
+        loop {
+            pos = skip_spaces_and_comments(text, pos);
+            if pos >= text.len() {
+                break;
+            }
+
+            let Some(end) = find_top_level(text, pos, |c| c == b'{' || c == b';') else {
+                self.warn(format!(
+                    "Failed to parse a style sheet. {} bytes were left.",
+                    text.len() - pos
+                ));
+                break;
+            };
+            let prelude = text[pos..end].trim();
+
+            if text.as_bytes()[end] == b';' {
+                // Only at-rules like `@import` can end with a semicolon.
+                match at_rule_name(prelude) {
+                    Some(name) if name.eq_ignore_ascii_case("import") => {
+                        self.warn("@import rules must precede all other rules. Skipped.");
+                    }
+                    Some(name) => self.warn_unsupported(name),
+                    None => {}
+                }
+                pos = end + 1;
+                continue;
+            }
+
+            let block_start = end + 1;
+            let block_end = find_top_level(text, block_start, |c| c == b'}').unwrap_or(text.len());
+            let block = &text[block_start..block_end];
+            pos = (block_end + 1).min(text.len());
+
+            if let Some(name) = at_rule_name(prelude) {
+                if name.eq_ignore_ascii_case("media") {
+                    if self.opt.media.matches(&prelude[1 + name.len()..]) {
+                        self.parse_rules(block, origin);
+                    }
+                } else if name.eq_ignore_ascii_case("keyframes")
+                    || name.eq_ignore_ascii_case("-webkit-keyframes")
+                {
+                    let keyframes_name = prelude[1 + name.len()..].trim();
+                    let keyframes_name =
+                        parse_string(keyframes_name).map_or(keyframes_name, |(name, _)| name);
+                    let keyframes = self.parse_keyframes(block);
+                    self.sheet.keyframes.push(Keyframes {
+                        name: keyframes_name,
+                        keyframes,
+                    });
+                } else {
+                    self.warn_unsupported(name);
+                }
+
+                continue;
+            }
+
+            let declarations = parse_declarations(block);
+            if declarations.is_empty() {
+                continue;
+            }
+
+            for selector in split_top_level(prelude, b',') {
+                match Selector::parse(selector.trim(), &self.opt.diagnostics) {
+                    Some(selector) => self.sheet.rules.push(Rule {
+                        selector,
+                        declarations: declarations.clone(),
+                        origin,
+                    }),
+                    None => self.warn(format!(
+                        "Failed to parse a '{}' selector. Skipped.",
+                        selector.trim()
+                    )),
+                }
+            }
+        }
+    }
+
+    /// Parses a `@keyframes` rule block.
+    fn parse_keyframes(&self, text: &'a str) -> Vec<Keyframe<'a>> {
+        let mut keyframes = Vec::new();
+        let mut pos = 0;
+        loop {
+            pos = skip_spaces_and_comments(text, pos);
+            if pos >= text.len() {
+                break;
+            }
+
+            let Some(end) = find_top_level(text, pos, |c| c == b'{') else {
+                self.warn("Failed to parse a @keyframes rule. Skipped.");
+                break;
+            };
+            let selectors = &text[pos..end];
+
+            let block_start = end + 1;
+            let block_end = find_top_level(text, block_start, |c| c == b'}').unwrap_or(text.len());
+            // Important declarations inside keyframes are ignored.
+            let declarations: Vec<_> = parse_declarations(&text[block_start..block_end])
+                .into_iter()
+                .filter(|d| !d.important)
+                .collect();
+            pos = (block_end + 1).min(text.len());
+
+            for selector in split_top_level(selectors, b',') {
+                match parse_keyframe_selector(selector.trim()) {
+                    Some(offset) => keyframes.push(Keyframe {
+                        offset,
+                        declarations: declarations.clone(),
+                    }),
+                    None => self.warn(format!(
+                        "Failed to parse a '{}' keyframe selector. Skipped.",
+                        selector.trim()
+                    )),
+                }
+            }
+        }
+
+        // The sort is stable, therefore keyframes with the same offset preserve the source order.
+        keyframes.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+        keyframes
+    }
+
+    fn warn(&self, message: impl Into<String>) {
+        self.opt
+            .diagnostics
+            .warn(DiagnosticCode::InvalidStyleSheet, message);
+    }
+
+    fn warn_unsupported(&self, at_rule: &str) {
+        self.opt.diagnostics.warn(
+            DiagnosticCode::Unsupported,
+            format!("The @{} rule is not supported. Skipped.", at_rule),
+        );
+    }
+}
+
+/// Parses a keyframe selector, like `from` or `50%`, into an offset.
+fn parse_keyframe_selector(text: &str) -> Option<f64> {
+    if text.eq_ignore_ascii_case("from") {
+        return Some(0.0);
+    } else if text.eq_ignore_ascii_case("to") {
+        return Some(1.0);
+    }
+
+    let percent: f64 = text.strip_suffix('%')?.trim().parse().ok()?;
+    (0.0..=100.0).contains(&percent).then_some(percent / 100.0)
+}
+
+/// Parses `@import` rules at the start of a style sheet.
+///
+/// Returns the rules, where `None` indicates an invalid one,
+/// and the position right after the last one.
+fn parse_imports<'a>(text: &'a str, base_url: Option<&str>) -> (Vec<Option<Import<'a>>>, usize) {
+    let mut imports = Vec::new();
+    let mut pos = 0;
+    loop {
+        let start = skip_spaces_and_comments(text, pos);
+        if start >= text.len() {
+            break;
+        }
+
+        // The last statement is allowed to end without a semicolon.
+        let end = find_top_level(text, start, |c| c == b'{' || c == b';').unwrap_or(text.len());
+        if text.as_bytes().get(end) == Some(&b'{') {
+            break;
+        }
+
+        let prelude = text[start..end].trim();
+        match at_rule_name(prelude) {
+            // `@charset` is allowed before `@import`, but has no effect.
+            Some(name) if name.eq_ignore_ascii_case("charset") => {}
+            Some(name) if name.eq_ignore_ascii_case("import") => {
+                imports.push(parse_import(&prelude[1 + name.len()..], base_url));
+            }
+            _ => break,
+        }
+
+        pos = (end + 1).min(text.len());
+    }
+
+    (imports, pos)
+}
+
+/// Parses an `@import` rule prelude, like `url("theme.css") screen`.
+fn parse_import<'a>(text: &'a str, base_url: Option<&str>) -> Option<Import<'a>> {
+    let text = text.trim_start();
+    let (url, media) = if text.len() > 4 && text.as_bytes()[..4].eq_ignore_ascii_case(b"url(") {
+        let end = find_top_level(text, 4, |c| c == b')')?;
+        let url = text[4..end].trim();
+        let url = match parse_string(url) {
+            Some((url, rest)) if rest.trim().is_empty() => url,
+            Some(_) => return None,
+            None => url,
+        };
+        (url, &text[end + 1..])
+    } else {
+        parse_string(text)?
+    };
+
+    if url.is_empty() {
+        return None;
+    }
+
+    Some(Import {
+        url: resolve_url(base_url, url),
+        media: media.trim(),
+    })
+}
+
+/// Parses a quoted string and returns its content and the remaining text.
+fn parse_string(text: &str) -> Option<(&str, &str)> {
+    let quote = text.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let end = text[1..].find(quote)? + 1;
+    Some((&text[1..end], &text[end + 1..]))
+}
+
+/// Resolves a relative URL against the URL of a style sheet that contains it.
+fn resolve_url(base_url: Option<&str>, url: &str) -> String {
+    let is_absolute = url.starts_with(['/', '\\'])
+        || std::path::Path::new(url).is_absolute()
+        || url.split_once(':').is_some_and(|(scheme, _)| {
+            // Skip Windows drive letters.
+            scheme.len() > 1
+                && scheme
+                    .bytes()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, b'+' | b'-' | b'.'))
+        });
+
+    match base_url.and_then(|base| base.rfind(['/', '\\']).map(|idx| &base[..=idx])) {
+        Some(base_dir) if !is_absolute => format!("{}{}", base_dir, url),
+        _ => url.to_string(),
+    }
+}
+
+/// Splits a declaration block, like a `style` attribute, into declarations.
+///
+/// Invalid declarations are skipped.
+pub(crate) fn parse_declarations(text: &str) -> Vec<Declaration<'_>> {
+    let mut declarations = Vec::new();
+    for declaration in split_top_level(text, b';') {
+        let Some(colon) = find_top_level(declaration, 0, |c| c == b':') else {
+            continue;
+        };
+
+        let name_start = skip_spaces_and_comments(declaration, 0);
+        let name = declaration[name_start.min(colon)..colon].trim();
+        // https://snook.ca/archives/html_and_css/targetting_ie7
+        let name = name.strip_prefix('*').unwrap_or(name);
+        if name.is_empty()
+            || !name
+                .bytes()
+                .all(|c| c.is_ascii_alphanumeric() || c == b'-' || c == b'_')
+        {
+            continue;
+        }
+
+        let mut value = trim_comments(&declaration[colon + 1..]);
+        let mut important = false;
+        if let Some(idx) = value.rfind('!') {
+            if value[idx + 1..].trim().eq_ignore_ascii_case("important") {
+                important = true;
+                value = value[..idx].trim_end();
+            }
+        }
+
+        if value.is_empty() {
+            continue;
+        }
+
+        declarations.push(Declaration {
+            name,
+            value,
+            important,
+        });
+    }
+
+    declarations
+}
+
+fn at_rule_name(prelude: &str) -> Option<&str> {
+    let rest = prelude.strip_prefix('@')?;
+    let end = rest
+        .find(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_'))
+        .unwrap_or(rest.len());
+    Some(&rest[..end])
+}
+
+/// Splits text by a separator that is not inside a string, a comment or brackets.
+pub(crate) fn split_top_level(text: &str, separator: u8) -> impl Iterator<Item = &str> {
+    let mut pos = 0;
+    let mut done = false;
+    std::iter::from_fn(move || {
+        if done {
+            return None;
+        }
+
+        match find_top_level(text, pos, |c| c == separator) {
+            Some(end) => {
+                let part = &text[pos..end];
+                pos = end + 1;
+                Some(part)
+            }
+            None => {
+                done = true;
+                Some(&text[pos..])
+            }
+        }
+    })
+}
+
+/// Finds the first byte matching `predicate` that is not inside a string, a comment or brackets.
+///
+/// Closing brackets without a matching opening one are treated as top-level,
+/// so `}` can be searched for too.
+fn find_top_level(text: &str, start: usize, predicate: impl Fn(u8) -> bool) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let mut depth = 0usize;
+    let mut pos = start;
+    while pos < bytes.len() {
+        let c = bytes[pos];
+        if depth == 0 && predicate(c) {
+            return Some(pos);
+        }
+
+        match c {
+            b'"' | b'\'' => {
+                pos += 1;
+                while pos < bytes.len() && bytes[pos] != c {
+                    if bytes[pos] == b'\\' {
+                        pos += 1;
+                    }
+                    pos += 1;
+                }
+            }
+            b'/' if bytes.get(pos + 1) == Some(&b'*') => {
+                pos = text[pos + 2..]
+                    .find("*/")
+                    .map(|idx| pos + 2 + idx + 1)
+                    .unwrap_or(bytes.len());
+            }
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+
+        pos += 1;
+    }
+
+    None
+}
+
+fn skip_spaces_and_comments(text: &str, mut pos: usize) -> usize {
+    loop {
+        let rest = &text[pos..];
+        let trimmed = rest.trim_start();
+        pos += rest.len() - trimmed.len();
+
+        if let Some(comment) = trimmed.strip_prefix("/*") {
+            pos += 2 + comment
+                .find("*/")
+                .map(|idx| idx + 2)
+                .unwrap_or(comment.len());
+        } else if trimmed.starts_with("<!--") || trimmed.starts_with("-->") {
+            // Legacy HTML comment markers are allowed at the top level of a style sheet.
+            pos += if trimmed.starts_with("<!--") { 4 } else { 3 };
+        } else {
+            return pos;
+        }
+    }
+}
+
+/// Trims spaces and leading and trailing comments.
+fn trim_comments(mut text: &str) -> &str {
+    loop {
+        text = text.trim();
+        if let Some(rest) = text.strip_prefix("/*") {
+            text = rest.find("*/").map(|idx| &rest[idx + 2..]).unwrap_or("");
+        } else if text.ends_with("*/") {
+            match text[..text.len() - 2].rfind("/*") {
+                Some(idx) => text = &text[..idx],
+                None => return text,
+            }
+        } else {
+            return text;
+        }
+    }
+}
@@ -0,0 +1,190 @@
+// Copyright 2026 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! CSS custom properties and `var()` substitution.
+//!
+//! https://www.w3.org/TR/css-variables-1/
+
+use std::collections::{HashMap, HashSet};
+
+/// Computed custom properties of an element.
+pub(crate) type CustomProperties = HashMap<String, String>;
+
+/// Substituted values longer than this are treated as invalid.
+///
+/// Prevents exponential growth caused by properties that reference other properties multiple times.
+const MAX_VALUE_LEN: usize = 1 << 16;
+
+pub(crate) fn is_custom_property(name: &str) -> bool {
+    name.len() > 2 && name.starts_with("--")
+}
+
+/// Computes element's custom properties from inherited ones and the declared ones.
+///
+/// `declared` must contain only cascaded values, i.e. a single value per property.
+pub(crate) fn compute(inherited: &CustomProperties, declared: &[(&str, &str)]) -> CustomProperties {
+    let mut resolver = Resolver {
+        inherited,
+        declared,
+        resolved: HashMap::new(),
+        stack: Vec::new(),
+        cyclic: HashSet::new(),
+    };
+
+    let mut computed = inherited.clone();
+    for (name, _) in declared {
+        match resolver.resolve(name) {
+            Some(value) => computed.insert(name.to_string(), value),
+            None => computed.remove(*name),
+        };
+    }
+
+    computed
+}
+
+struct Resolver<'a> {
+    inherited: &'a CustomProperties,
+    declared: &'a [(&'a str, &'a str)],
+    resolved: HashMap<&'a str, Option<String>>,
+    stack: Vec<&'a str>,
+    cyclic: HashSet<&'a str>,
+}
+
+impl<'a> Resolver<'a> {
+    fn resolve(&mut self, name: &str) -> Option<String> {
+        let Some(&(name, value)) = self.declared.iter().find(|(n, _)| *n == name) else {
+            return self.inherited.get(name).cloned();
+        };
+
+        if let Some(value) = self.resolved.get(name) {
+            return value.clone();
+        }
+
+        if let Some(idx) = self.stack.iter().position(|n| *n == name) {
+            // All properties in a dependency cycle are invalid.
+            self.cyclic.extend(self.stack[idx..].iter().copied());
+            return None;
+        }
+
+        let result = match value.trim() {
+            "initial" => None,
+            "inherit" | "unset" => self.inherited.get(name).cloned(),
+            _ => {
+                self.stack.push(name);
+                let result = substitute(value, &mut |n| self.resolve(n));
+                self.stack.pop();
+                result
+            }
+        };
+
+        let result = result.filter(|_| !self.cyclic.contains(name));
+        self.resolved.insert(name, result.clone());
+        result
+    }
+}
+
+/// Checks that the value contains a `var()` function.
+pub(crate) fn has_var(value: &str) -> bool {
+    find_var(value, 0).is_some()
+}
+
+/// Replaces all `var()` functions in a value.
+///
+/// Returns `None` when a referenced property is not defined and there is no fallback,
+/// in which case the whole value is invalid.
+pub(crate) fn substitute(
+    value: &str,
+    lookup: &mut dyn FnMut(&str) -> Option<String>,
+) -> Option<String> {
+    let mut result = String::new();
+    let mut pos = 0;
+    while let Some(start) = find_var(value, pos) {
+        result.push_str(&value[pos..start]);
+
+        let args_start = start + "var(".len();
+        let args_end = find_closing_paren(value, args_start)?;
+        let args = &value[args_start..args_end];
+        let (name, fallback) = match args.split_once(',') {
+            Some((name, fallback)) => (name.trim(), Some(fallback)),
+            None => (args.trim(), None),
+        };
+
+        if !is_custom_property(name) {
+            return None;
+        }
+
+        match lookup(name) {
+            Some(v) => result.push_str(&v),
+            None => result.push_str(&substitute(fallback?, lookup)?),
+        }
+
+        if result.len() > MAX_VALUE_LEN {
+            return None;
+        }
+
+        pos = args_end + 1;
+    }
+
+    result.push_str(&value[pos..]);
+    Some(result.trim().to_string())
+}
+
+/// Finds the start of a `var(` function that is not inside a string.
+fn find_var(value: &str, start: usize) -> Option<usize> {
+    let bytes = value.as_bytes();
+    let mut pos = start;
+    while pos < bytes.len() {
+        match bytes[pos] {
+            c @ (b'"' | b'\'') => pos = skip_string(bytes, pos, c),
+            b'(' if pos >= 3 && bytes[pos - 3..pos].eq_ignore_ascii_case(b"var") => {
+                let is_start = pos == 3 || {
+                    let prev = bytes[pos - 4];
+                    !(prev.is_ascii_alphanumeric() || prev == b'-' || prev == b'_')
+                };
+
+                if is_start {
+                    return Some(pos - 3);
+                }
+            }
+            _ => {}
+        }
+
+        pos += 1;
+    }
+
+    None
+}
+
+/// Returns the index of a closing parenthesis,
+/// assuming that the opening one is right before `start`.
+fn find_closing_paren(value: &str, start: usize) -> Option<usize> {
+    let bytes = value.as_bytes();
+    let mut depth = 0usize;
+    let mut pos = start;
+    while pos < bytes.len() {
+        match bytes[pos] {
+            c @ (b'"' | b'\'') => pos = skip_string(bytes, pos, c),
+            b'(' => depth += 1,
+            b')' if depth == 0 => return Some(pos),
+            b')' => depth -= 1,
+            _ => {}
+        }
+
+        pos += 1;
+    }
+
+    None
+}
+
+/// Returns the index of a closing quote.
+fn skip_string(bytes: &[u8], start: usize, quote: u8) -> usize {
+    let mut pos = start + 1;
+    while pos < bytes.len() && bytes[pos] != quote {
+        if bytes[pos] == b'\\' {
+            pos += 1;
+        }
+        pos += 1;
+    }
+
+    pos
+}
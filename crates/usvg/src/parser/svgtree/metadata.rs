@@ -0,0 +1,143 @@
+// Copyright 2026 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Collects non-rendering data of elements, like titles and `data-*` attributes.
+
+use super::{SVG_NS, XML_NAMESPACE_NS};
+use crate::{Metadata, MetadataAttribute, MetadataOptions, XmlElement, XmlNode};
+
+/// Collects element's metadata according to the options.
+///
+/// Returns `None` when there is nothing to preserve.
+pub(crate) fn collect(xml_node: roxmltree::Node, opt: &MetadataOptions) -> Option<Metadata> {
+    let mut metadata = Metadata::default();
+
+    if opt.title_and_desc {
+        metadata.title = find_child(xml_node, "title").map(text_content);
+        metadata.description = find_child(xml_node, "desc").map(text_content);
+    }
+
+    if opt.metadata_elements {
+        metadata.metadata = find_child(xml_node, "metadata").map(|node| {
+            let mut element = to_xml_element(node);
+            // Namespaces are usually declared on the root element,
+            // so we have to redeclare them to make the element self-contained.
+            for ns in node.namespaces() {
+                if let Some(prefix) = ns.name() {
+                    if ns.uri() != XML_NAMESPACE_NS {
+                        element
+                            .attributes
+                            .push((format!("xmlns:{}", prefix), ns.uri().to_string()));
+                    }
+                }
+            }
+            element
+        });
+    }
+
+    for attr in xml_node.attributes() {
+        match attr.namespace() {
+            None => {
+                if opt.classes && attr.name() == "class" {
+                    metadata.classes = attr
+                        .value()
+                        .split_ascii_whitespace()
+                        .map(|s| s.to_string())
+                        .collect();
+                } else if opt.data_attributes && attr.name().starts_with("data-") {
+                    metadata.attributes.push(MetadataAttribute {
+                        namespace: None,
+                        prefix: None,
+                        name: attr.name().to_string(),
+                        value: attr.value().to_string(),
+                    });
+                }
+            }
+            Some(ns) => {
+                if opt.attribute_namespaces.iter().any(|n| n == ns) {
+                    metadata.attributes.push(MetadataAttribute {
+                        namespace: Some(ns.to_string()),
+                        prefix: xml_node.lookup_prefix(ns).map(|s| s.to_string()),
+                        name: attr.name().to_string(),
+                        value: attr.value().to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    if metadata.is_empty() {
+        None
+    } else {
+        Some(metadata)
+    }
+}
+
+fn find_child<'a, 'input>(
+    xml_node: roxmltree::Node<'a, 'input>,
+    name: &str,
+) -> Option<roxmltree::Node<'a, 'input>> {
+    xml_node.children().find(|n| n.has_tag_name((SVG_NS, name)))
+}
+
+fn text_content(xml_node: roxmltree::Node) -> String {
+    let text: String = xml_node
+        .descendants()
+        .filter_map(|n| n.text().filter(|_| n.is_text()))
+        .collect();
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn to_xml_element(xml_node: roxmltree::Node) -> XmlElement {
+    let tag_name = xml_node.tag_name();
+    let mut attributes = Vec::new();
+
+    let name = match tag_name.namespace() {
+        Some(SVG_NS) | None => tag_name.name().to_string(),
+        Some(ns) => match xml_node.lookup_prefix(ns) {
+            Some(prefix) => format!("{}:{}", prefix, tag_name.name()),
+            None => {
+                // An element in a non-SVG default namespace.
+                attributes.push(("xmlns".to_string(), ns.to_string()));
+                tag_name.name().to_string()
+            }
+        },
+    };
+
+    for attr in xml_node.attributes() {
+        let name = match attr.namespace() {
+            Some(XML_NAMESPACE_NS) => format!("xml:{}", attr.name()),
+            Some(ns) => match xml_node.lookup_prefix(ns) {
+                Some(prefix) => format!("{}:{}", prefix, attr.name()),
+                None => attr.name().to_string(),
+            },
+            None => attr.name().to_string(),
+        };
+        attributes.push((name, attr.value().to_string()));
+    }
+
+    let children = xml_node
+        .children()
+        .filter_map(|child| {
+            if child.is_element() {
+                Some(XmlNode::Element(to_xml_element(child)))
+            } else if child.is_text() {
+                let text = child.text()?;
+                // Whitespaces between elements are not preserved.
+                if text.trim().is_empty() {
+                    None
+                } else {
+                    Some(XmlNode::Text(text.to_string()))
+                }
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    XmlElement {
+        name,
+        attributes,
+        children,
+    }
+}
@@ -1,21 +1,30 @@
 // Copyright 2021 the Resvg Authors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use std::cell::OnceCell;
 use std::collections::HashMap;
 use std::num::NonZeroU32;
 use std::str::FromStr;
 
 #[rustfmt::skip] mod names;
+mod animation;
+mod css;
+mod custom_properties;
+mod metadata;
 mod parse;
+mod selector;
 mod text;
 
 use tiny_skia_path::Transform;
 
+use crate::parser::fragment::Fragment;
 use crate::{
-    BlendMode, ImageRendering, Opacity, ShapeRendering, SpreadMethod, TextRendering, Units,
+    BlendMode, Diagnostic, DiagnosticCode, DiagnosticSink, ImageRendering, Limits, Metadata,
+    MetadataOptions, Opacity, ShapeRendering, SourceSpan, SpreadMethod, TextRendering, Units,
     Visibility,
 };
 pub use names::{AId, EId};
+pub(crate) use parse::{SVG_NS, XLINK_NS, XML_NAMESPACE_NS};
 
 /// An SVG tree container.
 ///
@@ -25,6 +34,39 @@ pub struct Document<'input> {
     nodes: Vec<NodeData>,
     attrs: Vec<Attribute<'input>>,
     links: HashMap<String, NodeId>,
+    /// Computed custom properties of elements that declare them.
+    ///
+    /// Used only during parsing.
+    custom_properties: HashMap<NodeId, custom_properties::CustomProperties>,
+    /// Non-rendering data of elements. Collected only when requested.
+    metadata: HashMap<NodeId, Metadata>,
+    /// Standalone XML of `foreignObject` elements, including their content.
+    foreign_objects: HashMap<NodeId, String>,
+    metadata_options: &'input MetadataOptions,
+    /// The source XML. Used to resolve elements position.
+    input: &'input str,
+    /// Byte offsets of lines in the source XML. Computed on demand.
+    line_starts: OnceCell<Vec<usize>>,
+    diagnostics: &'input DiagnosticSink<'input>,
+    limits: Limits,
+    /// The current nesting depth of `use` elements.
+    ///
+    /// Used only during parsing.
+    use_depth: u32,
+    /// A resolved [`Options::fragment`](crate::Options::fragment).
+    fragment: Option<Fragment>,
+    /// Animated attributes of the source XML elements.
+    ///
+    /// Used only during parsing.
+    animated_values: crate::parser::smil::AnimatedValues,
+    /// [`Options::animation_time`](crate::Options::animation_time).
+    ///
+    /// Used only during parsing.
+    animation_time: Option<f32>,
+    /// [`Options::dpi`](crate::Options::dpi).
+    ///
+    /// Used only during parsing.
+    dpi: f32,
 }
 
 impl<'input> Document<'input> {
@@ -53,6 +95,12 @@ impl<'input> Document<'input> {
         self.root().descendants()
     }
 
+    /// Returns a fragment identifier that selects a part of the document to render.
+    #[inline]
+    pub(crate) fn fragment(&self) -> Option<&Fragment> {
+        self.fragment.as_ref()
+    }
+
     /// Returns an element by ID.
     ///
     /// Unlike the [`Descendants`] iterator, this is just a HashMap lookup.
@@ -71,6 +119,31 @@ impl<'input> Document<'input> {
             doc: self,
         }
     }
+
+    /// Reports a warning about an element of the source XML.
+    fn warn_xml(&self, node: roxmltree::Node, code: DiagnosticCode, message: impl Into<String>) {
+        let mut diagnostic = Diagnostic::new(code, crate::Severity::Warning, message)
+            .with_element_id(node.attribute("id").unwrap_or_default());
+        if self.is_source_node(node) {
+            diagnostic.position = Some(self.text_pos_at(node.range().start));
+        }
+        self.diagnostics.report(diagnostic);
+    }
+
+    /// Checks that a node belongs to the source XML and not to an external document.
+    fn is_source_node(&self, node: roxmltree::Node) -> bool {
+        std::ptr::eq(node.document().input_text(), self.input)
+    }
+
+    /// Returns custom properties inherited by the children of the specified node.
+    fn inherited_custom_properties(
+        &self,
+        id: NodeId,
+    ) -> Option<&custom_properties::CustomProperties> {
+        self.get(id)
+            .ancestors()
+            .find_map(|n| self.custom_properties.get(&n.id))
+    }
 }
 
 impl std::fmt::Debug for Document<'_> {
@@ -149,7 +222,7 @@ impl ShortRange {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub(crate) struct NodeId(NonZeroU32);
 
 impl NodeId {
@@ -186,6 +259,8 @@ pub(crate) enum NodeKind {
     Element {
         tag_name: EId,
         attributes: ShortRange,
+        /// A byte range of the element in the source XML.
+        source_range: ShortRange,
     },
     Text(String),
 }
@@ -275,6 +350,52 @@ impl<'a, 'input: 'a> SvgNode<'a, 'input> {
         self.attribute(AId::Id).unwrap_or("")
     }
 
+    /// Returns element's position in the source XML.
+    pub fn text_pos(&self) -> Option<roxmltree::TextPos> {
+        match self.d.kind {
+            NodeKind::Element { source_range, .. } => {
+                Some(self.doc.text_pos_at(source_range.start as usize))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns element's non-rendering data, like a title or classes.
+    pub fn metadata(&self) -> Option<&'a Metadata> {
+        self.doc.metadata.get(&self.id)
+    }
+
+    /// Returns `foreignObject` element's XML, including its content.
+    ///
+    /// Namespaces declared by ancestors are declared on the element itself,
+    /// so it can be parsed as a standalone document.
+    pub fn foreign_object_xml(&self) -> Option<&'a str> {
+        self.doc.foreign_objects.get(&self.id).map(String::as_str)
+    }
+
+    /// Returns element's location in the source XML.
+    ///
+    /// Elements copied from external documents have no location.
+    pub fn source_span(&self) -> Option<SourceSpan> {
+        match self.d.kind {
+            NodeKind::Element { source_range, .. } if source_range.start != source_range.end => {
+                Some(SourceSpan {
+                    range: source_range.to_urange(),
+                    position: self.doc.text_pos_at(source_range.start as usize),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Reports a warning about this element.
+    pub(crate) fn warn(&self, code: DiagnosticCode, message: impl Into<String>) {
+        let mut diagnostic = Diagnostic::new(code, crate::Severity::Warning, message)
+            .with_element_id(self.element_id());
+        diagnostic.position = self.text_pos();
+        self.doc.diagnostics.report(diagnostic);
+    }
+
     /// Returns an attribute value.
     pub fn attribute<T: FromValue<'a, 'input>>(&self, aid: AId) -> Option<T> {
         let value = self
@@ -302,8 +423,10 @@ impl<'a, 'input: 'a> SvgNode<'a, 'input> {
         match T::parse(*self, aid, value) {
             Some(v) => Some(v),
             None => {
-                // TODO: show position in XML
-                log::warn!("Failed to parse {} value: '{}'.", aid, value);
+                self.warn(
+                    DiagnosticCode::InvalidAttribute,
+                    format!("Failed to parse {} value: '{}'.", aid, value),
+                );
                 None
             }
         }
@@ -631,9 +754,13 @@ impl<'a, 'input: 'a> Iterator for HrefIter<'a, 'input> {
 
         if let Some(link) = self.doc.get(self.curr).node_attribute(AId::Href) {
             if link.id() == self.curr || link.id() == self.origin {
-                log::warn!(
-                    "Element '#{}' cannot reference itself via 'xlink:href'.",
-                    self.doc.get(self.origin).element_id()
+                let origin = self.doc.get(self.origin);
+                origin.warn(
+                    DiagnosticCode::InvalidReference,
+                    format!(
+                        "Element '#{}' cannot reference itself via 'xlink:href'.",
+                        origin.element_id()
+                    ),
                 );
                 self.is_finished = true;
                 return None;
@@ -724,6 +851,14 @@ impl AId {
                 | AId::MarkerMid
                 | AId::MarkerStart
                 | AId::Mask
+                | AId::MaskClip // technically not presentation
+                | AId::MaskComposite // technically not presentation
+                | AId::MaskImage // technically not presentation
+                | AId::MaskMode // technically not presentation
+                | AId::MaskOrigin // technically not presentation
+                | AId::MaskPosition // technically not presentation
+                | AId::MaskRepeat // technically not presentation
+                | AId::MaskSize // technically not presentation
                 | AId::MaskType
                 | AId::MixBlendMode // technically not presentation
                 | AId::Opacity
@@ -772,6 +907,7 @@ impl AId {
                 | AId::ClipPath
                 | AId::ClipRule
                 | AId::Color
+                | AId::ColorInterpolation
                 | AId::ColorInterpolationFilters
                 | AId::Direction
                 | AId::Display
@@ -832,6 +968,14 @@ fn is_non_inheritable(id: AId) -> bool {
             | AId::FloodColor
             | AId::FloodOpacity
             | AId::Mask
+            | AId::MaskClip
+            | AId::MaskComposite
+            | AId::MaskImage
+            | AId::MaskMode
+            | AId::MaskOrigin
+            | AId::MaskPosition
+            | AId::MaskRepeat
+            | AId::MaskSize
             | AId::Opacity
             | AId::Overflow
             | AId::LightingColor
@@ -944,7 +1088,7 @@ impl<'a, 'input: 'a> FromValue<'a, 'input> for svgtypes::PaintOrder {
 
 impl<'a, 'input: 'a> FromValue<'a, 'input> for svgtypes::Color {
     fn parse(_: SvgNode, _: AId, value: &str) -> Option<Self> {
-        Self::from_str(value).ok()
+        super::color::parse_color(value)
     }
 }
 
@@ -1081,3 +1225,21 @@ impl<'a, 'input: 'a> FromValue<'a, 'input> for SvgNode<'a, 'input> {
         node.document().element_by_id(id)
     }
 }
+
+impl Document<'_> {
+    /// Converts a byte offset into a row and column, starting from 1:1.
+    fn text_pos_at(&self, pos: usize) -> roxmltree::TextPos {
+        let line_starts = self.line_starts.get_or_init(|| {
+            std::iter::once(0)
+                .chain(self.input.match_indices('\n').map(|(idx, _)| idx + 1))
+                .collect()
+        });
+
+        let pos = pos.min(self.input.len());
+        // `line_starts` always has at least one item and starts with zero.
+        let row = line_starts.partition_point(|start| *start <= pos);
+        let line_start = line_starts[row - 1];
+        let col = self.input[line_start..pos].chars().count() + 1;
+        roxmltree::TextPos::new(row as u32, col as u32)
+    }
+}
@@ -39,6 +39,7 @@ pub enum EId {
     FeTile,
     FeTurbulence,
     Filter,
+    ForeignObject,
     G,
     Image,
     Line,
@@ -64,74 +65,75 @@ pub enum EId {
 }
 
 static ELEMENTS: Map<EId> = Map {
-    key: 732231254413039614,
+    key: 8726423004985182586,
     disps: &[
-        (0, 12),
-        (1, 11),
-        (10, 26),
-        (2, 42),
-        (1, 19),
-        (0, 5),
-        (1, 13),
-        (8, 50),
+        (3, 16),
+        (0, 39),
+        (1, 45),
+        (0, 37),
+        (6, 16),
         (0, 0),
-        (1, 0),
-        (7, 45),
+        (2, 36),
+        (0, 1),
+        (0, 15),
+        (19, 46),
+        (16, 2),
     ],
     entries: &[
-        ("feFlood", EId::FeFlood),
-        ("radialGradient", EId::RadialGradient),
-        ("feImage", EId::FeImage),
-        ("stop", EId::Stop),
-        ("fePointLight", EId::FePointLight),
-        ("feConvolveMatrix", EId::FeConvolveMatrix),
-        ("feComposite", EId::FeComposite),
-        ("clipPath", EId::ClipPath),
-        ("feMerge", EId::FeMerge),
+        ("feOffset", EId::FeOffset),
         ("defs", EId::Defs),
-        ("mask", EId::Mask),
-        ("svg", EId::Svg),
-        ("symbol", EId::Symbol),
-        ("linearGradient", EId::LinearGradient),
-        ("feSpecularLighting", EId::FeSpecularLighting),
-        ("feFuncB", EId::FeFuncB),
-        ("filter", EId::Filter),
-        ("feFuncG", EId::FeFuncG),
-        ("circle", EId::Circle),
-        ("g", EId::G),
-        ("tref", EId::Tref),
-        ("feFuncA", EId::FeFuncA),
-        ("image", EId::Image),
-        ("text", EId::Text),
-        ("line", EId::Line),
-        ("pattern", EId::Pattern),
-        ("use", EId::Use),
         ("feDropShadow", EId::FeDropShadow),
-        ("feSpotLight", EId::FeSpotLight),
-        ("marker", EId::Marker),
         ("style", EId::Style),
-        ("switch", EId::Switch),
-        ("tspan", EId::Tspan),
-        ("feColorMatrix", EId::FeColorMatrix),
-        ("feOffset", EId::FeOffset),
+        ("circle", EId::Circle),
         ("path", EId::Path),
-        ("feGaussianBlur", EId::FeGaussianBlur),
-        ("feTile", EId::FeTile),
-        ("feTurbulence", EId::FeTurbulence),
-        ("feMergeNode", EId::FeMergeNode),
-        ("feMorphology", EId::FeMorphology),
+        ("polyline", EId::Polyline),
         ("a", EId::A),
         ("textPath", EId::TextPath),
         ("ellipse", EId::Ellipse),
-        ("feComponentTransfer", EId::FeComponentTransfer),
-        ("feDistantLight", EId::FeDistantLight),
-        ("polyline", EId::Polyline),
-        ("polygon", EId::Polygon),
-        ("feBlend", EId::FeBlend),
-        ("feDisplacementMap", EId::FeDisplacementMap),
         ("feDiffuseLighting", EId::FeDiffuseLighting),
+        ("image", EId::Image),
+        ("stop", EId::Stop),
         ("rect", EId::Rect),
+        ("feFuncG", EId::FeFuncG),
+        ("feSpotLight", EId::FeSpotLight),
+        ("feTurbulence", EId::FeTurbulence),
+        ("feDisplacementMap", EId::FeDisplacementMap),
         ("feFuncR", EId::FeFuncR),
+        ("feBlend", EId::FeBlend),
+        ("feSpecularLighting", EId::FeSpecularLighting),
+        ("linearGradient", EId::LinearGradient),
+        ("feFuncA", EId::FeFuncA),
+        ("fePointLight", EId::FePointLight),
+        ("marker", EId::Marker),
+        ("feComposite", EId::FeComposite),
+        ("feTile", EId::FeTile),
+        ("feConvolveMatrix", EId::FeConvolveMatrix),
+        ("g", EId::G),
+        ("radialGradient", EId::RadialGradient),
+        ("feDistantLight", EId::FeDistantLight),
+        ("feColorMatrix", EId::FeColorMatrix),
+        ("switch", EId::Switch),
+        ("symbol", EId::Symbol),
+        ("feComponentTransfer", EId::FeComponentTransfer),
+        ("polygon", EId::Polygon),
+        ("pattern", EId::Pattern),
+        ("tref", EId::Tref),
+        ("line", EId::Line),
+        ("filter", EId::Filter),
+        ("tspan", EId::Tspan),
+        ("feGaussianBlur", EId::FeGaussianBlur),
+        ("feImage", EId::FeImage),
+        ("use", EId::Use),
+        ("clipPath", EId::ClipPath),
+        ("feMergeNode", EId::FeMergeNode),
+        ("feFuncB", EId::FeFuncB),
+        ("feMerge", EId::FeMerge),
+        ("feFlood", EId::FeFlood),
+        ("foreignObject", EId::ForeignObject),
+        ("mask", EId::Mask),
+        ("svg", EId::Svg),
+        ("feMorphology", EId::FeMorphology),
+        ("text", EId::Text),
     ],
 };
 
@@ -267,12 +269,14 @@ pub enum AId {
     MaskMode,
     MaskOrigin,
     MaskPosition,
+    MaskRepeat,
     MaskSize,
     MaskType,
     MaskContentUnits,
     MaskUnits,
     MixBlendMode,
     Mode,
+    NoComposite,
     NumOctaves,
     Offset,
     Opacity,
@@ -379,262 +383,264 @@ pub enum AId {
 static ATTRIBUTES: Map<AId> = Map {
     key: 3213172566270843353,
     disps: &[
-        (0, 63),
-        (4, 146),
-        (0, 0),
-        (3, 42),
-        (2, 197),
-        (0, 0),
+        (1, 140),
+        (2, 186),
+        (0, 1),
+        (0, 32),
+        (2, 163),
+        (0, 5),
+        (0, 4),
         (0, 1),
+        (0, 16),
+        (2, 92),
+        (0, 37),
+        (0, 12),
         (0, 0),
+        (7, 151),
+        (19, 144),
+        (1, 56),
+        (2, 177),
+        (1, 166),
+        (0, 4),
+        (1, 148),
+        (0, 113),
         (0, 0),
-        (0, 18),
-        (0, 11),
-        (1, 20),
-        (0, 8),
-        (17, 110),
-        (1, 112),
-        (1, 108),
-        (5, 94),
-        (2, 128),
-        (4, 95),
-        (0, 63),
-        (0, 96),
+        (1, 171),
         (0, 0),
-        (1, 110),
-        (0, 1),
-        (40, 30),
-        (17, 157),
-        (0, 61),
-        (0, 16),
-        (7, 16),
-        (0, 80),
-        (0, 107),
-        (6, 111),
-        (0, 153),
-        (6, 202),
-        (18, 86),
-        (0, 194),
+        (5, 183),
+        (1, 98),
+        (1, 39),
+        (0, 15),
+        (21, 198),
+        (2, 37),
+        (0, 35),
+        (6, 204),
+        (0, 88),
+        (13, 204),
+        (40, 72),
+        (0, 204),
         (0, 0),
-        (0, 7),
-        (0, 69),
-        (0, 5),
-        (0, 19),
+        (0, 2),
+        (2, 96),
+        (0, 24),
+        (0, 57),
         (0, 0),
-        (4, 65),
+        (4, 161),
     ],
     entries: &[
-        ("alignment-baseline", AId::AlignmentBaseline),
         ("fx", AId::Fx),
-        ("targetY", AId::TargetY),
-        ("clip-path", AId::ClipPath),
-        ("lengthAdjust", AId::LengthAdjust),
+        ("orient", AId::Orient),
+        ("mask-origin", AId::MaskOrigin),
+        ("font-variant-ligatures", AId::FontVariantLigatures),
+        ("color-profile", AId::ColorProfile),
+        ("slope", AId::Slope),
         ("mask-size", AId::MaskSize),
-        ("unicode-bidi", AId::UnicodeBidi),
-        ("z", AId::Z),
-        ("font-variant-numeric", AId::FontVariantNumeric),
+        ("stroke-opacity", AId::StrokeOpacity),
+        ("k4", AId::K4),
+        ("pathLength", AId::PathLength),
+        ("specularExponent", AId::SpecularExponent),
+        ("fill-opacity", AId::FillOpacity),
+        ("mask-type", AId::MaskType),
+        ("requiredFeatures", AId::RequiredFeatures),
+        ("primitiveUnits", AId::PrimitiveUnits),
+        ("offset", AId::Offset),
+        ("text-overflow", AId::TextOverflow),
+        ("in2", AId::In2),
+        ("glyph-orientation-horizontal", AId::GlyphOrientationHorizontal),
+        ("exponent", AId::Exponent),
+        ("mask", AId::Mask),
+        ("transform-box", AId::TransformBox),
+        ("gradientTransform", AId::GradientTransform),
+        ("marker-mid", AId::MarkerMid),
+        ("flood-color", AId::FloodColor),
+        ("refY", AId::RefY),
+        ("side", AId::Side),
+        ("font-size-adjust", AId::FontSizeAdjust),
+        ("font-variant-position", AId::FontVariantPosition),
         ("clip-rule", AId::ClipRule),
-        ("font", AId::Font),
+        ("text-rendering", AId::TextRendering),
+        ("preserveAlpha", AId::PreserveAlpha),
+        ("text-underline-position", AId::TextUnderlinePosition),
+        ("stop-color", AId::StopColor),
+        ("d", AId::D),
+        ("filterUnits", AId::FilterUnits),
+        ("letter-spacing", AId::LetterSpacing),
+        ("points", AId::Points),
+        ("class", AId::Class),
+        ("font-variant-numeric", AId::FontVariantNumeric),
+        ("transform", AId::Transform),
+        ("tableValues", AId::TableValues),
+        ("writing-mode", AId::WritingMode),
+        ("text-align-last", AId::TextAlignLast),
+        ("mask-border-width", AId::MaskBorderWidth),
+        ("order", AId::Order),
+        ("y2", AId::Y2),
+        ("systemLanguage", AId::SystemLanguage),
+        ("font-family", AId::FontFamily),
+        ("width", AId::Width),
+        ("text-decoration-style", AId::TextDecorationStyle),
+        ("text-decoration", AId::TextDecoration),
+        ("operator", AId::Operator),
+        ("stroke", AId::Stroke),
+        ("marker-end", AId::MarkerEnd),
         ("gradientUnits", AId::GradientUnits),
         ("style", AId::Style),
-        ("font-stretch", AId::FontStretch),
+        ("x2", AId::X2),
+        ("mask-position", AId::MaskPosition),
+        ("markerWidth", AId::MarkerWidth),
+        ("x1", AId::X1),
         ("intercept", AId::Intercept),
-        ("mask-border-slice", AId::MaskBorderSlice),
-        ("y", AId::Y),
-        ("xChannelSelector", AId::XChannelSelector),
+        ("dy", AId::Dy),
+        ("scale", AId::Scale),
+        ("text-align", AId::TextAlign),
+        ("type", AId::Type),
+        ("font-stretch", AId::FontStretch),
+        ("color-rendering", AId::ColorRendering),
+        ("z", AId::Z),
+        ("limitingConeAngle", AId::LimitingConeAngle),
         ("numOctaves", AId::NumOctaves),
-        ("x1", AId::X1),
-        ("fill-rule", AId::FillRule),
-        ("image-rendering", AId::ImageRendering),
-        ("surfaceScale", AId::SurfaceScale),
-        ("seed", AId::Seed),
         ("mix-blend-mode", AId::MixBlendMode),
-        ("path", AId::Path),
+        ("k3", AId::K3),
+        ("pointsAtY", AId::PointsAtY),
+        ("href", AId::Href),
+        ("isolation", AId::Isolation),
+        ("mode", AId::Mode),
+        ("font-variant", AId::FontVariant),
+        ("mask-composite", AId::MaskComposite),
+        ("color", AId::Color),
         ("mask-border-repeat", AId::MaskBorderRepeat),
-        ("transform", AId::Transform),
-        ("stroke", AId::Stroke),
-        ("refX", AId::RefX),
-        ("text-orientation", AId::TextOrientation),
-        ("line-height", AId::LineHeight),
-        ("display", AId::Display),
-        ("kerning", AId::Kerning),
-        ("transform-origin", AId::TransformOrigin),
-        ("shape-subtract", AId::ShapeSubtract),
-        ("width", AId::Width),
-        ("stroke-miterlimit", AId::StrokeMiterlimit),
-        ("dy", AId::Dy),
-        ("text-decoration-color", AId::TextDecorationColor),
-        ("white-space", AId::WhiteSpace),
-        ("diffuseConstant", AId::DiffuseConstant),
-        ("text-decoration-stroke", AId::TextDecorationStroke),
-        ("values", AId::Values),
-        ("font-size", AId::FontSize),
+        ("stroke-dasharray", AId::StrokeDasharray),
+        ("stop-opacity", AId::StopOpacity),
+        ("x", AId::X),
+        ("background-color", AId::BackgroundColor),
+        ("unicode-bidi", AId::UnicodeBidi),
+        ("word-spacing", AId::WordSpacing),
+        ("result", AId::Result),
+        ("font-optical-sizing", AId::FontOpticalSizing),
+        ("targetY", AId::TargetY),
+        ("shape-inside", AId::ShapeInside),
+        ("mask-border-source", AId::MaskBorderSource),
+        ("stroke-linecap", AId::StrokeLinecap),
         ("shape-image-threshold", AId::ShapeImageThreshold),
-        ("href", AId::Href),
-        ("cy", AId::Cy),
-        ("mask-image", AId::MaskImage),
-        ("unicode-range", AId::UnicodeRange),
-        ("specularConstant", AId::SpecularConstant),
-        ("baseline-shift", AId::BaselineShift),
-        ("k3", AId::K3),
-        ("text-anchor", AId::TextAnchor),
-        ("mask-border-mode", AId::MaskBorderMode),
-        ("requiredFeatures", AId::RequiredFeatures),
-        ("color-rendering", AId::ColorRendering),
+        ("seed", AId::Seed),
+        ("xChannelSelector", AId::XChannelSelector),
+        ("edgeMode", AId::EdgeMode),
+        ("cx", AId::Cx),
+        ("surfaceScale", AId::SurfaceScale),
+        ("text-indent", AId::TextIndent),
+        ("image-rendering", AId::ImageRendering),
+        ("glyph-orientation-vertical", AId::GlyphOrientationVertical),
+        ("startOffset", AId::StartOffset),
         ("amplitude", AId::Amplitude),
-        ("mask-border-width", AId::MaskBorderWidth),
-        ("stroke-linecap", AId::StrokeLinecap),
-        ("paint-order", AId::PaintOrder),
-        ("lighting-color", AId::LightingColor),
+        ("mask-clip", AId::MaskClip),
+        ("textLength", AId::TextLength),
+        ("text-decoration-stroke", AId::TextDecorationStroke),
+        ("mask-border", AId::MaskBorder),
+        ("font-weight", AId::FontWeight),
+        ("space", AId::Space),
+        ("dominant-baseline", AId::DominantBaseline),
+        ("unicode-range", AId::UnicodeRange),
+        ("in", AId::In),
         ("dx", AId::Dx),
-        ("markerWidth", AId::MarkerWidth),
-        ("scale", AId::Scale),
-        ("id", AId::Id),
-        ("color", AId::Color),
-        ("in2", AId::In2),
-        ("targetX", AId::TargetX),
+        ("alignment-baseline", AId::AlignmentBaseline),
+        ("fr", AId::Fr),
+        ("vector-effect", AId::VectorEffect),
+        ("spreadMethod", AId::SpreadMethod),
+        ("diffuseConstant", AId::DiffuseConstant),
         ("direction", AId::Direction),
-        ("pointsAtX", AId::PointsAtX),
-        ("stitchTiles", AId::StitchTiles),
-        ("patternUnits", AId::PatternUnits),
+        ("font-feature-settings", AId::FontFeatureSettings),
+        ("maskUnits", AId::MaskUnits),
+        ("markerUnits", AId::MarkerUnits),
+        ("font", AId::Font),
         ("shape-padding", AId::ShapePadding),
-        ("k2", AId::K2),
-        ("font-optical-sizing", AId::FontOpticalSizing),
-        ("k4", AId::K4),
-        ("vector-effect", AId::VectorEffect),
-        ("mask-composite", AId::MaskComposite),
-        ("stroke-width", AId::StrokeWidth),
-        ("font-variation-settings", AId::FontVariationSettings),
-        ("mask-border-outset", AId::MaskBorderOutset),
-        ("in", AId::In),
-        ("stroke-linejoin", AId::StrokeLinejoin),
-        ("stop-opacity", AId::StopOpacity),
-        ("inline-size", AId::InlineSize),
-        ("mask-type", AId::MaskType),
-        ("filterUnits", AId::FilterUnits),
-        ("color-profile", AId::ColorProfile),
-        ("space", AId::Space),
-        ("text-decoration-fill", AId::TextDecorationFill),
-        ("font-kerning", AId::FontKerning),
-        ("offset", AId::Offset),
-        ("pointsAtZ", AId::PointsAtZ),
-        ("text-align", AId::TextAlign),
-        ("clip", AId::Clip),
-        ("y1", AId::Y1),
-        ("mask-origin", AId::MaskOrigin),
-        ("mask-mode", AId::MaskMode),
-        ("yChannelSelector", AId::YChannelSelector),
         ("font-variant-caps", AId::FontVariantCaps),
-        ("marker-mid", AId::MarkerMid),
-        ("shape-rendering", AId::ShapeRendering),
-        ("text-rendering", AId::TextRendering),
-        ("fill-opacity", AId::FillOpacity),
-        ("word-spacing", AId::WordSpacing),
-        ("fill", AId::Fill),
-        ("mask-clip", AId::MaskClip),
-        ("font-feature-settings", AId::FontFeatureSettings),
-        ("radius", AId::Radius),
-        ("kernelMatrix", AId::KernelMatrix),
+        ("ry", AId::Ry),
+        ("kerning", AId::Kerning),
+        ("rotate", AId::Rotate),
+        ("mask-mode", AId::MaskMode),
+        ("fy", AId::Fy),
         ("kernelUnitLength", AId::KernelUnitLength),
-        ("mask-border-source", AId::MaskBorderSource),
-        ("k1", AId::K1),
-        ("mask", AId::Mask),
+        ("elevation", AId::Elevation),
+        ("baseline-shift", AId::BaselineShift),
+        ("radius", AId::Radius),
+        ("mask-image", AId::MaskImage),
+        ("baseFrequency", AId::BaseFrequency),
+        ("inline-size", AId::InlineSize),
+        ("font-synthesis", AId::FontSynthesis),
+        ("mask-repeat", AId::MaskRepeat),
+        ("display", AId::Display),
+        ("stdDeviation", AId::StdDeviation),
+        ("values", AId::Values),
         ("opacity", AId::Opacity),
-        ("markerUnits", AId::MarkerUnits),
-        ("visibility", AId::Visibility),
-        ("spreadMethod", AId::SpreadMethod),
-        ("pointsAtY", AId::PointsAtY),
-        ("d", AId::D),
-        ("slope", AId::Slope),
-        ("side", AId::Side),
-        ("tableValues", AId::TableValues),
-        ("order", AId::Order),
-        ("text-align-last", AId::TextAlignLast),
-        ("font-size-adjust", AId::FontSizeAdjust),
-        ("rotate", AId::Rotate),
+        ("fill", AId::Fill),
+        ("r", AId::R),
+        ("fill-rule", AId::FillRule),
+        ("marker-start", AId::MarkerStart),
+        ("k2", AId::K2),
+        ("divisor", AId::Divisor),
+        ("mask-border-slice", AId::MaskBorderSlice),
+        ("y1", AId::Y1),
         ("shape-margin", AId::ShapeMargin),
-        ("limitingConeAngle", AId::LimitingConeAngle),
-        ("font-weight", AId::FontWeight),
-        ("text-decoration-line", AId::TextDecorationLine),
-        ("stop-color", AId::StopColor),
+        ("patternUnits", AId::PatternUnits),
+        ("lighting-color", AId::LightingColor),
+        ("y", AId::Y),
+        ("color-interpolation-filters", AId::ColorInterpolationFilters),
+        ("visibility", AId::Visibility),
+        ("maskContentUnits", AId::MaskContentUnits),
+        ("preserveAspectRatio", AId::PreserveAspectRatio),
         ("requiredExtensions", AId::RequiredExtensions),
-        ("enable-background", AId::EnableBackground),
-        ("systemLanguage", AId::SystemLanguage),
+        ("kernelMatrix", AId::KernelMatrix),
+        ("no-composite", AId::NoComposite),
+        ("yChannelSelector", AId::YChannelSelector),
+        ("mask-border-mode", AId::MaskBorderMode),
+        ("font-variant-east-asian", AId::FontVariantEastAsian),
+        ("font-size", AId::FontSize),
+        ("text-orientation", AId::TextOrientation),
         ("clipPathUnits", AId::ClipPathUnits),
-        ("stroke-dashoffset", AId::StrokeDashoffset),
-        ("ry", AId::Ry),
-        ("overflow", AId::Overflow),
-        ("class", AId::Class),
-        ("mask-border", AId::MaskBorder),
-        ("specularExponent", AId::SpecularExponent),
-        ("text-decoration", AId::TextDecoration),
-        ("startOffset", AId::StartOffset),
-        ("stroke-dasharray", AId::StrokeDasharray),
-        ("fr", AId::Fr),
-        ("mask-position", AId::MaskPosition),
-        ("writing-mode", AId::WritingMode),
-        ("font-synthesis", AId::FontSynthesis),
-        ("isolation", AId::Isolation),
-        ("rx", AId::Rx),
-        ("bias", AId::Bias),
-        ("markerHeight", AId::MarkerHeight),
-        ("edgeMode", AId::EdgeMode),
-        ("r", AId::R),
-        ("stroke-opacity", AId::StrokeOpacity),
-        ("maskContentUnits", AId::MaskContentUnits),
+        ("shape-rendering", AId::ShapeRendering),
+        ("white-space", AId::WhiteSpace),
+        ("stitchTiles", AId::StitchTiles),
+        ("font-kerning", AId::FontKerning),
+        ("clip", AId::Clip),
+        ("cy", AId::Cy),
+        ("path", AId::Path),
+        ("stroke-miterlimit", AId::StrokeMiterlimit),
         ("height", AId::Height),
-        ("font-variant-position", AId::FontVariantPosition),
-        ("operator", AId::Operator),
-        ("font-family", AId::FontFamily),
-        ("fy", AId::Fy),
-        ("dominant-baseline", AId::DominantBaseline),
-        ("y2", AId::Y2),
-        ("shape-inside", AId::ShapeInside),
-        ("letter-spacing", AId::LetterSpacing),
+        ("rx", AId::Rx),
+        ("k1", AId::K1),
+        ("paint-order", AId::PaintOrder),
         ("azimuth", AId::Azimuth),
-        ("stdDeviation", AId::StdDeviation),
-        ("flood-color", AId::FloodColor),
-        ("flood-opacity", AId::FloodOpacity),
-        ("type", AId::Type),
-        ("font-variant-east-asian", AId::FontVariantEastAsian),
-        ("points", AId::Points),
-        ("refY", AId::RefY),
-        ("text-underline-position", AId::TextUnderlinePosition),
-        ("patternContentUnits", AId::PatternContentUnits),
-        ("baseFrequency", AId::BaseFrequency),
-        ("color-interpolation", AId::ColorInterpolation),
-        ("font-variant-ligatures", AId::FontVariantLigatures),
+        ("text-anchor", AId::TextAnchor),
+        ("pointsAtX", AId::PointsAtX),
+        ("transform-origin", AId::TransformOrigin),
+        ("stroke-width", AId::StrokeWidth),
+        ("mask-border-outset", AId::MaskBorderOutset),
+        ("patternTransform", AId::PatternTransform),
+        ("overflow", AId::Overflow),
         ("font-style", AId::FontStyle),
+        ("text-decoration-fill", AId::TextDecorationFill),
+        ("stroke-linejoin", AId::StrokeLinejoin),
+        ("id", AId::Id),
+        ("lengthAdjust", AId::LengthAdjust),
+        ("patternContentUnits", AId::PatternContentUnits),
+        ("pointsAtZ", AId::PointsAtZ),
         ("filter", AId::Filter),
-        ("text-decoration-style", AId::TextDecorationStyle),
-        ("preserveAlpha", AId::PreserveAlpha),
-        ("mode", AId::Mode),
-        ("divisor", AId::Divisor),
-        ("cx", AId::Cx),
-        ("patternTransform", AId::PatternTransform),
-        ("background-color", AId::BackgroundColor),
-        ("preserveAspectRatio", AId::PreserveAspectRatio),
-        ("gradientTransform", AId::GradientTransform),
-        ("x2", AId::X2),
-        ("pathLength", AId::PathLength),
-        ("marker-start", AId::MarkerStart),
-        ("glyph-orientation-horizontal", AId::GlyphOrientationHorizontal),
-        ("maskUnits", AId::MaskUnits),
-        ("textLength", AId::TextLength),
+        ("enable-background", AId::EnableBackground),
+        ("text-decoration-line", AId::TextDecorationLine),
+        ("clip-path", AId::ClipPath),
+        ("markerHeight", AId::MarkerHeight),
+        ("color-interpolation", AId::ColorInterpolation),
+        ("targetX", AId::TargetX),
+        ("bias", AId::Bias),
+        ("flood-opacity", AId::FloodOpacity),
         ("viewBox", AId::ViewBox),
-        ("text-overflow", AId::TextOverflow),
-        ("glyph-orientation-vertical", AId::GlyphOrientationVertical),
-        ("result", AId::Result),
-        ("primitiveUnits", AId::PrimitiveUnits),
-        ("exponent", AId::Exponent),
-        ("x", AId::X),
-        ("font-variant", AId::FontVariant),
-        ("elevation", AId::Elevation),
-        ("color-interpolation-filters", AId::ColorInterpolationFilters),
-        ("text-indent", AId::TextIndent),
-        ("marker-end", AId::MarkerEnd),
-        ("transform-box", AId::TransformBox),
-        ("orient", AId::Orient),
+        ("refX", AId::RefX),
+        ("stroke-dashoffset", AId::StrokeDashoffset),
+        ("text-decoration-color", AId::TextDecorationColor),
+        ("line-height", AId::LineHeight),
+        ("shape-subtract", AId::ShapeSubtract),
+        ("specularConstant", AId::SpecularConstant),
+        ("font-variation-settings", AId::FontVariationSettings),
     ],
 };
 
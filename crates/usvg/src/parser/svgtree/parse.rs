@@ -3,23 +3,36 @@
 
 use std::collections::HashMap;
 
-use roxmltree::Error;
-use simplecss::Declaration;
 use svgtypes::FontShorthand;
 
-use super::{AId, Attribute, Document, EId, NodeData, NodeId, NodeKind, ShortRange};
+use super::css::{Declaration, Origin, Precedence, StyleSheet};
+use super::custom_properties;
+use super::{AId, Attribute, Document, EId, NodeData, NodeId, NodeKind, ShortRange, SvgNode};
+use crate::{DiagnosticCode, Error, Options};
 
-const SVG_NS: &str = "http://www.w3.org/2000/svg";
-const XLINK_NS: &str = "http://www.w3.org/1999/xlink";
-const XML_NAMESPACE_NS: &str = "http://www.w3.org/XML/1998/namespace";
+pub(crate) const SVG_NS: &str = "http://www.w3.org/2000/svg";
+pub(crate) const XLINK_NS: &str = "http://www.w3.org/1999/xlink";
+pub(crate) const XML_NAMESPACE_NS: &str = "http://www.w3.org/XML/1998/namespace";
 
 impl<'input> Document<'input> {
     /// Parses a [`Document`] from a [`roxmltree::Document`].
     pub fn parse_tree(
         xml: &roxmltree::Document<'input>,
-        injected_stylesheet: Option<&'input str>,
+        opt: &'input Options<'input>,
     ) -> Result<Document<'input>, Error> {
-        parse(xml, injected_stylesheet)
+        parse(xml, &HashMap::new(), opt)
+    }
+
+    /// Parses a [`Document`] from a [`roxmltree::Document`],
+    /// resolving external `use` targets using the provided documents.
+    ///
+    /// `sprites` are indexed by a reference without a fragment, like `sprites.svg`.
+    pub(crate) fn parse_tree_with_sprites(
+        xml: &roxmltree::Document<'input>,
+        sprites: &HashMap<String, roxmltree::Document<'input>>,
+        opt: &'input Options<'input>,
+    ) -> Result<Document<'input>, Error> {
+        parse(xml, sprites, opt)
     }
 
     pub(crate) fn append(&mut self, parent_id: NodeId, kind: NodeKind) -> NodeId {
@@ -64,14 +77,32 @@ impl<'input> Document<'input> {
 
 fn parse<'input>(
     xml: &roxmltree::Document<'input>,
-    injected_stylesheet: Option<&'input str>,
+    sprites: &HashMap<String, roxmltree::Document<'input>>,
+    opt: &'input Options<'input>,
 ) -> Result<Document<'input>, Error> {
     let mut doc = Document {
         nodes: Vec::new(),
         attrs: Vec::new(),
         links: HashMap::new(),
+        custom_properties: HashMap::new(),
+        metadata: HashMap::new(),
+        foreign_objects: HashMap::new(),
+        metadata_options: &opt.metadata,
+        input: xml.input_text(),
+        line_starts: Default::default(),
+        diagnostics: &opt.diagnostics,
+        limits: opt.limits,
+        use_depth: 0,
+        fragment: None,
+        animated_values: HashMap::new(),
+        animation_time: opt.animation_time,
+        dpi: opt.dpi,
     };
 
+    if let Some(time) = opt.animation_time {
+        doc.animated_values = crate::parser::smil::sample(xml, time, opt);
+    }
+
     // build a map of id -> node for resolve_href
     let mut id_map = HashMap::new();
     for node in xml.descendants() {
@@ -82,6 +113,29 @@ fn parse<'input>(
         }
     }
 
+    // External `use` targets, like `sprites.svg#icon`, are stored by a full reference.
+    if !sprites.is_empty() {
+        for node in xml
+            .descendants()
+            .filter(|n| n.has_tag_name((SVG_NS, "use")))
+        {
+            let Some(href) = href_value(node).map(str::trim) else {
+                continue;
+            };
+
+            let Some((path, id)) = href.split_once('#') else {
+                continue;
+            };
+
+            let target = sprites
+                .get(path)
+                .and_then(|sprite| sprite.descendants().find(|n| n.attribute("id") == Some(id)));
+            if let Some(target) = target {
+                id_map.insert(href, target);
+            }
+        }
+    }
+
     // Add a root node.
     doc.nodes.push(NodeData {
         parent: None,
@@ -90,7 +144,22 @@ fn parse<'input>(
         kind: NodeKind::Root,
     });
 
-    let style_sheet = resolve_css(xml, injected_stylesheet);
+    // Root-level custom properties are inherited by the root element.
+    let declared: Vec<_> = opt
+        .custom_properties
+        .iter()
+        .filter(|(name, _)| custom_properties::is_custom_property(name))
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+        .collect();
+    if !declared.is_empty() {
+        let root_id = doc.root().id;
+        let computed = custom_properties::compute(&HashMap::new(), &declared);
+        doc.custom_properties.insert(root_id, computed);
+    }
+
+    let style_sheets = collect_style_sheets(xml, opt);
+    let imported = super::css::load_imports(&style_sheets, opt);
+    let style_sheet = super::css::parse_style_sheets(&style_sheets, &imported, opt);
 
     parse_xml_node_children(
         xml.root(),
@@ -107,10 +176,10 @@ fn parse<'input>(
     match doc.root().first_element_child() {
         Some(child) => {
             if child.tag_name() != Some(EId::Svg) {
-                return Err(roxmltree::Error::NoRootNode);
+                return Err(roxmltree::Error::NoRootNode.into());
             }
         }
-        None => return Err(roxmltree::Error::NoRootNode),
+        None => return Err(roxmltree::Error::NoRootNode.into()),
     }
 
     // Collect all elements with `id` attribute.
@@ -124,10 +193,12 @@ fn parse<'input>(
 
     fix_recursive_patterns(&mut doc);
     fix_recursive_links(EId::ClipPath, AId::ClipPath, &mut doc);
-    fix_recursive_links(EId::Mask, AId::Mask, &mut doc);
+    fix_recursive_mask_links(&mut doc);
     fix_recursive_links(EId::Filter, AId::Filter, &mut doc);
     fix_recursive_fe_image(&mut doc);
 
+    doc.fragment = crate::parser::fragment::resolve(xml, &doc, opt);
+
     Ok(doc)
 }
 
@@ -147,7 +218,7 @@ fn parse_xml_node_children<'input>(
     parent: roxmltree::Node<'_, 'input>,
     origin: roxmltree::Node,
     parent_id: NodeId,
-    style_sheet: &simplecss::StyleSheet,
+    style_sheet: &StyleSheet,
     ignore_ids: bool,
     depth: u32,
     doc: &mut Document<'input>,
@@ -173,14 +244,14 @@ fn parse_xml_node<'input>(
     node: roxmltree::Node<'_, 'input>,
     origin: roxmltree::Node,
     parent_id: NodeId,
-    style_sheet: &simplecss::StyleSheet,
+    style_sheet: &StyleSheet,
     ignore_ids: bool,
     depth: u32,
     doc: &mut Document<'input>,
     id_map: &HashMap<&str, roxmltree::Node<'_, 'input>>,
 ) -> Result<(), Error> {
     if depth > 1024 {
-        return Err(Error::NodesLimitReached);
+        return Err(roxmltree::Error::NodesLimitReached.into());
     }
 
     let mut tag_name = match parse_tag_name(node) {
@@ -203,6 +274,16 @@ fn parse_xml_node<'input>(
         super::text::parse_svg_text_element(node, node_id, style_sheet, doc)?;
     } else if tag_name == EId::Use {
         parse_svg_use_element(node, origin, node_id, style_sheet, depth + 1, doc, id_map)?;
+    } else if tag_name == EId::ForeignObject {
+        // The content is not SVG, so it's stored as is and rendered by a resolver.
+        let namespaces: Vec<_> = node
+            .namespaces()
+            .filter(|ns| ns.name() != Some("xml"))
+            .map(|ns| (ns.name(), ns.uri()))
+            .collect();
+        let text = &node.document().input_text()[node.range()];
+        let xml = crate::parser::embedded::standalone_element(text, &namespaces, &[]);
+        doc.foreign_objects.insert(node_id, xml);
     } else {
         parse_xml_node_children(
             node,
@@ -223,7 +304,7 @@ pub(crate) fn parse_svg_element<'input>(
     xml_node: roxmltree::Node<'_, 'input>,
     parent_id: NodeId,
     tag_name: EId,
-    style_sheet: &simplecss::StyleSheet,
+    style_sheet: &StyleSheet,
     ignore_ids: bool,
     doc: &mut Document<'input>,
 ) -> Result<NodeId, Error> {
@@ -249,7 +330,20 @@ pub(crate) fn parse_svg_element<'input>(
         }
 
         // For some reason those properties are allowed only inside a `style` attribute and CSS.
-        if matches!(aid, AId::MixBlendMode | AId::Isolation | AId::FontKerning) {
+        if matches!(
+            aid,
+            AId::MixBlendMode
+                | AId::Isolation
+                | AId::FontKerning
+                | AId::MaskClip
+                | AId::MaskComposite
+                | AId::MaskImage
+                | AId::MaskMode
+                | AId::MaskOrigin
+                | AId::MaskPosition
+                | AId::MaskRepeat
+                | AId::MaskSize
+        ) {
             continue;
         } else if aid == AId::ImageRendering
             && matches!(
@@ -294,6 +388,86 @@ pub(crate) fn parse_svg_element<'input>(
         }
     }
 
+    // Collect declarations from style sheets first and from a `style` attribute afterwards.
+    let style_declarations = xml_node
+        .attribute("style")
+        .map(super::css::parse_declarations)
+        .unwrap_or_default();
+    let mut declarations: Vec<(Precedence, &Declaration)> = Vec::new();
+    for rule in &style_sheet.rules {
+        if rule.selector.matches(xml_node) {
+            let specificity = rule.selector.specificity();
+            for declaration in &rule.declarations {
+                let precedence = Precedence::new(rule.origin, declaration.important, specificity);
+                declarations.push((precedence, declaration));
+            }
+        }
+    }
+    for declaration in &style_declarations {
+        declarations.push((Precedence::inline(declaration.important), declaration));
+    }
+
+    // Apply declarations in the cascade order, so the last one wins.
+    // The sort is stable, therefore declarations with the same precedence preserve the source order.
+    declarations.sort_by_key(|(precedence, _)| *precedence);
+    let declarations: Vec<&Declaration> = declarations.into_iter().map(|(_, d)| d).collect();
+
+    let custom_properties = resolve_custom_properties(parent_id, &declarations, doc);
+
+    // Shorthands are expanded during the cascade, so they have to be substituted beforehand.
+    let shorthand_values: Vec<Option<String>> = {
+        let vars = custom_properties
+            .as_ref()
+            .or_else(|| doc.inherited_custom_properties(parent_id));
+        declarations
+            .iter()
+            .map(|declaration| {
+                if matches!(declaration.name, "font" | "marker")
+                    && custom_properties::has_var(declaration.value)
+                {
+                    let value = custom_properties::substitute(declaration.value, &mut |name| {
+                        vars.and_then(|vars| vars.get(name).cloned())
+                    });
+                    Some(value.unwrap_or_default())
+                } else {
+                    None
+                }
+            })
+            .collect()
+    };
+
+    // CSS animations override all declarations, except important ones.
+    let css_animated_values = match doc.animation_time {
+        Some(time) => {
+            let declaration = |aid: AId| declarations.iter().rev().find(|d| d.name == aid.to_str());
+            let underlying = |aid: AId| {
+                let value = declaration(aid)
+                    .map(|d| d.value)
+                    .or_else(|| xml_node.attribute(aid.to_str()))
+                    .or_else(|| {
+                        let parent = doc.get(parent_id);
+                        aid.is_inheritable()
+                            .then(|| parent.find_attribute::<&str>(aid))
+                            .flatten()
+                    });
+                value.map(str::to_string)
+            };
+
+            let mut values =
+                super::animation::sample(&declarations, style_sheet, time, doc.dpi, underlying);
+            values.retain(|(aid, _)| !declaration(*aid).is_some_and(|d| d.important));
+            values
+        }
+        None => Vec::new(),
+    };
+
+    // Elements of external documents are never animated.
+    let animated_values = if doc.is_source_node(xml_node) {
+        doc.animated_values.get(&xml_node.id()).cloned()
+    } else {
+        None
+    };
+
     let mut insert_attribute = |aid, value: &str, important: bool| {
         // Check that attribute already exists.
         let idx = doc.attrs[attrs_start_idx..]
@@ -313,41 +487,24 @@ pub(crate) fn parse_svg_element<'input>(
         // Check that attribute was actually added, because it could be skipped.
         if added {
             if let Some(idx) = idx {
+                // Declarations are already sorted by precedence, so the new value always wins.
                 let last_idx = doc.attrs.len() - 1;
-                let existing_idx = attrs_start_idx + idx;
-
-                // See https://developer.mozilla.org/en-US/docs/Web/CSS/important
-                // When a declaration is important, the order of precedence is reversed.
-                // Declarations marked as important in the user-agent style sheets override
-                // all important declarations in the user style sheets. Similarly, all important
-                // declarations in the user style sheets override all important declarations in the
-                // author's style sheets. Finally, all important declarations take precedence over
-                // all animations.
-                //
-                // Which means:
-                // 1) Existing is not important, new is not important -> swap
-                // 2) Existing is important, new is not important -> don't swap
-                // 3) Existing is not important, new is important -> swap
-                // 4) Existing is important, new is important -> don't swap (since the order
-                // is reversed, so existing important attributes take precedence over new
-                // important attributes)
-                let has_precedence = !doc.attrs[existing_idx].important;
-
-                if has_precedence {
-                    doc.attrs.swap(existing_idx, last_idx);
-                }
-
-                // Remove last.
+                doc.attrs.swap(attrs_start_idx + idx, last_idx);
                 doc.attrs.pop();
             }
         }
     };
 
+    let mut invalid_fonts = Vec::new();
     let mut write_declaration = |declaration: &Declaration| {
         // TODO: perform XML attribute normalization
         let imp = declaration.important;
         let val = declaration.value;
 
+        if custom_properties::is_custom_property(declaration.name) {
+            return;
+        }
+
         if declaration.name == "marker" {
             insert_attribute(AId::MarkerStart, val, imp);
             insert_attribute(AId::MarkerMid, val, imp);
@@ -384,11 +541,7 @@ pub(crate) fn parse_svg_element<'input>(
                 insert_attribute(AId::FontSize, shorthand.font_size, imp);
                 insert_attribute(AId::FontFamily, shorthand.font_family, imp);
             } else {
-                log::warn!(
-                    "Failed to parse {} value: '{}'",
-                    AId::Font,
-                    declaration.value
-                );
+                invalid_fonts.push(declaration.value.to_string());
             }
         } else if let Some(aid) = AId::from_str(declaration.name) {
             // Parse only the presentation attributes.
@@ -399,23 +552,40 @@ pub(crate) fn parse_svg_element<'input>(
     };
 
     // Apply CSS.
-    for rule in &style_sheet.rules {
-        if rule.selector.matches(&XmlNode(xml_node)) {
-            for declaration in &rule.declarations {
-                write_declaration(declaration);
-            }
-        }
+    for (declaration, value) in declarations.iter().zip(&shorthand_values) {
+        write_declaration(&Declaration {
+            value: value.as_deref().unwrap_or(declaration.value),
+            ..**declaration
+        });
     }
 
-    // Split a `style` attribute.
-    if let Some(value) = xml_node.attribute("style") {
-        for declaration in simplecss::DeclarationTokenizer::from(value) {
-            write_declaration(&declaration);
-        }
+    for (aid, value) in &css_animated_values {
+        insert_attribute(*aid, value, false);
     }
 
-    if doc.nodes.len() > 1_000_000 {
-        return Err(Error::NodesLimitReached);
+    // SMIL animations override both attributes and CSS.
+    for (aid, value) in animated_values.iter().flatten() {
+        insert_attribute(*aid, value, false);
+    }
+
+    for value in invalid_fonts {
+        doc.warn_xml(
+            xml_node,
+            DiagnosticCode::InvalidAttribute,
+            format!("Failed to parse {} value: '{}'.", AId::Font, value),
+        );
+    }
+
+    substitute_vars(
+        xml_node,
+        parent_id,
+        attrs_start_idx,
+        custom_properties.as_ref(),
+        doc,
+    );
+
+    if doc.nodes.len() > doc.limits.max_elements {
+        return Err(Error::ElementsLimitReached);
     }
 
     let node_id = doc.append(
@@ -423,12 +593,115 @@ pub(crate) fn parse_svg_element<'input>(
         NodeKind::Element {
             tag_name,
             attributes: ShortRange::new(attrs_start_idx as u32, doc.attrs.len() as u32),
+            source_range: if doc.is_source_node(xml_node) {
+                ShortRange::new(xml_node.range().start as u32, xml_node.range().end as u32)
+            } else {
+                // Elements from external documents have no location in the source XML.
+                ShortRange::new(0, 0)
+            },
         },
     );
 
+    if let Some(custom_properties) = custom_properties {
+        doc.custom_properties.insert(node_id, custom_properties);
+    }
+
+    if doc.metadata_options.is_enabled() {
+        if let Some(metadata) = super::metadata::collect(xml_node, doc.metadata_options) {
+            doc.metadata.insert(node_id, metadata);
+        }
+    }
+
     Ok(node_id)
 }
 
+/// Computes element's custom properties.
+///
+/// Returns `None` when the element doesn't declare any, i.e. inherits them as is.
+fn resolve_custom_properties(
+    parent_id: NodeId,
+    declarations: &[&Declaration],
+    doc: &Document,
+) -> Option<custom_properties::CustomProperties> {
+    // Declarations are sorted by precedence, so the last one wins.
+    let mut declared: Vec<(&str, &str)> = Vec::new();
+    for declaration in declarations {
+        if !custom_properties::is_custom_property(declaration.name) {
+            continue;
+        }
+
+        match declared
+            .iter_mut()
+            .find(|(name, _)| *name == declaration.name)
+        {
+            Some(prev) => prev.1 = declaration.value,
+            None => declared.push((declaration.name, declaration.value)),
+        }
+    }
+
+    if declared.is_empty() {
+        return None;
+    }
+
+    let empty = custom_properties::CustomProperties::new();
+    let inherited = doc.inherited_custom_properties(parent_id).unwrap_or(&empty);
+    Some(custom_properties::compute(inherited, &declared))
+}
+
+/// Replaces `var()` functions in element's presentation attributes.
+///
+/// Attributes with unresolvable references are removed, which is equivalent to `unset`.
+fn substitute_vars(
+    xml_node: roxmltree::Node,
+    parent_id: NodeId,
+    attrs_start_idx: usize,
+    custom_properties: Option<&custom_properties::CustomProperties>,
+    doc: &mut Document,
+) {
+    let mut substituted = Vec::new();
+    {
+        let empty = custom_properties::CustomProperties::new();
+        let vars = custom_properties
+            .or_else(|| doc.inherited_custom_properties(parent_id))
+            .unwrap_or(&empty);
+
+        for (idx, attr) in doc.attrs.iter().enumerate().skip(attrs_start_idx) {
+            if attr.name.is_presentation() && custom_properties::has_var(&attr.value) {
+                let value =
+                    custom_properties::substitute(&attr.value, &mut |name| vars.get(name).cloned());
+                substituted.push((idx, value));
+            }
+        }
+    }
+
+    // Iterate in reverse, so removals will not affect indices.
+    let mut inherited = Vec::new();
+    for (idx, value) in substituted.into_iter().rev() {
+        let aid = doc.attrs[idx].name;
+        match value {
+            Some(value) if value == "inherit" && aid.allows_inherit_value() => {
+                doc.attrs.remove(idx);
+                inherited.push(aid);
+            }
+            Some(value) => {
+                doc.attrs[idx].value = roxmltree::StringStorage::new_owned(&value);
+            }
+            None => {
+                doc.warn_xml(
+                    xml_node,
+                    DiagnosticCode::InvalidAttribute,
+                    format!("Failed to resolve 'var()' in {}.", aid),
+                );
+                doc.attrs.remove(idx);
+            }
+        }
+    }
+
+    for aid in inherited {
+        resolve_inherit(parent_id, aid, doc);
+    }
+}
+
 fn append_attribute<'input>(
     parent_id: NodeId,
     tag_name: EId,
@@ -535,6 +808,7 @@ fn resolve_inherit(parent_id: NodeId, aid: AId, doc: &mut Document) -> bool {
         AId::ClipRule | AId::FillRule => "nonzero",
 
         AId::BaselineShift => "baseline",
+        AId::ColorInterpolation => "sRGB",
         AId::ColorInterpolationFilters => "linearRGB",
         AId::Direction => "ltr",
         AId::Display => "inline",
@@ -559,30 +833,34 @@ fn resolve_href<'a, 'input: 'a>(
     node: roxmltree::Node<'a, 'input>,
     id_map: &HashMap<&str, roxmltree::Node<'a, 'input>>,
 ) -> Option<roxmltree::Node<'a, 'input>> {
+    let link_value = href_value(node)?;
+    match svgtypes::IRI::from_str(link_value) {
+        Ok(iri) => id_map.get(iri.0).copied(),
+        // External references are present only when a referenced document was provided.
+        Err(_) => id_map.get(link_value.trim()).copied(),
+    }
+}
+
+fn href_value<'a>(node: roxmltree::Node<'a, '_>) -> Option<&'a str> {
     // See the comment in `parse_svg_element` about `href` precedence.
     //
     // Note: `roxmltree::Node::attribute("href")` matches by local name only and
     // would return whichever `href`/`xlink:href` comes first, so we have to
     // filter by namespace explicitly.
-    let link_value = node
-        .attributes()
+    node.attributes()
         .find(|a| a.name() == "href" && a.namespace().is_none())
         .or_else(|| {
             node.attributes()
                 .find(|a| a.name() == "href" && a.namespace() == Some(XLINK_NS))
         })
-        .map(|a| a.value())?;
-
-    let link_id = svgtypes::IRI::from_str(link_value).ok()?.0;
-
-    id_map.get(link_id).copied()
+        .map(|a| a.value())
 }
 
 fn parse_svg_use_element<'input>(
     node: roxmltree::Node<'_, 'input>,
     origin: roxmltree::Node,
     parent_id: NodeId,
-    style_sheet: &simplecss::StyleSheet,
+    style_sheet: &StyleSheet,
     depth: u32,
     doc: &mut Document<'input>,
     id_map: &HashMap<&str, roxmltree::Node<'_, 'input>>,
@@ -593,9 +871,13 @@ fn parse_svg_use_element<'input>(
     };
 
     if link == node || link == origin {
-        log::warn!(
-            "Recursive 'use' detected. '{}' will be skipped.",
-            node.attribute((SVG_NS, "id")).unwrap_or_default()
+        doc.warn_xml(
+            node,
+            DiagnosticCode::InvalidReference,
+            format!(
+                "Recursive 'use' detected. '{}' will be skipped.",
+                node.attribute((SVG_NS, "id")).unwrap_or_default()
+            ),
         );
         return Ok(());
     }
@@ -637,14 +919,31 @@ fn parse_svg_use_element<'input>(
     }
 
     if is_recursive {
-        log::warn!(
-            "Recursive 'use' detected. '{}' will be skipped.",
-            node.attribute((SVG_NS, "id")).unwrap_or_default()
+        doc.warn_xml(
+            node,
+            DiagnosticCode::InvalidReference,
+            format!(
+                "Recursive 'use' detected. '{}' will be skipped.",
+                node.attribute((SVG_NS, "id")).unwrap_or_default()
+            ),
         );
         return Ok(());
     }
 
-    parse_xml_node(
+    if doc.use_depth >= doc.limits.max_use_depth {
+        doc.warn_xml(
+            node,
+            DiagnosticCode::LimitExceeded,
+            format!(
+                "'use' nesting limit reached. '{}' will be skipped.",
+                node.attribute((SVG_NS, "id")).unwrap_or_default()
+            ),
+        );
+        return Ok(());
+    }
+
+    doc.use_depth += 1;
+    let result = parse_xml_node(
         link,
         node,
         parent_id,
@@ -653,19 +952,22 @@ fn parse_svg_use_element<'input>(
         depth + 1,
         doc,
         id_map,
-    )
+    );
+    doc.use_depth -= 1;
+    result
 }
 
-fn resolve_css<'a>(
+/// Returns the text of all style sheets that apply to the document, in the cascade order.
+fn collect_style_sheets<'a>(
     xml: &'a roxmltree::Document<'a>,
-    style_sheet: Option<&'a str>,
-) -> simplecss::StyleSheet<'a> {
-    let mut sheet = simplecss::StyleSheet::new();
+    opt: &'a Options,
+) -> Vec<(&'a str, Origin)> {
+    let mut sheets = Vec::new();
 
     // Injected style sheets do not override internal ones (we mimic the logic of rsvg-convert),
-    // so we need to parse it first.
-    if let Some(style_sheet) = style_sheet {
-        sheet.parse_more(style_sheet);
+    // unless their declarations are important. See `Precedence` for details.
+    if let Some(ref style_sheet) = opt.style_sheet {
+        sheets.push((style_sheet.as_str(), Origin::User));
     }
 
     for node in xml.descendants().filter(|n| n.has_tag_name("style")) {
@@ -675,46 +977,21 @@ fn resolve_css<'a>(
             None => {}
         }
 
+        if let Some(query) = node.attribute("media") {
+            if !opt.media.matches(query) {
+                continue;
+            }
+        }
+
         let text = match node.text() {
             Some(v) => v,
             None => continue,
         };
 
-        sheet.parse_more(text);
-    }
-
-    sheet
-}
-
-struct XmlNode<'a, 'input: 'a>(roxmltree::Node<'a, 'input>);
-
-impl simplecss::Element for XmlNode<'_, '_> {
-    fn parent_element(&self) -> Option<Self> {
-        self.0.parent_element().map(XmlNode)
-    }
-
-    fn prev_sibling_element(&self) -> Option<Self> {
-        self.0.prev_sibling_element().map(XmlNode)
-    }
-
-    fn has_local_name(&self, local_name: &str) -> bool {
-        self.0.tag_name().name() == local_name
-    }
-
-    fn attribute_matches(&self, local_name: &str, operator: simplecss::AttributeOperator) -> bool {
-        match self.0.attribute(local_name) {
-            Some(value) => operator.matches(value),
-            None => false,
-        }
+        sheets.push((text, Origin::Author));
     }
 
-    fn pseudo_class_matches(&self, class: simplecss::PseudoClass) -> bool {
-        match class {
-            simplecss::PseudoClass::FirstChild => self.prev_sibling_element().is_none(),
-            // TODO: lang
-            _ => false, // Since we are querying a static SVG we can ignore other pseudo-classes.
-        }
-    }
+    sheets
 }
 
 fn fix_recursive_patterns(doc: &mut Document) {
@@ -810,6 +1087,74 @@ fn find_recursive_link(eid: EId, aid: AId, doc: &Document) -> Option<NodeId> {
     None
 }
 
+/// Like `fix_recursive_links`, but for mask layers.
+///
+/// Unlike other links, `mask` and `mask-image` can contain multiple references.
+fn fix_recursive_mask_links(doc: &mut Document) {
+    while let Some((node_id, aid)) = find_recursive_mask_link(doc) {
+        let idx = doc.get(node_id).attribute_id(aid).unwrap();
+        doc.attrs[idx].value = roxmltree::StringStorage::Borrowed("none");
+    }
+}
+
+fn find_recursive_mask_link(doc: &Document) -> Option<(NodeId, AId)> {
+    let links = |node: SvgNode<'_, '_>, aid: AId| {
+        node.attribute::<&str>(aid)
+            .map(mask_link_ids)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|id| doc.element_by_id(id))
+            .collect::<Vec<_>>()
+    };
+
+    for node in doc
+        .root()
+        .descendants()
+        .filter(|n| n.tag_name() == Some(EId::Mask))
+    {
+        for child in node.descendants() {
+            for aid in [AId::Mask, AId::MaskImage] {
+                for link in links(child, aid) {
+                    if link == node {
+                        return Some((child.id, aid));
+                    }
+
+                    for node2 in link.descendants() {
+                        for aid2 in [AId::Mask, AId::MaskImage] {
+                            if links(node2, aid2).contains(&node) {
+                                return Some((node2.id, aid2));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Extracts all local `url(#id)` references from a mask layers list.
+fn mask_link_ids(value: &str) -> Vec<&str> {
+    let mut ids = Vec::new();
+    let mut s = value;
+    while let Some(idx) = s.find("url(") {
+        s = &s[idx + 4..];
+        let Some(end) = s.find(')') else {
+            break;
+        };
+
+        let url = s[..end].trim().trim_matches(|c| c == '"' || c == '\'');
+        if let Some(id) = url.strip_prefix('#') {
+            ids.push(id);
+        }
+
+        s = &s[end..];
+    }
+
+    ids
+}
+
 /// Detects cases like:
 ///
 /// ```xml
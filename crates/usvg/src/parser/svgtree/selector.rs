@@ -0,0 +1,796 @@
+// Copyright 2026 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! CSS selectors parsing and matching.
+//!
+//! Implements a subset of [Selectors Level 4](https://www.w3.org/TR/selectors-4/)
+//! that makes sense for a static document. Dynamic pseudo-classes, like `:hover`,
+//! are parsed, but never match.
+
+use std::borrow::Cow;
+
+use roxmltree::Node;
+
+use super::XML_NAMESPACE_NS;
+use crate::{DiagnosticCode, DiagnosticSink};
+
+/// Selector's specificity.
+///
+/// Contains the number of ID selectors, the number of class-like selectors
+/// and the number of type selectors.
+pub(crate) type Specificity = [u32; 3];
+
+/// A complex selector, like `g > rect.red`.
+#[derive(Clone, Debug)]
+pub(crate) struct Selector {
+    /// Compound selectors from left to right.
+    compounds: Vec<Vec<SimpleSelector>>,
+    /// Combinators between compound selectors.
+    combinators: Vec<Combinator>,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Combinator {
+    /// ` `
+    Descendant,
+    /// `>`
+    Child,
+    /// `+`
+    NextSibling,
+    /// `~`
+    SubsequentSibling,
+}
+
+#[derive(Clone, Debug)]
+enum SimpleSelector {
+    Universal,
+    Type(String),
+    Id(String),
+    Class(String),
+    Attribute {
+        name: String,
+        operator: AttributeOperator,
+        case_insensitive: bool,
+    },
+    PseudoClass(PseudoClass),
+}
+
+#[derive(Clone, Debug)]
+enum AttributeOperator {
+    /// `[attr]`
+    Exists,
+    /// `[attr=value]`
+    Matches(String),
+    /// `[attr~=value]`
+    Contains(String),
+    /// `[attr|=value]`
+    StartsWithDash(String),
+    /// `[attr^=value]`
+    StartsWith(String),
+    /// `[attr$=value]`
+    EndsWith(String),
+    /// `[attr*=value]`
+    Substring(String),
+}
+
+#[derive(Clone, Debug)]
+enum PseudoClass {
+    Root,
+    Empty,
+    FirstChild,
+    LastChild,
+    OnlyChild,
+    FirstOfType,
+    LastOfType,
+    OnlyOfType,
+    NthChild {
+        nth: Nth,
+        of: Option<Vec<Selector>>,
+        from_end: bool,
+    },
+    NthOfType {
+        nth: Nth,
+        from_end: bool,
+    },
+    Not(Vec<Selector>),
+    /// `:is()` and `:matches()`.
+    Is(Vec<Selector>),
+    Where(Vec<Selector>),
+    Lang(Vec<String>),
+    /// Dynamic pseudo-classes, like `:hover`.
+    Never,
+}
+
+/// An `An+B` expression.
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct Nth {
+    a: i32,
+    b: i32,
+}
+
+impl Nth {
+    fn matches(self, index: i32) -> bool {
+        if self.a == 0 {
+            return index == self.b;
+        }
+
+        let diff = index - self.b;
+        diff % self.a == 0 && diff / self.a >= 0
+    }
+}
+
+impl Selector {
+    /// Parses a single complex selector.
+    ///
+    /// Unsupported pseudo-classes are reported to `diagnostics`.
+    pub(crate) fn parse(text: &str, diagnostics: &DiagnosticSink) -> Option<Self> {
+        let mut s = Stream {
+            text,
+            pos: 0,
+            diagnostics,
+        };
+        let selector = s.parse_complex()?;
+        s.skip_spaces();
+        if s.at_end() { Some(selector) } else { None }
+    }
+
+    /// Returns selector's specificity.
+    pub(crate) fn specificity(&self) -> Specificity {
+        let mut spec = [0; 3];
+        for simple in self.compounds.iter().flatten() {
+            add(&mut spec, simple.specificity());
+        }
+
+        spec
+    }
+
+    /// Checks that the selector matches an element.
+    pub(crate) fn matches(&self, node: Node) -> bool {
+        node.is_element() && self.matches_compound(self.compounds.len() - 1, node)
+    }
+
+    fn matches_compound(&self, idx: usize, node: Node) -> bool {
+        if !self.compounds[idx].iter().all(|s| s.matches(node)) {
+            return false;
+        }
+
+        if idx == 0 {
+            return true;
+        }
+
+        match self.combinators[idx - 1] {
+            Combinator::Descendant => node
+                .ancestors()
+                .skip(1)
+                .filter(|n| n.is_element())
+                .any(|n| self.matches_compound(idx - 1, n)),
+            Combinator::Child => node
+                .parent_element()
+                .is_some_and(|n| self.matches_compound(idx - 1, n)),
+            Combinator::NextSibling => node
+                .prev_sibling_element()
+                .is_some_and(|n| self.matches_compound(idx - 1, n)),
+            Combinator::SubsequentSibling => {
+                prev_sibling_elements(node).any(|n| self.matches_compound(idx - 1, n))
+            }
+        }
+    }
+}
+
+impl SimpleSelector {
+    fn specificity(&self) -> Specificity {
+        match self {
+            SimpleSelector::Universal => [0, 0, 0],
+            SimpleSelector::Type(_) => [0, 0, 1],
+            SimpleSelector::Id(_) => [1, 0, 0],
+            SimpleSelector::Class(_) | SimpleSelector::Attribute { .. } => [0, 1, 0],
+            SimpleSelector::PseudoClass(class) => match class {
+                PseudoClass::Not(list) | PseudoClass::Is(list) => max_specificity(list),
+                PseudoClass::Where(_) => [0, 0, 0],
+                PseudoClass::NthChild { of: Some(list), .. } => {
+                    let mut spec = max_specificity(list);
+                    add(&mut spec, [0, 1, 0]);
+                    spec
+                }
+                _ => [0, 1, 0],
+            },
+        }
+    }
+
+    fn matches(&self, node: Node) -> bool {
+        match self {
+            SimpleSelector::Universal => true,
+            SimpleSelector::Type(name) => node.tag_name().name() == name,
+            SimpleSelector::Id(id) => node.attribute("id") == Some(id.as_str()),
+            SimpleSelector::Class(class) => node
+                .attribute("class")
+                .is_some_and(|v| v.split_ascii_whitespace().any(|c| c == class)),
+            SimpleSelector::Attribute {
+                name,
+                operator,
+                case_insensitive,
+            } => match node.attribute(name.as_str()) {
+                Some(value) => operator.matches(value, *case_insensitive),
+                None => false,
+            },
+            SimpleSelector::PseudoClass(class) => class.matches(node),
+        }
+    }
+}
+
+impl AttributeOperator {
+    fn matches(&self, value: &str, case_insensitive: bool) -> bool {
+        let expected = match self {
+            AttributeOperator::Exists => return true,
+            AttributeOperator::Matches(v)
+            | AttributeOperator::Contains(v)
+            | AttributeOperator::StartsWithDash(v)
+            | AttributeOperator::StartsWith(v)
+            | AttributeOperator::EndsWith(v)
+            | AttributeOperator::Substring(v) => v.as_str(),
+        };
+
+        let (value, expected) = if case_insensitive {
+            (
+                Cow::Owned(value.to_lowercase()),
+                Cow::Owned(expected.to_lowercase()),
+            )
+        } else {
+            (Cow::Borrowed(value), Cow::Borrowed(expected))
+        };
+        let (value, expected) = (value.as_ref(), expected.as_ref());
+
+        match self {
+            AttributeOperator::Exists => true,
+            AttributeOperator::Matches(_) => value == expected,
+            AttributeOperator::Contains(_) => {
+                !expected.is_empty() && value.split_ascii_whitespace().any(|v| v == expected)
+            }
+            AttributeOperator::StartsWithDash(_) => {
+                value == expected
+                    || value
+                        .strip_prefix(expected)
+                        .is_some_and(|rest| rest.starts_with('-'))
+            }
+            AttributeOperator::StartsWith(_) => !expected.is_empty() && value.starts_with(expected),
+            AttributeOperator::EndsWith(_) => !expected.is_empty() && value.ends_with(expected),
+            AttributeOperator::Substring(_) => !expected.is_empty() && value.contains(expected),
+        }
+    }
+}
+
+impl PseudoClass {
+    fn matches(&self, node: Node) -> bool {
+        match self {
+            PseudoClass::Root => node.parent().is_some_and(|n| n.is_root()),
+            PseudoClass::Empty => !node.children().any(|n| {
+                n.is_element() || (n.is_text() && n.text().is_some_and(|t| !t.is_empty()))
+            }),
+            PseudoClass::FirstChild => node.prev_sibling_element().is_none(),
+            PseudoClass::LastChild => node.next_sibling_element().is_none(),
+            PseudoClass::OnlyChild => {
+                node.prev_sibling_element().is_none() && node.next_sibling_element().is_none()
+            }
+            PseudoClass::FirstOfType => !prev_sibling_elements(node).any(|n| same_type(n, node)),
+            PseudoClass::LastOfType => !next_sibling_elements(node).any(|n| same_type(n, node)),
+            PseudoClass::OnlyOfType => {
+                !prev_sibling_elements(node).any(|n| same_type(n, node))
+                    && !next_sibling_elements(node).any(|n| same_type(n, node))
+            }
+            PseudoClass::NthChild { nth, of, from_end } => {
+                let is_candidate = |n: Node| match of {
+                    Some(list) => list.iter().any(|s| s.matches(n)),
+                    None => true,
+                };
+
+                if !is_candidate(node) {
+                    return false;
+                }
+
+                let count = if *from_end {
+                    next_sibling_elements(node)
+                        .filter(|n| is_candidate(*n))
+                        .count()
+                } else {
+                    prev_sibling_elements(node)
+                        .filter(|n| is_candidate(*n))
+                        .count()
+                };
+                nth.matches(count as i32 + 1)
+            }
+            PseudoClass::NthOfType { nth, from_end } => {
+                let count = if *from_end {
+                    next_sibling_elements(node)
+                        .filter(|n| same_type(*n, node))
+                        .count()
+                } else {
+                    prev_sibling_elements(node)
+                        .filter(|n| same_type(*n, node))
+                        .count()
+                };
+                nth.matches(count as i32 + 1)
+            }
+            PseudoClass::Not(list) => !list.iter().any(|s| s.matches(node)),
+            PseudoClass::Is(list) | PseudoClass::Where(list) => {
+                list.iter().any(|s| s.matches(node))
+            }
+            PseudoClass::Lang(ranges) => {
+                let lang = node.ancestors().find_map(|n| {
+                    n.attribute((XML_NAMESPACE_NS, "lang"))
+                        .or_else(|| n.attribute("lang"))
+                });
+                match lang {
+                    Some(lang) => ranges.iter().any(|range| {
+                        let lang = lang.to_ascii_lowercase();
+                        let range = range.to_ascii_lowercase();
+                        range == "*"
+                            || lang == range
+                            || lang
+                                .strip_prefix(range.as_str())
+                                .is_some_and(|rest| rest.starts_with('-'))
+                    }),
+                    None => false,
+                }
+            }
+            PseudoClass::Never => false,
+        }
+    }
+}
+
+fn max_specificity(list: &[Selector]) -> Specificity {
+    list.iter()
+        .map(|s| s.specificity())
+        .max()
+        .unwrap_or_default()
+}
+
+fn add(spec: &mut Specificity, other: Specificity) {
+    for (a, b) in spec.iter_mut().zip(other) {
+        *a = a.saturating_add(b);
+    }
+}
+
+fn same_type(a: Node, b: Node) -> bool {
+    a.tag_name() == b.tag_name()
+}
+
+fn prev_sibling_elements<'a, 'input>(
+    node: Node<'a, 'input>,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.prev_siblings().skip(1).filter(|n| n.is_element())
+}
+
+fn next_sibling_elements<'a, 'input>(
+    node: Node<'a, 'input>,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.next_siblings().skip(1).filter(|n| n.is_element())
+}
+
+struct Stream<'a, 'd> {
+    text: &'a str,
+    pos: usize,
+    diagnostics: &'d DiagnosticSink<'d>,
+}
+
+impl<'a> Stream<'a, '_> {
+    fn at_end(&self) -> bool {
+        self.pos >= self.text.len()
+    }
+
+    fn curr(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn consume(&mut self, c: char) -> bool {
+        if self.curr() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Skips spaces and returns `true` when something was skipped.
+    fn skip_spaces(&mut self) -> bool {
+        let start = self.pos;
+        loop {
+            let rest = &self.text[self.pos..];
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+
+            // Comments are allowed anywhere in selectors.
+            if let Some(comment) = trimmed.strip_prefix("/*") {
+                self.pos += 2 + comment.find("*/").map(|i| i + 2).unwrap_or(comment.len());
+            } else {
+                break;
+            }
+        }
+
+        self.pos != start
+    }
+
+    /// Parses a comma-separated list of selectors.
+    ///
+    /// A forgiving list skips invalid selectors instead of failing.
+    fn parse_list(&mut self, forgiving: bool) -> Option<Vec<Selector>> {
+        let mut list = Vec::new();
+        loop {
+            let start = self.pos;
+            match self.parse_complex() {
+                Some(selector) => list.push(selector),
+                None if forgiving => {
+                    // Skip until the next top-level comma or the end of the list.
+                    self.pos = start;
+                    let mut depth = 0usize;
+                    while let Some(c) = self.curr() {
+                        match c {
+                            '(' | '[' => depth += 1,
+                            ')' | ']' if depth == 0 => break,
+                            ')' | ']' => depth -= 1,
+                            ',' if depth == 0 => break,
+                            _ => {}
+                        }
+                        self.pos += c.len_utf8();
+                    }
+                }
+                None => return None,
+            }
+
+            self.skip_spaces();
+            if !self.consume(',') {
+                break;
+            }
+        }
+
+        Some(list)
+    }
+
+    fn parse_complex(&mut self) -> Option<Selector> {
+        self.skip_spaces();
+
+        let mut compounds = vec![self.parse_compound()?];
+        let mut combinators = Vec::new();
+        loop {
+            let had_spaces = self.skip_spaces();
+            let combinator = match self.curr() {
+                Some('>') => Combinator::Child,
+                Some('+') => Combinator::NextSibling,
+                Some('~') => Combinator::SubsequentSibling,
+                Some(',' | ')') | None => break,
+                Some(_) if had_spaces => Combinator::Descendant,
+                Some(_) => return None,
+            };
+
+            if combinator != Combinator::Descendant {
+                self.pos += 1;
+                self.skip_spaces();
+            }
+
+            combinators.push(combinator);
+            compounds.push(self.parse_compound()?);
+        }
+
+        Some(Selector {
+            compounds,
+            combinators,
+        })
+    }
+
+    fn parse_compound(&mut self) -> Option<Vec<SimpleSelector>> {
+        let mut compound = Vec::new();
+
+        if self.consume('*') {
+            compound.push(SimpleSelector::Universal);
+        } else if let Some(name) = self.parse_ident() {
+            compound.push(SimpleSelector::Type(name));
+        }
+
+        loop {
+            match self.curr() {
+                Some('#') => {
+                    self.pos += 1;
+                    compound.push(SimpleSelector::Id(self.parse_name()?));
+                }
+                Some('.') => {
+                    self.pos += 1;
+                    compound.push(SimpleSelector::Class(self.parse_ident()?));
+                }
+                Some('[') => {
+                    self.pos += 1;
+                    compound.push(self.parse_attribute()?);
+                }
+                Some(':') => {
+                    self.pos += 1;
+                    if self.curr() == Some(':') {
+                        // Pseudo-elements are not supported.
+                        return None;
+                    }
+
+                    compound.push(SimpleSelector::PseudoClass(self.parse_pseudo_class()?));
+                }
+                _ => break,
+            }
+        }
+
+        if compound.is_empty() {
+            None
+        } else {
+            Some(compound)
+        }
+    }
+
+    fn parse_attribute(&mut self) -> Option<SimpleSelector> {
+        self.skip_spaces();
+        let name = self.parse_ident()?;
+        self.skip_spaces();
+
+        let rest = &self.text[self.pos..];
+        let operator: Option<fn(String) -> AttributeOperator> = if rest.starts_with(']') {
+            None
+        } else if rest.starts_with('=') {
+            self.pos += 1;
+            Some(AttributeOperator::Matches)
+        } else {
+            let operator: fn(String) -> AttributeOperator = match rest.get(..2)? {
+                "~=" => AttributeOperator::Contains,
+                "|=" => AttributeOperator::StartsWithDash,
+                "^=" => AttributeOperator::StartsWith,
+                "$=" => AttributeOperator::EndsWith,
+                "*=" => AttributeOperator::Substring,
+                _ => return None,
+            };
+            self.pos += 2;
+            Some(operator)
+        };
+
+        let Some(operator) = operator else {
+            self.pos += 1;
+            return Some(SimpleSelector::Attribute {
+                name,
+                operator: AttributeOperator::Exists,
+                case_insensitive: false,
+            });
+        };
+
+        self.skip_spaces();
+        let value = match self.curr()? {
+            c @ ('"' | '\'') => self.parse_string(c)?,
+            _ => self.parse_ident()?,
+        };
+        self.skip_spaces();
+
+        let mut case_insensitive = false;
+        if let Some(c) = self.curr() {
+            if c.eq_ignore_ascii_case(&'i') || c.eq_ignore_ascii_case(&'s') {
+                case_insensitive = c.eq_ignore_ascii_case(&'i');
+                self.pos += 1;
+                self.skip_spaces();
+            }
+        }
+
+        if !self.consume(']') {
+            return None;
+        }
+
+        Some(SimpleSelector::Attribute {
+            name,
+            operator: operator(value),
+            case_insensitive,
+        })
+    }
+
+    fn parse_pseudo_class(&mut self) -> Option<PseudoClass> {
+        let name = self.parse_ident()?.to_ascii_lowercase();
+        if !self.consume('(') {
+            let class = match name.as_str() {
+                "root" => PseudoClass::Root,
+                "empty" => PseudoClass::Empty,
+                "first-child" => PseudoClass::FirstChild,
+                "last-child" => PseudoClass::LastChild,
+                "only-child" => PseudoClass::OnlyChild,
+                "first-of-type" => PseudoClass::FirstOfType,
+                "last-of-type" => PseudoClass::LastOfType,
+                "only-of-type" => PseudoClass::OnlyOfType,
+                "link" | "any-link" | "visited" | "hover" | "active" | "focus"
+                | "focus-visible" | "focus-within" | "target" | "checked" | "disabled"
+                | "enabled" => PseudoClass::Never,
+                _ => {
+                    self.diagnostics.warn(
+                        DiagnosticCode::Unsupported,
+                        format!("':{}' is not supported. Selector skipped.", name),
+                    );
+                    return None;
+                }
+            };
+
+            return Some(class);
+        }
+
+        self.skip_spaces();
+        let class = match name.as_str() {
+            "not" => PseudoClass::Not(self.parse_list(false)?),
+            "is" | "matches" => PseudoClass::Is(self.parse_list(true)?),
+            "where" => PseudoClass::Where(self.parse_list(true)?),
+            "nth-child" | "nth-last-child" => {
+                let nth = self.parse_nth()?;
+                self.skip_spaces();
+                let of = if self
+                    .parse_ident()
+                    .is_some_and(|s| s.eq_ignore_ascii_case("of"))
+                {
+                    Some(self.parse_list(false)?)
+                } else {
+                    None
+                };
+
+                PseudoClass::NthChild {
+                    nth,
+                    of,
+                    from_end: name == "nth-last-child",
+                }
+            }
+            "nth-of-type" | "nth-last-of-type" => PseudoClass::NthOfType {
+                nth: self.parse_nth()?,
+                from_end: name == "nth-last-of-type",
+            },
+            "lang" => {
+                let mut ranges = Vec::new();
+                loop {
+                    self.skip_spaces();
+                    let range = match self.curr()? {
+                        c @ ('"' | '\'') => self.parse_string(c)?,
+                        _ => self.parse_ident()?,
+                    };
+                    ranges.push(range);
+                    self.skip_spaces();
+                    if !self.consume(',') {
+                        break;
+                    }
+                }
+
+                PseudoClass::Lang(ranges)
+            }
+            _ => {
+                self.diagnostics.warn(
+                    DiagnosticCode::Unsupported,
+                    format!("':{}()' is not supported. Selector skipped.", name),
+                );
+                return None;
+            }
+        };
+
+        self.skip_spaces();
+        if self.consume(')') { Some(class) } else { None }
+    }
+
+    /// Parses an `An+B` expression.
+    fn parse_nth(&mut self) -> Option<Nth> {
+        // Collect everything up to `of` or `)` and parse it without spaces.
+        let start = self.pos;
+        while let Some(c) = self.curr() {
+            if c == ')' || self.text[self.pos..].starts_with(" of ") {
+                break;
+            }
+            self.pos += c.len_utf8();
+        }
+
+        let text: String = self.text[start..self.pos]
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_ascii_lowercase();
+
+        match text.as_str() {
+            "odd" => return Some(Nth { a: 2, b: 1 }),
+            "even" => return Some(Nth { a: 2, b: 0 }),
+            _ => {}
+        }
+
+        let Some((a, b)) = text.split_once('n') else {
+            return Some(Nth {
+                a: 0,
+                b: parse_int(&text)?,
+            });
+        };
+
+        let a = match a {
+            "" | "+" => 1,
+            "-" => -1,
+            _ => parse_int(a)?,
+        };
+        let b = if b.is_empty() {
+            0
+        } else if b.starts_with(['+', '-']) {
+            parse_int(b)?
+        } else {
+            return None;
+        };
+
+        Some(Nth { a, b })
+    }
+
+    /// Parses an identifier, resolving escapes.
+    fn parse_ident(&mut self) -> Option<String> {
+        let start = self.pos;
+        let rest = &self.text[self.pos..];
+        let starts_ident = match rest.strip_prefix('-').unwrap_or(rest).chars().next() {
+            Some(c) => is_name_start(c) || c == '\\' || rest.starts_with("--"),
+            None => false,
+        };
+
+        if !starts_ident {
+            return None;
+        }
+
+        let name = self.parse_name();
+        if name.is_none() {
+            self.pos = start;
+        }
+
+        name
+    }
+
+    /// Parses a sequence of name characters, resolving escapes.
+    fn parse_name(&mut self) -> Option<String> {
+        let mut name = String::new();
+        while let Some(c) = self.curr() {
+            if c == '\\' {
+                self.pos += 1;
+                name.push(self.parse_escape()?);
+            } else if is_name_start(c) || c.is_ascii_digit() || c == '-' {
+                name.push(c);
+                self.pos += c.len_utf8();
+            } else {
+                break;
+            }
+        }
+
+        if name.is_empty() { None } else { Some(name) }
+    }
+
+    /// Parses an escape sequence after a backslash.
+    fn parse_escape(&mut self) -> Option<char> {
+        let rest = &self.text[self.pos..];
+        let hex_len = rest
+            .chars()
+            .take(6)
+            .take_while(|c| c.is_ascii_hexdigit())
+            .count();
+        if hex_len == 0 {
+            let c = self.curr()?;
+            self.pos += c.len_utf8();
+            return Some(c);
+        }
+
+        let code = u32::from_str_radix(&rest[..hex_len], 16).ok()?;
+        self.pos += hex_len;
+        // A single whitespace after a hex escape is a part of it.
+        if self.curr().is_some_and(|c| c.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+
+        Some(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+
+    fn parse_string(&mut self, quote: char) -> Option<String> {
+        self.pos += 1;
+        let mut value = String::new();
+        loop {
+            let c = self.curr()?;
+            self.pos += c.len_utf8();
+            match c {
+                '\\' => value.push(self.parse_escape()?),
+                _ if c == quote => return Some(value),
+                _ => value.push(c),
+            }
+        }
+    }
+}
+
+fn is_name_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_' || !c.is_ascii()
+}
+
+fn parse_int(text: &str) -> Option<i32> {
+    text.strip_prefix('+').unwrap_or(text).parse().ok()
+}
@@ -3,16 +3,14 @@
 
 #![allow(clippy::comparison_chain)]
 
-use roxmltree::Error;
+use crate::Error;
 
-use super::{AId, Document, EId, NodeId, NodeKind, SvgNode};
-
-const XLINK_NS: &str = "http://www.w3.org/1999/xlink";
+use super::{AId, Document, EId, NodeId, NodeKind, SvgNode, XLINK_NS};
 
 pub(crate) fn parse_svg_text_element<'input>(
     parent: roxmltree::Node<'_, 'input>,
     parent_id: NodeId,
-    style_sheet: &simplecss::StyleSheet,
+    style_sheet: &super::css::StyleSheet,
     doc: &mut Document<'input>,
 ) -> Result<(), Error> {
     debug_assert_eq!(parent.tag_name().name(), "text");
@@ -40,7 +38,7 @@ pub(crate) fn parse_svg_text_element<'input>(
 fn parse_svg_text_element_impl<'input>(
     parent: roxmltree::Node<'_, 'input>,
     parent_id: NodeId,
-    style_sheet: &simplecss::StyleSheet,
+    style_sheet: &super::css::StyleSheet,
     space: XmlSpace,
     doc: &mut Document<'input>,
 ) -> Result<(), Error> {
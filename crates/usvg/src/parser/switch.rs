@@ -40,6 +40,72 @@ static FEATURES: &[&str] = &[
     // "http://www.w3.org/TR/SVG11/feature#BasicFont",
 ];
 
+/// A shorthand for [ConditionResolver]'s feature function.
+pub type ConditionFeatureFn<'a> =
+    Box<dyn Fn(&str, ConditionalElement, &Options) -> bool + Send + Sync + 'a>;
+
+/// A shorthand for [ConditionResolver]'s extension function.
+pub type ConditionExtensionFn<'a> =
+    Box<dyn Fn(&str, ConditionalElement, &Options) -> bool + Send + Sync + 'a>;
+
+/// An element with conditional processing attributes.
+#[derive(Clone, Copy, Debug)]
+pub struct ConditionalElement<'a> {
+    /// An element name, like `g` or `text`.
+    pub tag_name: &'a str,
+    /// An element ID. Empty when not set.
+    pub id: &'a str,
+}
+
+/// A `requiredFeatures` and `requiredExtensions` resolver.
+///
+/// Decides which `switch` children and conditional elements are rendered.
+/// For example, an embedder that can render XHTML can accept
+/// the `http://www.w3.org/1999/xhtml` extension.
+pub struct ConditionResolver<'a> {
+    /// Checks that a feature string from `requiredFeatures` is supported.
+    ///
+    /// An element is rendered only when all of its features are supported.
+    pub is_feature_supported: ConditionFeatureFn<'a>,
+
+    /// Checks that an extension namespace URI from `requiredExtensions` is supported.
+    ///
+    /// An element is rendered only when all of its extensions are supported.
+    pub is_extension_supported: ConditionExtensionFn<'a>,
+}
+
+impl Default for ConditionResolver<'_> {
+    fn default() -> Self {
+        ConditionResolver {
+            is_feature_supported: ConditionResolver::default_feature_resolver(),
+            is_extension_supported: ConditionResolver::default_extension_resolver(),
+        }
+    }
+}
+
+impl ConditionResolver<'_> {
+    /// Creates a default feature resolver.
+    ///
+    /// Accepts [SVG 1.1 feature strings](https://www.w3.org/TR/SVG11/feature.html)
+    /// supported by usvg.
+    pub fn default_feature_resolver() -> ConditionFeatureFn<'static> {
+        Box::new(|feature, _, _| FEATURES.contains(&feature))
+    }
+
+    /// Creates a default extension resolver.
+    ///
+    /// No extensions are supported.
+    pub fn default_extension_resolver() -> ConditionExtensionFn<'static> {
+        Box::new(|_, _, _| false)
+    }
+}
+
+impl std::fmt::Debug for ConditionResolver<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ConditionResolver { .. }")
+    }
+}
+
 pub(crate) fn convert(
     node: SvgNode,
     state: &converter::State,
@@ -63,8 +129,20 @@ pub(crate) fn is_condition_passed(node: SvgNode, opt: &Options) -> bool {
         return false;
     }
 
-    if node.has_attribute(AId::RequiredExtensions) {
-        return false;
+    let element = ConditionalElement {
+        tag_name: node.tag_name().map(|tag| tag.to_str()).unwrap_or_default(),
+        id: node.element_id(),
+    };
+
+    // 'The value is a list of URI references which identify the required extensions,
+    // with the individual values separated by white space.
+    // If a null string or empty string value is given to attribute 'requiredExtensions',
+    // the attribute evaluates to "false".'
+    if let Some(extensions) = node.attribute::<&str>(AId::RequiredExtensions) {
+        let resolver = &opt.condition_resolver.is_extension_supported;
+        if !is_list_supported(extensions, |ext| resolver(ext, element, opt)) {
+            return false;
+        }
     }
 
     // 'The value is a list of feature strings, with the individual values separated by white space.
@@ -73,10 +151,9 @@ pub(crate) fn is_condition_passed(node: SvgNode, opt: &Options) -> bool {
     // If all of the given features are supported, then the attribute evaluates to true;
     // otherwise, the current element and its children are skipped and thus will not be rendered.'
     if let Some(features) = node.attribute::<&str>(AId::RequiredFeatures) {
-        for feature in features.split(' ') {
-            if !FEATURES.contains(&feature) {
-                return false;
-            }
+        let resolver = &opt.condition_resolver.is_feature_supported;
+        if !is_list_supported(features, |feature| resolver(feature, element, opt)) {
+            return false;
         }
     }
 
@@ -87,39 +164,75 @@ pub(crate) fn is_condition_passed(node: SvgNode, opt: &Options) -> bool {
     true
 }
 
+/// Checks that a non-empty whitespace-separated list contains only supported values.
+fn is_list_supported(list: &str, is_supported: impl Fn(&str) -> bool) -> bool {
+    let mut values = list.split_ascii_whitespace().peekable();
+    values.peek().is_some() && values.all(is_supported)
+}
+
 /// SVG spec 5.8.5
 fn is_valid_sys_lang(node: SvgNode, opt: &Options) -> bool {
     // 'The attribute value is a comma-separated list of language names
     // as defined in BCP 47.'
     //
-    // But we support only simple cases like `en` or `en-US`.
-    // No one really uses this, especially with complex BCP 47 values.
+    // User languages are treated as BCP 47 language ranges.
     if let Some(langs) = node.attribute::<&str>(AId::SystemLanguage) {
-        let mut has_match = false;
-        for lang in langs.split(',') {
-            let lang = lang.trim();
-
-            // 'Evaluates to `true` if one of the languages indicated by user preferences exactly
-            // equals one of the languages given in the value of this parameter.'
-            if opt.languages.iter().any(|v| v == lang) {
-                has_match = true;
-                break;
-            }
+        langs.split(',').map(str::trim).any(|lang| {
+            !lang.is_empty()
+                && opt
+                    .languages
+                    .iter()
+                    .any(|range| matches_language_range(range.trim(), lang))
+        })
+    } else {
+        true
+    }
+}
 
-            // 'If one of the languages indicated by user preferences exactly equals a prefix
-            // of one of the languages given in the value of this parameter such that
-            // the first tag character following the prefix is `-`.'
-            if let Some(idx) = lang.bytes().position(|c| c == b'-') {
-                let lang_prefix = &lang[..idx];
-                if opt.languages.iter().any(|v| v == lang_prefix) {
-                    has_match = true;
-                    break;
-                }
+/// Matches a language tag against a BCP 47 language range, case-insensitively.
+///
+/// Uses both basic filtering and lookup from RFC 4647,
+/// so `en` matches `en-US` and `en-US` matches `en`.
+fn matches_language_range(range: &str, tag: &str) -> bool {
+    if range == "*" {
+        return true;
+    }
+
+    // 'Evaluates to `true` if one of the languages indicated by user preferences exactly
+    // equals one of the languages given in the value of this parameter, or if one of
+    // the languages indicated by user preferences exactly equals a prefix of one of
+    // the languages given in the value of this parameter such that the first tag character
+    // following the prefix is `-`.'
+    if is_language_prefix(range, tag) {
+        return true;
+    }
+
+    // Lookup progressively truncates the range, so `de-CH-1996` falls back to `de-CH` and `de`.
+    let mut range = range;
+    while let Some(idx) = range.rfind('-') {
+        range = &range[..idx];
+
+        // A single-character subtag, like `x` in `en-x-private`, cannot end a range.
+        if let Some(idx) = range.rfind('-') {
+            if range.len() - idx == 2 {
+                continue;
             }
         }
 
-        has_match
-    } else {
-        true
+        if range.eq_ignore_ascii_case(tag) {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Checks that a tag is equal to a prefix or starts with it, followed by `-`.
+fn is_language_prefix(prefix: &str, tag: &str) -> bool {
+    match tag.get(..prefix.len()) {
+        Some(start) if start.eq_ignore_ascii_case(prefix) => {
+            tag.len() == prefix.len() || tag.as_bytes()[prefix.len()] == b'-'
+        }
+        _ => false,
     }
 }
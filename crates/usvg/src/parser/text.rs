@@ -103,6 +103,14 @@ pub(crate) fn convert(
     cache: &mut converter::Cache,
     parent: &mut Group,
 ) {
+    if count_chars(text_node) > state.opt.limits.max_text_length {
+        text_node.warn(
+            DiagnosticCode::LimitExceeded,
+            "Text has more characters than allowed. Skipped.",
+        );
+        return;
+    }
+
     let pos_list = resolve_positions_list(text_node, state);
     let rotate_list = resolve_rotate_list(text_node);
     let writing_mode = convert_writing_mode(text_node);
@@ -124,6 +132,8 @@ pub(crate) fn convert(
 
     let mut text = Text {
         id,
+        source_span: state.source_span(text_node),
+        metadata: state.metadata(text_node),
         rendering_mode,
         dx: pos_list.iter().map(|v| v.dx.unwrap_or(0.0)).collect(),
         dy: pos_list.iter().map(|v| v.dy.unwrap_or(0.0)).collect(),
@@ -452,11 +462,14 @@ fn convert_font(node: SvgNode, state: &converter::State) -> Font {
     let mut families = parse_font_families(font_families)
         .ok()
         .log_none(|| {
-            log::warn!(
-                "Failed to parse {} value: '{}'. Falling back to {}.",
-                AId::FontFamily,
-                font_families,
-                state.opt.font_family
+            node.warn(
+                DiagnosticCode::InvalidAttribute,
+                format!(
+                    "Failed to parse {} value: '{}'. Falling back to {}.",
+                    AId::FontFamily,
+                    font_families,
+                    state.opt.font_family
+                ),
             );
         })
         .unwrap_or_default();
@@ -530,9 +543,12 @@ fn parse_font_variation_settings(node: SvgNode) -> Vec<FontVariation> {
 
         // Tag must be exactly 4 characters
         if tag_str.len() != 4 {
-            log::warn!(
-                "Invalid font-variation-settings tag: '{}' (must be 4 characters)",
-                tag_str
+            node.warn(
+                DiagnosticCode::InvalidAttribute,
+                format!(
+                    "Invalid font-variation-settings tag: '{}' (must be 4 characters)",
+                    tag_str
+                ),
             );
             continue;
         }
@@ -549,7 +565,10 @@ fn parse_font_variation_settings(node: SvgNode) -> Vec<FontVariation> {
         let value = match value_str.parse::<f32>() {
             Ok(v) => v,
             Err(_) => {
-                log::warn!("Invalid font-variation-settings value: '{}'", value_str);
+                node.warn(
+                    DiagnosticCode::InvalidAttribute,
+                    format!("Invalid font-variation-settings value: '{}'", value_str),
+                );
                 continue;
             }
         };
@@ -5,7 +5,7 @@ use svgtypes::{Length, LengthUnit as Unit};
 
 use super::converter;
 use super::svgtree::{AId, SvgNode};
-use crate::Units;
+use crate::{DiagnosticCode, Units};
 
 #[inline(never)]
 pub(crate) fn convert_length(
@@ -116,14 +116,14 @@ pub(crate) fn resolve_font_size(node: SvgNode, state: &converter::State) -> f32
                 }
             }
         } else if let Some(name) = n.attribute(AId::FontSize) {
-            font_size = convert_named_font_size(name, font_size);
+            font_size = convert_named_font_size(*n, name, font_size);
         }
     }
 
     font_size
 }
 
-fn convert_named_font_size(name: &str, parent_font_size: f32) -> f32 {
+fn convert_named_font_size(node: SvgNode, name: &str, parent_font_size: f32) -> f32 {
     let factor = match name {
         "xx-small" => -3,
         "x-small" => -2,
@@ -135,7 +135,10 @@ fn convert_named_font_size(name: &str, parent_font_size: f32) -> f32 {
         "smaller" => -1,
         "larger" => 1,
         _ => {
-            log::warn!("Invalid 'font-size' value: '{}'.", name);
+            node.warn(
+                DiagnosticCode::InvalidAttribute,
+                format!("Invalid 'font-size' value: '{}'.", name),
+            );
             0
         }
     };
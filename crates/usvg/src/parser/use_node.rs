@@ -255,6 +255,8 @@ fn clip_element(
 
     Group {
         id,
+        source_span: state.source_span(node),
+        metadata: state.metadata(node),
         transform,
         clip_path: Some(Arc::new(clip_path)),
         ..Group::empty()
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use crate::parser::OptionLog;
+use crate::{DiagnosticCode, DiagnosticSink};
 use skrifa::instance::LocationRef;
 use skrifa::prelude::Size;
 use skrifa::raw::types::Point;
@@ -145,6 +146,7 @@ pub(crate) struct GlyphPainter<'a> {
     /// The bounding box of every active clip, in the root coordinate space.
     /// `None` means the clip is empty (or its bounds are unknown).
     pub(crate) clip_stack: Vec<Option<tiny_skia_path::Rect>>,
+    pub(crate) diagnostics: &'a DiagnosticSink<'a>,
 }
 
 impl<'a> GlyphPainter<'a> {
@@ -181,7 +183,8 @@ impl<'a> GlyphPainter<'a> {
         let gradient_id = format!("lg{}", self.gradient_index);
         self.gradient_index += 1;
 
-        let gradient_transform = paint_transform(self.outline_transform, self.transform);
+        let gradient_transform =
+            paint_transform(self.outline_transform, self.transform, self.diagnostics);
 
         self.svg.start_element("linearGradient");
         self.svg.write_attribute("id", &gradient_id);
@@ -217,7 +220,8 @@ impl<'a> GlyphPainter<'a> {
         let gradient_id = format!("rg{}", self.gradient_index);
         self.gradient_index += 1;
 
-        let gradient_transform = paint_transform(self.outline_transform, self.transform);
+        let gradient_transform =
+            paint_transform(self.outline_transform, self.transform, self.diagnostics);
 
         // TODO: Normalizing the stops into the 0..1 range moves the circles onto the
         // first and last stop, which can make `r0` (and in theory `r1`) negative.
@@ -256,18 +260,29 @@ impl<'a> GlyphPainter<'a> {
         _color_stops: &[ColorStop],
         _extend: Extend,
     ) {
-        // Sweep gradients are not supported.
-        // TODO: surface warning without printing to stdout
+        self.diagnostics.warn(
+            DiagnosticCode::Unsupported,
+            "Sweep gradients in COLR glyphs are not supported.",
+        );
     }
 }
 
-fn paint_transform(outline_transform: Transform, transform: Transform) -> Transform {
+fn paint_transform(
+    outline_transform: Transform,
+    transform: Transform,
+    diagnostics: &DiagnosticSink,
+) -> Transform {
     let outline_transform = skrifa_to_tsp_transform(outline_transform);
     let gradient_transform = skrifa_to_tsp_transform(transform);
 
     let gradient_transform = outline_transform
         .invert()
-        .log_none(|| log::warn!("Failed to calculate transform for gradient in glyph."))
+        .log_none(|| {
+            diagnostics.warn(
+                DiagnosticCode::RenderingFailed,
+                "Failed to calculate transform for gradient in glyph.",
+            )
+        })
         .unwrap_or_default()
         .pre_concat(gradient_transform);
 
@@ -497,7 +512,10 @@ impl<'a> skrifa::color::ColorPainter for GlyphPainter<'a> {
         // Paint a rectangle covering that intersection and let the enclosing
         // clip groups shape it.
         if self.clip_stack.is_empty() {
-            log::warn!("Unclipped COLR fills are not supported.");
+            self.diagnostics.warn(
+                DiagnosticCode::Unsupported,
+                "Unclipped COLR fills are not supported.",
+            );
             return;
         }
 
@@ -558,9 +576,11 @@ impl<'a> skrifa::color::ColorPainter for GlyphPainter<'a> {
             CompositeMode::HslColor => "color",
             CompositeMode::HslLuminosity => "luminosity",
             _ => {
-                // Unsupported blend mode
                 // TODO: support other blend modes
-                // TODO: surface warning without printing to stdout
+                self.diagnostics.warn(
+                    DiagnosticCode::Unsupported,
+                    "Unsupported COLR composite mode. Fallback to normal.",
+                );
                 "normal"
             }
         };
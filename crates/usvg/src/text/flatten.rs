@@ -194,6 +194,8 @@ pub(crate) fn flatten(text: &mut Text, cache: &mut Cache) -> Option<(Group, NonZ
 
     let mut group = Group {
         id: text.id.clone(),
+        source_span: text.source_span.clone(),
+        metadata: text.metadata.clone(),
         ..Group::empty()
     };
 
@@ -201,11 +203,30 @@ pub(crate) fn flatten(text: &mut Text, cache: &mut Cache) -> Option<(Group, NonZ
         group.children.push(child);
     }
 
+    // All generated nodes point to the `text` element.
+    if let Some(ref source_span) = text.source_span {
+        set_source_span(&mut group, source_span);
+    }
+
     group.calculate_bounding_boxes();
     let stroke_bbox = group.stroke_bounding_box().to_non_zero_rect()?;
     Some((group, stroke_bbox))
 }
 
+fn set_source_span(group: &mut Group, source_span: &SourceSpan) {
+    for node in &mut group.children {
+        match node {
+            Node::Group(g) => {
+                g.source_span = Some(source_span.clone());
+                set_source_span(g, source_span);
+            }
+            Node::Path(path) => path.source_span = Some(source_span.clone()),
+            Node::Image(image) => image.source_span = Some(source_span.clone()),
+            Node::Text(text) => text.source_span = Some(source_span.clone()),
+        }
+    }
+}
+
 #[derive(Default)]
 struct PathBuilder {
     builder: tiny_skia_path::PathBuilder,
@@ -242,8 +263,14 @@ pub(crate) trait DatabaseExt {
     ) -> Option<tiny_skia_path::Path>;
     fn has_opsz_axis(&self, id: ID) -> bool;
     fn raster(&self, id: ID, glyph_id: GlyphId) -> Option<BitmapImage>;
-    fn svg(&self, id: ID, glyph_id: GlyphId) -> Option<Node>;
-    fn colr(&self, id: ID, glyph_id: GlyphId, variations: &[crate::FontVariation]) -> Option<Tree>;
+    fn svg(&self, id: ID, glyph_id: GlyphId, diagnostics: &DiagnosticSink) -> Option<Node>;
+    fn colr(
+        &self,
+        id: ID,
+        glyph_id: GlyphId,
+        variations: &[crate::FontVariation],
+        diagnostics: &DiagnosticSink,
+    ) -> Option<Tree>;
 }
 
 #[derive(Clone)]
@@ -318,6 +345,8 @@ impl DatabaseExt for Database {
 
                     let bitmap_image = BitmapImage {
                         image: Image {
+                            source_span: None,
+                            metadata: None,
                             id: String::new(),
                             visible: true,
                             size: Size::from_wh(image.width as f32, image.height as f32)?,
@@ -346,7 +375,7 @@ impl DatabaseExt for Database {
         })?
     }
 
-    fn svg(&self, id: ID, glyph_id: GlyphId) -> Option<Node> {
+    fn svg(&self, id: ID, glyph_id: GlyphId, diagnostics: &DiagnosticSink) -> Option<Node> {
         // SEE: https://docs.rs/read-fonts/latest/read_fonts/tables/svg/type.Svg.html
 
         // TODO: Technically not 100% accurate because the SVG format in a OTF font
@@ -359,7 +388,7 @@ impl DatabaseExt for Database {
             let font = skrifa::FontRef::from_index(data, face_index).ok()?;
             let svg_table = font.svg().ok()?;
             let image_data = svg_table.glyph_data(glyph_id.into()).ok()??;
-            let tree = Tree::from_data(image_data, &Options::default()).ok()?;
+            let tree = Tree::from_data(image_data, &glyph_options(diagnostics)).ok()?;
 
             // Twitter Color Emoji seems to always have one SVG record per glyph,
             // while Noto Color Emoji sometimes contains multiple ones. It's kind of hacky,
@@ -374,7 +403,10 @@ impl DatabaseExt for Database {
             } else {
                 tree.node_by_id(&format!("glyph{}", glyph_id.0))
                     .log_none(|| {
-                        log::warn!("Failed to find SVG glyph node for glyph {}", glyph_id.0);
+                        diagnostics.warn(
+                            DiagnosticCode::InvalidReference,
+                            format!("Failed to find SVG glyph node for glyph {}.", glyph_id.0),
+                        );
                     })
                     .cloned()?
             };
@@ -383,7 +415,13 @@ impl DatabaseExt for Database {
         })?
     }
 
-    fn colr(&self, id: ID, glyph_id: GlyphId, variations: &[crate::FontVariation]) -> Option<Tree> {
+    fn colr(
+        &self,
+        id: ID,
+        glyph_id: GlyphId,
+        variations: &[crate::FontVariation],
+        diagnostics: &DiagnosticSink,
+    ) -> Option<Tree> {
         self.with_face_data(id, |data, face_index| -> Option<Tree> {
             let font = skrifa::FontRef::from_index(data, face_index).ok()?;
 
@@ -417,6 +455,7 @@ impl DatabaseExt for Database {
                 outline_transform: skrifa::color::Transform::default(),
                 transforms_stack: vec![skrifa::color::Transform::default()],
                 clip_stack: Vec::new(),
+                diagnostics,
             };
 
             font.color_glyphs()
@@ -425,7 +464,19 @@ impl DatabaseExt for Database {
                 .ok()?;
             svg.end_element();
 
-            Tree::from_data(svg.end_document().as_bytes(), &Options::default()).ok()
+            Tree::from_data(svg.end_document().as_bytes(), &glyph_options(diagnostics)).ok()
         })?
     }
 }
+
+/// Creates options for parsing SVG documents embedded into fonts.
+///
+/// Diagnostics are forwarded to the document's sink.
+fn glyph_options<'a>(diagnostics: &'a DiagnosticSink) -> Options<'a> {
+    Options {
+        diagnostics: DiagnosticSink {
+            report: Box::new(|diagnostic| diagnostics.report(diagnostic)),
+        },
+        ..Options::default()
+    }
+}
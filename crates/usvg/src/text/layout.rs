@@ -18,9 +18,10 @@ use unicode_script::UnicodeScript;
 
 use crate::tree::{BBox, IsValidLength};
 use crate::{
-    AlignmentBaseline, ApproxZeroUlps, BaselineShift, DominantBaseline, Fill, FillRule, Font,
-    FontResolver, GlyphId, LengthAdjust, PaintOrder, Path, ShapeRendering, Stroke, Text,
-    TextAnchor, TextChunk, TextDecorationStyle, TextFlow, TextPath, TextSpan, WritingMode,
+    AlignmentBaseline, ApproxZeroUlps, BaselineShift, Diagnostic, DiagnosticCode, DiagnosticSink,
+    DominantBaseline, Fill, FillRule, Font, FontResolver, GlyphId, LengthAdjust, PaintOrder, Path,
+    Severity, ShapeRendering, Stroke, Text, TextAnchor, TextChunk, TextDecorationStyle, TextFlow,
+    TextPath, TextSpan, WritingMode,
 };
 
 /// A glyph that has already been positioned correctly.
@@ -203,15 +204,33 @@ pub(crate) fn layout_text(
     text_node: &Text,
     resolver: &FontResolver,
     fontdb: &mut Arc<fontdb::Database>,
+    diagnostics: &DiagnosticSink,
 ) -> Option<(Vec<Span>, NonZeroRect)> {
     let mut fonts_cache: FontsCache = HashMap::new();
 
     for chunk in &text_node.chunks {
         for span in &chunk.spans {
             if !fonts_cache.contains_key(&span.font) {
-                if let Some(font) = (resolver.select_font)(&span.font, fontdb)
-                    .and_then(|id| fontdb.load_font(id, &span.font.variations))
-                {
+                let Some(id) = (resolver.select_font)(&span.font, fontdb) else {
+                    let families = span
+                        .font
+                        .families
+                        .iter()
+                        .map(|f| f.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    diagnostics.report(
+                        Diagnostic::new(
+                            DiagnosticCode::FontNotFound,
+                            Severity::Warning,
+                            format!("No match for '{}' font-family.", families),
+                        )
+                        .with_element_id(&text_node.id),
+                    );
+                    continue;
+                };
+
+                if let Some(font) = fontdb.load_font(id, &span.font.variations) {
                     fonts_cache.insert(span.font.clone(), Arc::new(font));
                 }
             }
@@ -229,7 +248,9 @@ pub(crate) fn layout_text(
             TextFlow::Path(_) => (0.0, 0.0),
         };
 
-        let mut clusters = process_chunk(chunk, &fonts_cache, resolver, fontdb);
+        let mut clusters = process_chunk(chunk, &fonts_cache, resolver, fontdb, |diagnostic| {
+            diagnostics.report(diagnostic.with_element_id(&text_node.id))
+        });
         if clusters.is_empty() {
             char_offset += chunk.text.chars().count();
             continue;
@@ -289,9 +310,15 @@ pub(crate) fn layout_text(
                     WritingMode::TopToBottom => font.height(span.font_size.get()) / 2.0,
                 };
 
-                if let Some(path) =
-                    convert_decoration(offset, span, font, decoration, &decoration_spans, span_ts)
-                {
+                if let Some(path) = convert_decoration(
+                    offset,
+                    span,
+                    font,
+                    decoration,
+                    &decoration_spans,
+                    span_ts,
+                    diagnostics,
+                ) {
                     bbox = bbox.expand(path.data.bounds());
                     underline = Some(path);
                 }
@@ -303,9 +330,15 @@ pub(crate) fn layout_text(
                     WritingMode::TopToBottom => -font.height(span.font_size.get()) / 2.0,
                 };
 
-                if let Some(path) =
-                    convert_decoration(offset, span, font, decoration, &decoration_spans, span_ts)
-                {
+                if let Some(path) = convert_decoration(
+                    offset,
+                    span,
+                    font,
+                    decoration,
+                    &decoration_spans,
+                    span_ts,
+                    diagnostics,
+                ) {
                     bbox = bbox.expand(path.data.bounds());
                     overline = Some(path);
                 }
@@ -317,9 +350,15 @@ pub(crate) fn layout_text(
                     WritingMode::TopToBottom => 0.0,
                 };
 
-                if let Some(path) =
-                    convert_decoration(offset, span, font, decoration, &decoration_spans, span_ts)
-                {
+                if let Some(path) = convert_decoration(
+                    offset,
+                    span,
+                    font,
+                    decoration,
+                    &decoration_spans,
+                    span_ts,
+                    diagnostics,
+                ) {
                     bbox = bbox.expand(path.data.bounds());
                     line_through = Some(path);
                 }
@@ -461,6 +500,7 @@ pub(crate) fn convert_decoration(
     mut decoration: TextDecorationStyle,
     decoration_spans: &[DecorationSpan],
     transform: Transform,
+    diagnostics: &DiagnosticSink,
 ) -> Option<Path> {
     debug_assert!(!decoration_spans.is_empty());
 
@@ -471,7 +511,10 @@ pub(crate) fn convert_decoration(
         let rect = match NonZeroRect::from_xywh(0.0, -thickness / 2.0, dec_span.width, thickness) {
             Some(v) => v,
             None => {
-                log::warn!("a decoration span has a malformed bbox");
+                diagnostics.warn(
+                    DiagnosticCode::RenderingFailed,
+                    "A decoration span has a malformed bbox.",
+                );
                 continue;
             }
         };
@@ -864,6 +907,7 @@ fn process_chunk(
     fonts_cache: &FontsCache,
     resolver: &FontResolver,
     fontdb: &mut Arc<fontdb::Database>,
+    report: impl Fn(Diagnostic),
 ) -> Vec<GlyphCluster> {
     // The way this function works is a bit tricky.
     //
@@ -918,6 +962,7 @@ fn process_chunk(
             span.font_optical_sizing,
             resolver,
             fontdb,
+            &report,
         );
 
         // Do nothing with the first run.
@@ -1206,6 +1251,7 @@ fn form_glyph_clusters(glyphs: &[Glyph], text: &str, font_size: f32) -> GlyphClu
 pub(crate) trait DatabaseExt {
     fn load_font(&self, id: ID, variations: &[crate::FontVariation]) -> Option<ResolvedFont>;
     fn has_char(&self, id: ID, c: char) -> bool;
+    fn face_family_name(&self, id: ID) -> &str;
 }
 
 impl DatabaseExt for Database {
@@ -1316,6 +1362,18 @@ impl DatabaseExt for Database {
 
         res == Some(Some(true))
     }
+
+    fn face_family_name(&self, id: ID) -> &str {
+        self.face(id)
+            .and_then(|face| {
+                face.families
+                    .iter()
+                    .find(|f| f.1 == fontdb::Language::English_UnitedStates)
+                    .or(face.families.first())
+            })
+            .map(|f| f.0.as_str())
+            .unwrap_or_default()
+    }
 }
 
 /// Text shaping with font fallback.
@@ -1329,6 +1387,7 @@ pub(crate) fn shape_text(
     font_optical_sizing: crate::FontOpticalSizing,
     resolver: &FontResolver,
     fontdb: &mut Arc<fontdb::Database>,
+    report: &dyn Fn(Diagnostic),
 ) -> Vec<Glyph> {
     let mut glyphs = shape_text_with_font(
         text,
@@ -1363,6 +1422,16 @@ pub(crate) fn shape_text(
                 None => break 'outer,
             };
 
+            report(Diagnostic::new(
+                DiagnosticCode::FontFallback,
+                Severity::Info,
+                format!(
+                    "Fallback from {} to {}.",
+                    fontdb.face_family_name(font.id),
+                    fontdb.face_family_name(fallback_font.id)
+                ),
+            ));
+
             // Shape again, using a new font.
             let fallback_glyphs = shape_text_with_font(
                 text,
@@ -1410,11 +1479,14 @@ pub(crate) fn shape_text(
         if glyph.is_missing() {
             let c = glyph.byte_idx.char_from(text);
             // TODO: print a full grapheme
-            log::warn!(
-                "No fonts with a {}/U+{:X} character were found.",
-                c,
-                c as u32
-            );
+            report(Diagnostic::new(
+                DiagnosticCode::FontNotFound,
+                Severity::Warning,
+                format!(
+                    "No fonts with a {}/U+{:X} character were found.",
+                    c, c as u32
+                ),
+            ));
         }
     }
 
@@ -138,19 +138,7 @@ impl FontResolver<'_> {
                 style,
             };
 
-            let id = fontdb.query(&query);
-            if id.is_none() {
-                log::warn!(
-                    "No match for '{}' font-family.",
-                    font.families
-                        .iter()
-                        .map(|f| f.to_string())
-                        .collect::<Vec<_>>()
-                        .join(", ")
-                );
-            }
-
-            id
+            fontdb.query(&query)
         })
     }
 
@@ -182,19 +170,6 @@ impl FontResolver<'_> {
                     continue;
                 }
 
-                let base_family = base_face
-                    .families
-                    .iter()
-                    .find(|f| f.1 == fontdb::Language::English_UnitedStates)
-                    .unwrap_or(&base_face.families[0]);
-
-                let new_family = face
-                    .families
-                    .iter()
-                    .find(|f| f.1 == fontdb::Language::English_UnitedStates)
-                    .unwrap_or(&base_face.families[0]);
-
-                log::warn!("Fallback from {} to {}.", base_family.0, new_family.0);
                 return Some(face.id);
             }
 
@@ -216,7 +191,8 @@ impl std::fmt::Debug for FontResolver<'_> {
 ///    as decoration spans).
 /// 2. We convert all of the positioned glyphs into outlines.
 pub(crate) fn convert(text: &mut Text, resolver: &FontResolver, cache: &mut Cache) -> Option<()> {
-    let (text_fragments, bbox) = layout::layout_text(text, resolver, &mut cache.fontdb)?;
+    let (text_fragments, bbox) =
+        layout::layout_text(text, resolver, &mut cache.fontdb, cache.diagnostics)?;
     text.layouted = text_fragments;
     text.bounding_box = bbox.to_rect();
     text.abs_bounding_box = bbox.transform(text.abs_transform)?.to_rect();
@@ -152,6 +152,7 @@ pub struct Blend {
     pub(crate) input1: Input,
     pub(crate) input2: Input,
     pub(crate) mode: BlendMode,
+    pub(crate) no_composite: bool,
 }
 
 impl Blend {
@@ -175,6 +176,13 @@ impl Blend {
     pub fn mode(&self) -> BlendMode {
         self.mode
     }
+
+    /// Indicates that inputs should only be blended and not composited.
+    ///
+    /// `no-composite` in the SVG.
+    pub fn no_composite(&self) -> bool {
+        self.no_composite
+    }
 }
 
 /// A color matrix filter primitive.
@@ -642,6 +650,7 @@ pub struct GaussianBlur {
     pub(crate) input: Input,
     pub(crate) std_dev_x: PositiveF32,
     pub(crate) std_dev_y: PositiveF32,
+    pub(crate) edge_mode: EdgeMode,
 }
 
 impl GaussianBlur {
@@ -665,6 +674,13 @@ impl GaussianBlur {
     pub fn std_dev_y(&self) -> PositiveF32 {
         self.std_dev_y
     }
+
+    /// An edges processing mode.
+    ///
+    /// `edgeMode` in the SVG.
+    pub fn edge_mode(&self) -> EdgeMode {
+        self.edge_mode
+    }
 }
 
 /// An image filter primitive.
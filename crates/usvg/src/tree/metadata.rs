@@ -0,0 +1,126 @@
+// Copyright 2026 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+/// Non-rendering data of an element.
+///
+/// Collected only for data enabled via [`Options::metadata`](crate::Options::metadata).
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct Metadata {
+    pub(crate) title: Option<String>,
+    pub(crate) description: Option<String>,
+    pub(crate) metadata: Option<XmlElement>,
+    pub(crate) classes: Vec<String>,
+    pub(crate) attributes: Vec<MetadataAttribute>,
+}
+
+impl Metadata {
+    /// An accessible name of the element.
+    ///
+    /// Text content of the first `title` child element.
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// An accessible description of the element.
+    ///
+    /// Text content of the first `desc` child element.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// The first `metadata` child element.
+    pub fn metadata(&self) -> Option<&XmlElement> {
+        self.metadata.as_ref()
+    }
+
+    /// A list of classes from the `class` attribute.
+    pub fn classes(&self) -> &[String] {
+        &self.classes
+    }
+
+    /// A list of preserved attributes, like `data-*` ones.
+    pub fn attributes(&self) -> &[MetadataAttribute] {
+        &self.attributes
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.description.is_none()
+            && self.metadata.is_none()
+            && self.classes.is_empty()
+            && self.attributes.is_empty()
+    }
+}
+
+/// An attribute preserved as is.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MetadataAttribute {
+    pub(crate) namespace: Option<String>,
+    pub(crate) prefix: Option<String>,
+    pub(crate) name: String,
+    pub(crate) value: String,
+}
+
+impl MetadataAttribute {
+    /// Attribute's namespace URI.
+    ///
+    /// `None` for attributes without a namespace, like `data-*` ones.
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
+    /// Attribute's namespace prefix, as it was in the source SVG.
+    pub fn prefix(&self) -> Option<&str> {
+        self.prefix.as_deref()
+    }
+
+    /// Attribute's local name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Attribute's value.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+/// A generic XML element.
+///
+/// Used to preserve the content of `metadata` elements, which is usually
+/// in a different namespace, like RDF.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct XmlElement {
+    pub(crate) name: String,
+    pub(crate) attributes: Vec<(String, String)>,
+    pub(crate) children: Vec<XmlNode>,
+}
+
+impl XmlElement {
+    /// Element's qualified name, like `rdf:RDF`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Element's attributes as qualified name and value pairs.
+    ///
+    /// Namespace declarations required by the element and its descendants
+    /// are included as `xmlns:*` attributes.
+    pub fn attributes(&self) -> &[(String, String)] {
+        &self.attributes
+    }
+
+    /// Element's children.
+    pub fn children(&self) -> &[XmlNode] {
+        &self.children
+    }
+}
+
+/// A generic XML node.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum XmlNode {
+    /// An element node.
+    Element(XmlElement),
+    /// A text node.
+    Text(String),
+}
@@ -3,6 +3,8 @@
 
 pub mod filter;
 mod geom;
+mod metadata;
+mod stroke;
 mod text;
 
 use std::fmt::Display;
@@ -13,13 +15,21 @@ pub use strict_num::{self, ApproxEqUlps, NonZeroPositiveF32, NormalizedF32, Posi
 pub use tiny_skia_path;
 
 pub use self::geom::*;
+pub use self::metadata::*;
 pub use self::text::*;
 
-use crate::OptionLog;
-
 /// An alias to `NormalizedF32`.
 pub type Opacity = NormalizedF32;
 
+/// A location of an element in the source SVG.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SourceSpan {
+    /// A byte range of the element in the source text, including its children.
+    pub range: std::ops::Range<usize>,
+    /// A row and column of the element start.
+    pub position: roxmltree::TextPos,
+}
+
 // Must not be clone-able to preserve ID uniqueness.
 #[derive(Debug)]
 pub(crate) struct NonEmptyString(String);
@@ -286,6 +296,7 @@ pub struct BaseGradient {
     pub(crate) units: Units, // used only during parsing
     pub(crate) transform: Transform,
     pub(crate) spread_method: SpreadMethod,
+    pub(crate) color_interpolation: filter::ColorInterpolation,
     pub(crate) stops: Vec<Stop>,
 }
 
@@ -312,6 +323,13 @@ impl BaseGradient {
         self.spread_method
     }
 
+    /// Color space in which stops are interpolated.
+    ///
+    /// `color-interpolation` in SVG. Unlike in filters, the default is `ColorInterpolation::SRGB`.
+    pub fn color_interpolation(&self) -> filter::ColorInterpolation {
+        self.color_interpolation
+    }
+
     /// A list of `stop` elements.
     pub fn stops(&self) -> &[Stop] {
         &self.stops
@@ -569,6 +587,8 @@ pub enum LineJoin {
     MiterClip,
     Round,
     Bevel,
+    /// Cannot be stroked by tiny-skia directly. Use [`Stroke::outline`] instead.
+    Arcs,
 }
 
 impl Default for LineJoin {
@@ -635,6 +655,9 @@ impl Stroke {
     }
 
     /// Converts into a `tiny_skia_path::Stroke` type.
+    ///
+    /// Since tiny-skia doesn't support the `arcs` line join,
+    /// it will be replaced with `miter-clip`.
     pub fn to_tiny_skia(&self) -> tiny_skia_path::Stroke {
         let mut stroke = tiny_skia_path::Stroke {
             width: self.width.get(),
@@ -649,6 +672,7 @@ impl Stroke {
                 LineJoin::MiterClip => tiny_skia_path::LineJoin::MiterClip,
                 LineJoin::Round => tiny_skia_path::LineJoin::Round,
                 LineJoin::Bevel => tiny_skia_path::LineJoin::Bevel,
+                LineJoin::Arcs => tiny_skia_path::LineJoin::MiterClip,
             },
             // According to the spec, dash should not be accounted during
             // bbox calculation.
@@ -661,6 +685,21 @@ impl Stroke {
 
         stroke
     }
+
+    /// Converts a stroked path into a filled one.
+    ///
+    /// Unlike [`to_tiny_skia`](Self::to_tiny_skia), supports all line joins, including `arcs`.
+    /// The returned path must be filled using the non-zero fill rule.
+    ///
+    /// `resolution_scale` can be obtained via
+    /// [`PathStroker::compute_resolution_scale`](tiny_skia_path::PathStroker::compute_resolution_scale).
+    pub fn outline(
+        &self,
+        path: &tiny_skia_path::Path,
+        resolution_scale: f32,
+    ) -> Option<tiny_skia_path::Path> {
+        stroke::outline(path, &self.to_tiny_skia(), self.linejoin, resolution_scale)
+    }
 }
 
 /// A fill rule.
@@ -850,6 +889,55 @@ impl Default for MaskType {
     }
 }
 
+/// A mask compositing operator.
+///
+/// `mask-composite` in SVG.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+pub enum MaskComposite {
+    /// The mask layer is placed over the layers below it.
+    #[default]
+    Add,
+    /// The mask layer is placed where it falls outside of the layers below it.
+    Subtract,
+    /// The parts of the mask layer that overlap the layers below it are kept.
+    Intersect,
+    /// The non-overlapping regions of the mask layer and the layers below it are combined.
+    Exclude,
+}
+
+/// A mask layer.
+///
+/// A single `mask-image` value in SVG, alongside its `mask-mode` and `mask-composite`.
+/// Image and gradient mask layers are represented by masks with generated content.
+#[derive(Clone, Debug)]
+pub struct MaskLayer {
+    pub(crate) mask: Arc<Mask>,
+    pub(crate) mode: MaskType,
+    pub(crate) composite: MaskComposite,
+}
+
+impl MaskLayer {
+    /// Layer's mask.
+    pub fn mask(&self) -> &Mask {
+        &self.mask
+    }
+
+    /// Masking mode.
+    ///
+    /// `mask-mode` in SVG, with `match-source` already resolved.
+    /// Can be different from `Mask::kind`, which should be used only for nested masks.
+    pub fn mode(&self) -> MaskType {
+        self.mode
+    }
+
+    /// Compositing operator used to combine this layer with the layers below it.
+    ///
+    /// `mask-composite` in SVG.
+    pub fn composite(&self) -> MaskComposite {
+        self.composite
+    }
+}
+
 /// A mask element.
 ///
 /// `mask` element in SVG.
@@ -921,6 +1009,30 @@ impl Node {
         }
     }
 
+    /// Returns node's location in the source SVG.
+    ///
+    /// Available only when [`Options::source_spans`](crate::Options::source_spans) is set.
+    pub fn source_span(&self) -> Option<&SourceSpan> {
+        match self {
+            Node::Group(e) => e.source_span(),
+            Node::Path(e) => e.source_span(),
+            Node::Image(e) => e.source_span(),
+            Node::Text(e) => e.source_span(),
+        }
+    }
+
+    /// Returns node's non-rendering data, like a title or classes.
+    ///
+    /// Available only when enabled via [`Options::metadata`](crate::Options::metadata).
+    pub fn metadata(&self) -> Option<&Metadata> {
+        match self {
+            Node::Group(e) => e.metadata(),
+            Node::Path(e) => e.metadata(),
+            Node::Image(e) => e.metadata(),
+            Node::Text(e) => e.metadata(),
+        }
+    }
+
     /// Returns node's absolute transform.
     ///
     /// This method is cheap since absolute transforms are already resolved.
@@ -1034,15 +1146,18 @@ impl Node {
 #[derive(Clone, Debug)]
 pub struct Group {
     pub(crate) id: String,
+    pub(crate) source_span: Option<SourceSpan>,
+    pub(crate) metadata: Option<Box<Metadata>>,
     pub(crate) transform: Transform,
     pub(crate) abs_transform: Transform,
     pub(crate) opacity: Opacity,
     pub(crate) blend_mode: BlendMode,
     pub(crate) isolate: bool,
+    pub(crate) color_interpolation: filter::ColorInterpolation,
     pub(crate) clip_path: Option<Arc<ClipPath>>,
     /// Whether the group is a context element (i.e. a use node)
     pub(crate) is_context_element: bool,
-    pub(crate) mask: Option<Arc<Mask>>,
+    pub(crate) masks: Vec<MaskLayer>,
     pub(crate) filters: Vec<Arc<filter::Filter>>,
     pub(crate) bounding_box: Rect,
     pub(crate) abs_bounding_box: Rect,
@@ -1058,13 +1173,16 @@ impl Group {
         let dummy = Rect::from_xywh(0.0, 0.0, 0.0, 0.0).unwrap();
         Group {
             id: String::new(),
+            source_span: None,
+            metadata: None,
             transform: Transform::default(),
             abs_transform: Transform::default(),
             opacity: Opacity::ONE,
             blend_mode: BlendMode::Normal,
             isolate: false,
+            color_interpolation: filter::ColorInterpolation::SRGB,
             clip_path: None,
-            mask: None,
+            masks: Vec::new(),
             filters: Vec::new(),
             is_context_element: false,
             bounding_box: dummy,
@@ -1086,6 +1204,20 @@ impl Group {
         &self.id
     }
 
+    /// Element's location in the source SVG.
+    ///
+    /// Available only when [`Options::source_spans`](crate::Options::source_spans) is set.
+    pub fn source_span(&self) -> Option<&SourceSpan> {
+        self.source_span.as_ref()
+    }
+
+    /// Element's non-rendering data, like a title or classes.
+    ///
+    /// Available only when enabled via [`Options::metadata`](crate::Options::metadata).
+    pub fn metadata(&self) -> Option<&Metadata> {
+        self.metadata.as_deref()
+    }
+
     /// Element's transform.
     ///
     /// This is a relative transform. The one that is set via the `transform` attribute in SVG.
@@ -1125,14 +1257,23 @@ impl Group {
         self.isolate
     }
 
+    /// Color space in which group's children are composited.
+    ///
+    /// `color-interpolation` in SVG. Unlike in filters, the default is `ColorInterpolation::SRGB`.
+    pub fn color_interpolation(&self) -> filter::ColorInterpolation {
+        self.color_interpolation
+    }
+
     /// Element's clip path.
     pub fn clip_path(&self) -> Option<&ClipPath> {
         self.clip_path.as_deref()
     }
 
-    /// Element's mask.
-    pub fn mask(&self) -> Option<&Mask> {
-        self.mask.as_deref()
+    /// Element's mask layers.
+    ///
+    /// The first layer is the topmost one.
+    pub fn masks(&self) -> &[MaskLayer] {
+        &self.masks
     }
 
     /// Element's filters.
@@ -1202,9 +1343,11 @@ impl Group {
         self.isolate
             || self.opacity != Opacity::ONE
             || self.clip_path.is_some()
-            || self.mask.is_some()
+            || !self.masks.is_empty()
             || !self.filters.is_empty()
             || self.blend_mode != BlendMode::Normal // TODO: probably not needed?
+            || (self.color_interpolation == filter::ColorInterpolation::LinearRGB
+                && self.children.len() > 1)
     }
 
     /// Returns `true` if the group has any children.
@@ -1240,10 +1383,10 @@ impl Group {
             }
         }
 
-        if let Some(ref mask) = self.mask {
-            f(&mask.root);
+        for layer in &self.masks {
+            f(&layer.mask.root);
 
-            if let Some(ref sub_mask) = mask.mask {
+            if let Some(ref sub_mask) = layer.mask.mask {
                 f(&sub_mask.root);
             }
         }
@@ -1281,6 +1424,8 @@ impl Default for PaintOrder {
 #[derive(Clone, Debug)]
 pub struct Path {
     pub(crate) id: String,
+    pub(crate) source_span: Option<SourceSpan>,
+    pub(crate) metadata: Option<Box<Metadata>>,
     pub(crate) visible: bool,
     pub(crate) fill: Option<Fill>,
     pub(crate) stroke: Option<Stroke>,
@@ -1339,6 +1484,8 @@ impl Path {
 
         Some(Path {
             id,
+            source_span: None,
+            metadata: None,
             visible,
             fill,
             stroke,
@@ -1362,6 +1509,20 @@ impl Path {
         &self.id
     }
 
+    /// Element's location in the source SVG.
+    ///
+    /// Available only when [`Options::source_spans`](crate::Options::source_spans) is set.
+    pub fn source_span(&self) -> Option<&SourceSpan> {
+        self.source_span.as_ref()
+    }
+
+    /// Element's non-rendering data, like a title or classes.
+    ///
+    /// Available only when enabled via [`Options::metadata`](crate::Options::metadata).
+    pub fn metadata(&self) -> Option<&Metadata> {
+        self.metadata.as_deref()
+    }
+
     /// Element visibility.
     pub fn is_visible(&self) -> bool {
         self.visible
@@ -1441,6 +1602,7 @@ impl Path {
     }
 
     fn calculate_stroke_bbox(stroke: Option<&Stroke>, path: &tiny_skia_path::Path) -> Option<Rect> {
+        let linejoin = stroke?.linejoin;
         let mut stroke = stroke?.to_tiny_skia();
         // According to the spec, dash should not be accounted during bbox calculation.
         stroke.dash = None;
@@ -1448,7 +1610,7 @@ impl Path {
         // TODO: avoid for round and bevel caps
 
         // Expensive, but there is not much we can do about it.
-        if let Some(stroked_path) = path.stroke(&stroke, 1.0) {
+        if let Some(stroked_path) = stroke::outline(path, &stroke, linejoin, 1.0) {
             return stroked_path.compute_tight_bounds();
         }
 
@@ -1488,8 +1650,7 @@ impl ImageKind {
             | ImageKind::GIF(data)
             | ImageKind::WEBP(data) => imagesize::blob_size(data)
                 .ok()
-                .and_then(|size| Size::from_wh(size.width as f32, size.height as f32))
-                .log_none(|| log::warn!("Image has an invalid size. Skipped.")),
+                .and_then(|size| Size::from_wh(size.width as f32, size.height as f32)),
             ImageKind::SVG(svg) => Some(svg.size),
         }
     }
@@ -1513,6 +1674,8 @@ impl std::fmt::Debug for ImageKind {
 #[derive(Clone, Debug)]
 pub struct Image {
     pub(crate) id: String,
+    pub(crate) source_span: Option<SourceSpan>,
+    pub(crate) metadata: Option<Box<Metadata>>,
     pub(crate) visible: bool,
     pub(crate) size: Size,
     pub(crate) rendering_mode: ImageRendering,
@@ -1531,6 +1694,20 @@ impl Image {
         &self.id
     }
 
+    /// Element's location in the source SVG.
+    ///
+    /// Available only when [`Options::source_spans`](crate::Options::source_spans) is set.
+    pub fn source_span(&self) -> Option<&SourceSpan> {
+        self.source_span.as_ref()
+    }
+
+    /// Element's non-rendering data, like a title or classes.
+    ///
+    /// Available only when enabled via [`Options::metadata`](crate::Options::metadata).
+    pub fn metadata(&self) -> Option<&Metadata> {
+        self.metadata.as_deref()
+    }
+
     /// Element visibility.
     pub fn is_visible(&self) -> bool {
         self.visible
@@ -1593,6 +1770,7 @@ impl Image {
 pub struct Tree {
     pub(crate) size: Size,
     pub(crate) root: Group,
+    pub(crate) metadata: Option<Box<Metadata>>,
     pub(crate) linear_gradients: Vec<Arc<LinearGradient>>,
     pub(crate) radial_gradients: Vec<Arc<RadialGradient>>,
     pub(crate) patterns: Vec<Arc<Pattern>>,
@@ -1623,6 +1801,13 @@ impl Tree {
         &self.root
     }
 
+    /// The root `svg` element's non-rendering data, like a document title.
+    ///
+    /// Available only when enabled via [`Options::metadata`](crate::Options::metadata).
+    pub fn metadata(&self) -> Option<&Metadata> {
+        self.metadata.as_deref()
+    }
+
     /// Returns a renderable node by ID.
     ///
     /// If an empty ID is provided, than this method will always return `None`.
@@ -1808,7 +1993,8 @@ impl Group {
     pub(crate) fn collect_masks(&self, masks: &mut Vec<Arc<Mask>>) {
         for node in self.children() {
             if let Node::Group(g) = node {
-                if let Some(mask) = &g.mask {
+                for layer in &g.masks {
+                    let mask = &layer.mask;
                     if !masks.iter().any(|other| Arc::ptr_eq(mask, other)) {
                         masks.push(mask.clone());
                     }
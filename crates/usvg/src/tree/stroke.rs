@@ -0,0 +1,441 @@
+// Copyright 2026 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use kurbo::{Point, Vec2};
+use tiny_skia_path::{PathBuilder, PathSegment};
+
+use super::LineJoin;
+
+/// Strokes a path, handling line joins tiny-skia doesn't support natively.
+///
+/// Unlike tiny-skia's stroker output, the returned path may contain overlapping contours,
+/// therefore it must be filled using the non-zero rule.
+pub(crate) fn outline(
+    path: &tiny_skia_path::Path,
+    stroke: &tiny_skia_path::Stroke,
+    linejoin: LineJoin,
+    res_scale: f32,
+) -> Option<tiny_skia_path::Path> {
+    if linejoin != LineJoin::Arcs {
+        return path.stroke(stroke, res_scale);
+    }
+
+    let dashed;
+    let path = match stroke.dash {
+        Some(ref dash) => {
+            dashed = path.dash(dash, res_scale)?;
+            &dashed
+        }
+        None => path,
+    };
+
+    // Stroke with bevel joins first and then fill the area between
+    // the bevel and the actual join with separate contours.
+    let mut bevel_stroke = stroke.clone();
+    bevel_stroke.dash = None;
+    bevel_stroke.line_join = tiny_skia_path::LineJoin::Bevel;
+    let stroked = path.stroke(&bevel_stroke, res_scale)?;
+
+    let mut builder = PathBuilder::new();
+    builder.push_path(&stroked);
+
+    let joiner = ArcsJoiner {
+        half_width: stroke.width as f64 / 2.0,
+        miter_limit: stroke.miter_limit as f64,
+        tolerance: 0.25 / res_scale.max(f32::EPSILON) as f64,
+        orientation: stroker_orientation()?,
+    };
+
+    for_each_join(path, |p, incoming, outgoing| {
+        joiner.push_join(p, incoming, outgoing, &mut builder);
+    });
+
+    builder.finish()
+}
+
+/// A direction and a signed curvature of a segment at one of its ends.
+#[derive(Clone, Copy, Debug)]
+struct EdgeEnd {
+    tangent: Vec2,
+    curvature: f64,
+}
+
+struct ArcsJoiner {
+    half_width: f64,
+    miter_limit: f64,
+    tolerance: f64,
+    orientation: f64,
+}
+
+impl ArcsJoiner {
+    fn push_join(&self, p: Point, incoming: EdgeEnd, outgoing: EdgeEnd, builder: &mut PathBuilder) {
+        let (t1, t2) = (incoming.tangent, outgoing.tangent);
+        let cross = t1.cross(t2);
+        if cross.abs() < 1e-9 && t1.dot(t2) > 0.0 {
+            // Collinear segments do not need a join.
+            return;
+        }
+
+        // The join is drawn on the outer side of the turn.
+        let outer_normal = |t: Vec2| {
+            if cross > 0.0 {
+                Vec2::new(t.y, -t.x)
+            } else {
+                Vec2::new(-t.y, t.x)
+            }
+        };
+        let n1 = outer_normal(t1);
+        let n2 = outer_normal(t2);
+        let a = p + n1 * self.half_width;
+        let b = p + n2 * self.half_width;
+
+        let mut polygon = vec![p, a];
+        let edge1 = OuterEdge::new(p, a, incoming);
+        let edge2 = OuterEdge::new(p, b, outgoing);
+        match intersect_edges(&edge1, a, t1, &edge2, b, t2, p) {
+            Some(x) => {
+                edge1.flatten(a, x, t1, self.tolerance, &mut polygon);
+                let mut tail = vec![b];
+                edge2.flatten(b, x, -t2, self.tolerance, &mut tail);
+                tail.reverse();
+                polygon.extend(tail);
+            }
+            None => {
+                // Fallback to `miter-clip` when the extended edges never meet.
+                let limit = self.miter_limit * self.half_width * 2.0;
+                match line_line(a, t1, b, t2) {
+                    Some((s, u)) if s >= 0.0 && u <= 0.0 => polygon.push(a + t1 * s),
+                    _ => {
+                        polygon.push(a + t1 * limit);
+                        polygon.push(b - t2 * limit);
+                    }
+                }
+                polygon.push(b);
+            }
+        }
+
+        // Both `arcs` and `miter-clip` joins are clipped by a line perpendicular
+        // to the angle bisector at a `stroke-miterlimit * stroke-width / 2` distance.
+        let mut bisector = n1 + n2;
+        if bisector.hypot() < 1e-9 {
+            bisector = t1;
+        }
+        let bisector = bisector.normalize();
+        let mut polygon = clip_polygon(&polygon, p, bisector, self.miter_limit * self.half_width);
+        if polygon.len() < 3 {
+            return;
+        }
+
+        // Join contours must have the same orientation as the stroker output,
+        // otherwise overlapping regions would cancel each other out.
+        if signed_area(&polygon) * self.orientation < 0.0 {
+            polygon.reverse();
+        }
+
+        builder.move_to(polygon[0].x as f32, polygon[0].y as f32);
+        for pt in &polygon[1..] {
+            builder.line_to(pt.x as f32, pt.y as f32);
+        }
+        builder.close();
+    }
+}
+
+/// An outer stroke edge extended past a join point.
+///
+/// Either a line or an osculating circle of the offset curve.
+enum OuterEdge {
+    Line,
+    Circle { center: Point, radius: f64 },
+}
+
+impl OuterEdge {
+    fn new(p: Point, offset: Point, end: EdgeEnd) -> Self {
+        // Parallel curves share the center of curvature.
+        if end.curvature.abs() < 1e-6 {
+            return OuterEdge::Line;
+        }
+
+        let t = end.tangent;
+        let center = p + Vec2::new(-t.y, t.x) * (1.0 / end.curvature);
+        let radius = (offset - center).hypot();
+        if radius < 1e-6 {
+            return OuterEdge::Line;
+        }
+
+        OuterEdge::Circle { center, radius }
+    }
+
+    /// Appends points from `from` (exclusive) to `to` (inclusive),
+    /// starting in the `direction` direction.
+    fn flatten(
+        &self,
+        from: Point,
+        to: Point,
+        direction: Vec2,
+        tolerance: f64,
+        points: &mut Vec<Point>,
+    ) {
+        if let OuterEdge::Circle { center, radius } = *self {
+            let v0 = from - center;
+            let v1 = to - center;
+            let ccw = v0.cross(direction) > 0.0;
+            let mut sweep = v0.cross(v1).atan2(v0.dot(v1));
+            if ccw && sweep < 0.0 {
+                sweep += std::f64::consts::TAU;
+            } else if !ccw && sweep > 0.0 {
+                sweep -= std::f64::consts::TAU;
+            }
+
+            let step = 2.0 * (1.0 - (tolerance / radius).min(1.0)).acos();
+            let count = (sweep.abs() / step.max(1e-3)).ceil().clamp(1.0, 256.0) as usize;
+            let start_angle = v0.atan2();
+            for i in 1..count {
+                let angle = start_angle + sweep * i as f64 / count as f64;
+                points.push(center + Vec2::from_angle(angle) * radius);
+            }
+        }
+
+        points.push(to);
+    }
+}
+
+/// Finds where the extensions of two outer edges meet.
+///
+/// The first edge is extended forward from its end, the second one backward from its start.
+/// Returns the intersection closest to the join point.
+fn intersect_edges(
+    edge1: &OuterEdge,
+    a: Point,
+    t1: Vec2,
+    edge2: &OuterEdge,
+    b: Point,
+    t2: Vec2,
+    p: Point,
+) -> Option<Point> {
+    let mut candidates = Vec::with_capacity(2);
+    match (edge1, edge2) {
+        (OuterEdge::Line, OuterEdge::Line) => {
+            if let Some((s, _)) = line_line(a, t1, b, t2) {
+                candidates.push(a + t1 * s);
+            }
+        }
+        (OuterEdge::Line, OuterEdge::Circle { center, radius }) => {
+            line_circle(a, t1, *center, *radius, &mut candidates);
+        }
+        (OuterEdge::Circle { center, radius }, OuterEdge::Line) => {
+            line_circle(b, t2, *center, *radius, &mut candidates);
+        }
+        (
+            OuterEdge::Circle {
+                center: c1,
+                radius: r1,
+            },
+            OuterEdge::Circle {
+                center: c2,
+                radius: r2,
+            },
+        ) => {
+            circle_circle(*c1, *r1, *c2, *r2, &mut candidates);
+        }
+    }
+
+    const EPS: f64 = 1e-6;
+    candidates
+        .into_iter()
+        .filter(|x| (*x - a).dot(t1) >= -EPS && (*x - b).dot(t2) <= EPS)
+        .min_by(|x1, x2| x1.distance(p).total_cmp(&x2.distance(p)))
+}
+
+fn line_circle(origin: Point, dir: Vec2, center: Point, radius: f64, out: &mut Vec<Point>) {
+    // `dir` is normalized, so the quadratic's leading coefficient is 1.
+    let oc = origin - center;
+    let half_b = dir.dot(oc);
+    let c = oc.hypot2() - radius * radius;
+    let discriminant = half_b * half_b - c;
+    if discriminant < 0.0 {
+        return;
+    }
+
+    let root = discriminant.sqrt();
+    out.push(origin + dir * (-half_b - root));
+    out.push(origin + dir * (-half_b + root));
+}
+
+fn circle_circle(c1: Point, r1: f64, c2: Point, r2: f64, out: &mut Vec<Point>) {
+    let d = c2 - c1;
+    let dist = d.hypot();
+    if dist < 1e-9 || dist > r1 + r2 || dist < (r1 - r2).abs() {
+        return;
+    }
+
+    let along = (r1 * r1 - r2 * r2 + dist * dist) / (2.0 * dist);
+    let across = (r1 * r1 - along * along).max(0.0).sqrt();
+    let base = c1 + d * (along / dist);
+    let perp = Vec2::new(-d.y, d.x) * (across / dist);
+    out.push(base + perp);
+    out.push(base - perp);
+}
+
+fn line_line(a: Point, t1: Vec2, b: Point, t2: Vec2) -> Option<(f64, f64)> {
+    let denom = t1.cross(t2);
+    if denom.abs() < 1e-12 {
+        return None;
+    }
+
+    let d = b - a;
+    Some((d.cross(t2) / denom, d.cross(t1) / denom))
+}
+
+/// Keeps the part of a polygon for which `dot(q - p, normal) <= limit`.
+fn clip_polygon(polygon: &[Point], p: Point, normal: Vec2, limit: f64) -> Vec<Point> {
+    let dist = |q: Point| (q - p).dot(normal) - limit;
+
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    for (i, &curr) in polygon.iter().enumerate() {
+        let prev = polygon[(i + polygon.len() - 1) % polygon.len()];
+        let (d_prev, d_curr) = (dist(prev), dist(curr));
+        if (d_prev <= 0.0) != (d_curr <= 0.0) {
+            let t = d_prev / (d_prev - d_curr);
+            clipped.push(prev.lerp(curr, t));
+        }
+
+        if d_curr <= 0.0 {
+            clipped.push(curr);
+        }
+    }
+
+    clipped
+}
+
+fn signed_area(polygon: &[Point]) -> f64 {
+    let mut area = 0.0;
+    for (i, curr) in polygon.iter().enumerate() {
+        let next = polygon[(i + 1) % polygon.len()];
+        area += curr.x * next.y - next.x * curr.y;
+    }
+
+    area / 2.0
+}
+
+/// Returns the orientation of contours produced by tiny-skia's stroker.
+///
+/// The stroker always emits contours with the same orientation,
+/// no matter the direction of the source path.
+fn stroker_orientation() -> Option<f64> {
+    let mut builder = PathBuilder::new();
+    builder.move_to(0.0, 0.0);
+    builder.line_to(1.0, 0.0);
+    let stroked = builder
+        .finish()?
+        .stroke(&tiny_skia_path::Stroke::default(), 1.0)?;
+
+    let points: Vec<_> = stroked
+        .points()
+        .iter()
+        .map(|p| Point::new(p.x as f64, p.y as f64))
+        .collect();
+    Some(signed_area(&points).signum())
+}
+
+/// Calls `f` for each join in a path.
+///
+/// Zero-length segments are ignored, since they have no direction.
+fn for_each_join<F: FnMut(Point, EdgeEnd, EdgeEnd)>(path: &tiny_skia_path::Path, mut f: F) {
+    let to_point = |p: tiny_skia_path::Point| Point::new(p.x as f64, p.y as f64);
+
+    let mut start = Point::ZERO;
+    let mut last = Point::ZERO;
+    let mut first: Option<EdgeEnd> = None;
+    let mut prev: Option<EdgeEnd> = None;
+    for segment in path.segments() {
+        let (ends, end_point) = match segment {
+            PathSegment::MoveTo(p) => {
+                start = to_point(p);
+                last = start;
+                first = None;
+                prev = None;
+                continue;
+            }
+            PathSegment::LineTo(p) => {
+                let p = to_point(p);
+                (line_ends(last, p), p)
+            }
+            PathSegment::QuadTo(p1, p) => {
+                let (p1, p) = (to_point(p1), to_point(p));
+                (quad_ends(last, p1, p), p)
+            }
+            PathSegment::CubicTo(p1, p2, p) => {
+                let (p1, p2, p) = (to_point(p1), to_point(p2), to_point(p));
+                (cubic_ends(last, p1, p2, p), p)
+            }
+            PathSegment::Close => {
+                if let Some((s, e)) = line_ends(last, start) {
+                    if let Some(prev) = prev {
+                        f(last, prev, s);
+                    }
+
+                    first = first.or(Some(s));
+                    prev = Some(e);
+                }
+
+                if let (Some(prev), Some(first)) = (prev, first) {
+                    f(start, prev, first);
+                }
+
+                last = start;
+                first = None;
+                prev = None;
+                continue;
+            }
+        };
+
+        if let Some((s, e)) = ends {
+            if let Some(prev) = prev {
+                f(last, prev, s);
+            }
+
+            first = first.or(Some(s));
+            prev = Some(e);
+        }
+
+        last = end_point;
+    }
+}
+
+fn edge_end(d1: Vec2, d2: Vec2) -> Option<EdgeEnd> {
+    let len = d1.hypot();
+    if len.is_nan() || len <= 1e-9 {
+        return None;
+    }
+
+    Some(EdgeEnd {
+        tangent: d1 / len,
+        curvature: d1.cross(d2) / (len * len * len),
+    })
+}
+
+fn line_ends(p0: Point, p1: Point) -> Option<(EdgeEnd, EdgeEnd)> {
+    let end = edge_end(p1 - p0, Vec2::ZERO)?;
+    Some((end, end))
+}
+
+fn quad_ends(p0: Point, p1: Point, p2: Point) -> Option<(EdgeEnd, EdgeEnd)> {
+    let dd = (p2.to_vec2() - p1.to_vec2() * 2.0 + p0.to_vec2()) * 2.0;
+    let chord = line_ends(p0, p2);
+    let start = edge_end((p1 - p0) * 2.0, dd).or(chord.map(|c| c.0))?;
+    let end = edge_end((p2 - p1) * 2.0, dd).or(chord.map(|c| c.1))?;
+    Some((start, end))
+}
+
+fn cubic_ends(p0: Point, p1: Point, p2: Point, p3: Point) -> Option<(EdgeEnd, EdgeEnd)> {
+    let dd0 = (p2.to_vec2() - p1.to_vec2() * 2.0 + p0.to_vec2()) * 6.0;
+    let dd1 = (p3.to_vec2() - p2.to_vec2() * 2.0 + p1.to_vec2()) * 6.0;
+    let chord = line_ends(p0, p3);
+    let start = edge_end((p1 - p0) * 3.0, dd0)
+        .or_else(|| edge_end(p2 - p0, Vec2::ZERO))
+        .or(chord.map(|c| c.0))?;
+    let end = edge_end((p3 - p2) * 3.0, dd1)
+        .or_else(|| edge_end(p3 - p1, Vec2::ZERO))
+        .or(chord.map(|c| c.1))?;
+    Some((start, end))
+}
@@ -8,7 +8,10 @@ pub use svgtypes::FontFamily;
 
 #[cfg(feature = "text")]
 use crate::layout::Span;
-use crate::{Fill, Group, NonEmptyString, PaintOrder, Rect, Stroke, TextRendering, Transform};
+use crate::{
+    Fill, Group, Metadata, NonEmptyString, PaintOrder, Rect, SourceSpan, Stroke, TextRendering,
+    Transform,
+};
 
 /// A font stretch property.
 #[allow(missing_docs)]
@@ -575,6 +578,8 @@ pub enum WritingMode {
 #[derive(Clone, Debug)]
 pub struct Text {
     pub(crate) id: String,
+    pub(crate) source_span: Option<SourceSpan>,
+    pub(crate) metadata: Option<Box<Metadata>>,
     pub(crate) rendering_mode: TextRendering,
     pub(crate) dx: Vec<f32>,
     pub(crate) dy: Vec<f32>,
@@ -601,6 +606,20 @@ impl Text {
         &self.id
     }
 
+    /// Element's location in the source SVG.
+    ///
+    /// Available only when [`Options::source_spans`](crate::Options::source_spans) is set.
+    pub fn source_span(&self) -> Option<&SourceSpan> {
+        self.source_span.as_ref()
+    }
+
+    /// Element's non-rendering data, like a title or classes.
+    ///
+    /// Available only when enabled via [`Options::metadata`](crate::Options::metadata).
+    pub fn metadata(&self) -> Option<&Metadata> {
+        self.metadata.as_deref()
+    }
+
     /// Rendering mode.
     ///
     /// `text-rendering` in SVG.
@@ -149,10 +149,25 @@ pub(crate) fn convert(tree: &Tree, opt: &WriteOptions) -> String {
     xml.write_svg_attribute(AId::Width, &tree.size.width());
     xml.write_svg_attribute(AId::Height, &tree.size.height());
     xml.write_attribute("xmlns", "http://www.w3.org/2000/svg");
-    if has_xlink(&tree.root) {
+    let has_xlink = has_xlink(&tree.root);
+    if has_xlink {
         xml.write_attribute("xmlns:xlink", "http://www.w3.org/1999/xlink");
     }
 
+    let mut namespaces = Vec::new();
+    collect_metadata_namespaces(tree.metadata(), &mut namespaces);
+    collect_tree_metadata_namespaces(&tree.root, &mut namespaces);
+    for (prefix, uri) in namespaces {
+        if has_xlink && prefix == "xlink" {
+            continue;
+        }
+
+        xml.write_attribute_fmt(&format!("xmlns:{}", prefix), format_args!("{}", uri));
+    }
+
+    xml.write_metadata_attributes(tree.metadata());
+    xml.write_metadata_elements(tree.metadata());
+
     let has_text_paths = has_text_paths(&tree.root);
     if tree.has_defs_nodes() || has_text_paths {
         write_defs(tree, opt, &mut xml, has_text_paths);
@@ -211,6 +226,9 @@ fn write_filters(tree: &Tree, opt: &WriteOptions, xml: &mut XmlWriter) {
                         AId::StdDeviation.to_str(),
                         format_args!("{} {}", blur.std_dev_x.get(), blur.std_dev_y.get()),
                     );
+                    if blur.edge_mode != filter::EdgeMode::None {
+                        xml.write_svg_attribute(AId::EdgeMode, edge_mode_to_str(blur.edge_mode));
+                    }
                     xml.write_svg_attribute(AId::Result, &fe.result);
                     xml.end_element();
                 }
@@ -229,6 +247,9 @@ fn write_filters(tree: &Tree, opt: &WriteOptions, xml: &mut XmlWriter) {
                     xml.write_filter_input(AId::In, &blend.input1);
                     xml.write_filter_input(AId::In2, &blend.input2);
                     xml.write_svg_attribute(AId::Mode, &blend.mode.to_string());
+                    if blend.no_composite {
+                        xml.write_svg_attribute(AId::NoComposite, "");
+                    }
                     xml.write_svg_attribute(AId::Result, &fe.result);
                     xml.end_element();
                 }
@@ -356,14 +377,7 @@ fn write_filters(tree: &Tree, opt: &WriteOptions, xml: &mut XmlWriter) {
                     xml.write_svg_attribute(AId::Bias, &matrix.bias);
                     xml.write_svg_attribute(AId::TargetX, &matrix.matrix.target_x);
                     xml.write_svg_attribute(AId::TargetY, &matrix.matrix.target_y);
-                    xml.write_svg_attribute(
-                        AId::EdgeMode,
-                        match matrix.edge_mode {
-                            filter::EdgeMode::None => "none",
-                            filter::EdgeMode::Duplicate => "duplicate",
-                            filter::EdgeMode::Wrap => "wrap",
-                        },
-                    );
+                    xml.write_svg_attribute(AId::EdgeMode, edge_mode_to_str(matrix.edge_mode));
                     xml.write_svg_attribute(
                         AId::PreserveAlpha,
                         if matrix.preserve_alpha {
@@ -657,6 +671,7 @@ fn write_element(node: &Node, is_clip_path: bool, opt: &WriteOptions, xml: &mut
             if !img.id.is_empty() {
                 xml.write_id_attribute(&img.id, opt);
             }
+            xml.write_metadata_attributes(img.metadata());
 
             xml.write_svg_attribute(AId::Width, &img.size().width());
             xml.write_svg_attribute(AId::Height, &img.size().height());
@@ -683,6 +698,7 @@ fn write_element(node: &Node, is_clip_path: bool, opt: &WriteOptions, xml: &mut
             }
 
             xml.write_image_data(&img.kind);
+            xml.write_metadata_elements(img.metadata());
 
             xml.end_element();
         }
@@ -696,6 +712,7 @@ fn write_element(node: &Node, is_clip_path: bool, opt: &WriteOptions, xml: &mut
                 if !text.id.is_empty() {
                     xml.write_id_attribute(&text.id, opt);
                 }
+                xml.write_metadata_attributes(text.metadata());
 
                 xml.write_attribute("xml:space", "preserve");
 
@@ -726,6 +743,8 @@ fn write_element(node: &Node, is_clip_path: bool, opt: &WriteOptions, xml: &mut
                     xml.write_numbers(AId::Dy, &text.dy);
                 }
 
+                xml.write_metadata_elements(text.metadata());
+
                 xml.set_preserve_whitespaces(true);
 
                 for chunk in &text.chunks {
@@ -856,13 +875,20 @@ fn write_group_element(g: &Group, is_clip_path: bool, opt: &WriteOptions, xml: &
     if !g.id.is_empty() {
         xml.write_id_attribute(&g.id, opt);
     };
+    xml.write_metadata_attributes(g.metadata());
 
     if let Some(ref clip) = g.clip_path {
         xml.write_func_iri(AId::ClipPath, clip.id(), opt);
     }
 
-    if let Some(ref mask) = g.mask {
-        xml.write_func_iri(AId::Mask, mask.id(), opt);
+    if !g.masks.is_empty() {
+        let prefix = opt.id_prefix.as_deref().unwrap_or_default();
+        let ids: Vec<_> = g
+            .masks
+            .iter()
+            .map(|layer| format!("url(#{}{})", prefix, layer.mask.id()))
+            .collect();
+        xml.write_svg_attribute(AId::Mask, &ids.join(", "));
     }
 
     if !g.filters.is_empty() {
@@ -879,18 +905,59 @@ fn write_group_element(g: &Group, is_clip_path: bool, opt: &WriteOptions, xml: &
         xml.write_svg_attribute(AId::Opacity, &g.opacity.get());
     }
 
+    if g.color_interpolation == filter::ColorInterpolation::LinearRGB {
+        xml.write_svg_attribute(AId::ColorInterpolation, "linearRGB");
+    }
+
     xml.write_transform(AId::Transform, g.transform, opt);
 
+    let mut style = Vec::new();
     if g.blend_mode != BlendMode::Normal || g.isolate {
         // For reasons unknown, `mix-blend-mode` and `isolation` must be written
         // as `style` attribute.
         let isolation = if g.isolate { "isolate" } else { "auto" };
-        xml.write_attribute_fmt(
-            AId::Style.to_str(),
-            format_args!("mix-blend-mode:{};isolation:{}", g.blend_mode, isolation),
-        );
+        style.push(format!(
+            "mix-blend-mode:{};isolation:{}",
+            g.blend_mode, isolation
+        ));
     }
 
+    // Mask longhands are CSS-only properties as well.
+    if g.masks.iter().any(|layer| layer.mode != layer.mask.kind) {
+        let modes: Vec<_> = g
+            .masks
+            .iter()
+            .map(|layer| match layer.mode {
+                _ if layer.mode == layer.mask.kind => "match-source",
+                MaskType::Luminance => "luminance",
+                MaskType::Alpha => "alpha",
+            })
+            .collect();
+        style.push(format!("mask-mode:{}", modes.join(",")));
+    }
+
+    if g.masks
+        .iter()
+        .any(|layer| layer.composite != MaskComposite::Add)
+    {
+        let composites: Vec<_> = g
+            .masks
+            .iter()
+            .map(|layer| match layer.composite {
+                MaskComposite::Add => "add",
+                MaskComposite::Subtract => "subtract",
+                MaskComposite::Intersect => "intersect",
+                MaskComposite::Exclude => "exclude",
+            })
+            .collect();
+        style.push(format!("mask-composite:{}", composites.join(",")));
+    }
+
+    if !style.is_empty() {
+        xml.write_svg_attribute(AId::Style, &style.join(";"));
+    }
+
+    xml.write_metadata_elements(g.metadata());
     write_elements(g, false, opt, xml);
 
     xml.end_element();
@@ -911,6 +978,9 @@ trait XmlWriterExt {
     fn write_filter_input(&mut self, id: AId, input: &filter::Input);
     fn write_filter_primitive_attrs(&mut self, parent_rect: NonZeroRect, fe: &filter::Primitive);
     fn write_filter_transfer_function(&mut self, eid: EId, fe: &filter::TransferFunction);
+    fn write_metadata_attributes(&mut self, metadata: Option<&Metadata>);
+    fn write_metadata_elements(&mut self, metadata: Option<&Metadata>);
+    fn write_xml_element(&mut self, element: &XmlElement, preserve_whitespaces: bool);
 }
 
 impl XmlWriterExt for XmlWriter {
@@ -1104,6 +1174,113 @@ impl XmlWriterExt for XmlWriter {
             enc.finish().unwrap();
         });
     }
+
+    fn write_metadata_attributes(&mut self, metadata: Option<&Metadata>) {
+        let Some(metadata) = metadata else {
+            return;
+        };
+
+        if !metadata.classes.is_empty() {
+            self.write_attribute("class", &escape_attribute(&metadata.classes.join(" ")));
+        }
+
+        for attr in &metadata.attributes {
+            let value = escape_attribute(&attr.value);
+            match attr.prefix {
+                Some(ref prefix) => {
+                    self.write_attribute(&format!("{}:{}", prefix, attr.name), &value);
+                }
+                None => self.write_attribute(&attr.name, &value),
+            }
+        }
+    }
+
+    fn write_metadata_elements(&mut self, metadata: Option<&Metadata>) {
+        let Some(metadata) = metadata else {
+            return;
+        };
+
+        for (name, text) in [("title", &metadata.title), ("desc", &metadata.description)] {
+            if let Some(text) = text {
+                self.start_element(name);
+                self.set_preserve_whitespaces(true);
+                self.write_text(&text.replace('&', "&amp;"));
+                self.end_element();
+                self.set_preserve_whitespaces(false);
+            }
+        }
+
+        if let Some(ref element) = metadata.metadata {
+            self.write_xml_element(element, false);
+        }
+    }
+
+    fn write_xml_element(&mut self, element: &XmlElement, preserve_whitespaces: bool) {
+        self.start_element(&element.name);
+        for (name, value) in &element.attributes {
+            self.write_attribute(name, &escape_attribute(value));
+        }
+
+        // Indenting mixed content would alter the text.
+        let preserve_children = preserve_whitespaces
+            || element
+                .children
+                .iter()
+                .any(|child| matches!(child, XmlNode::Text(_)));
+        self.set_preserve_whitespaces(preserve_children);
+
+        for child in &element.children {
+            match child {
+                XmlNode::Element(child) => self.write_xml_element(child, preserve_children),
+                XmlNode::Text(text) => self.write_text(&text.replace('&', "&amp;")),
+            }
+        }
+
+        self.end_element();
+        self.set_preserve_whitespaces(preserve_whitespaces);
+    }
+}
+
+/// Escapes characters that `xmlwriter` keeps as is in attribute values.
+fn escape_attribute(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;")
+}
+
+/// Collects namespace declarations required by preserved attributes.
+fn collect_tree_metadata_namespaces(parent: &Group, namespaces: &mut Vec<(String, String)>) {
+    for node in &parent.children {
+        collect_metadata_namespaces(node.metadata(), namespaces);
+
+        if let Node::Group(g) = node {
+            collect_tree_metadata_namespaces(g, namespaces);
+        }
+
+        node.subroots(|root| collect_tree_metadata_namespaces(root, namespaces));
+    }
+}
+
+fn collect_metadata_namespaces(
+    metadata: Option<&Metadata>,
+    namespaces: &mut Vec<(String, String)>,
+) {
+    let Some(metadata) = metadata else {
+        return;
+    };
+
+    for attr in &metadata.attributes {
+        let (Some(prefix), Some(uri)) = (&attr.prefix, &attr.namespace) else {
+            continue;
+        };
+
+        // The `xml` prefix is predefined.
+        if prefix == "xml" {
+            continue;
+        }
+
+        if !namespaces.iter().any(|(p, _)| p == prefix) {
+            namespaces.push((prefix.clone(), uri.clone()));
+        }
+    }
 }
 
 fn has_xlink(parent: &Group) -> bool {
@@ -1120,7 +1297,8 @@ fn has_xlink(parent: &Group) -> bool {
                     }
                 }
 
-                if let Some(mask) = &g.mask {
+                for layer in &g.masks {
+                    let mask = &layer.mask;
                     if has_xlink(mask.root()) {
                         return true;
                     }
@@ -1171,6 +1349,10 @@ fn write_base_grad(g: &BaseGradient, opt: &WriteOptions, xml: &mut XmlWriter) {
         SpreadMethod::Repeat => xml.write_svg_attribute(AId::SpreadMethod, "repeat"),
     }
 
+    if g.color_interpolation == filter::ColorInterpolation::LinearRGB {
+        xml.write_svg_attribute(AId::ColorInterpolation, "linearRGB");
+    }
+
     for s in &g.stops {
         xml.start_svg_element(EId::Stop);
         xml.write_svg_attribute(AId::Offset, &s.offset.get());
@@ -1195,6 +1377,7 @@ fn write_path(
     if !path.id.is_empty() {
         xml.write_id_attribute(&path.id, opt);
     }
+    xml.write_metadata_attributes(path.metadata());
 
     write_fill(&path.fill, is_clip_path, opt, xml);
     write_stroke(&path.stroke, opt, xml);
@@ -1273,6 +1456,8 @@ fn write_path(
         buf.pop();
     });
 
+    xml.write_metadata_elements(path.metadata());
+
     xml.end_element();
 }
 
@@ -1329,6 +1514,7 @@ fn write_stroke(stroke: &Option<Stroke>, opt: &WriteOptions, xml: &mut XmlWriter
             LineJoin::MiterClip => xml.write_svg_attribute(AId::StrokeLinejoin, "miter-clip"),
             LineJoin::Round => xml.write_svg_attribute(AId::StrokeLinejoin, "round"),
             LineJoin::Bevel => xml.write_svg_attribute(AId::StrokeLinejoin, "bevel"),
+            LineJoin::Arcs => xml.write_svg_attribute(AId::StrokeLinejoin, "arcs"),
         }
 
         if let Some(ref array) = stroke.dasharray {
@@ -1357,6 +1543,14 @@ fn write_paint(aid: AId, paint: &Paint, opt: &WriteOptions, xml: &mut XmlWriter)
     }
 }
 
+fn edge_mode_to_str(mode: filter::EdgeMode) -> &'static str {
+    match mode {
+        filter::EdgeMode::None => "none",
+        filter::EdgeMode::Duplicate => "duplicate",
+        filter::EdgeMode::Wrap => "wrap",
+    }
+}
+
 fn write_light_source(light: &filter::LightSource, xml: &mut XmlWriter) {
     match light {
         filter::LightSource::DistantLight(light) => {
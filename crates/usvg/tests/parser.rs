@@ -1,9 +1,42 @@
 // Copyright 2018 the Resvg Authors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use std::sync::{Arc, Mutex};
+
 use tiny_skia_path::Rect;
 use usvg::Color;
 
+type Diagnostics = Arc<Mutex<Vec<usvg::Diagnostic>>>;
+
+/// Returns a sink that collects all diagnostics into a shared list.
+fn collecting_sink() -> (usvg::DiagnosticSink<'static>, Diagnostics) {
+    let diagnostics = Diagnostics::default();
+    let sink = usvg::DiagnosticSink {
+        report: Box::new({
+            let diagnostics = diagnostics.clone();
+            move |diagnostic| diagnostics.lock().unwrap().push(diagnostic)
+        }),
+    };
+    (sink, diagnostics)
+}
+
+/// Returns a path by its ID.
+fn path<'a>(tree: &'a usvg::Tree, id: &str) -> &'a usvg::Path {
+    match tree.node_by_id(id) {
+        Some(usvg::Node::Path(path)) => path,
+        _ => unreachable!(),
+    }
+}
+
+/// Parses an SVG with animations sampled at the specified time.
+fn tree_at(svg: &str, time: Option<f32>) -> usvg::Tree {
+    let options = usvg::Options {
+        animation_time: time,
+        ..usvg::Options::default()
+    };
+    usvg::Tree::from_str(svg, &options).unwrap()
+}
+
 #[test]
 fn gradient_stop_offset_overflowing_f32() {
     // `4e38` overflows f32 to infinity; parsing must not panic.
@@ -675,3 +708,1697 @@ fn nested_svg_abs_bounding_box() {
         Rect::from_xywh(10.0, 20.0, 10.0, 10.0).unwrap()
     );
 }
+
+#[test]
+fn stroke_linejoin_arcs_bbox() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+        <path id='path' d='M 10 50 L 50 10 L 90 50' fill='none' stroke='black'
+              stroke-width='10' stroke-linejoin='arcs' stroke-miterlimit='10'/>
+    </svg>
+    ";
+    let tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
+    let usvg::Node::Path(path) = tree.node_by_id("path").unwrap() else {
+        unreachable!()
+    };
+    assert_eq!(path.stroke().unwrap().linejoin(), usvg::LineJoin::Arcs);
+
+    // Joins between straight lines are the same as miter ones,
+    // so the bbox must include the miter tip at `10 - 5 * sqrt(2)`.
+    let top = path.stroke_bounding_box().top();
+    assert!((top - (10.0 - 5.0 * std::f32::consts::SQRT_2)).abs() < 0.01);
+}
+
+#[test]
+fn clip_path_basic_shape() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+        <g id='g1' clip-path='inset(10% round 5px) fill-box'>
+            <rect x='20' y='20' width='60' height='40'/>
+        </g>
+        <g id='g2' style='clip-path: circle(10px at 20px 30px) view-box'>
+            <rect x='20' y='20' width='60' height='40'/>
+        </g>
+    </svg>
+    ";
+    let tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
+    assert_eq!(tree.clip_paths().len(), 2);
+
+    let clip_bbox = |id: &str| {
+        let usvg::Node::Group(group) = tree.node_by_id(id).unwrap() else {
+            unreachable!()
+        };
+        group.clip_path().unwrap().root().bounding_box()
+    };
+
+    assert_eq!(
+        clip_bbox("g1"),
+        Rect::from_xywh(26.0, 24.0, 48.0, 32.0).unwrap()
+    );
+    assert_eq!(
+        clip_bbox("g2"),
+        Rect::from_xywh(10.0, 20.0, 20.0, 20.0).unwrap()
+    );
+}
+
+#[test]
+fn mask_layers() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+        <mask id='mask1'>
+            <rect width='100' height='100' fill='white'/>
+        </mask>
+        <g id='g1' style='mask: url(#mask1) alpha, linear-gradient(black, transparent) subtract'>
+            <rect x='20' y='20' width='60' height='40'/>
+        </g>
+        <g id='g2' mask='url(#mask1), none, url(#missing)'>
+            <rect x='20' y='20' width='60' height='40'/>
+        </g>
+    </svg>
+    ";
+    let tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
+
+    let masks = |id: &str| {
+        let usvg::Node::Group(group) = tree.node_by_id(id).unwrap() else {
+            unreachable!()
+        };
+        group
+            .masks()
+            .iter()
+            .map(|layer| (layer.mode(), layer.composite()))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        masks("g1"),
+        vec![
+            (usvg::MaskType::Alpha, usvg::MaskComposite::Add),
+            (usvg::MaskType::Alpha, usvg::MaskComposite::Subtract),
+        ]
+    );
+    assert_eq!(
+        masks("g2"),
+        vec![(usvg::MaskType::Luminance, usvg::MaskComposite::Add)]
+    );
+}
+
+#[test]
+fn css_color_4_colors() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+        <rect id='oklch' width='10' height='10' fill='oklch(0.628 0.2577 29.23)'/>
+        <rect id='lab' width='10' height='10' fill='lab(54.29 80.8 69.9 / 50%)'/>
+        <rect id='rgb' width='10' height='10' fill='rgb(0 128 0)'/>
+        <rect id='mix' width='10' height='10' fill='color-mix(in srgb, red, blue)'/>
+        <rect id='p3' width='10' height='10' fill='color(display-p3 0 1 0)'/>
+        <rect id='fallback' width='10' height='10' fill='url(#missing) oklch(0 0 0)'/>
+        <rect id='invalid' width='10' height='10' fill='oklch(0.5 0.1)'/>
+        <rect id='legacy' width='10' height='10' fill='rgb(0, 50%, 0)'/>
+        <rect id='nested' width='10' height='10'
+              style='fill: color-mix(in srgb, rgb(255 0 0 / 1), hsl(240 100% 50%)); stroke: blue'/>
+    </svg>
+    ";
+    let tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
+
+    let fill = |id: &str| {
+        let usvg::Node::Path(path) = tree.node_by_id(id).unwrap() else {
+            unreachable!()
+        };
+        let fill = path.fill().unwrap();
+        let usvg::Paint::Color(color) = fill.paint() else {
+            unreachable!()
+        };
+        (*color, fill.opacity().get())
+    };
+
+    assert_eq!(fill("oklch"), (Color::new_rgb(255, 0, 0), 1.0));
+    assert_eq!(fill("lab").0, Color::new_rgb(255, 0, 0));
+    assert!((fill("lab").1 - 0.5).abs() < 0.01);
+    assert_eq!(fill("rgb"), (Color::new_rgb(0, 128, 0), 1.0));
+    assert_eq!(fill("mix"), (Color::new_rgb(127, 0, 127), 1.0));
+    // Out of gamut colors are mapped by reducing their chroma and not simply clipped.
+    assert_eq!(fill("p3"), (Color::new_rgb(0, 251, 41), 1.0));
+    assert_eq!(fill("fallback"), (Color::black(), 1.0));
+    assert_eq!(fill("invalid"), (Color::black(), 1.0));
+    // Numbers and percentages cannot be mixed in the legacy syntax.
+    assert_eq!(fill("legacy"), (Color::black(), 1.0));
+    assert_eq!(fill("nested"), (Color::new_rgb(127, 0, 127), 1.0));
+}
+
+#[test]
+fn media_queries() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+        <style><![CDATA[
+            rect { fill: black }
+            @media (prefers-color-scheme: dark) {
+                #scheme { fill: white }
+            }
+            @media screen and (min-width: 300px) and (max-width: 500px) {
+                #width { fill: green }
+            }
+            @media (400px <= width < 500px) and (orientation: landscape) {
+                #range { fill: green }
+            }
+            @media print, (min-resolution: 2dppx) {
+                #resolution { fill: green }
+            }
+            @media not (prefers-reduced-motion) {
+                #motion { fill: green }
+            }
+            @media (unknown-feature), not all and (color) {
+                #unknown { fill: red }
+            }
+            @media (min-aspect-ratio: 16/9) {
+                @media (height > 200px) {
+                    #nested { fill: green }
+                }
+            }
+        ]]></style>
+        <style media='(prefers-color-scheme: dark)'>
+            #attribute { fill: white }
+        </style>
+        <rect id='scheme' width='10' height='10'/>
+        <rect id='width' width='10' height='10'/>
+        <rect id='range' width='10' height='10'/>
+        <rect id='resolution' width='10' height='10'/>
+        <rect id='motion' width='10' height='10'/>
+        <rect id='unknown' width='10' height='10'/>
+        <rect id='nested' width='10' height='10'/>
+        <rect id='attribute' width='10' height='10'/>
+    </svg>
+    ";
+
+    let fill = |tree: &usvg::Tree, id: &str| {
+        let usvg::Node::Path(path) = tree.node_by_id(id).unwrap() else {
+            unreachable!()
+        };
+        match path.fill().unwrap().paint() {
+            usvg::Paint::Color(color) => *color,
+            _ => unreachable!(),
+        }
+    };
+
+    let tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
+    for id in [
+        "scheme",
+        "width",
+        "range",
+        "resolution",
+        "motion",
+        "unknown",
+        "nested",
+        "attribute",
+    ] {
+        let expected = if id == "motion" {
+            Color::new_rgb(0, 128, 0)
+        } else {
+            Color::black()
+        };
+        assert_eq!(fill(&tree, id), expected, "{}", id);
+    }
+
+    let options = usvg::Options {
+        media: usvg::MediaContext {
+            color_scheme: usvg::ColorScheme::Dark,
+            viewport_size: usvg::Size::from_wh(400.0, 220.0),
+            resolution: 2.0,
+            prefers_reduced_motion: true,
+        },
+        ..usvg::Options::default()
+    };
+    let tree = usvg::Tree::from_str(&svg, &options).unwrap();
+    let white = Color::new_rgb(255, 255, 255);
+    let green = Color::new_rgb(0, 128, 0);
+    assert_eq!(fill(&tree, "scheme"), white);
+    assert_eq!(fill(&tree, "width"), green);
+    assert_eq!(fill(&tree, "range"), green);
+    assert_eq!(fill(&tree, "resolution"), green);
+    assert_eq!(fill(&tree, "motion"), Color::black());
+    assert_eq!(fill(&tree, "unknown"), Color::black());
+    assert_eq!(fill(&tree, "nested"), green);
+    assert_eq!(fill(&tree, "attribute"), white);
+}
+
+#[test]
+fn custom_properties_from_options() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+        <rect id='rect1' width='10' height='10' fill='var(--accent, red)'/>
+        <g style='--accent: var(--secondary)'>
+            <rect id='rect2' width='10' height='10' style='fill: var(--accent)'/>
+        </g>
+        <rect id='rect3' width='10' height='10' style='fill: var(--invalid, green)'/>
+    </svg>
+    ";
+
+    let mut options = usvg::Options::default();
+    options
+        .custom_properties
+        .insert("--accent".to_string(), "var(--secondary)".to_string());
+    options
+        .custom_properties
+        .insert("--secondary".to_string(), "#00f".to_string());
+    // Names without the leading `--` are ignored.
+    options
+        .custom_properties
+        .insert("invalid".to_string(), "red".to_string());
+    let tree = usvg::Tree::from_str(&svg, &options).unwrap();
+
+    let fill = |id: &str| {
+        let usvg::Node::Path(path) = tree.node_by_id(id).unwrap() else {
+            unreachable!()
+        };
+        match path.fill().unwrap().paint() {
+            usvg::Paint::Color(color) => *color,
+            _ => unreachable!(),
+        }
+    };
+
+    assert_eq!(fill("rect1"), Color::new_rgb(0, 0, 255));
+    assert_eq!(fill("rect2"), Color::new_rgb(0, 0, 255));
+    assert_eq!(fill("rect3"), Color::new_rgb(0, 128, 0));
+}
+
+#[test]
+fn custom_properties_non_ascii() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100' style='--accent: green'>
+        <rect id='rect1' width='10' height='10' font-family='éé(x)' fill='var(--accent)'/>
+        <rect id='rect2' width='10' height='10' fill='éévar(--accent)'/>
+    </svg>
+    ";
+
+    let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+    let usvg::Node::Path(ref path) = *tree.node_by_id("rect1").unwrap() else {
+        unreachable!()
+    };
+    assert_eq!(
+        path.fill().unwrap().paint(),
+        &usvg::Paint::Color(Color::new_rgb(0, 128, 0))
+    );
+}
+
+#[test]
+fn selector_escape_before_non_ascii_whitespace() {
+    // Only ASCII whitespace is a part of an escape, so U+00A0 is a part of the class name.
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+        <style>.\\31\u{a0}x { fill: green }</style>
+        <rect id='rect1' class='1\u{a0}x' width='10' height='10'/>
+    </svg>
+    ";
+
+    let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+    let usvg::Node::Path(ref path) = *tree.node_by_id("rect1").unwrap() else {
+        unreachable!()
+    };
+    assert_eq!(
+        path.fill().unwrap().paint(),
+        &usvg::Paint::Color(Color::new_rgb(0, 128, 0))
+    );
+}
+
+#[test]
+fn style_sheet_import_resolver() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+        <style>
+            @import url('themes/base.css');
+            #rect2 { fill: green }
+        </style>
+        <rect id='rect1' width='10' height='10'/>
+        <rect id='rect2' width='10' height='10'/>
+        <rect id='rect3' width='10' height='10'/>
+    </svg>
+    ";
+
+    let files: std::collections::HashMap<&str, &str> = [
+        (
+            "themes/base.css",
+            "@import 'colors.css'; #rect2 { fill: red }",
+        ),
+        ("themes/colors.css", "#rect1 { fill: #00f }"),
+        ("user.css", "#rect3 { fill: #0ff !important }"),
+    ]
+    .into_iter()
+    .collect();
+
+    let options = usvg::Options {
+        style_sheet: Some("@import 'user.css'; #rect1 { fill: red }".to_string()),
+        style_sheet_resolver: usvg::StyleSheetResolver {
+            resolve_import: Box::new(move |url, _| files.get(url).map(|s| s.to_string())),
+        },
+        ..usvg::Options::default()
+    };
+    let tree = usvg::Tree::from_str(&svg, &options).unwrap();
+
+    let fill = |id: &str| {
+        let usvg::Node::Path(path) = tree.node_by_id(id).unwrap() else {
+            unreachable!()
+        };
+        match path.fill().unwrap().paint() {
+            usvg::Paint::Color(color) => *color,
+            _ => unreachable!(),
+        }
+    };
+
+    // Author style sheets override the user one, regardless of imports.
+    assert_eq!(fill("rect1"), Color::new_rgb(0, 0, 255));
+    // The importing style sheet overrides the imported one.
+    assert_eq!(fill("rect2"), Color::new_rgb(0, 128, 0));
+    // Imported important user declarations override everything.
+    assert_eq!(fill("rect3"), Color::new_rgb(0, 255, 255));
+}
+
+#[test]
+fn style_sheet_import_non_ascii() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+        <style>@import aéé; #rect1 { fill: green }</style>
+        <rect id='rect1' width='10' height='10'/>
+    </svg>
+    ";
+
+    let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+    assert!(tree.node_by_id("rect1").is_some());
+}
+
+#[test]
+fn style_sheet_import_fan_out() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let parse = |style: String, resolve: fn(&str) -> String| {
+        let svg = format!(
+            "<svg xmlns='http://www.w3.org/2000/svg'><style>{}</style></svg>",
+            style
+        );
+        let calls = Arc::new(AtomicUsize::new(0));
+        let options = {
+            let calls = calls.clone();
+            usvg::Options {
+                style_sheet_resolver: usvg::StyleSheetResolver {
+                    resolve_import: Box::new(move |url, _| {
+                        calls.fetch_add(1, Ordering::Relaxed);
+                        Some(resolve(url))
+                    }),
+                },
+                ..usvg::Options::default()
+            }
+        };
+        usvg::Tree::from_str(&svg, &options).unwrap();
+        calls.load(Ordering::Relaxed)
+    };
+
+    // Each style sheet imports the next one three times.
+    let calls = parse("@import '0.css';".to_string(), |url| {
+        let n: u32 = url.trim_end_matches(".css").parse().unwrap();
+        format!(
+            "@import '{0}.css'; @import '{0}.css'; @import '{0}.css';",
+            n + 1
+        )
+    });
+    // Each URL is loaded once, up to the maximum nesting level.
+    assert_eq!(calls, 16);
+
+    let style = (0..1000).map(|n| format!("@import '{}.css';", n)).collect();
+    let calls = parse(style, |_| String::new());
+    assert_eq!(calls, 256);
+}
+
+#[test]
+fn diagnostics_sink() {
+    let svg = "\
+<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+    <rect id='rect1' width='10' height='10' fill='qwe'/>
+    <rect width='-10' height='10'/>
+    <use id='use1' href='#use1'/>
+</svg>
+";
+
+    let (sink, diagnostics) = collecting_sink();
+    let options = usvg::Options {
+        diagnostics: sink,
+        ..usvg::Options::default()
+    };
+    let tree = usvg::Tree::from_str(svg, &options).unwrap();
+    drop(tree);
+
+    let diagnostics = diagnostics.lock().unwrap();
+    let find = |code: usvg::DiagnosticCode| {
+        diagnostics
+            .iter()
+            .find(|d| d.code == code)
+            .unwrap_or_else(|| panic!("no '{}' diagnostic", code))
+    };
+
+    let invalid_fill = find(usvg::DiagnosticCode::InvalidAttribute);
+    assert_eq!(invalid_fill.severity, usvg::Severity::Warning);
+    assert_eq!(invalid_fill.element_id.as_deref(), Some("rect1"));
+    assert_eq!(
+        invalid_fill.position,
+        Some(usvg::roxmltree::TextPos::new(2, 5))
+    );
+
+    let invalid_rect = find(usvg::DiagnosticCode::InvalidElement);
+    assert_eq!(invalid_rect.element_id, None);
+    assert_eq!(
+        invalid_rect.position,
+        Some(usvg::roxmltree::TextPos::new(3, 5))
+    );
+
+    let recursive_use = find(usvg::DiagnosticCode::InvalidReference);
+    assert_eq!(recursive_use.element_id.as_deref(), Some("use1"));
+    assert_eq!(
+        recursive_use.position,
+        Some(usvg::roxmltree::TextPos::new(4, 5))
+    );
+}
+
+#[test]
+fn source_spans() {
+    let svg = "\
+<svg xmlns='http://www.w3.org/2000/svg' xmlns:xlink='http://www.w3.org/1999/xlink'>
+    <defs>
+        <rect id='rect1' width='10' height='10'/>
+    </defs>
+    <g id='g1' opacity='0.5'>
+        <use id='use1' xlink:href='#rect1' x='20'/>
+    </g>
+    <text id='text1'>Text</text>
+</svg>
+";
+
+    let mut options = usvg::Options {
+        source_spans: true,
+        font_family: "Noto Sans".to_string(),
+        ..usvg::Options::default()
+    };
+    options
+        .fontdb_mut()
+        .load_fonts_dir(env!("CARGO_MANIFEST_DIR").to_string() + "/../resvg/tests/fonts");
+    let tree = usvg::Tree::from_str(svg, &options).unwrap();
+
+    let group = tree.node_by_id("g1").unwrap();
+    let span = group.source_span().unwrap();
+    assert_eq!(span.position, usvg::roxmltree::TextPos::new(5, 5));
+    assert!(svg[span.range.clone()].starts_with("<g id='g1'"));
+    assert!(svg[span.range.clone()].ends_with("</g>"));
+
+    let usvg::Node::Group(ref use_group) = *tree.node_by_id("use1").unwrap() else {
+        unreachable!()
+    };
+    assert_eq!(
+        use_group.source_span().unwrap().position,
+        usvg::roxmltree::TextPos::new(6, 9)
+    );
+    // Referenced elements point to their own location.
+    let path = &use_group.children()[0];
+    assert_eq!(
+        path.source_span().unwrap().position,
+        usvg::roxmltree::TextPos::new(3, 9)
+    );
+
+    let usvg::Node::Text(ref text) = *tree.node_by_id("text1").unwrap() else {
+        unreachable!()
+    };
+    let text_pos = usvg::roxmltree::TextPos::new(8, 5);
+    assert_eq!(text.source_span().unwrap().position, text_pos);
+    // Outlines point to the `text` element as well.
+    let outline = &text.flattened().children()[0];
+    assert_eq!(outline.source_span().unwrap().position, text_pos);
+
+    // Spans are not preserved by default.
+    let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+    assert!(tree.node_by_id("g1").unwrap().source_span().is_none());
+}
+
+#[test]
+fn metadata() {
+    let svg = "
+<svg viewBox='0 0 100 100' xmlns='http://www.w3.org/2000/svg'
+     xmlns:ex='http://example.com/ns'>
+    <title>Document</title>
+    <g id='g1' class='a b' data-kind='bar' ex:role='series'>
+        <title>Group  title</title>
+        <desc>Group description</desc>
+        <rect id='rect1' width='10' height='10' data-value='42'/>
+    </g>
+</svg>
+";
+
+    let options = usvg::Options {
+        metadata: usvg::MetadataOptions {
+            attribute_namespaces: vec!["http://example.com/ns".to_string()],
+            ..usvg::MetadataOptions::all()
+        },
+        ..usvg::Options::default()
+    };
+    let tree = usvg::Tree::from_str(svg, &options).unwrap();
+
+    assert_eq!(tree.metadata().unwrap().title(), Some("Document"));
+
+    let metadata = tree.node_by_id("g1").unwrap().metadata().unwrap();
+    assert_eq!(metadata.title(), Some("Group title"));
+    assert_eq!(metadata.description(), Some("Group description"));
+    assert_eq!(metadata.classes(), ["a", "b"]);
+    let attrs = metadata.attributes();
+    assert_eq!(attrs.len(), 2);
+    assert_eq!((attrs[0].name(), attrs[0].value()), ("data-kind", "bar"));
+    assert_eq!(attrs[1].namespace(), Some("http://example.com/ns"));
+    assert_eq!(attrs[1].prefix(), Some("ex"));
+    assert_eq!((attrs[1].name(), attrs[1].value()), ("role", "series"));
+
+    let metadata = tree.node_by_id("rect1").unwrap().metadata().unwrap();
+    assert_eq!(metadata.attributes()[0].value(), "42");
+
+    // Nothing is preserved by default.
+    let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+    assert!(tree.metadata().is_none());
+    assert!(tree.node_by_id("g1").unwrap().metadata().is_none());
+}
+
+#[test]
+fn limits() {
+    let parse = |svg: &str, limits: usvg::Limits| {
+        let (sink, diagnostics) = collecting_sink();
+        let options = usvg::Options {
+            limits,
+            diagnostics: sink,
+            ..usvg::Options::default()
+        };
+        let tree = usvg::Tree::from_str(svg, &options);
+        let has_diagnostic = diagnostics
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|d| d.code == usvg::DiagnosticCode::LimitExceeded);
+        (tree, has_diagnostic)
+    };
+
+    let svg = "
+<svg viewBox='0 0 100 100' xmlns='http://www.w3.org/2000/svg'>
+    <rect id='rect1' width='10' height='10'/>
+    <use id='use1' href='#rect1'/>
+    <use id='use2' href='#use1'/>
+</svg>
+";
+    let (tree, _) = parse(
+        svg,
+        usvg::Limits {
+            max_elements: 3,
+            ..usvg::Limits::default()
+        },
+    );
+    assert!(matches!(tree, Err(usvg::Error::ElementsLimitReached)));
+
+    let (tree, has_diagnostic) = parse(
+        svg,
+        usvg::Limits {
+            max_use_depth: 1,
+            ..usvg::Limits::default()
+        },
+    );
+    let tree = tree.unwrap();
+    assert!(has_diagnostic);
+    fn has_paths(group: &usvg::Group) -> bool {
+        group.children().iter().any(|node| match node {
+            usvg::Node::Path(_) => true,
+            usvg::Node::Group(g) => has_paths(g),
+            _ => false,
+        })
+    }
+    let use_has_paths = |id| match *tree.node_by_id(id).unwrap() {
+        usvg::Node::Group(ref g) => has_paths(g),
+        _ => unreachable!(),
+    };
+    assert!(use_has_paths("use1"));
+    // The nested `use` is not instantiated.
+    assert!(!use_has_paths("use2"));
+
+    let svg = "
+<svg viewBox='0 0 100 100' xmlns='http://www.w3.org/2000/svg'>
+    <image id='image1' width='10' height='10' href='data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAIAAAACCAIAAAD91JpzAAAAEElEQVR4nGP4z8AARAwQCgAf7gP9i18U1AAAAABJRU5ErkJggg=='/>
+    <image id='image2' width='10' height='10' href='data:image/svg+xml;base64,PHN2ZyB4bWxucz0naHR0cDovL3d3dy53My5vcmcvMjAwMC9zdmcnIHdpZHRoPScxMCcgaGVpZ2h0PScxMCcvPg=='/>
+    <text id='text1'>Text</text>
+    <g id='g1' filter='url(#filter1)'>
+        <rect width='10' height='10'/>
+    </g>
+    <filter id='filter1' filterUnits='userSpaceOnUse' x='0' y='0' width='100' height='100'>
+        <feFlood/>
+    </filter>
+</svg>
+";
+    let (tree, has_diagnostic) = parse(svg, usvg::Limits::default());
+    let tree = tree.unwrap();
+    assert!(!has_diagnostic);
+    assert!(tree.node_by_id("image1").is_some());
+    assert!(tree.node_by_id("image2").is_some());
+    assert!(tree.node_by_id("g1").is_some());
+
+    let (tree, has_diagnostic) = parse(
+        svg,
+        usvg::Limits {
+            max_image_pixels: 3,
+            max_nested_images_depth: 0,
+            max_text_length: 3,
+            max_filter_region_pixels: 9999,
+            ..usvg::Limits::default()
+        },
+    );
+    let tree = tree.unwrap();
+    assert!(has_diagnostic);
+    assert!(tree.node_by_id("image1").is_none());
+    assert!(tree.node_by_id("image2").is_none());
+    assert!(tree.node_by_id("text1").is_none());
+    assert!(tree.node_by_id("g1").is_none());
+}
+
+#[test]
+fn resource_policy() {
+    let svg = "
+<svg viewBox='0 0 100 100' xmlns='http://www.w3.org/2000/svg'>
+    <image id='image1' width='10' height='10' href='image.png'/>
+    <image id='image2' width='10' height='10' href='../tests/structure/style/attribute-selector.png'/>
+</svg>
+";
+
+    let resources_dir =
+        std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../resvg/tests/resources");
+    let parse = |resource_policy| {
+        let options = usvg::Options {
+            resources_dir: Some(resources_dir.clone()),
+            resource_policy,
+            ..usvg::Options::default()
+        };
+        let tree = usvg::Tree::from_str(svg, &options).unwrap();
+        (
+            tree.node_by_id("image1").is_some(),
+            tree.node_by_id("image2").is_some(),
+        )
+    };
+
+    assert_eq!(parse(usvg::ResourcePolicy::AllowAll), (true, true));
+    assert_eq!(parse(usvg::ResourcePolicy::DenyAll), (false, false));
+    // `..` cannot be used to escape the resources directory.
+    assert_eq!(parse(usvg::ResourcePolicy::ResourcesDir), (true, false));
+    assert_eq!(
+        parse(usvg::ResourcePolicy::Allowlist(vec![
+            resources_dir.join("../tests/structure/style")
+        ])),
+        (false, true)
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn resource_policy_symlinks() {
+    let image = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../resvg/tests/resources/image.png");
+
+    let root = std::env::temp_dir().join(format!("usvg-resource-policy-{}", std::process::id()));
+    let resources_dir = root.join("resources");
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(&resources_dir).unwrap();
+    std::fs::copy(&image, root.join("outside.png")).unwrap();
+    std::fs::copy(&image, resources_dir.join("inside.png")).unwrap();
+    std::os::unix::fs::symlink(root.join("outside.png"), resources_dir.join("escape.png")).unwrap();
+    std::os::unix::fs::symlink(
+        resources_dir.join("inside.png"),
+        resources_dir.join("alias.png"),
+    )
+    .unwrap();
+
+    let options = usvg::Options {
+        resources_dir: Some(resources_dir.clone()),
+        resource_policy: usvg::ResourcePolicy::ResourcesDir,
+        ..usvg::Options::default()
+    };
+
+    // A symlink cannot be used to escape the resources directory.
+    assert_eq!(options.resolve_resource_path("escape.png"), None);
+    // Allowed paths are canonicalized, so only the checked file is opened.
+    assert_eq!(
+        options.resolve_resource_path("alias.png"),
+        Some(resources_dir.join("inside.png").canonicalize().unwrap())
+    );
+
+    let svg = "
+<svg viewBox='0 0 100 100' xmlns='http://www.w3.org/2000/svg'>
+    <image id='image1' width='10' height='10' href='alias.png'/>
+    <image id='image2' width='10' height='10' href='escape.png'/>
+</svg>
+";
+    let tree = usvg::Tree::from_str(svg, &options).unwrap();
+    assert!(tree.node_by_id("image1").is_some());
+    assert!(tree.node_by_id("image2").is_none());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn dependencies() {
+    let svg = "
+<svg viewBox='0 0 100 100' xmlns='http://www.w3.org/2000/svg'>
+    <style>@import url('green.css');</style>
+    <filter id='filter1'>
+        <feImage href='image.jpg'/>
+    </filter>
+    <image width='10' height='10' href='image.png'/>
+    <image width='10' height='10' href='image.png'/>
+    <image width='10' height='10' href='data:image/png;base64,AAAA'/>
+    <use href='image.svg#rect1'/>
+    <use href='#rect1'/>
+    <rect id='rect1' width='10' height='10'/>
+    <text x='10' y='50' font-family='Noto Sans'>Text</text>
+</svg>
+";
+
+    let resources_dir = std::fs::canonicalize(
+        std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../resvg/tests/resources"),
+    )
+    .unwrap();
+    let mut options = usvg::Options {
+        resources_dir: Some(resources_dir.clone()),
+        ..usvg::Options::default()
+    };
+    options
+        .fontdb_mut()
+        .load_fonts_dir(env!("CARGO_MANIFEST_DIR").to_string() + "/../resvg/tests/fonts");
+
+    let deps = usvg::collect_dependencies(svg, &options).unwrap();
+    let hrefs: Vec<_> = deps.iter().map(|dep| dep.href.as_str()).collect();
+    assert_eq!(
+        hrefs[..4],
+        ["image.jpg", "image.png", "image.svg", "green.css"]
+    );
+
+    let deps: Vec<_> = deps
+        .iter()
+        .map(|dep| (dep.kind, dep.path.clone()))
+        .collect();
+
+    let font_path = std::fs::canonicalize(
+        std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../resvg/tests/fonts/NotoSans-Regular.ttf"),
+    )
+    .unwrap();
+
+    assert_eq!(
+        deps,
+        vec![
+            (
+                usvg::DependencyKind::FilterImage,
+                resources_dir.join("image.jpg")
+            ),
+            (usvg::DependencyKind::Image, resources_dir.join("image.png")),
+            (usvg::DependencyKind::Use, resources_dir.join("image.svg")),
+            (
+                usvg::DependencyKind::StyleSheet,
+                resources_dir.join("green.css")
+            ),
+            (usvg::DependencyKind::Font, font_path),
+        ]
+    );
+}
+
+#[test]
+fn deferred_resources() {
+    let svg = "
+<svg viewBox='0 0 100 100' xmlns='http://www.w3.org/2000/svg'>
+    <image id='image1' width='10' height='10' href='photos/image.png'/>
+    <use id='use1' href='sprites.svg#circle'/>
+    <use id='use2' href='sprites.svg#circle'/>
+    <text id='text1' x='10' y='50' font-family='Noto Sans'>Text</text>
+</svg>
+";
+
+    let sprites = "
+<svg xmlns='http://www.w3.org/2000/svg'>
+    <circle id='circle' cx='50' cy='50' r='10'/>
+</svg>
+";
+
+    // An in-memory stand-in for a remote storage.
+    let fonts_dir =
+        std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../resvg/tests/fonts");
+    let resources_dir =
+        std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../resvg/tests/resources");
+    let storage = std::collections::HashMap::from([
+        (
+            "photos/image.png",
+            std::fs::read(resources_dir.join("image.png")).unwrap(),
+        ),
+        ("sprites.svg", sprites.as_bytes().to_vec()),
+        (
+            "Noto Sans",
+            std::fs::read(fonts_dir.join("NotoSans-Regular.ttf")).unwrap(),
+        ),
+    ]);
+
+    let options = usvg::Options {
+        source_spans: true,
+        ..usvg::Options::default()
+    };
+    let mut pending = usvg::PendingTree::from_str(svg, &options).unwrap();
+
+    let requests = pending.requests().to_vec();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(
+        requests[0],
+        usvg::ResourceRequest::Sprite("sprites.svg".to_string())
+    );
+    assert_eq!(
+        requests[1],
+        usvg::ResourceRequest::Image("photos/image.png".to_string())
+    );
+    let usvg::ResourceRequest::Font(ref font) = requests[2] else {
+        unreachable!()
+    };
+    assert_eq!(
+        font.families(),
+        [usvg::FontFamily::Named("Noto Sans".to_string())]
+    );
+
+    // Requests can be fulfilled in parallel.
+    let loaded: Vec<_> = std::thread::scope(|s| {
+        let handles: Vec<_> = requests
+            .into_iter()
+            .map(|request| {
+                let storage = &storage;
+                s.spawn(move || {
+                    let key = match request {
+                        usvg::ResourceRequest::Image(ref href) => href.as_str(),
+                        usvg::ResourceRequest::Sprite(ref href) => href.as_str(),
+                        usvg::ResourceRequest::Font(_) => "Noto Sans",
+                    };
+                    let data = storage[key].clone();
+                    (request, data)
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    for (request, data) in loaded {
+        pending.fulfill(request, data);
+    }
+
+    let tree = pending.finish(&options).unwrap();
+
+    let usvg::Node::Group(ref image) = *tree.node_by_id("image1").unwrap() else {
+        unreachable!()
+    };
+    assert!(matches!(image.children()[0], usvg::Node::Image(_)));
+
+    for id in ["use1", "use2"] {
+        let usvg::Node::Group(ref group) = *tree.node_by_id(id).unwrap() else {
+            unreachable!()
+        };
+        let usvg::Node::Path(ref path) = group.children()[0] else {
+            unreachable!()
+        };
+        // Elements from external documents have no location in the source.
+        assert!(path.source_span().is_none());
+    }
+
+    let usvg::Node::Text(ref text) = *tree.node_by_id("text1").unwrap() else {
+        unreachable!()
+    };
+    assert!(text.flattened().has_children());
+}
+
+#[test]
+fn encodings() {
+    let svg = "<?xml version='1.0' encoding='UTF-16'?>
+<svg viewBox='0 0 100 100' xmlns='http://www.w3.org/2000/svg'>
+    <rect id='café' width='10' height='10'/>
+</svg>
+";
+
+    let parse = |data: &[u8]| usvg::Tree::from_data(data, &usvg::Options::default());
+
+    let mut utf16le = vec![0xFF, 0xFE];
+    utf16le.extend(svg.encode_utf16().flat_map(|c| c.to_le_bytes()));
+    assert!(parse(&utf16le).unwrap().node_by_id("café").is_some());
+
+    // Without a byte order mark.
+    let utf16be: Vec<u8> = svg.encode_utf16().flat_map(|c| c.to_be_bytes()).collect();
+    assert!(parse(&utf16be).unwrap().node_by_id("café").is_some());
+
+    let mut utf8 = vec![0xEF, 0xBB, 0xBF];
+    utf8.extend(svg.as_bytes());
+    assert!(parse(&utf8).unwrap().node_by_id("café").is_some());
+
+    let latin1 = b"<?xml version='1.0' encoding='ISO-8859-1'?>
+<svg viewBox='0 0 100 100' xmlns='http://www.w3.org/2000/svg'>
+    <rect id='caf\xE9' width='10' height='10'/>
+    <rect id='\x80' width='10' height='10'/>
+</svg>
+";
+    let tree = parse(latin1).unwrap();
+    assert!(tree.node_by_id("café").is_some());
+    // Decoded as Windows-1252.
+    assert!(tree.node_by_id("€").is_some());
+
+    let latin9 = b"<?xml version='1.0' encoding='ISO-8859-15'?>
+<svg viewBox='0 0 100 100' xmlns='http://www.w3.org/2000/svg'>
+    <rect id='caf\xE9' width='10' height='10'/>
+    <rect id='\xA4' width='10' height='10'/>
+</svg>
+";
+    let tree = parse(latin9).unwrap();
+    assert!(tree.node_by_id("café").is_some());
+    assert!(tree.node_by_id("€").is_some());
+
+    let koi8 = b"<?xml version='1.0' encoding='KOI8-R'?>
+<svg viewBox='0 0 100 100' xmlns='http://www.w3.org/2000/svg'/>
+";
+    assert!(matches!(
+        parse(koi8),
+        Err(usvg::Error::UnsupportedEncoding(ref encoding)) if encoding == "koi8-r"
+    ));
+
+    assert!(matches!(
+        parse(b"<svg id='caf\xE9'/>"),
+        Err(usvg::Error::NotAnUtf8Str)
+    ));
+
+    // Sprites are decoded the same way.
+    let svg =
+        "<svg xmlns='http://www.w3.org/2000/svg'><use id='use1' href='sprite.svg#café'/></svg>";
+    let options = usvg::Options::default();
+    let mut pending = usvg::PendingTree::from_str(svg, &options).unwrap();
+    let request = pending.requests()[0].clone();
+    pending.fulfill(request, utf16le);
+    let tree = pending.finish(&options).unwrap();
+    let usvg::Node::Group(ref group) = *tree.node_by_id("use1").unwrap() else {
+        unreachable!()
+    };
+    assert!(matches!(group.children()[0], usvg::Node::Path(_)));
+}
+
+#[test]
+fn from_reader() {
+    let resources_dir =
+        std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../resvg/tests/resources");
+
+    let options = usvg::Options::default();
+    for name in ["image.svg", "image.svgz"] {
+        let file = std::fs::File::open(resources_dir.join(name)).unwrap();
+        let tree = usvg::Tree::from_reader(file, &options).unwrap();
+        assert!(tree.root().has_children());
+    }
+
+    let options = usvg::Options {
+        limits: usvg::Limits {
+            max_input_size: 100,
+            ..usvg::Limits::default()
+        },
+        ..usvg::Options::default()
+    };
+    for name in ["image.svg", "image.svgz"] {
+        let file = std::fs::File::open(resources_dir.join(name)).unwrap();
+        assert!(matches!(
+            usvg::Tree::from_reader(file, &options),
+            Err(usvg::Error::InputSizeLimitReached)
+        ));
+
+        let data = std::fs::read(resources_dir.join(name)).unwrap();
+        assert!(matches!(
+            usvg::Tree::from_data(&data, &options),
+            Err(usvg::Error::InputSizeLimitReached)
+        ));
+        assert!(matches!(
+            usvg::read_svg_data(data.as_slice(), 100),
+            Err(usvg::Error::InputSizeLimitReached)
+        ));
+    }
+
+    // Compressed data is decompressed.
+    let svgz = std::fs::read(resources_dir.join("image.svgz")).unwrap();
+    let svg = usvg::read_svg_data(svgz.as_slice(), u64::MAX).unwrap();
+    assert!(svg.starts_with(b"<svg"));
+}
+
+#[test]
+fn recover_malformed_xml() {
+    let (sink, diagnostics) = collecting_sink();
+    let options = usvg::Options {
+        recover_malformed_xml: true,
+        diagnostics: sink,
+        ..usvg::Options::default()
+    };
+
+    // Truncated in the middle of a tag.
+    let svg = "\
+<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+    <!-- a > b -->
+    <rect id='rect1' width='10' height='10'/>
+    <g id='g1' opacity='0.5'>
+        <rect id='rect2' width='10' height='10'/>
+        <rect id='rect3' width='10' hei";
+    let tree = usvg::Tree::from_reader(svg.as_bytes(), &options).unwrap();
+    assert!(tree.node_by_id("rect1").is_some());
+    assert!(tree.node_by_id("g1").is_some());
+    assert!(tree.node_by_id("rect2").is_some());
+    assert!(tree.node_by_id("rect3").is_none());
+    assert_eq!(
+        diagnostics.lock().unwrap()[0].code,
+        usvg::DiagnosticCode::MalformedXml
+    );
+
+    // Unclosed tags at the end of the file.
+    let svg = "\
+<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+    <g id='g1' opacity='0.5'>
+        <rect id='rect1' width='10' height='10'/>
+";
+    let tree = usvg::Tree::from_str(svg, &options).unwrap();
+    assert!(tree.node_by_id("rect1").is_some());
+
+    // A mismatched closing tag.
+    let svg = "\
+<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+    <rect id='rect1' width='10' height='10'/>
+    <g>
+        <rect id='rect2' width='10' height='10'>
+    </g>
+    <rect id='rect3' width='10' height='10'/>
+</svg>
+";
+    let tree = usvg::Tree::from_str(svg, &options).unwrap();
+    assert!(tree.node_by_id("rect1").is_some());
+    assert!(tree.node_by_id("rect2").is_some());
+    assert!(tree.node_by_id("rect3").is_none());
+
+    assert!(matches!(
+        usvg::Tree::from_str(svg, &usvg::Options::default()),
+        Err(usvg::Error::ParsingFailed(_))
+    ));
+
+    // Cannot be repaired without a root element.
+    assert!(matches!(
+        usvg::Tree::from_str("<sv", &options),
+        Err(usvg::Error::ParsingFailed(_))
+    ));
+}
+
+#[test]
+fn embedded_svgs() {
+    fn fill_of(tree: &usvg::Tree, id: &str) -> Option<usvg::Paint> {
+        match tree.node_by_id(id)? {
+            usvg::Node::Path(path) => path.fill().map(|fill| fill.paint().clone()),
+            _ => None,
+        }
+    }
+
+    let red = usvg::Paint::Color(Color::new_rgb(255, 0, 0));
+    let green = usvg::Paint::Color(Color::new_rgb(0, 128, 0));
+
+    // A well-formed XHTML document.
+    let xhtml = "\
+<html xmlns='http://www.w3.org/1999/xhtml' xmlns:xlink='http://www.w3.org/1999/xlink'>
+<head>
+    <style>#rect1 { fill: green }</style>
+</head>
+<body>
+    <svg xmlns='http://www.w3.org/2000/svg' id='first' viewBox='0 0 10 10'>
+        <rect id='rect1' width='10' height='10' fill='red'/>
+    </svg>
+    <p>Text</p>
+    <svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 10 10'>
+        <rect id='rect2' width='10' height='10' fill='red'/>
+        <use id='use1' xlink:href='#rect2'/>
+        <svg id='nested'/>
+    </svg>
+</body>
+</html>
+";
+    let svgs = usvg::parse_embedded_svgs(xhtml, &usvg::Options::default());
+    assert_eq!(svgs.len(), 2);
+
+    assert_eq!(svgs[0].index, 0);
+    assert_eq!(svgs[0].id.as_deref(), Some("first"));
+    assert!(xhtml[svgs[0].range.clone()].starts_with("<svg"));
+    assert!(xhtml[svgs[0].range.clone()].ends_with("</svg>"));
+    let tree = svgs[0].tree.as_ref().unwrap();
+    assert_eq!(fill_of(tree, "rect1"), Some(green.clone()));
+
+    assert_eq!(svgs[1].index, 1);
+    assert_eq!(svgs[1].id, None);
+    let tree = svgs[1].tree.as_ref().unwrap();
+    assert_eq!(fill_of(tree, "rect2"), Some(red.clone()));
+    // `xlink` is declared by the page.
+    assert!(tree.node_by_id("use1").is_some());
+
+    // A loosely-written HTML document.
+    let html = "\
+<!DOCTYPE html>
+<html>
+<head>
+<style type=text/css>
+    rect.ok { fill: green }
+</style>
+<script>if (a <svg) {}</script>
+</head>
+<body>
+<p>Broken <b>markup
+<SVG id=icon viewBox='0 0 10 10'>
+    <rect id='rect1' class='ok' width='10' height='10' fill='red'/>
+    <!-- <svg> -->
+</SVG>
+<br>
+<svg viewBox='0 0 10 10'><rect id='rect2' width='10' height='10' fill='red'/></svg>
+<svg viewBox='0 0 10 10'><rect width='10' height='10'></svg>
+</body>
+";
+    let svgs = usvg::parse_embedded_svgs(html, &usvg::Options::default());
+    assert_eq!(svgs.len(), 3);
+
+    assert_eq!(svgs[0].id.as_deref(), Some("icon"));
+    let tree = svgs[0].tree.as_ref().unwrap();
+    assert_eq!(fill_of(tree, "rect1"), Some(green));
+
+    let tree = svgs[1].tree.as_ref().unwrap();
+    assert_eq!(fill_of(tree, "rect2"), Some(red));
+
+    // Each SVG is parsed separately.
+    assert_eq!(svgs[2].index, 2);
+    assert!(matches!(svgs[2].tree, Err(usvg::Error::ParsingFailed(_))));
+}
+
+#[test]
+fn embedded_svg_unclosed() {
+    // An unclosed `svg` element that ends with non-ASCII text.
+    let svgs = usvg::parse_embedded_svgs("<html><body><svg>€€x", &usvg::Options::default());
+    assert_eq!(svgs.len(), 1);
+    assert!(matches!(svgs[0].tree, Err(usvg::Error::ParsingFailed(_))));
+}
+
+#[test]
+fn fragment_identifiers() {
+    let svg = "
+<svg id='svg1' xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 50'>
+    <view id='view1' viewBox='50 0 50 50'/>
+    <view id='view2' viewBox='0 0 100 100' preserveAspectRatio='none'/>
+    <rect id='rect1' x='10' y='20' width='30' height='20'/>
+    <rect id='rect2' x='60' y='10' width='20' height='20'/>
+</svg>
+";
+
+    let parse = |fragment: &str| {
+        let options = usvg::Options {
+            fragment: Some(fragment.to_string()),
+            ..usvg::Options::default()
+        };
+        usvg::Tree::from_str(svg, &options).unwrap()
+    };
+    let bbox = |tree: &usvg::Tree, id: &str| tree.node_by_id(id).unwrap().abs_bounding_box();
+
+    // A `view` element.
+    let tree = parse("view1");
+    assert_eq!(tree.size(), usvg::Size::from_wh(50.0, 50.0).unwrap());
+    assert_eq!(
+        bbox(&tree, "rect2"),
+        Rect::from_xywh(10.0, 10.0, 20.0, 20.0).unwrap()
+    );
+
+    let tree = parse("#view2");
+    assert_eq!(tree.size(), usvg::Size::from_wh(100.0, 100.0).unwrap());
+
+    // An SVG view specification.
+    let tree = parse("svgView(viewBox(0,0,50,25);transform(translate(-10,-20)))");
+    assert_eq!(tree.size(), usvg::Size::from_wh(50.0, 25.0).unwrap());
+    assert_eq!(
+        bbox(&tree, "rect1"),
+        Rect::from_xywh(0.0, 0.0, 30.0, 20.0).unwrap()
+    );
+
+    // An element.
+    let tree = parse("rect1");
+    assert_eq!(tree.size(), usvg::Size::from_wh(30.0, 20.0).unwrap());
+    assert_eq!(
+        bbox(&tree, "rect1"),
+        Rect::from_xywh(0.0, 0.0, 30.0, 20.0).unwrap()
+    );
+    assert_eq!(
+        bbox(&tree, "rect2"),
+        Rect::from_xywh(50.0, -10.0, 20.0, 20.0).unwrap()
+    );
+
+    // The root element and unknown fragments are ignored.
+    let tree = parse("svg1");
+    assert_eq!(tree.size(), usvg::Size::from_wh(100.0, 50.0).unwrap());
+
+    let (sink, diagnostics) = collecting_sink();
+    let options = usvg::Options {
+        fragment: Some("missing".to_string()),
+        diagnostics: sink,
+        ..usvg::Options::default()
+    };
+    let tree = usvg::Tree::from_str(svg, &options).unwrap();
+    assert_eq!(tree.size(), usvg::Size::from_wh(100.0, 50.0).unwrap());
+    let diagnostics = diagnostics.lock().unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, usvg::DiagnosticCode::InvalidReference);
+}
+
+#[test]
+fn conditional_processing() {
+    let svg = "
+<svg xmlns='http://www.w3.org/2000/svg'>
+    <switch>
+        <g id='ext' requiredExtensions='http://example.com/ext'/>
+        <g id='feature' requiredFeatures='http://example.com/feature'/>
+        <g id='fallback'/>
+    </switch>
+    <g id='en-US' systemLanguage='en-US'/>
+    <g id='en' systemLanguage='EN'/>
+    <g id='de-CH' systemLanguage='de-CH-1996, fr'/>
+    <g id='empty' systemLanguage=''/>
+</svg>
+";
+
+    let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+    assert!(tree.node_by_id("ext").is_none());
+    assert!(tree.node_by_id("feature").is_none());
+    assert!(tree.node_by_id("fallback").is_some());
+
+    let options = usvg::Options {
+        languages: vec!["en-GB".to_string(), "de".to_string()],
+        condition_resolver: usvg::ConditionResolver {
+            is_feature_supported: {
+                let default = usvg::ConditionResolver::default_feature_resolver();
+                Box::new(move |feature, element, opt| {
+                    feature == "http://example.com/feature" || default(feature, element, opt)
+                })
+            },
+            is_extension_supported: Box::new(|extension, element, _| {
+                extension == "http://example.com/ext" && element.id != "ext"
+            }),
+        },
+        ..usvg::Options::default()
+    };
+    let tree = usvg::Tree::from_str(svg, &options).unwrap();
+    // Extensions are checked per element.
+    assert!(tree.node_by_id("ext").is_none());
+    assert!(tree.node_by_id("feature").is_some());
+    assert!(tree.node_by_id("fallback").is_none());
+
+    // `en-GB` matches `en`, but not `en-US`.
+    assert!(tree.node_by_id("en-US").is_none());
+    assert!(tree.node_by_id("en").is_some());
+    // `de` matches `de-CH-1996`.
+    assert!(tree.node_by_id("de-CH").is_some());
+    assert!(tree.node_by_id("empty").is_none());
+}
+
+#[test]
+fn foreign_object() {
+    let svg = "
+<svg xmlns='http://www.w3.org/2000/svg' xmlns:h='http://www.w3.org/1999/xhtml'>
+    <foreignObject id='object1' x='10' y='20' width='60' height='100'>
+        <h:div>First paragraph with several words</h:div>
+        <h:p>Second<h:br/>Third</h:p>
+    </foreignObject>
+    <switch>
+        <foreignObject id='object2' width='10' height='10'
+                       requiredFeatures='http://www.w3.org/TR/SVG11/feature#Extensibility'/>
+        <rect id='fallback' width='10' height='10'/>
+    </switch>
+</svg>
+";
+
+    // The built-in resolver renders text paragraphs.
+    let mut options = usvg::Options {
+        font_family: "Noto Sans".to_string(),
+        font_size: 10.0,
+        ..usvg::Options::default()
+    };
+    options
+        .fontdb_mut()
+        .load_fonts_dir(env!("CARGO_MANIFEST_DIR").to_string() + "/../resvg/tests/fonts");
+
+    let tree = usvg::Tree::from_str(svg, &options).unwrap();
+    let usvg::Node::Group(ref group) = *tree.node_by_id("object1").unwrap() else {
+        unreachable!()
+    };
+    assert_eq!(
+        group.transform(),
+        usvg::Transform::from_translate(10.0, 20.0)
+    );
+    let usvg::Node::Image(ref image) = group.children()[0] else {
+        unreachable!()
+    };
+    assert_eq!(image.size(), usvg::Size::from_wh(60.0, 100.0).unwrap());
+    let usvg::ImageKind::SVG(ref object_tree) = *image.kind() else {
+        unreachable!()
+    };
+    let usvg::Node::Text(ref text) = object_tree.root().children()[0] else {
+        unreachable!()
+    };
+    let lines: Vec<_> = text.chunks().iter().map(|c| c.text()).collect();
+    assert!(lines.len() > 3);
+    assert_eq!(lines[0], "First");
+    assert_eq!(&lines[lines.len() - 2..], ["Second", "Third"]);
+
+    // Fallbacks are preferred without the `Extensibility` feature.
+    assert!(tree.node_by_id("object2").is_none());
+    assert!(tree.node_by_id("fallback").is_some());
+
+    // A custom resolver.
+    let options = usvg::Options {
+        condition_resolver: usvg::ConditionResolver {
+            is_feature_supported: Box::new(|feature, _, _| feature.ends_with("#Extensibility")),
+            ..usvg::ConditionResolver::default()
+        },
+        foreign_object_resolver: usvg::ForeignObjectResolver {
+            resolve: Box::new(|object, opt| {
+                assert!(object.xml.starts_with("<foreignObject xmlns="));
+                let svg = format!(
+                    "<svg xmlns='http://www.w3.org/2000/svg' width='{}' height='{}'/>",
+                    object.size.width() * 2.0,
+                    object.size.height() * 2.0
+                );
+                usvg::Tree::from_str(&svg, opt)
+                    .ok()
+                    .map(usvg::ImageKind::SVG)
+            }),
+        },
+        ..usvg::Options::default()
+    };
+    let tree = usvg::Tree::from_str(svg, &options).unwrap();
+    let node = tree.node_by_id("object1").unwrap();
+    assert_eq!(
+        node.abs_bounding_box(),
+        usvg::Rect::from_xywh(10.0, 20.0, 60.0, 100.0).unwrap()
+    );
+    assert!(tree.node_by_id("object2").is_some());
+    assert!(tree.node_by_id("fallback").is_none());
+}
+
+#[test]
+fn foreign_object_long_paragraph() {
+    let words: Vec<_> = (0..1000).map(|i| format!("word{}", i % 300)).collect();
+    let svg = format!(
+        "<svg xmlns='http://www.w3.org/2000/svg' xmlns:h='http://www.w3.org/1999/xhtml'>\
+         <foreignObject id='object1' width='100000000' height='100'><h:p>{}</h:p></foreignObject>\
+         </svg>",
+        words.join(" ")
+    );
+
+    let mut options = usvg::Options {
+        font_family: "Noto Sans".to_string(),
+        condition_resolver: usvg::ConditionResolver {
+            is_feature_supported: Box::new(|feature, _, _| feature.ends_with("#Extensibility")),
+            ..usvg::ConditionResolver::default()
+        },
+        ..usvg::Options::default()
+    };
+    options
+        .fontdb_mut()
+        .load_fonts_dir(env!("CARGO_MANIFEST_DIR").to_string() + "/../resvg/tests/fonts");
+
+    // Words are measured once, so a long paragraph is laid out in a reasonable time.
+    let start = std::time::Instant::now();
+    let tree = usvg::Tree::from_str(&svg, &options).unwrap();
+    assert!(start.elapsed() < std::time::Duration::from_secs(30));
+    let usvg::Node::Group(ref group) = *tree.node_by_id("object1").unwrap() else {
+        unreachable!()
+    };
+    let usvg::Node::Image(ref image) = group.children()[0] else {
+        unreachable!()
+    };
+    let usvg::ImageKind::SVG(ref object_tree) = *image.kind() else {
+        unreachable!()
+    };
+    let usvg::Node::Text(ref text) = object_tree.root().children()[0] else {
+        unreachable!()
+    };
+    assert_eq!(text.chunks().len(), 1);
+
+    // Text longer than `max_text_length` is skipped.
+    options.limits.max_text_length = 1000;
+    let tree = usvg::Tree::from_str(&svg, &options).unwrap();
+    assert!(tree.node_by_id("object1").is_none());
+}
+
+#[test]
+fn smil_animations() {
+    let svg = "
+<svg xmlns='http://www.w3.org/2000/svg'>
+    <rect id='rect1' width='10' height='10' style='fill: red'>
+        <animate attributeName='width' from='10' to='30' dur='2s'/>
+        <animate attributeName='fill' values='red;blue' begin='0.5s' dur='1s'/>
+    </rect>
+    <rect id='rect2' width='10' height='10'>
+        <set attributeName='visibility' to='hidden' begin='0.5s' dur='0.25s'/>
+        <animateTransform attributeName='transform' type='translate' from='0' to='40' dur='2s'
+                          additive='sum'/>
+    </rect>
+    <rect id='rect3' width='10' height='10'>
+        <animate attributeName='x' values='0;10;20' keyTimes='0;0.8;1' dur='1s' repeatCount='2'
+                 accumulate='sum' fill='freeze'/>
+    </rect>
+    <rect id='rect4' width='10' height='10'>
+        <animate id='grow' attributeName='height' from='10' to='20' dur='1s' begin='0s;shrink.end'/>
+        <animate id='shrink' attributeName='height' from='20' to='10' dur='1s' begin='grow.end'/>
+    </rect>
+</svg>
+";
+
+    // Animations are ignored by default.
+    let tree = tree_at(svg, None);
+    assert_eq!(path(&tree, "rect1").abs_bounding_box().width(), 10.0);
+    assert_eq!(
+        path(&tree, "rect1").fill().unwrap().paint(),
+        &usvg::Paint::Color(Color::new_rgb(255, 0, 0))
+    );
+
+    let tree = tree_at(svg, Some(0.6));
+    assert!(!path(&tree, "rect2").is_visible());
+    assert_eq!(path(&tree, "rect3").abs_bounding_box().x(), 7.5);
+
+    let tree = tree_at(svg, Some(1.0));
+    assert_eq!(path(&tree, "rect1").abs_bounding_box().width(), 20.0);
+    // Animations override CSS.
+    assert_eq!(
+        path(&tree, "rect1").fill().unwrap().paint(),
+        &usvg::Paint::Color(Color::new_rgb(128, 0, 128))
+    );
+    assert!(path(&tree, "rect2").is_visible());
+    assert_eq!(path(&tree, "rect2").abs_transform().tx, 20.0);
+    // The second iteration starts from the last value of the first one.
+    assert_eq!(path(&tree, "rect3").abs_bounding_box().x(), 20.0);
+
+    // `grow` and `shrink` restart each other.
+    let tree = tree_at(svg, Some(3.25));
+    assert_eq!(path(&tree, "rect4").abs_bounding_box().height(), 17.5);
+
+    // The frozen value is the last value of the last iteration.
+    let tree = tree_at(svg, Some(5.0));
+    assert_eq!(path(&tree, "rect3").abs_bounding_box().x(), 40.0);
+}
+
+#[test]
+fn smil_animation_units() {
+    let svg = "
+<svg xmlns='http://www.w3.org/2000/svg'>
+    <rect id='rect1' width='10' height='10'>
+        <animate attributeName='x' from='0' to='100px' dur='4s'/>
+    </rect>
+    <rect id='rect2' width='10' height='10'>
+        <animate attributeName='x' from='10px' to='1in' dur='2s'/>
+    </rect>
+    <rect id='rect3' width='10' height='10'>
+        <animate attributeName='x' from='0%' to='100' dur='2s'/>
+    </rect>
+    <path id='path1' d='M 0 0 H 10' stroke='black'>
+        <animate attributeName='d' from='M0,0 L10,0' to='M 0 0 L 30 0' dur='2s'/>
+    </path>
+</svg>
+";
+
+    let tree = tree_at(svg, Some(1.0));
+    assert_eq!(path(&tree, "rect1").abs_bounding_box().x(), 25.0);
+    // Absolute units are converted to user units.
+    assert_eq!(path(&tree, "rect2").abs_bounding_box().x(), 53.0);
+    // Relative units cannot be converted, so values are not interpolated.
+    assert_eq!(path(&tree, "rect3").abs_bounding_box().x(), 100.0);
+    // Separators do not matter.
+    assert_eq!(path(&tree, "path1").abs_bounding_box().width(), 20.0);
+}
+
+#[test]
+fn smil_animation_calc_modes() {
+    let svg = "
+<svg xmlns='http://www.w3.org/2000/svg'>
+    <rect id='rect1' width='10' height='10'>
+        <animate attributeName='x' values='0;10;30' calcMode='discrete' dur='3s'/>
+    </rect>
+    <rect id='rect2' width='10' height='10'>
+        <animate attributeName='x' values='0;10;30' calcMode='paced' dur='3s'/>
+    </rect>
+    <rect id='rect3' width='10' height='10'>
+        <animate attributeName='x' values='0;100' keyTimes='0;1' calcMode='spline'
+                 keySplines='0.5 0 0.5 1' dur='4s'/>
+    </rect>
+    <rect id='rect4' x='5' width='10' height='10'>
+        <animate attributeName='x' by='10' dur='1s' fill='freeze'/>
+    </rect>
+    <rect id='rect5' width='10' height='10'>
+        <animate attributeName='x' to='10' dur='2s' additive='sum'/>
+    </rect>
+</svg>
+";
+
+    let tree = tree_at(svg, Some(1.5));
+    assert_eq!(path(&tree, "rect1").abs_bounding_box().x(), 10.0);
+    assert_eq!(path(&tree, "rect2").abs_bounding_box().x(), 15.0);
+    // The curve is symmetric.
+    let tree = tree_at(svg, Some(2.0));
+    assert_eq!(path(&tree, "rect3").abs_bounding_box().x(), 50.0);
+    assert_eq!(path(&tree, "rect4").abs_bounding_box().x(), 15.0);
+    // `to` animations ignore `additive` and start from the initial value of an unset attribute.
+    let tree = tree_at(svg, Some(1.0));
+    assert_eq!(path(&tree, "rect5").abs_bounding_box().x(), 5.0);
+}
+
+#[test]
+fn smil_animation_timing() {
+    let svg = "
+<svg xmlns='http://www.w3.org/2000/svg'>
+    <rect id='rect1' width='10' height='10'>
+        <animate attributeName='x' from='0' to='10' dur='1s' repeatDur='2.5s' fill='freeze'/>
+    </rect>
+    <rect id='rect2' width='10' height='10'>
+        <animate attributeName='x' from='0' to='20' dur='2s' begin='0s;1s' restart='never'/>
+    </rect>
+    <rect id='rect3' width='10' height='10'>
+        <animate attributeName='x' from='0' to='20' dur='2s' begin='0s;1s'/>
+    </rect>
+    <rect id='rect4' width='10' height='10'>
+        <animate attributeName='x' from='0' to='20' dur='2s' begin='click'/>
+    </rect>
+</svg>
+";
+
+    let tree = tree_at(svg, Some(1.5));
+    assert_eq!(path(&tree, "rect2").abs_bounding_box().x(), 15.0);
+    // The second begin time restarts the animation.
+    assert_eq!(path(&tree, "rect3").abs_bounding_box().x(), 5.0);
+    // Events never start an animation.
+    assert_eq!(path(&tree, "rect4").abs_bounding_box().x(), 0.0);
+
+    // Frozen in the middle of the third iteration.
+    let tree = tree_at(svg, Some(5.0));
+    assert_eq!(path(&tree, "rect1").abs_bounding_box().x(), 5.0);
+}
+
+#[test]
+fn smil_animate_motion() {
+    let svg = "
+<svg xmlns='http://www.w3.org/2000/svg'>
+    <path id='track' d='M 0 0 L 0 100'/>
+    <rect id='rect1' width='10' height='10'>
+        <animateMotion path='M 0 0 L 100 0' dur='4s'/>
+    </rect>
+    <rect id='rect2' width='10' height='10'>
+        <animateMotion dur='4s' rotate='auto'>
+            <mpath href='#track'/>
+        </animateMotion>
+    </rect>
+    <rect id='rect3' width='10' height='10'>
+        <animateMotion path='M 0 0 L 100 0' keyPoints='0;0.5' keyTimes='0;1' calcMode='linear'
+                       dur='2s'/>
+    </rect>
+</svg>
+";
+
+    let tree = tree_at(svg, Some(1.0));
+    assert_eq!(path(&tree, "rect1").abs_transform().tx, 25.0);
+    let ts = path(&tree, "rect2").abs_transform();
+    assert_eq!((ts.tx, ts.ty), (0.0, 25.0));
+    // Rotated along the path.
+    assert!((ts.ky - 1.0).abs() < 1e-6);
+    assert_eq!(path(&tree, "rect3").abs_transform().tx, 25.0);
+}
+
+#[test]
+fn css_animations() {
+    let svg = "
+<svg xmlns='http://www.w3.org/2000/svg'>
+    <style>
+        @keyframes spin { to { transform: rotate(0.25turn) } }
+        @keyframes fade { 50% { opacity: 0 } }
+        @keyframes color {
+            from { fill: red; animation-timing-function: steps(4) }
+            to { fill: blue }
+        }
+        @keyframes grow { from { d: path('M 0 0 H 10') } to { d: path('M 0 0 H 30') } }
+        #rect1 { animation: spin 2s linear, fade 2s linear 1s both }
+        #rect2 {
+            animation-name: color;
+            animation-duration: 1s;
+            animation-iteration-count: 2;
+            animation-direction: alternate;
+            animation-fill-mode: forwards;
+        }
+        #rect3 { animation: fade 1s linear paused; animation-delay: -0.5s; opacity: 1 !important }
+        #path1 { animation: grow 1s cubic-bezier(0, 0, 1, 1) infinite }
+    </style>
+    <rect id='rect1' width='10' height='10'/>
+    <rect id='rect2' width='10' height='10'/>
+    <rect id='rect3' width='10' height='10'/>
+    <path id='path1' d='M 0 0 H 5' stroke='black'/>
+</svg>
+";
+
+    fn opacity(tree: &usvg::Tree, id: &str) -> f32 {
+        let node = tree.node_by_id(id).unwrap();
+        let parent = tree.root().children().iter().find_map(|n| match n {
+            usvg::Node::Group(g) if g.children().iter().any(|c| c.id() == node.id()) => Some(g),
+            _ => None,
+        });
+        parent.map_or(1.0, |g| g.opacity().get())
+    }
+
+    // Animations are ignored by default.
+    let tree = tree_at(svg, None);
+    assert_eq!(path(&tree, "path1").abs_bounding_box().width(), 5.0);
+    assert_eq!(opacity(&tree, "rect1"), 1.0);
+
+    let tree = tree_at(svg, Some(0.3));
+    // A missing `from` keyframe uses the underlying value.
+    let ts = path(&tree, "rect1").abs_transform();
+    assert!(
+        (ts.ky - (13.5f32).to_radians().sin()).abs() < 1e-6,
+        "{ts:?}"
+    );
+    // The animation has a delay, but fills backwards.
+    assert_eq!(opacity(&tree, "rect1"), 1.0);
+    // `steps(4)` jumps to a quarter.
+    assert_eq!(
+        path(&tree, "rect2").fill().unwrap().paint(),
+        &usvg::Paint::Color(Color::new_rgb(191, 0, 64))
+    );
+    assert_eq!(path(&tree, "path1").abs_bounding_box().width(), 16.0);
+
+    let tree = tree_at(svg, Some(1.5));
+    assert_eq!(opacity(&tree, "rect1"), 0.5);
+    // The second iteration is reversed.
+    assert_eq!(
+        path(&tree, "rect2").fill().unwrap().paint(),
+        &usvg::Paint::Color(Color::new_rgb(128, 0, 128))
+    );
+    // Important declarations override animations.
+    assert_eq!(opacity(&tree, "rect3"), 1.0);
+
+    // The value after the last iteration is frozen.
+    let tree = tree_at(svg, Some(5.0));
+    assert_eq!(
+        path(&tree, "rect2").fill().unwrap().paint(),
+        &usvg::Paint::Color(Color::new_rgb(255, 0, 0))
+    );
+}
@@ -17,23 +17,38 @@ static GLOBAL_FONTDB: Lazy<Arc<usvg::fontdb::Database>> = Lazy::new(|| {
 });
 
 fn resave(name: &str) {
-    resave_impl(name, None, false);
+    resave_impl(name, None, false, usvg::MetadataOptions::default());
 }
 
 fn resave_with_text(name: &str) {
-    resave_impl(name, None, true);
+    resave_impl(name, None, true, usvg::MetadataOptions::default());
 }
 
 fn resave_with_prefix(name: &str, id_prefix: &str) {
-    resave_impl(name, Some(id_prefix.to_string()), false);
+    resave_impl(
+        name,
+        Some(id_prefix.to_string()),
+        false,
+        usvg::MetadataOptions::default(),
+    );
 }
 
-fn resave_impl(name: &str, id_prefix: Option<String>, preserve_text: bool) {
+fn resave_with_metadata(name: &str, metadata: usvg::MetadataOptions) {
+    resave_impl(name, None, false, metadata);
+}
+
+fn resave_impl(
+    name: &str,
+    id_prefix: Option<String>,
+    preserve_text: bool,
+    metadata: usvg::MetadataOptions,
+) {
     let input_svg = std::fs::read_to_string(format!("tests/files/{}.svg", name)).unwrap();
 
     let tree = {
         let opt = usvg::Options {
             fontdb: GLOBAL_FONTDB.clone(),
+            metadata,
             ..Default::default()
         };
         usvg::Tree::from_str(&input_svg, &opt).unwrap()
@@ -218,3 +233,19 @@ fn preserve_text_with_nested_baseline_shift() {
 fn optimize_paths_without_markers() {
     resave("optimize-paths-without-markers");
 }
+
+#[test]
+fn preserve_metadata() {
+    resave_with_metadata(
+        "preserve-metadata",
+        usvg::MetadataOptions {
+            attribute_namespaces: vec!["http://example.com/ns".to_string()],
+            ..usvg::MetadataOptions::all()
+        },
+    );
+}
+
+#[test]
+fn preserve_metadata_disabled() {
+    resave("preserve-metadata-disabled");
+}